@@ -211,12 +211,21 @@ export const api = {
   },
 
   async getAdminPosts(): Promise<AdminPostListResponse[]> {
-    const response = await authenticatedFetch(`${API_BASE_URL}/admin/posts`);
-    if (!response.ok) {
-      throw new Error('Failed to get posts');
+    // The list is paginated; collect every page so no post is left out
+    const items: any[] = [];
+    let page: number | null = 1;
+    while (page !== null) {
+      const response = await authenticatedFetch(
+        `${API_BASE_URL}/admin/posts?page=${page}&per_page=100`
+      );
+      if (!response.ok) {
+        throw new Error('Failed to get posts');
+      }
+      const data = await response.json();
+      items.push(...data.items);
+      page = data.next_page ?? null;
     }
-    const data = await response.json();
-    return data.map((item: any) => ({
+    return items.map((item: any) => ({
       ...item,
       post: {
         ...item.post,
@@ -77,6 +77,36 @@ pub async fn get_settings_tabs<
                     ),
                     input_type: "textarea".to_string(),
                 },
+                SettingItem {
+                    key: "site_url".to_string(),
+                    label: "Site URL".to_string(),
+                    description: Some(
+                        "Public address of the site, used for links in feeds (e.g. https://example.com); defaults to the request's host"
+                            .to_string(),
+                    ),
+                    value: serde_json::Value::String(
+                        settings_map
+                            .get("site_url")
+                            .map(|raw| super::settings_helper::decode_setting_value(raw))
+                            .unwrap_or_default(),
+                    ),
+                    input_type: "text".to_string(),
+                },
+                SettingItem {
+                    key: "robots_txt".to_string(),
+                    label: "robots.txt".to_string(),
+                    description: Some(
+                        "Contents of /robots.txt; a Sitemap line is added when missing. Leave empty for the default"
+                            .to_string(),
+                    ),
+                    value: serde_json::Value::String(
+                        settings_map
+                            .get("robots_txt")
+                            .map(|raw| super::settings_helper::decode_setting_value(raw))
+                            .unwrap_or_default(),
+                    ),
+                    input_type: "textarea".to_string(),
+                },
                 SettingItem {
                     key: "posts_per_page".to_string(),
                     label: "Posts Per Page".to_string(),
@@ -123,14 +153,49 @@ pub async fn get_settings_tabs<
                 SettingItem {
                     key: "default_category".to_string(),
                     label: "Default Category".to_string(),
-                    description: Some("Default category for new posts".to_string()),
+                    description: Some(
+                        "ID of the category assigned to new posts without a category".to_string(),
+                    ),
+                    value: settings_map
+                        .get("default_category")
+                        .and_then(|raw| {
+                            super::settings_helper::decode_setting_value(raw)
+                                .trim()
+                                .parse::<i64>()
+                                .ok()
+                        })
+                        .map(serde_json::Value::from)
+                        .unwrap_or(serde_json::Value::Null),
+                    input_type: "number".to_string(),
+                },
+                SettingItem {
+                    key: "feed_items".to_string(),
+                    label: "Feed Items".to_string(),
+                    description: Some(
+                        "Number of posts in the RSS and Atom feeds (max 100)".to_string(),
+                    ),
                     value: serde_json::Value::String(
                         settings_map
-                            .get("default_category")
-                            .unwrap_or(&"uncategorized".to_string())
-                            .clone(),
+                            .get("feed_items")
+                            .map(|raw| super::settings_helper::decode_setting_value(raw))
+                            .unwrap_or_else(|| "20".to_string()),
                     ),
-                    input_type: "text".to_string(),
+                    input_type: "number".to_string(),
+                },
+                SettingItem {
+                    key: "trash_retention_days".to_string(),
+                    label: "Trash Retention (days)".to_string(),
+                    description: Some(
+                        "Deleted posts are purged for good, with their versions, drafts and unused media, after this many days in the trash; 0 keeps them until the trash is emptied"
+                            .to_string(),
+                    ),
+                    value: serde_json::Value::String(
+                        settings_map
+                            .get("trash_retention_days")
+                            .map(|raw| super::settings_helper::decode_setting_value(raw))
+                            .unwrap_or_else(|| crate::trash::DEFAULT_RETENTION_DAYS.to_string()),
+                    ),
+                    input_type: "number".to_string(),
                 },
                 SettingItem {
                     key: "auto_publish_comments".to_string(),
@@ -148,6 +213,134 @@ pub async fn get_settings_tabs<
                 },
             ],
         },
+        SettingsTab {
+            id: "media".to_string(),
+            label: "Media".to_string(),
+            description: Some(
+                "Which uploads are accepted, how they are stored, and the resized copies generated for images"
+                    .to_string(),
+            ),
+            items: vec![
+                SettingItem {
+                    key: "deduplicate_uploads".to_string(),
+                    label: "Deduplicate Uploads".to_string(),
+                    description: Some(
+                        "Store new uploads under their SHA-256 digest; uploading a file that is already in the media library returns the existing file"
+                            .to_string(),
+                    ),
+                    value: serde_json::Value::Bool(
+                        settings_map
+                            .get("deduplicate_uploads")
+                            .map(|s| s == "true")
+                            .unwrap_or(false),
+                    ),
+                    input_type: "checkbox".to_string(),
+                },
+                SettingItem {
+                    key: "upload_allowed_types".to_string(),
+                    label: "Allowed File Types".to_string(),
+                    description: Some(
+                        "Comma-separated MIME types that may be uploaded, or patterns like audio/*; the type is detected from the file's content. HTML is never accepted"
+                            .to_string(),
+                    ),
+                    value: serde_json::Value::String(
+                        settings_map
+                            .get("upload_allowed_types")
+                            .map(|raw| super::settings_helper::decode_setting_value(raw))
+                            .unwrap_or_else(|| {
+                                crate::upload_policy::DEFAULT_ALLOWED_TYPES.to_string()
+                            }),
+                    ),
+                    input_type: "text".to_string(),
+                },
+                SettingItem {
+                    key: "upload_size_limits".to_string(),
+                    label: "Upload Size Limits (MB)".to_string(),
+                    description: Some(
+                        "Comma-separated type=megabytes entries, like video/*=200 or *=20; the most specific matching entry applies"
+                            .to_string(),
+                    ),
+                    value: serde_json::Value::String(
+                        settings_map
+                            .get("upload_size_limits")
+                            .map(|raw| super::settings_helper::decode_setting_value(raw))
+                            .unwrap_or_else(|| {
+                                crate::upload_policy::DEFAULT_SIZE_LIMITS.to_string()
+                            }),
+                    ),
+                    input_type: "text".to_string(),
+                },
+                SettingItem {
+                    key: "image_variant_widths".to_string(),
+                    label: "Responsive Widths".to_string(),
+                    description: Some(
+                        "Comma-separated widths in pixels that JPEG, PNG and WebP uploads are scaled down to; empty disables them"
+                            .to_string(),
+                    ),
+                    value: serde_json::Value::String(
+                        settings_map
+                            .get("image_variant_widths")
+                            .map(|raw| super::settings_helper::decode_setting_value(raw))
+                            .unwrap_or_else(|| {
+                                crate::image_variants::DEFAULT_WIDTHS
+                                    .iter()
+                                    .map(u32::to_string)
+                                    .collect::<Vec<_>>()
+                                    .join(",")
+                            }),
+                    ),
+                    input_type: "text".to_string(),
+                },
+                SettingItem {
+                    key: "image_thumbnail_size".to_string(),
+                    label: "Thumbnail Size".to_string(),
+                    description: Some(
+                        "Edge length in pixels of the square thumbnail; 0 disables it".to_string(),
+                    ),
+                    value: serde_json::Value::String(
+                        settings_map
+                            .get("image_thumbnail_size")
+                            .map(|raw| super::settings_helper::decode_setting_value(raw))
+                            .unwrap_or_else(|| {
+                                crate::image_variants::DEFAULT_THUMBNAIL_SIZE.to_string()
+                            }),
+                    ),
+                    input_type: "number".to_string(),
+                },
+                SettingItem {
+                    key: "image_variant_formats".to_string(),
+                    label: "Extra Formats".to_string(),
+                    description: Some(
+                        "Also generate the responsive widths as webp, avif or both (comma-separated); AVIF makes uploads noticeably slower"
+                            .to_string(),
+                    ),
+                    value: serde_json::Value::String(
+                        settings_map
+                            .get("image_variant_formats")
+                            .map(|raw| super::settings_helper::decode_setting_value(raw))
+                            .unwrap_or_default(),
+                    ),
+                    input_type: "text".to_string(),
+                },
+                SettingItem {
+                    key: "image_max_megapixels".to_string(),
+                    label: "Maximum Image Size (megapixels)".to_string(),
+                    description: Some(
+                        "Larger JPEG, PNG and WebP uploads are rejected before they are decoded"
+                            .to_string(),
+                    ),
+                    value: serde_json::Value::String(
+                        settings_map
+                            .get("image_max_megapixels")
+                            .map(|raw| super::settings_helper::decode_setting_value(raw))
+                            .unwrap_or_else(|| {
+                                crate::image_variants::DEFAULT_MAX_MEGAPIXELS.to_string()
+                            }),
+                    ),
+                    input_type: "number".to_string(),
+                },
+            ],
+        },
         SettingsTab {
             id: "plugins".to_string(),
             label: "Plugins".to_string(),
@@ -197,6 +390,7 @@ pub async fn get_settings_tabs<
     request_body = AdminSettingsUpdateRequest,
     responses(
         (status = 200, description = "Successfully updated settings", body = AdminSettingsTabsResponse),
+        (status = 400, description = "Bad request (default_category is not an existing category, or trash_retention_days is not a number of days)"),
         (status = 500, description = "Internal server error")
     ),
     tag = "Admin"
@@ -208,11 +402,107 @@ pub async fn update_settings<
 >(
     State(state): State<Arc<crate::api::post_controller::ExtendedAppState<PR, UR, SB>>>,
     Extension(current_user): Extension<Arc<crate::auth::middleware::CurrentUser>>,
-    axum::Json(updates): axum::Json<AdminSettingsUpdateRequest>,
+    axum::Json(mut updates): axum::Json<AdminSettingsUpdateRequest>,
 ) -> Result<Json<AdminSettingsTabsResponse>, StatusCode> {
     // Get database connection from state
     let db = get_db_connection(&state);
 
+    // default_category must reference an existing category; empty or null clears it
+    if let Some(value) = updates.settings.get_mut("default_category") {
+        let id = match value {
+            serde_json::Value::Null => None,
+            serde_json::Value::Number(n) => Some(n.as_i64().ok_or(StatusCode::BAD_REQUEST)?),
+            serde_json::Value::String(s) if s.trim().is_empty() => None,
+            serde_json::Value::String(s) => Some(
+                s.trim()
+                    .parse::<i64>()
+                    .map_err(|_| StatusCode::BAD_REQUEST)?,
+            ),
+            _ => return Err(StatusCode::BAD_REQUEST),
+        };
+        if let Some(id) = id {
+            match state
+                .app_state
+                .post_repository
+                .find_category_by_id(&id)
+                .await
+            {
+                Ok(Some(_)) => {}
+                Ok(None) => return Err(StatusCode::BAD_REQUEST),
+                Err(e) => {
+                    tracing::error!("Failed to look up default category: {}", e);
+                    return Err(StatusCode::INTERNAL_SERVER_ERROR);
+                }
+            }
+        }
+        *value = id
+            .map(serde_json::Value::from)
+            .unwrap_or_else(|| serde_json::Value::String(String::new()));
+    }
+
+    // trash_retention_days must be a whole number of days; 0 disables purging
+    if let Some(value) = updates.settings.get_mut("trash_retention_days") {
+        let days = match value {
+            serde_json::Value::Number(n) => n.as_u64(),
+            serde_json::Value::String(s) => s.trim().parse::<u64>().ok(),
+            _ => None,
+        }
+        .ok_or(StatusCode::BAD_REQUEST)?;
+        *value = serde_json::Value::from(days);
+    }
+
+    // Image variant settings are stored normalized; anything unparsable is refused
+    if let Some(value) = updates.settings.get_mut("image_variant_widths") {
+        let widths = setting_text(value)
+            .and_then(|text| crate::image_variants::parse_widths(&text))
+            .ok_or(StatusCode::BAD_REQUEST)?;
+        *value = serde_json::Value::String(
+            widths
+                .iter()
+                .map(u32::to_string)
+                .collect::<Vec<_>>()
+                .join(","),
+        );
+    }
+    if let Some(value) = updates.settings.get_mut("image_thumbnail_size") {
+        let size = setting_text(value)
+            .and_then(|text| crate::image_variants::parse_thumbnail_size(&text))
+            .ok_or(StatusCode::BAD_REQUEST)?;
+        *value = serde_json::Value::from(size);
+    }
+    if let Some(value) = updates.settings.get_mut("image_variant_formats") {
+        let formats = setting_text(value)
+            .and_then(|text| crate::image_variants::parse_formats(&text))
+            .ok_or(StatusCode::BAD_REQUEST)?;
+        *value = serde_json::Value::String(
+            formats
+                .iter()
+                .map(|format| format.as_str())
+                .collect::<Vec<_>>()
+                .join(","),
+        );
+    }
+    if let Some(value) = updates.settings.get_mut("image_max_megapixels") {
+        let megapixels = setting_text(value)
+            .and_then(|text| crate::image_variants::parse_max_megapixels(&text))
+            .ok_or(StatusCode::BAD_REQUEST)?;
+        *value = serde_json::Value::from(megapixels);
+    }
+
+    // Upload policy settings are stored normalized as well
+    if let Some(value) = updates.settings.get_mut("upload_allowed_types") {
+        let types = setting_text(value)
+            .and_then(|text| crate::upload_policy::parse_allowed_types(&text))
+            .ok_or(StatusCode::BAD_REQUEST)?;
+        *value = serde_json::Value::String(types.join(","));
+    }
+    if let Some(value) = updates.settings.get_mut("upload_size_limits") {
+        let limits = setting_text(value)
+            .and_then(|text| crate::upload_policy::parse_size_limits(&text))
+            .ok_or(StatusCode::BAD_REQUEST)?;
+        *value = serde_json::Value::String(crate::upload_policy::format_size_limits(&limits));
+    }
+
     // Update each setting
     for (key, value) in &updates.settings {
         // Try to find existing setting
@@ -248,6 +538,15 @@ pub async fn update_settings<
     get_settings_tabs(State(state), Extension(current_user)).await
 }
 
+// Text of a submitted setting value, with numbers written out
+fn setting_text(value: &serde_json::Value) -> Option<String> {
+    match value {
+        serde_json::Value::String(s) => Some(s.clone()),
+        serde_json::Value::Number(n) => Some(n.to_string()),
+        _ => None,
+    }
+}
+
 // Helper function to get database connection from state
 fn get_db_connection<PR: PostRepository, UR: UserRepository, SB: crate::storage::StorageBackend>(
     state: &Arc<crate::api::post_controller::ExtendedAppState<PR, UR, SB>>,
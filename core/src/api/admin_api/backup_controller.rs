@@ -0,0 +1,205 @@
+use crate::backup::{self, BackupPaths, InvalidBackup, SchemaMismatch};
+use crate::dto::{RestoreQuery, RestoreReport};
+use crate::repository::{PostRepository, UserRepository};
+use axum::{
+    body::Body,
+    extract::{Extension, Multipart, Query, State},
+    http::{StatusCode, header},
+    response::{IntoResponse, Json, Response},
+};
+use std::sync::Arc;
+use tokio::io::AsyncWriteExt;
+use tokio_util::io::ReaderStream;
+
+/// Request body for restoring a backup (multipart/form-data)
+#[derive(utoipa::ToSchema)]
+#[allow(dead_code)]
+struct RestoreBackupRequest {
+    /// Archive downloaded from `GET /api/admin/backup`
+    file: String,
+}
+
+/// Files of this server that go into backups, besides the stored media
+fn backup_paths<PR: PostRepository, UR: UserRepository, SB: crate::storage::StorageBackend>(
+    state: &crate::api::post_controller::ExtendedAppState<PR, UR, SB>,
+) -> BackupPaths {
+    BackupPaths {
+        plugins_dir: state.plugin_registry.install_dir().to_path_buf(),
+    }
+}
+
+/// Download a backup of the whole site (root only)
+///
+/// A zip archive with all posts, versions, drafts, comments, users (with password
+/// hashes), settings, plugins with their packages and permissions, OpenAI key
+/// settings (without the keys) and the media of the configured storage backend.
+#[utoipa::path(
+    get,
+    path = "/api/admin/backup",
+    responses(
+        (status = 200, description = "Backup archive", content_type = "application/zip"),
+        (status = 403, description = "Only the root user can create backups"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Admin"
+)]
+pub async fn create_backup<
+    PR: PostRepository,
+    UR: UserRepository,
+    SB: crate::storage::StorageBackend,
+>(
+    State(state): State<Arc<crate::api::post_controller::ExtendedAppState<PR, UR, SB>>>,
+    Extension(current_user): Extension<Arc<crate::auth::middleware::CurrentUser>>,
+) -> Result<Response, StatusCode> {
+    if !current_user.is_root() {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    let temp = tempfile::NamedTempFile::new().map_err(|e| {
+        tracing::error!("Failed to create temporary backup file: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+    let manifest = backup::create_backup(
+        &state.db,
+        state.storage.as_ref(),
+        &backup_paths(&state),
+        temp.path(),
+    )
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to create backup: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+    // The open file stays readable after the temporary path is removed
+    let file = tokio::fs::File::open(temp.path()).await.map_err(|e| {
+        tracing::error!("Failed to open backup file: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+    drop(temp);
+
+    tracing::info!(
+        "Backup created by {}: {} tables, {} plugin packages, {} media files",
+        current_user.username,
+        manifest.tables.len(),
+        manifest.plugin_files,
+        manifest.media_files
+    );
+    let file_name = format!(
+        "rustpress-backup-{}.zip",
+        manifest.created_at.format("%Y%m%d-%H%M%S")
+    );
+    Ok((
+        [
+            (header::CONTENT_TYPE, "application/zip".to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{}\"", file_name),
+            ),
+        ],
+        Body::from_stream(ReaderStream::new(file)),
+    )
+        .into_response())
+}
+
+/// Restore a backup (root only)
+///
+/// Replaces all posts, comments, users, settings, plugins and OpenAI key settings
+/// with the ones in the archive, and writes its plugin packages and media files,
+/// the media to the configured storage backend.
+/// The archive must have been written with the same schema migrations as this
+/// server runs. Requires `?confirm=true`.
+#[utoipa::path(
+    post,
+    path = "/api/admin/backup/restore",
+    params(RestoreQuery),
+    request_body(content_type = "multipart/form-data", content = inline(RestoreBackupRequest)),
+    responses(
+        (status = 200, description = "Backup restored", body = RestoreReport),
+        (status = 400, description = "Missing confirmation, or not a backup archive"),
+        (status = 403, description = "Only the root user can restore backups"),
+        (status = 409, description = "Backup was made with a different schema version"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Admin"
+)]
+pub async fn restore_backup<
+    PR: PostRepository,
+    UR: UserRepository,
+    SB: crate::storage::StorageBackend,
+>(
+    State(state): State<Arc<crate::api::post_controller::ExtendedAppState<PR, UR, SB>>>,
+    Extension(current_user): Extension<Arc<crate::auth::middleware::CurrentUser>>,
+    Query(query): Query<RestoreQuery>,
+    mut multipart: Multipart,
+) -> Result<Json<RestoreReport>, StatusCode> {
+    if !current_user.is_root() {
+        return Err(StatusCode::FORBIDDEN);
+    }
+    if !query.confirm {
+        tracing::error!("Backup restore requested without confirm=true");
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    // Archives can be large; write the upload to disk as it arrives
+    let temp = tempfile::NamedTempFile::new().map_err(|e| {
+        tracing::error!("Failed to create temporary file: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+    let mut received = false;
+    while let Some(mut field) = multipart.next_field().await.map_err(|e| {
+        tracing::error!("Failed to read multipart field: {}", e);
+        StatusCode::BAD_REQUEST
+    })? {
+        if field.name() != Some("file") {
+            continue;
+        }
+        let mut file = tokio::fs::File::create(temp.path()).await.map_err(|e| {
+            tracing::error!("Failed to open temporary file: {}", e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+        while let Some(chunk) = field.chunk().await.map_err(|e| {
+            tracing::error!("Failed to read backup upload: {}", e);
+            StatusCode::BAD_REQUEST
+        })? {
+            file.write_all(&chunk).await.map_err(|e| {
+                tracing::error!("Failed to write backup upload: {}", e);
+                StatusCode::INTERNAL_SERVER_ERROR
+            })?;
+        }
+        file.flush()
+            .await
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        received = true;
+    }
+    if !received {
+        tracing::error!("Missing file field in multipart request");
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    let report = backup::restore_backup(
+        &state.db,
+        state.storage.as_ref(),
+        &backup_paths(&state),
+        temp.path(),
+    )
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to restore backup: {}", e);
+        if e.is::<InvalidBackup>() {
+            StatusCode::BAD_REQUEST
+        } else if e.is::<SchemaMismatch>() {
+            StatusCode::CONFLICT
+        } else {
+            StatusCode::INTERNAL_SERVER_ERROR
+        }
+    })?;
+    state.render_cache.clear();
+    state.page_meta_cache.clear();
+
+    tracing::info!(
+        "Backup from {} restored by {}",
+        report.manifest.created_at,
+        current_user.username
+    );
+    Ok(Json(report))
+}
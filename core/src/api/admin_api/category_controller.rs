@@ -0,0 +1,244 @@
+use crate::api::admin_api::settings_helper;
+use crate::dto::category::descendant_ids;
+use crate::dto::{Category, CreateCategoryRequest, DeleteCategoryQuery, UpdateCategoryRequest};
+use crate::repository::{PostRepository, UserRepository};
+use crate::slug::slugify;
+use axum::{
+    extract::{Extension, Path, Query, State},
+    http::StatusCode,
+    response::Json,
+};
+use std::sync::Arc;
+
+/// Create a category
+///
+/// The slug is generated from the name when not given. Returns 409 if another
+/// category already has that slug.
+#[utoipa::path(
+    post,
+    path = "/api/admin/categories",
+    request_body = CreateCategoryRequest,
+    responses(
+        (status = 201, description = "Successfully created category", body = Category),
+        (status = 400, description = "Bad request (empty name or unknown parent)"),
+        (status = 409, description = "Another category already uses this slug"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Admin"
+)]
+pub async fn create_category<
+    PR: PostRepository,
+    UR: UserRepository,
+    SB: crate::storage::StorageBackend,
+>(
+    State(state): State<Arc<crate::api::post_controller::ExtendedAppState<PR, UR, SB>>>,
+    Extension(_current_user): Extension<Arc<crate::auth::middleware::CurrentUser>>,
+    Json(mut payload): Json<CreateCategoryRequest>,
+) -> Result<(StatusCode, Json<Category>), StatusCode> {
+    let repository = state.app_state.post_repository.as_ref();
+
+    payload.name = normalize_name(&payload.name);
+    let slug = slugify(payload.slug.as_deref().unwrap_or(&payload.name));
+    if payload.name.is_empty() || slug.is_empty() {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    ensure_slug_free(repository, &slug, None).await?;
+
+    if let Some(parent_id) = payload.parent_id {
+        find_category(repository, parent_id)
+            .await?
+            .ok_or(StatusCode::BAD_REQUEST)?;
+    }
+
+    payload.slug = Some(slug);
+    let category = repository.create_category(payload).await.map_err(|e| {
+        tracing::error!("Error creating category: {:?}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    Ok((StatusCode::CREATED, Json(category)))
+}
+
+/// Update a category
+///
+/// Omitted fields are left unchanged. A category cannot be moved below itself or
+/// one of its descendants.
+#[utoipa::path(
+    put,
+    path = "/api/admin/categories/{id}",
+    params(
+        ("id" = String, Path, description = "Category ID")
+    ),
+    request_body = UpdateCategoryRequest,
+    responses(
+        (status = 200, description = "Successfully updated category", body = Category),
+        (status = 400, description = "Bad request (empty name, unknown parent or parent cycle)"),
+        (status = 404, description = "Category not found"),
+        (status = 409, description = "Another category already uses this slug"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Admin"
+)]
+pub async fn update_category<
+    PR: PostRepository,
+    UR: UserRepository,
+    SB: crate::storage::StorageBackend,
+>(
+    Path(id): Path<String>,
+    State(state): State<Arc<crate::api::post_controller::ExtendedAppState<PR, UR, SB>>>,
+    Extension(_current_user): Extension<Arc<crate::auth::middleware::CurrentUser>>,
+    Json(payload): Json<UpdateCategoryRequest>,
+) -> Result<Json<Category>, StatusCode> {
+    let id_num: i64 = id.parse().map_err(|_| StatusCode::BAD_REQUEST)?;
+    let repository = state.app_state.post_repository.as_ref();
+
+    let mut category = find_category(repository, id_num)
+        .await?
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    if let Some(name) = payload.name {
+        category.name = normalize_name(&name);
+        if category.name.is_empty() {
+            return Err(StatusCode::BAD_REQUEST);
+        }
+    }
+    if let Some(slug) = payload.slug {
+        category.slug = slugify(&slug);
+        if category.slug.is_empty() {
+            return Err(StatusCode::BAD_REQUEST);
+        }
+        ensure_slug_free(repository, &category.slug, Some(id_num)).await?;
+    }
+    if let Some(description) = payload.description {
+        category.description = description;
+    }
+    if let Some(parent_id) = payload.parent_id {
+        if let Some(parent_id) = parent_id {
+            let categories = all_categories(repository).await?;
+            if !categories.iter().any(|c| c.id == parent_id)
+                || descendant_ids(&categories, id_num).contains(&parent_id)
+            {
+                return Err(StatusCode::BAD_REQUEST);
+            }
+        }
+        category.parent_id = parent_id;
+    }
+
+    match repository.update_category(&id_num, category).await {
+        Ok(Some(category)) => Ok(Json(category)),
+        Ok(None) => Err(StatusCode::NOT_FOUND),
+        Err(e) => {
+            tracing::error!("Error updating category: {:?}", e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+/// Delete a category
+///
+/// Posts in the category are moved to `reassign_to` (or left uncategorized) and
+/// child categories move up to the deleted category's parent.
+#[utoipa::path(
+    delete,
+    path = "/api/admin/categories/{id}",
+    params(
+        ("id" = String, Path, description = "Category ID"),
+        DeleteCategoryQuery
+    ),
+    responses(
+        (status = 204, description = "Successfully deleted category"),
+        (status = 400, description = "Bad request (unknown or same reassign target)"),
+        (status = 404, description = "Category not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Admin"
+)]
+pub async fn delete_category<
+    PR: PostRepository,
+    UR: UserRepository,
+    SB: crate::storage::StorageBackend,
+>(
+    Path(id): Path<String>,
+    State(state): State<Arc<crate::api::post_controller::ExtendedAppState<PR, UR, SB>>>,
+    Extension(_current_user): Extension<Arc<crate::auth::middleware::CurrentUser>>,
+    Query(query): Query<DeleteCategoryQuery>,
+) -> Result<StatusCode, StatusCode> {
+    let id_num: i64 = id.parse().map_err(|_| StatusCode::BAD_REQUEST)?;
+    let repository = state.app_state.post_repository.as_ref();
+
+    if let Some(target) = query.reassign_to
+        && (target == id_num || find_category(repository, target).await?.is_none())
+    {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    match repository.delete_category(&id_num, query.reassign_to).await {
+        Ok(true) => {}
+        Ok(false) => return Err(StatusCode::NOT_FOUND),
+        Err(e) => {
+            tracing::error!("Error deleting category: {:?}", e);
+            return Err(StatusCode::INTERNAL_SERVER_ERROR);
+        }
+    }
+
+    // Keep the default_category setting pointing at an existing category
+    let default_category = settings_helper::get_setting_value(&state.db, "default_category")
+        .await
+        .map_err(|e| {
+            tracing::error!("Error reading default_category setting: {:?}", e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+    if default_category.as_deref().map(str::trim) == Some(id_num.to_string().as_str()) {
+        let value = query
+            .reassign_to
+            .map(|target| serde_json::Value::from(target).to_string())
+            .unwrap_or_else(|| serde_json::Value::from("").to_string());
+        settings_helper::set_setting(&state.db, "default_category", value, None)
+            .await
+            .map_err(|e| {
+                tracing::error!("Error updating default_category setting: {:?}", e);
+                StatusCode::INTERNAL_SERVER_ERROR
+            })?;
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Collapse whitespace in a category name
+fn normalize_name(name: &str) -> String {
+    name.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+async fn find_category<PR: PostRepository>(
+    repository: &PR,
+    id: i64,
+) -> Result<Option<Category>, StatusCode> {
+    repository.find_category_by_id(&id).await.map_err(|e| {
+        tracing::error!("Error looking up category: {:?}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })
+}
+
+async fn all_categories<PR: PostRepository>(repository: &PR) -> Result<Vec<Category>, StatusCode> {
+    let stats = repository.get_category_stats().await.map_err(|e| {
+        tracing::error!("Error loading categories: {:?}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+    Ok(stats.into_iter().map(|(category, _)| category).collect())
+}
+
+/// Fail with 409 if a category other than `except` already uses `slug`
+async fn ensure_slug_free<PR: PostRepository>(
+    repository: &PR,
+    slug: &str,
+    except: Option<i64>,
+) -> Result<(), StatusCode> {
+    let categories = all_categories(repository).await?;
+    if categories
+        .iter()
+        .any(|category| category.slug == slug && Some(category.id) != except)
+    {
+        return Err(StatusCode::CONFLICT);
+    }
+    Ok(())
+}
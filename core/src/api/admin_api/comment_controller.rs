@@ -0,0 +1,244 @@
+use crate::dto::{
+    BulkModerateCommentsRequest, BulkModerateCommentsResponse, Comment, CommentListQuery,
+    CommentModeration, CommentPageResponse, CommentStatus, Paginated,
+};
+use crate::repository::{PostRepository, UserRepository};
+use axum::{
+    extract::{Extension, Path, Query, State},
+    http::StatusCode,
+    response::Json,
+};
+use std::sync::Arc;
+
+/// Get comments (moderation queue)
+///
+/// Filter by `status=pending` to get the comments waiting for moderation
+#[utoipa::path(
+    get,
+    path = "/api/admin/comments",
+    params(CommentListQuery),
+    responses(
+        (status = 200, description = "Successfully retrieved comments", body = CommentPageResponse),
+        (status = 400, description = "Bad request"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Admin"
+)]
+pub async fn get_all_comments<
+    PR: PostRepository,
+    UR: UserRepository,
+    SB: crate::storage::StorageBackend,
+>(
+    State(state): State<Arc<crate::api::post_controller::ExtendedAppState<PR, UR, SB>>>,
+    Extension(_current_user): Extension<Arc<crate::auth::middleware::CurrentUser>>,
+    Query(query): Query<CommentListQuery>,
+) -> Result<Json<CommentPageResponse>, StatusCode> {
+    let (page, per_page) =
+        crate::api::post_controller::resolve_pagination(&state.db, query.page, query.per_page)
+            .await;
+
+    let (comments, total) = state
+        .app_state
+        .comment_repository
+        .find_page(&query, page, per_page)
+        .await
+        .map_err(|e| {
+            tracing::error!("Error listing comments: {:?}", e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    Ok(Json(Paginated::new(comments, total, page, per_page)))
+}
+
+/// Approve a comment
+#[utoipa::path(
+    post,
+    path = "/api/admin/comments/{id}/approve",
+    params(
+        ("id" = String, Path, description = "Comment ID")
+    ),
+    responses(
+        (status = 200, description = "Successfully approved comment", body = Comment),
+        (status = 404, description = "Comment not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Admin"
+)]
+pub async fn approve_comment<
+    PR: PostRepository,
+    UR: UserRepository,
+    SB: crate::storage::StorageBackend,
+>(
+    Path(id): Path<String>,
+    State(state): State<Arc<crate::api::post_controller::ExtendedAppState<PR, UR, SB>>>,
+    Extension(_current_user): Extension<Arc<crate::auth::middleware::CurrentUser>>,
+) -> Result<Json<Comment>, StatusCode> {
+    set_comment_status(&state, &id, CommentStatus::Approved).await
+}
+
+/// Reject a comment
+#[utoipa::path(
+    post,
+    path = "/api/admin/comments/{id}/reject",
+    params(
+        ("id" = String, Path, description = "Comment ID")
+    ),
+    responses(
+        (status = 200, description = "Successfully rejected comment", body = Comment),
+        (status = 404, description = "Comment not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Admin"
+)]
+pub async fn reject_comment<
+    PR: PostRepository,
+    UR: UserRepository,
+    SB: crate::storage::StorageBackend,
+>(
+    Path(id): Path<String>,
+    State(state): State<Arc<crate::api::post_controller::ExtendedAppState<PR, UR, SB>>>,
+    Extension(_current_user): Extension<Arc<crate::auth::middleware::CurrentUser>>,
+) -> Result<Json<Comment>, StatusCode> {
+    set_comment_status(&state, &id, CommentStatus::Rejected).await
+}
+
+/// Mark a comment as spam
+#[utoipa::path(
+    post,
+    path = "/api/admin/comments/{id}/spam",
+    params(
+        ("id" = String, Path, description = "Comment ID")
+    ),
+    responses(
+        (status = 200, description = "Successfully marked comment as spam", body = Comment),
+        (status = 404, description = "Comment not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Admin"
+)]
+pub async fn spam_comment<
+    PR: PostRepository,
+    UR: UserRepository,
+    SB: crate::storage::StorageBackend,
+>(
+    Path(id): Path<String>,
+    State(state): State<Arc<crate::api::post_controller::ExtendedAppState<PR, UR, SB>>>,
+    Extension(_current_user): Extension<Arc<crate::auth::middleware::CurrentUser>>,
+) -> Result<Json<Comment>, StatusCode> {
+    set_comment_status(&state, &id, CommentStatus::Spam).await
+}
+
+/// Delete a comment
+///
+/// Replies to the comment are deleted with it
+#[utoipa::path(
+    delete,
+    path = "/api/admin/comments/{id}",
+    params(
+        ("id" = String, Path, description = "Comment ID")
+    ),
+    responses(
+        (status = 204, description = "Successfully deleted comment"),
+        (status = 404, description = "Comment not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Admin"
+)]
+pub async fn delete_comment<
+    PR: PostRepository,
+    UR: UserRepository,
+    SB: crate::storage::StorageBackend,
+>(
+    Path(id): Path<String>,
+    State(state): State<Arc<crate::api::post_controller::ExtendedAppState<PR, UR, SB>>>,
+    Extension(_current_user): Extension<Arc<crate::auth::middleware::CurrentUser>>,
+) -> Result<StatusCode, StatusCode> {
+    let id_num: i64 = id.parse().map_err(|_| StatusCode::BAD_REQUEST)?;
+
+    match state.app_state.comment_repository.delete(&[id_num]).await {
+        Ok(0) => Err(StatusCode::NOT_FOUND),
+        Ok(_) => Ok(StatusCode::NO_CONTENT),
+        Err(e) => {
+            tracing::error!("Error deleting comment: {:?}", e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+/// Moderate several comments at once
+///
+/// Unknown ids are skipped; the response counts the comments actually changed
+#[utoipa::path(
+    post,
+    path = "/api/admin/comments/bulk",
+    request_body = BulkModerateCommentsRequest,
+    responses(
+        (status = 200, description = "Successfully moderated comments", body = BulkModerateCommentsResponse),
+        (status = 400, description = "Bad request (no ids)"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Admin"
+)]
+pub async fn bulk_moderate_comments<
+    PR: PostRepository,
+    UR: UserRepository,
+    SB: crate::storage::StorageBackend,
+>(
+    State(state): State<Arc<crate::api::post_controller::ExtendedAppState<PR, UR, SB>>>,
+    Extension(_current_user): Extension<Arc<crate::auth::middleware::CurrentUser>>,
+    Json(payload): Json<BulkModerateCommentsRequest>,
+) -> Result<Json<BulkModerateCommentsResponse>, StatusCode> {
+    if payload.ids.is_empty() {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    let repository = &state.app_state.comment_repository;
+
+    let result = match payload.action {
+        CommentModeration::Delete => repository.delete(&payload.ids).await,
+        action => {
+            let status = action.status().ok_or(StatusCode::BAD_REQUEST)?;
+            repository.set_status(&payload.ids, status).await
+        }
+    };
+
+    match result {
+        Ok(affected) => Ok(Json(BulkModerateCommentsResponse { affected })),
+        Err(e) => {
+            tracing::error!("Error moderating comments: {:?}", e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+async fn set_comment_status<
+    PR: PostRepository,
+    UR: UserRepository,
+    SB: crate::storage::StorageBackend,
+>(
+    state: &crate::api::post_controller::ExtendedAppState<PR, UR, SB>,
+    id: &str,
+    status: CommentStatus,
+) -> Result<Json<Comment>, StatusCode> {
+    let id_num: i64 = id.parse().map_err(|_| StatusCode::BAD_REQUEST)?;
+    let repository = &state.app_state.comment_repository;
+
+    let updated = repository
+        .set_status(&[id_num], status)
+        .await
+        .map_err(|e| {
+            tracing::error!("Error updating comment status: {:?}", e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+    if updated == 0 {
+        return Err(StatusCode::NOT_FOUND);
+    }
+
+    match repository.find_by_id(&id_num).await {
+        Ok(Some(comment)) => Ok(Json(comment)),
+        Ok(None) => Err(StatusCode::NOT_FOUND),
+        Err(e) => {
+            tracing::error!("Error loading comment: {:?}", e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
@@ -0,0 +1,243 @@
+use crate::dto::{ImportQuery, ImportReport};
+use crate::front_matter;
+use crate::import::markdown::{self, MarkdownImportOptions};
+use crate::import::wxr::{self, WxrImportOptions};
+use crate::repository::{PostRepository, UserRepository};
+use axum::{
+    extract::{Extension, Multipart, Query, State},
+    http::StatusCode,
+    response::Json,
+};
+use std::path::Path;
+use std::sync::Arc;
+
+/// Largest WXR file accepted by the import endpoint
+pub const MAX_WXR_BYTES: usize = 64 * 1024 * 1024;
+
+/// Largest zip of Markdown files accepted by the import endpoint
+pub const MAX_MARKDOWN_ZIP_BYTES: usize = 64 * 1024 * 1024;
+
+/// Request body for WXR import (multipart/form-data)
+#[derive(utoipa::ToSchema)]
+#[allow(dead_code)]
+struct WxrImportRequest {
+    /// WXR file written by WordPress' "Tools → Export"
+    file: String,
+    /// Copy of the site's `wp-content/uploads` directory on the server, relative to
+    /// the `WXR_IMPORT_DIR` directory; attachments are skipped without it
+    uploads_dir: Option<String>,
+}
+
+/// Request body for Markdown import (multipart/form-data)
+#[derive(utoipa::ToSchema)]
+#[allow(dead_code)]
+struct MarkdownImportRequest {
+    /// Zip of Markdown files with YAML or TOML front matter, in any directory layout
+    file: String,
+}
+
+/// Import a WordPress export (WXR)
+///
+/// Creates users (matched by email), categories, posts with their revisions and
+/// autosaves, and attachments. Posts and attachments imported from the same site
+/// before are skipped, so an import can be run again after fixing missing files.
+/// Posts of authors that are not in the file are assigned to the importing user.
+/// Only the root user may import, since attachments are read from the server's disk.
+#[utoipa::path(
+    post,
+    path = "/api/admin/import/wxr",
+    params(ImportQuery),
+    request_body(content_type = "multipart/form-data", content = inline(WxrImportRequest)),
+    responses(
+        (status = 200, description = "Import finished (or dry run report)", body = ImportReport),
+        (status = 400, description = "Missing or invalid WXR file, or uploads directory outside WXR_IMPORT_DIR"),
+        (status = 403, description = "Not the root user"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Admin"
+)]
+pub async fn import_wxr<
+    PR: PostRepository,
+    UR: UserRepository,
+    SB: crate::storage::StorageBackend,
+>(
+    State(state): State<Arc<crate::api::post_controller::ExtendedAppState<PR, UR, SB>>>,
+    Extension(current_user): Extension<Arc<crate::auth::middleware::CurrentUser>>,
+    Query(query): Query<ImportQuery>,
+    mut multipart: Multipart,
+) -> Result<Json<ImportReport>, StatusCode> {
+    if !current_user.is_root() {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    let mut xml = None;
+    let mut uploads_dir = None;
+    while let Some(field) = multipart.next_field().await.map_err(|e| {
+        tracing::error!("Failed to read multipart field: {}", e);
+        StatusCode::BAD_REQUEST
+    })? {
+        match field.name() {
+            Some("file") => {
+                let data = field.bytes().await.map_err(|e| {
+                    tracing::error!("Failed to read WXR file: {}", e);
+                    StatusCode::BAD_REQUEST
+                })?;
+                xml = Some(String::from_utf8(data.to_vec()).map_err(|_| StatusCode::BAD_REQUEST)?);
+            }
+            Some("uploads_dir") => {
+                let value = field.text().await.map_err(|_| StatusCode::BAD_REQUEST)?;
+                uploads_dir = Some(value).filter(|dir| !dir.trim().is_empty());
+            }
+            _ => {}
+        }
+    }
+    let xml = xml.ok_or_else(|| {
+        tracing::error!("Missing WXR file field in multipart request");
+        StatusCode::BAD_REQUEST
+    })?;
+
+    let document = wxr::parse(&xml).map_err(|e| {
+        tracing::error!("Invalid WXR file: {}", e);
+        StatusCode::BAD_REQUEST
+    })?;
+    // Files are only read from below the configured import directory
+    let uploads_dir = match uploads_dir {
+        Some(requested) => {
+            let Some(root) = std::env::var_os(wxr::IMPORT_DIR_ENV).filter(|root| !root.is_empty())
+            else {
+                tracing::error!(
+                    "WXR uploads directory given, but {} is not set",
+                    wxr::IMPORT_DIR_ENV
+                );
+                return Err(StatusCode::BAD_REQUEST);
+            };
+            let Some(dir) = wxr::resolve_uploads_dir(Path::new(&root), &requested) else {
+                tracing::error!(
+                    "WXR uploads directory {:?} is not a directory inside {}",
+                    requested,
+                    wxr::IMPORT_DIR_ENV
+                );
+                return Err(StatusCode::BAD_REQUEST);
+            };
+            Some(dir)
+        }
+        None => None,
+    };
+
+    let file_naming = crate::media::FileNaming::load(&state.db)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to load upload settings: {}", e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+    let upload_policy = crate::upload_policy::UploadPolicy::load(&state.db)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to load upload settings: {}", e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+    let options = WxrImportOptions {
+        uploads_dir,
+        fallback_author_id: current_user.id,
+        dry_run: query.dry_run,
+        file_naming,
+        upload_policy,
+    };
+    let report = wxr::import(
+        state.app_state.post_repository.as_ref(),
+        state.app_state.user_repository.as_ref(),
+        state.app_state.media_repository.as_ref(),
+        state.storage.as_ref(),
+        &document,
+        &options,
+    )
+    .await
+    .map_err(|e| {
+        tracing::error!("WXR import failed: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    tracing::info!(
+        "WXR import from {} by {}: {} posts created, {} already imported",
+        document.site_url,
+        current_user.username,
+        report.posts.created,
+        report.posts.existing
+    );
+    Ok(Json(report))
+}
+
+/// Import Markdown files with front matter (Hugo or Jekyll content)
+///
+/// Each `.md` file in the zip is one post, identified by its slug. Missing posts
+/// are created; existing posts are updated to match their file, keeping the
+/// replaced content as a version. Files without a title are skipped.
+#[utoipa::path(
+    post,
+    path = "/api/admin/import/markdown",
+    params(ImportQuery),
+    request_body(content_type = "multipart/form-data", content = inline(MarkdownImportRequest)),
+    responses(
+        (status = 200, description = "Import finished (or dry run report)", body = ImportReport),
+        (status = 400, description = "Missing or invalid zip file"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Admin"
+)]
+pub async fn import_markdown<
+    PR: PostRepository,
+    UR: UserRepository,
+    SB: crate::storage::StorageBackend,
+>(
+    State(state): State<Arc<crate::api::post_controller::ExtendedAppState<PR, UR, SB>>>,
+    Extension(current_user): Extension<Arc<crate::auth::middleware::CurrentUser>>,
+    Query(query): Query<ImportQuery>,
+    mut multipart: Multipart,
+) -> Result<Json<ImportReport>, StatusCode> {
+    let mut archive = None;
+    while let Some(field) = multipart.next_field().await.map_err(|e| {
+        tracing::error!("Failed to read multipart field: {}", e);
+        StatusCode::BAD_REQUEST
+    })? {
+        if field.name() == Some("file") {
+            archive = Some(field.bytes().await.map_err(|e| {
+                tracing::error!("Failed to read Markdown zip: {}", e);
+                StatusCode::BAD_REQUEST
+            })?);
+        }
+    }
+    let archive = archive.ok_or_else(|| {
+        tracing::error!("Missing file field in multipart request");
+        StatusCode::BAD_REQUEST
+    })?;
+
+    let files = front_matter::read_zip(&archive).map_err(|e| {
+        tracing::error!("Invalid Markdown zip: {}", e);
+        StatusCode::BAD_REQUEST
+    })?;
+
+    let options = MarkdownImportOptions {
+        fallback_author_id: current_user.id,
+        dry_run: query.dry_run,
+    };
+    let report = markdown::import(
+        state.app_state.post_repository.as_ref(),
+        state.app_state.user_repository.as_ref(),
+        &files,
+        &options,
+    )
+    .await
+    .map_err(|e| {
+        tracing::error!("Markdown import failed: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    tracing::info!(
+        "Markdown import of {} files by {}: {} posts created, {} updated",
+        files.len(),
+        current_user.username,
+        report.posts.created,
+        report.posts.updated
+    );
+    Ok(Json(report))
+}
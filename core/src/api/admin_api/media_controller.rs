@@ -0,0 +1,273 @@
+use crate::dto::media::{MAX_ALT_TEXT_LEN, MAX_ORIGINAL_NAME_LEN};
+use crate::dto::{
+    DeleteMediaQuery, MediaDetailResponse, MediaListQuery, MediaPageResponse, MediaResponse,
+    Paginated, UpdateMediaRequest,
+};
+use crate::repository::{PostRepository, UserRepository};
+use axum::{
+    extract::{Extension, Path, Query, State},
+    http::StatusCode,
+    response::Json,
+};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Get the media library
+///
+/// Uploaded files, newest first, with the number of posts linking to each
+#[utoipa::path(
+    get,
+    path = "/api/admin/media",
+    params(MediaListQuery),
+    responses(
+        (status = 200, description = "Successfully retrieved media", body = MediaPageResponse),
+        (status = 400, description = "Bad request"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Admin"
+)]
+pub async fn get_media_library<
+    PR: PostRepository,
+    UR: UserRepository,
+    SB: crate::storage::StorageBackend,
+>(
+    State(state): State<Arc<crate::api::post_controller::ExtendedAppState<PR, UR, SB>>>,
+    Extension(_current_user): Extension<Arc<crate::auth::middleware::CurrentUser>>,
+    Query(query): Query<MediaListQuery>,
+) -> Result<Json<MediaPageResponse>, StatusCode> {
+    let (page, per_page) =
+        crate::api::post_controller::resolve_pagination(&state.db, query.page, query.per_page)
+            .await;
+
+    let media_repository = &state.app_state.media_repository;
+    let (items, total) = media_repository
+        .find_page(&query, page, per_page)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to list media: {}", e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    let ids: Vec<i64> = items.iter().map(|(media, _)| media.id).collect();
+    let mut variants: HashMap<i64, Vec<_>> = HashMap::new();
+    for variant in media_repository.find_variants(&ids).await.map_err(|e| {
+        tracing::error!("Failed to list media variants: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })? {
+        variants.entry(variant.media_id).or_default().push(variant);
+    }
+
+    Ok(Json(Paginated::new(items, total, page, per_page).map(
+        |(media, references)| {
+            let variants = variants.remove(&media.id).unwrap_or_default();
+            MediaResponse::new(media, references, variants)
+        },
+    )))
+}
+
+/// Get orphaned media
+///
+/// Files that no post links to; accepts the same parameters as `GET /api/admin/media`,
+/// with `orphaned` always `true`
+#[utoipa::path(
+    get,
+    path = "/api/admin/media/orphans",
+    params(MediaListQuery),
+    responses(
+        (status = 200, description = "Successfully retrieved orphaned media", body = MediaPageResponse),
+        (status = 400, description = "Bad request"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Admin"
+)]
+pub async fn get_orphaned_media<
+    PR: PostRepository,
+    UR: UserRepository,
+    SB: crate::storage::StorageBackend,
+>(
+    state: State<Arc<crate::api::post_controller::ExtendedAppState<PR, UR, SB>>>,
+    current_user: Extension<Arc<crate::auth::middleware::CurrentUser>>,
+    Query(mut query): Query<MediaListQuery>,
+) -> Result<Json<MediaPageResponse>, StatusCode> {
+    query.orphaned = Some(true);
+    get_media_library(state, current_user, Query(query)).await
+}
+
+/// Get a file of the media library with the posts that link to it
+#[utoipa::path(
+    get,
+    path = "/api/admin/media/{id}",
+    params(
+        ("id" = i64, Path, description = "Media ID")
+    ),
+    responses(
+        (status = 200, description = "Successfully retrieved media", body = MediaDetailResponse),
+        (status = 404, description = "Media not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Admin"
+)]
+pub async fn get_media<
+    PR: PostRepository,
+    UR: UserRepository,
+    SB: crate::storage::StorageBackend,
+>(
+    State(state): State<Arc<crate::api::post_controller::ExtendedAppState<PR, UR, SB>>>,
+    Extension(_current_user): Extension<Arc<crate::auth::middleware::CurrentUser>>,
+    Path(id): Path<i64>,
+) -> Result<Json<MediaDetailResponse>, StatusCode> {
+    let media_repository = &state.app_state.media_repository;
+    let media = match media_repository.find_by_id(&id).await {
+        Ok(Some(media)) => media,
+        Ok(None) => return Err(StatusCode::NOT_FOUND),
+        Err(e) => {
+            tracing::error!("Failed to get media {}: {}", id, e);
+            return Err(StatusCode::INTERNAL_SERVER_ERROR);
+        }
+    };
+    let used_by = media_repository.find_usages(&id).await.map_err(|e| {
+        tracing::error!("Failed to get usages of media {}: {}", id, e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+    let variants = find_variants(media_repository.as_ref(), id).await?;
+
+    Ok(Json(MediaDetailResponse {
+        media: MediaResponse::new(media, used_by.len() as u64, variants),
+        used_by,
+    }))
+}
+
+/// Edit the alt text or name of a file
+#[utoipa::path(
+    put,
+    path = "/api/admin/media/{id}",
+    params(
+        ("id" = i64, Path, description = "Media ID")
+    ),
+    request_body = UpdateMediaRequest,
+    responses(
+        (status = 200, description = "Successfully updated media", body = MediaResponse),
+        (status = 400, description = "Empty name, or name or alt text too long"),
+        (status = 404, description = "Media not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Admin"
+)]
+pub async fn update_media<
+    PR: PostRepository,
+    UR: UserRepository,
+    SB: crate::storage::StorageBackend,
+>(
+    State(state): State<Arc<crate::api::post_controller::ExtendedAppState<PR, UR, SB>>>,
+    Extension(_current_user): Extension<Arc<crate::auth::middleware::CurrentUser>>,
+    Path(id): Path<i64>,
+    Json(request): Json<UpdateMediaRequest>,
+) -> Result<Json<MediaResponse>, StatusCode> {
+    if request
+        .alt_text
+        .as_ref()
+        .is_some_and(|alt_text| alt_text.chars().count() > MAX_ALT_TEXT_LEN)
+    {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    if let Some(name) = &request.original_name
+        && (name.trim().is_empty() || name.chars().count() > MAX_ORIGINAL_NAME_LEN)
+    {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    let media_repository = &state.app_state.media_repository;
+    let media = match media_repository.update(&id, request).await {
+        Ok(Some(media)) => media,
+        Ok(None) => return Err(StatusCode::NOT_FOUND),
+        Err(e) => {
+            tracing::error!("Failed to update media {}: {}", id, e);
+            return Err(StatusCode::INTERNAL_SERVER_ERROR);
+        }
+    };
+    let references = media_repository.find_usages(&id).await.map_err(|e| {
+        tracing::error!("Failed to get usages of media {}: {}", id, e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    let variants = find_variants(media_repository.as_ref(), id).await?;
+
+    Ok(Json(MediaResponse::new(
+        media,
+        references.len() as u64,
+        variants,
+    )))
+}
+
+/// Delete a file and its variants from storage and from the media library
+///
+/// Files that posts still link to are only deleted with `?force=true`.
+#[utoipa::path(
+    delete,
+    path = "/api/admin/media/{id}",
+    params(
+        ("id" = i64, Path, description = "Media ID"),
+        DeleteMediaQuery
+    ),
+    responses(
+        (status = 204, description = "Successfully deleted media"),
+        (status = 404, description = "Media not found"),
+        (status = 409, description = "Posts still link to the file"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Admin"
+)]
+pub async fn delete_media<
+    PR: PostRepository,
+    UR: UserRepository,
+    SB: crate::storage::StorageBackend,
+>(
+    State(state): State<Arc<crate::api::post_controller::ExtendedAppState<PR, UR, SB>>>,
+    Extension(current_user): Extension<Arc<crate::auth::middleware::CurrentUser>>,
+    Path(id): Path<i64>,
+    Query(query): Query<DeleteMediaQuery>,
+) -> Result<StatusCode, StatusCode> {
+    let media_repository = &state.app_state.media_repository;
+    let media = match media_repository.find_by_id(&id).await {
+        Ok(Some(media)) => media,
+        Ok(None) => return Err(StatusCode::NOT_FOUND),
+        Err(e) => {
+            tracing::error!("Failed to get media {}: {}", id, e);
+            return Err(StatusCode::INTERNAL_SERVER_ERROR);
+        }
+    };
+    if !query.force {
+        let used_by = media_repository.find_usages(&id).await.map_err(|e| {
+            tracing::error!("Failed to get usages of media {}: {}", id, e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+        if !used_by.is_empty() {
+            return Err(StatusCode::CONFLICT);
+        }
+    }
+
+    crate::media::delete(state.storage.as_ref(), media_repository.as_ref(), &media)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to delete media {}: {}", id, e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    tracing::info!(
+        "Media {} ({}) deleted by {}",
+        id,
+        media.original_name,
+        current_user.username
+    );
+    Ok(StatusCode::NO_CONTENT)
+}
+
+async fn find_variants(
+    media_repository: &dyn crate::repository::MediaRepository,
+    id: i64,
+) -> Result<Vec<crate::dto::MediaVariant>, StatusCode> {
+    media_repository.find_variants(&[id]).await.map_err(|e| {
+        tracing::error!("Failed to get variants of media {}: {}", id, e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })
+}
@@ -1,23 +1,37 @@
 pub mod admin_controller;
+pub mod backup_controller;
+pub mod category_controller;
+pub mod comment_controller;
+pub mod import_controller;
+pub mod media_controller;
 pub mod metrics_controller;
 pub mod openai_controller;
 pub mod plugin_controller;
 pub mod post_controller;
 pub mod settings_helper;
+pub mod tag_controller;
 pub mod user_controller;
 
 pub use admin_controller::*;
+pub use backup_controller::*;
+pub use category_controller::*;
+pub use comment_controller::*;
+pub use import_controller::*;
+pub use media_controller::*;
 pub use metrics_controller::*;
 pub use openai_controller::*;
 pub use plugin_controller::*;
 pub use post_controller::*;
+pub use tag_controller::*;
 pub use user_controller::*;
 
 use crate::api::post_controller::ExtendedAppState;
 use crate::repository::{PostRepository, UserRepository};
 use crate::storage::StorageBackend;
 use axum::{
-    Router, middleware,
+    Router,
+    extract::DefaultBodyLimit,
+    middleware,
     routing::{delete, get, post, put},
 };
 use std::sync::Arc;
@@ -50,9 +64,85 @@ pub fn create_admin_router<
         )
         // Post management
         // GET /api/admin/posts - Get all posts (admin view)
-        // DELETE /api/admin/posts/:id - Delete any post
+        // DELETE /api/admin/posts/:id - Delete any post (moves it to the trash)
+        // GET /api/admin/posts/archived - Get archived posts
+        // POST /api/admin/posts/:id/archive - Archive a post
+        // POST /api/admin/posts/:id/restore - Restore an archived or trashed post
         .route("/posts", get(get_all_posts::<PR, UR, SB>))
+        .route("/posts/archived", get(get_archived_posts::<PR, UR, SB>))
         .route("/posts/:id", delete(admin_delete_post::<PR, UR, SB>))
+        .route("/posts/:id/archive", post(archive_post::<PR, UR, SB>))
+        .route("/posts/:id/restore", post(restore_post::<PR, UR, SB>))
+        // Trash
+        // GET /api/admin/trash - Get trashed posts
+        // DELETE /api/admin/trash - Empty the trash
+        // DELETE /api/admin/trash/:id - Permanently delete a trashed post
+        .route(
+            "/trash",
+            get(get_trash::<PR, UR, SB>).delete(empty_trash::<PR, UR, SB>),
+        )
+        .route("/trash/:id", delete(purge_post::<PR, UR, SB>))
+        // Category management
+        // POST /api/admin/categories - Create a category
+        // PUT /api/admin/categories/:id - Update a category
+        // DELETE /api/admin/categories/:id - Delete a category, reassigning its posts
+        .route("/categories", post(create_category::<PR, UR, SB>))
+        .route(
+            "/categories/:id",
+            put(update_category::<PR, UR, SB>).delete(delete_category::<PR, UR, SB>),
+        )
+        // Tag management
+        // PUT /api/admin/tags/:id - Rename a tag
+        // POST /api/admin/tags/merge - Merge tags into a target tag
+        .route("/tags/:id", put(rename_tag::<PR, UR, SB>))
+        .route("/tags/merge", post(merge_tags::<PR, UR, SB>))
+        // Comment moderation
+        // GET /api/admin/comments - Get comments (filter by status for the moderation queue)
+        // POST /api/admin/comments/:id/approve - Approve a comment
+        // POST /api/admin/comments/:id/reject - Reject a comment
+        // POST /api/admin/comments/:id/spam - Mark a comment as spam
+        // DELETE /api/admin/comments/:id - Delete a comment and its replies
+        // POST /api/admin/comments/bulk - Moderate several comments at once
+        .route("/comments", get(get_all_comments::<PR, UR, SB>))
+        .route("/comments/bulk", post(bulk_moderate_comments::<PR, UR, SB>))
+        .route("/comments/:id", delete(delete_comment::<PR, UR, SB>))
+        .route("/comments/:id/approve", post(approve_comment::<PR, UR, SB>))
+        .route("/comments/:id/reject", post(reject_comment::<PR, UR, SB>))
+        .route("/comments/:id/spam", post(spam_comment::<PR, UR, SB>))
+        // Media library
+        // GET /api/admin/media - Get uploaded files (search and filter by type, uploader or use)
+        // GET /api/admin/media/orphans - Get files no post links to
+        // GET /api/admin/media/:id - Get a file with the posts that link to it
+        // PUT /api/admin/media/:id - Edit a file's alt text or name
+        // DELETE /api/admin/media/:id - Delete a file (requires ?force=true while posts link to it)
+        .route("/media", get(get_media_library::<PR, UR, SB>))
+        .route("/media/orphans", get(get_orphaned_media::<PR, UR, SB>))
+        .route(
+            "/media/:id",
+            get(get_media::<PR, UR, SB>)
+                .put(update_media::<PR, UR, SB>)
+                .delete(delete_media::<PR, UR, SB>),
+        )
+        // Import
+        // POST /api/admin/import/wxr - Import a WordPress export (supports ?dry_run=true)
+        .route(
+            "/import/wxr",
+            post(import_wxr::<PR, UR, SB>).layer(DefaultBodyLimit::max(MAX_WXR_BYTES)),
+        )
+        // POST /api/admin/import/markdown - Import a zip of Markdown files with front matter
+        .route(
+            "/import/markdown",
+            post(import_markdown::<PR, UR, SB>)
+                .layer(DefaultBodyLimit::max(MAX_MARKDOWN_ZIP_BYTES)),
+        )
+        // Backup
+        // GET /api/admin/backup - Download a backup archive of the whole site (root only)
+        // POST /api/admin/backup/restore - Restore a backup archive (root only, requires ?confirm=true)
+        .route("/backup", get(create_backup::<PR, UR, SB>))
+        .route(
+            "/backup/restore",
+            post(restore_backup::<PR, UR, SB>).layer(DefaultBodyLimit::disable()),
+        )
         // Plugin management
         // GET /api/admin/plugins - Get all plugins
         // POST /api/admin/plugins - Install new plugin
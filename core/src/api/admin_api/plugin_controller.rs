@@ -437,7 +437,10 @@ fn get_permission_description(permission: &str) -> Option<String> {
     match permission {
         "post:read" => Some("Read access to blog posts and their content".to_string()),
         "post:write" => Some("Write/modify access to blog posts".to_string()),
-        "post:list_category" => Some("List all categories and their post counts".to_string()),
+        "post:list_category" => Some("List the category tree with post counts".to_string()),
+        "post:list_tag" => Some("List all tags, ordered by post count".to_string()),
+        "comment:read" => Some("Read access to comments and commenter details".to_string()),
+        "comment:write" => Some("Edit or moderate new comments before they are stored".to_string()),
         "ai:chat" => Some("Access to AI chat completion APIs".to_string()),
         _ => None,
     }
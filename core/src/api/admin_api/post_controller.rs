@@ -1,18 +1,26 @@
-use crate::dto::{AdminPostListResponse, PostResponse};
+use crate::dto::{
+    AdminPostListResponse, AdminPostPageResponse, AdminRemovedPostPageResponse,
+    AdminRemovedPostResponse, Paginated, Post, PostListQuery, PostResponse, PurgeTrashResponse,
+    TrashListQuery,
+};
 use crate::repository::{PostRepository, UserRepository};
 use axum::{
-    extract::{Extension, Path, State},
+    extract::{Extension, Path, Query, State},
     http::StatusCode,
     response::Json,
 };
 use std::sync::Arc;
 
-/// Get all posts (admin view)
+/// Get posts (admin view)
+///
+/// Accepts the same pagination, filter and sort parameters as `GET /api/posts`
 #[utoipa::path(
     get,
     path = "/api/admin/posts",
+    params(PostListQuery),
     responses(
-        (status = 200, description = "Successfully retrieved post list", body = Vec<AdminPostListResponse>),
+        (status = 200, description = "Successfully retrieved post list", body = AdminPostPageResponse),
+        (status = 400, description = "Bad request"),
         (status = 500, description = "Internal server error")
     ),
     tag = "Admin"
@@ -20,18 +28,24 @@ use std::sync::Arc;
 pub async fn get_all_posts<PR: PostRepository, UR: UserRepository, SB: crate::storage::StorageBackend>(
     State(state): State<Arc<crate::api::post_controller::ExtendedAppState<PR, UR, SB>>>,
     Extension(_current_user): Extension<Arc<crate::auth::middleware::CurrentUser>>,
-) -> Result<Json<Vec<AdminPostListResponse>>, StatusCode> {
-    let posts = state.app_state.post_repository.find_all().await
+    Query(query): Query<PostListQuery>,
+) -> Result<Json<AdminPostPageResponse>, StatusCode> {
+    let (page, per_page) =
+        crate::api::post_controller::resolve_pagination(&state.db, query.page, query.per_page)
+            .await;
+
+    let (posts, total) = state
+        .app_state
+        .post_repository
+        .find_page(&query, page, per_page)
+        .await
         .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
 
-    let result: Vec<AdminPostListResponse> = posts
-        .into_iter()
-        .map(|post| AdminPostListResponse {
+    Ok(Json(Paginated::new(posts, total, page, per_page).map(
+        |post| AdminPostListResponse {
             post: PostResponse::from(post),
-        })
-        .collect();
-
-    Ok(Json(result))
+        },
+    )))
 }
 
 /// Delete a post (admin can delete any post)
@@ -61,3 +75,249 @@ pub async fn admin_delete_post<PR: PostRepository, UR: UserRepository, SB: crate
         Err(_) => Err(StatusCode::INTERNAL_SERVER_ERROR),
     }
 }
+
+/// Archived or trashed post with the time it leaves the trash
+fn removed_post_response(post: Post, retention_days: Option<i64>) -> AdminRemovedPostResponse {
+    AdminRemovedPostResponse {
+        archived_at: post.archived_at.map(Into::into),
+        deleted_at: post.deleted_at.map(Into::into),
+        purge_at: post
+            .deleted_at
+            .and_then(|deleted_at| crate::trash::purge_at(deleted_at, retention_days))
+            .map(Into::into),
+        post: PostResponse::from(post),
+    }
+}
+
+/// Get archived posts
+///
+/// Accepts the same pagination, filter and sort parameters as `GET /api/admin/posts`;
+/// `archived` is always `true`
+#[utoipa::path(
+    get,
+    path = "/api/admin/posts/archived",
+    params(PostListQuery),
+    responses(
+        (status = 200, description = "Successfully retrieved archived posts", body = AdminRemovedPostPageResponse),
+        (status = 400, description = "Bad request"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Admin"
+)]
+pub async fn get_archived_posts<PR: PostRepository, UR: UserRepository, SB: crate::storage::StorageBackend>(
+    State(state): State<Arc<crate::api::post_controller::ExtendedAppState<PR, UR, SB>>>,
+    Extension(_current_user): Extension<Arc<crate::auth::middleware::CurrentUser>>,
+    Query(mut query): Query<PostListQuery>,
+) -> Result<Json<AdminRemovedPostPageResponse>, StatusCode> {
+    let (page, per_page) =
+        crate::api::post_controller::resolve_pagination(&state.db, query.page, query.per_page)
+            .await;
+    query.archived = Some(true);
+
+    let (posts, total) = state
+        .app_state
+        .post_repository
+        .find_page(&query, page, per_page)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to list archived posts: {}", e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    Ok(Json(
+        Paginated::new(posts, total, page, per_page).map(|post| removed_post_response(post, None)),
+    ))
+}
+
+/// Get the trash
+///
+/// Deleted posts, most recently deleted first, with the time the trash retention
+/// job purges each of them
+#[utoipa::path(
+    get,
+    path = "/api/admin/trash",
+    params(TrashListQuery),
+    responses(
+        (status = 200, description = "Successfully retrieved trashed posts", body = AdminRemovedPostPageResponse),
+        (status = 400, description = "Bad request"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Admin"
+)]
+pub async fn get_trash<PR: PostRepository, UR: UserRepository, SB: crate::storage::StorageBackend>(
+    State(state): State<Arc<crate::api::post_controller::ExtendedAppState<PR, UR, SB>>>,
+    Extension(_current_user): Extension<Arc<crate::auth::middleware::CurrentUser>>,
+    Query(query): Query<TrashListQuery>,
+) -> Result<Json<AdminRemovedPostPageResponse>, StatusCode> {
+    let (page, per_page) =
+        crate::api::post_controller::resolve_pagination(&state.db, query.page, query.per_page)
+            .await;
+    let retention_days = crate::trash::retention_days(&state.db).await.map_err(|e| {
+        tracing::error!("Failed to read trash retention setting: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    let (posts, total) = state
+        .app_state
+        .post_repository
+        .find_trash_page(page, per_page)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to list trashed posts: {}", e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    Ok(Json(
+        Paginated::new(posts, total, page, per_page)
+            .map(|post| removed_post_response(post, retention_days)),
+    ))
+}
+
+/// Archive a post
+#[utoipa::path(
+    post,
+    path = "/api/admin/posts/{id}/archive",
+    params(
+        ("id" = String, Path, description = "Post ID")
+    ),
+    responses(
+        (status = 204, description = "Successfully archived post"),
+        (status = 404, description = "Post not found or in the trash"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Admin"
+)]
+pub async fn archive_post<PR: PostRepository, UR: UserRepository, SB: crate::storage::StorageBackend>(
+    Path(id): Path<String>,
+    State(state): State<Arc<crate::api::post_controller::ExtendedAppState<PR, UR, SB>>>,
+    Extension(_current_user): Extension<Arc<crate::auth::middleware::CurrentUser>>,
+) -> Result<StatusCode, StatusCode> {
+    let id_num: i64 = id.parse().map_err(|_| StatusCode::BAD_REQUEST)?;
+
+    match state.app_state.post_repository.find_by_id(&id_num).await {
+        Ok(Some(post)) if post.deleted_at.is_none() => {}
+        Ok(_) => return Err(StatusCode::NOT_FOUND),
+        Err(_) => return Err(StatusCode::INTERNAL_SERVER_ERROR),
+    }
+    match state.app_state.post_repository.archive(&id_num).await {
+        Ok(true) => Ok(StatusCode::NO_CONTENT),
+        Ok(false) => Err(StatusCode::NOT_FOUND),
+        Err(_) => Err(StatusCode::INTERNAL_SERVER_ERROR),
+    }
+}
+
+/// Restore an archived or trashed post
+#[utoipa::path(
+    post,
+    path = "/api/admin/posts/{id}/restore",
+    params(
+        ("id" = String, Path, description = "Post ID")
+    ),
+    responses(
+        (status = 204, description = "Successfully restored post"),
+        (status = 404, description = "Post not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Admin"
+)]
+pub async fn restore_post<PR: PostRepository, UR: UserRepository, SB: crate::storage::StorageBackend>(
+    Path(id): Path<String>,
+    State(state): State<Arc<crate::api::post_controller::ExtendedAppState<PR, UR, SB>>>,
+    Extension(_current_user): Extension<Arc<crate::auth::middleware::CurrentUser>>,
+) -> Result<StatusCode, StatusCode> {
+    let id_num: i64 = id.parse().map_err(|_| StatusCode::BAD_REQUEST)?;
+
+    match state.app_state.post_repository.restore(&id_num).await {
+        Ok(true) => Ok(StatusCode::NO_CONTENT),
+        Ok(false) => Err(StatusCode::NOT_FOUND),
+        Err(_) => Err(StatusCode::INTERNAL_SERVER_ERROR),
+    }
+}
+
+/// Permanently delete a trashed post
+///
+/// Deletes the post with its versions and drafts, and the uploaded files that no
+/// other post links to. Only posts in the trash can be purged.
+#[utoipa::path(
+    delete,
+    path = "/api/admin/trash/{id}",
+    params(
+        ("id" = String, Path, description = "Post ID")
+    ),
+    responses(
+        (status = 200, description = "Successfully purged post", body = PurgeTrashResponse),
+        (status = 404, description = "Post not found"),
+        (status = 409, description = "Post is not in the trash"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Admin"
+)]
+pub async fn purge_post<PR: PostRepository, UR: UserRepository, SB: crate::storage::StorageBackend>(
+    Path(id): Path<String>,
+    State(state): State<Arc<crate::api::post_controller::ExtendedAppState<PR, UR, SB>>>,
+    Extension(current_user): Extension<Arc<crate::auth::middleware::CurrentUser>>,
+) -> Result<Json<PurgeTrashResponse>, StatusCode> {
+    let id_num: i64 = id.parse().map_err(|_| StatusCode::BAD_REQUEST)?;
+
+    match state.app_state.post_repository.find_by_id(&id_num).await {
+        Ok(Some(post)) if post.deleted_at.is_some() => {}
+        Ok(Some(_)) => return Err(StatusCode::CONFLICT),
+        Ok(None) => return Err(StatusCode::NOT_FOUND),
+        Err(_) => return Err(StatusCode::INTERNAL_SERVER_ERROR),
+    }
+    let media_files = crate::trash::purge_post(
+        state.app_state.post_repository.as_ref(),
+        state.app_state.media_repository.as_ref(),
+        state.storage.as_ref(),
+        id_num,
+    )
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to purge post {}: {}", id_num, e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?
+    .ok_or(StatusCode::NOT_FOUND)?;
+
+    tracing::info!("Post {} purged by {}", id_num, current_user.username);
+    Ok(Json(PurgeTrashResponse {
+        posts: 1,
+        media_files,
+    }))
+}
+
+/// Empty the trash
+///
+/// Permanently deletes every trashed post, as `DELETE /api/admin/trash/{id}` does
+#[utoipa::path(
+    delete,
+    path = "/api/admin/trash",
+    responses(
+        (status = 200, description = "Successfully emptied the trash", body = PurgeTrashResponse),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Admin"
+)]
+pub async fn empty_trash<PR: PostRepository, UR: UserRepository, SB: crate::storage::StorageBackend>(
+    State(state): State<Arc<crate::api::post_controller::ExtendedAppState<PR, UR, SB>>>,
+    Extension(current_user): Extension<Arc<crate::auth::middleware::CurrentUser>>,
+) -> Result<Json<PurgeTrashResponse>, StatusCode> {
+    let purged = crate::trash::purge_trashed_before(
+        state.app_state.post_repository.as_ref(),
+        state.app_state.media_repository.as_ref(),
+        state.storage.as_ref(),
+        chrono::Utc::now().into(),
+    )
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to empty the trash: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    tracing::info!(
+        "Trash emptied by {}: {} posts, {} media files",
+        current_user.username,
+        purged.posts,
+        purged.media_files
+    );
+    Ok(Json(purged))
+}
@@ -13,6 +13,27 @@ pub async fn get_setting(
     Ok(setting.map(|s| s.value))
 }
 
+/// Get a setting value by key, decoding values stored as JSON by the admin settings API
+///
+/// `update_settings` stores the JSON form of each value (e.g. `"10"` with quotes, or `true`),
+/// while seeded settings are stored as plain strings. This returns the plain string either way.
+pub async fn get_setting_value(
+    db: &Arc<sea_orm::DatabaseConnection>,
+    key: &str,
+) -> Result<Option<String>, sea_orm::DbErr> {
+    Ok(get_setting(db, key).await?.map(|raw| decode_setting_value(&raw)))
+}
+
+/// Decode a raw stored setting value into its plain string form
+pub fn decode_setting_value(raw: &str) -> String {
+    match serde_json::from_str::<serde_json::Value>(raw) {
+        Ok(serde_json::Value::String(s)) => s,
+        Ok(serde_json::Value::Number(n)) => n.to_string(),
+        Ok(serde_json::Value::Bool(b)) => b.to_string(),
+        _ => raw.to_string(),
+    }
+}
+
 /// Set a setting value by key
 pub async fn set_setting(
     db: &Arc<sea_orm::DatabaseConnection>,
@@ -0,0 +1,136 @@
+use crate::dto::{MergeTagsRequest, RenameTagRequest, TagResponse};
+use crate::repository::{PostRepository, UserRepository};
+use crate::slug::slugify;
+use axum::{
+    extract::{Extension, Path, State},
+    http::StatusCode,
+    response::Json,
+};
+use std::sync::Arc;
+
+/// Rename a tag
+///
+/// The slug is regenerated from the new name. Returns 409 if another tag already
+/// has that slug (merge the tags instead).
+#[utoipa::path(
+    put,
+    path = "/api/admin/tags/{id}",
+    params(
+        ("id" = String, Path, description = "Tag ID")
+    ),
+    request_body = RenameTagRequest,
+    responses(
+        (status = 200, description = "Successfully renamed tag", body = TagResponse),
+        (status = 400, description = "Bad request (empty name)"),
+        (status = 404, description = "Tag not found"),
+        (status = 409, description = "Another tag already uses this name"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Admin"
+)]
+pub async fn rename_tag<
+    PR: PostRepository,
+    UR: UserRepository,
+    SB: crate::storage::StorageBackend,
+>(
+    Path(id): Path<String>,
+    State(state): State<Arc<crate::api::post_controller::ExtendedAppState<PR, UR, SB>>>,
+    Extension(_current_user): Extension<Arc<crate::auth::middleware::CurrentUser>>,
+    Json(payload): Json<RenameTagRequest>,
+) -> Result<Json<TagResponse>, StatusCode> {
+    let id_num: i64 = id.parse().map_err(|_| StatusCode::BAD_REQUEST)?;
+    let repository = &state.app_state.post_repository;
+
+    let name = payload
+        .name
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+    let slug = slugify(&name);
+    if slug.is_empty() {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    match repository.find_tag_by_slug(&slug).await {
+        Ok(Some(existing)) if existing.id != id_num => return Err(StatusCode::CONFLICT),
+        Ok(_) => {}
+        Err(e) => {
+            tracing::error!("Error looking up tag: {:?}", e);
+            return Err(StatusCode::INTERNAL_SERVER_ERROR);
+        }
+    }
+
+    let tag = match repository.rename_tag(&id_num, name).await {
+        Ok(Some(tag)) => tag,
+        Ok(None) => return Err(StatusCode::NOT_FOUND),
+        Err(e) => {
+            tracing::error!("Error renaming tag: {:?}", e);
+            return Err(StatusCode::INTERNAL_SERVER_ERROR);
+        }
+    };
+
+    Ok(Json(tag_response(repository.as_ref(), tag).await?))
+}
+
+/// Merge tags
+///
+/// Re-tags every post that has one of the source tags with the target tag,
+/// then deletes the source tags.
+#[utoipa::path(
+    post,
+    path = "/api/admin/tags/merge",
+    request_body = MergeTagsRequest,
+    responses(
+        (status = 200, description = "Successfully merged tags", body = TagResponse),
+        (status = 400, description = "Bad request (no source tags)"),
+        (status = 404, description = "Target tag not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Admin"
+)]
+pub async fn merge_tags<
+    PR: PostRepository,
+    UR: UserRepository,
+    SB: crate::storage::StorageBackend,
+>(
+    State(state): State<Arc<crate::api::post_controller::ExtendedAppState<PR, UR, SB>>>,
+    Extension(_current_user): Extension<Arc<crate::auth::middleware::CurrentUser>>,
+    Json(payload): Json<MergeTagsRequest>,
+) -> Result<Json<TagResponse>, StatusCode> {
+    if payload.source_ids.is_empty() {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    let repository = &state.app_state.post_repository;
+
+    let tag = match repository
+        .merge_tags(&payload.source_ids, &payload.target_id)
+        .await
+    {
+        Ok(Some(tag)) => tag,
+        Ok(None) => return Err(StatusCode::NOT_FOUND),
+        Err(e) => {
+            tracing::error!("Error merging tags: {:?}", e);
+            return Err(StatusCode::INTERNAL_SERVER_ERROR);
+        }
+    };
+
+    Ok(Json(tag_response(repository.as_ref(), tag).await?))
+}
+
+/// Build a tag response with the tag's current post count
+async fn tag_response<PR: PostRepository>(
+    repository: &PR,
+    tag: crate::dto::Tag,
+) -> Result<TagResponse, StatusCode> {
+    let stats = repository.get_tag_stats().await.map_err(|e| {
+        tracing::error!("Error loading tag stats: {:?}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+    let count = stats
+        .into_iter()
+        .find(|(t, _)| t.id == tag.id)
+        .map(|(_, count)| count)
+        .unwrap_or(0);
+
+    Ok(TagResponse::new(tag, count))
+}
@@ -0,0 +1,294 @@
+//! Public comment controller
+//!
+//! Lists approved comments of a post and accepts new comments from guests and
+//! signed-in users. New comments pass through the `filter_comment_created` and
+//! `action_comment_created` plugin hooks.
+
+use crate::api::post_controller::ExtendedAppState;
+use crate::dto::comment::{MAX_COMMENT_AUTHOR_FIELD_LEN, MAX_COMMENT_LEN, build_comment_tree};
+use crate::dto::{CommentResponse, CommentStatus, CreateCommentRequest, NewComment, PostStatus};
+use crate::plugin::exports::rustpress::plugin::event_handler::{
+    OnCommentData, PluginActionEvent, PluginFilterEvent,
+};
+use crate::repository::{PostRepository, UserRepository};
+use axum::{
+    extract::{Extension, Path, State},
+    http::{HeaderMap, StatusCode, header},
+    response::Json,
+};
+use std::sync::Arc;
+
+/// Get the comments of a post
+///
+/// Returns the approved comments of a published post as threads, oldest first
+#[utoipa::path(
+    get,
+    path = "/api/posts/{id}/comments",
+    params(
+        ("id" = String, Path, description = "Post ID")
+    ),
+    responses(
+        (status = 200, description = "Successfully retrieved comments", body = Vec<CommentResponse>),
+        (status = 400, description = "Bad request"),
+        (status = 404, description = "Post not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Comments"
+)]
+pub async fn get_comments<
+    PR: PostRepository,
+    UR: UserRepository,
+    SB: crate::storage::StorageBackend,
+>(
+    Path(id): Path<String>,
+    State(state): State<Arc<ExtendedAppState<PR, UR, SB>>>,
+) -> Result<Json<Vec<CommentResponse>>, StatusCode> {
+    let post_id: i64 = id.parse().map_err(|_| StatusCode::BAD_REQUEST)?;
+    ensure_published(&state, post_id).await?;
+
+    match state
+        .app_state
+        .comment_repository
+        .find_approved_by_post(&post_id)
+        .await
+    {
+        Ok(comments) => Ok(Json(build_comment_tree(comments))),
+        Err(e) => {
+            tracing::error!("Error listing comments: {:?}", e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+/// Post a comment
+///
+/// Guests must give a name; signed-in users comment under their username. The
+/// comment is published right away when the `auto_publish_comments` setting is on
+/// (or the author is an admin), otherwise it waits for moderation.
+#[utoipa::path(
+    post,
+    path = "/api/posts/{id}/comments",
+    params(
+        ("id" = String, Path, description = "Post ID")
+    ),
+    request_body = CreateCommentRequest,
+    responses(
+        (status = 201, description = "Successfully posted comment", body = CommentResponse),
+        (status = 400, description = "Bad request (empty content, missing name, invalid email or URL, unknown parent)"),
+        (status = 404, description = "Post not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Comments"
+)]
+pub async fn create_comment<
+    PR: PostRepository,
+    UR: UserRepository,
+    SB: crate::storage::StorageBackend,
+>(
+    Path(id): Path<String>,
+    State(state): State<Arc<ExtendedAppState<PR, UR, SB>>>,
+    current_user: Option<Extension<Arc<crate::auth::middleware::CurrentUser>>>,
+    headers: HeaderMap,
+    axum::Json(payload): axum::Json<CreateCommentRequest>,
+) -> Result<(StatusCode, Json<CommentResponse>), StatusCode> {
+    let post_id: i64 = id.parse().map_err(|_| StatusCode::BAD_REQUEST)?;
+    ensure_published(&state, post_id).await?;
+    let repository = state.app_state.comment_repository.as_ref();
+
+    let content = payload.content.trim().to_string();
+    if content.is_empty() || content.chars().count() > MAX_COMMENT_LEN {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    // Replies must go to a visible comment on the same post
+    if let Some(parent_id) = payload.parent_id {
+        let parent = repository.find_by_id(&parent_id).await.map_err(|e| {
+            tracing::error!("Error looking up parent comment: {:?}", e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+        match parent {
+            Some(parent)
+                if parent.post_id == post_id && parent.status == CommentStatus::Approved => {}
+            _ => return Err(StatusCode::BAD_REQUEST),
+        }
+    }
+
+    let author_email = optional_field(payload.author_email)?;
+    if author_email
+        .as_ref()
+        .is_some_and(|email| !email.contains('@'))
+    {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    // Only plain web links, so the author link can't run scripts
+    let author_url = optional_field(payload.author_url)?;
+    if author_url
+        .as_ref()
+        .is_some_and(|url| !url.starts_with("http://") && !url.starts_with("https://"))
+    {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    let (author_id, author_name) = match &current_user {
+        Some(Extension(user)) => (Some(user.id), user.username.clone()),
+        None => (
+            None,
+            optional_field(payload.author_name)?.ok_or(StatusCode::BAD_REQUEST)?,
+        ),
+    };
+
+    let is_admin = current_user
+        .as_ref()
+        .is_some_and(|Extension(user)| user.is_admin());
+    let status = if is_admin || auto_publish_comments(&state.db).await {
+        CommentStatus::Approved
+    } else {
+        CommentStatus::Pending
+    };
+
+    let mut comment = NewComment {
+        post_id,
+        parent_id: payload.parent_id,
+        author_id,
+        author_name,
+        author_email,
+        author_url,
+        content,
+        status,
+        ip_address: client_ip(&headers),
+        user_agent: headers
+            .get(header::USER_AGENT)
+            .and_then(|value| value.to_str().ok())
+            .map(|value| value.chars().take(MAX_COMMENT_AUTHOR_FIELD_LEN).collect()),
+    };
+
+    // Plugins can rewrite the content or change the status (e.g. to mark spam)
+    match state
+        .plugin_registry
+        .call_filter_hook(
+            "filter_comment_created",
+            &PluginFilterEvent::OnCommentCreatedFilter(OnCommentData::from(comment.clone())),
+        )
+        .await
+    {
+        Ok(PluginFilterEvent::OnCommentCreatedFilter(data)) => comment.apply_filter(data),
+        Ok(_) => {
+            tracing::error!("Plugin returned unexpected filter event");
+            return Err(StatusCode::INTERNAL_SERVER_ERROR);
+        }
+        Err(e) => {
+            tracing::error!("Error calling filter_comment_created: {:?}", e);
+            return Err(StatusCode::INTERNAL_SERVER_ERROR);
+        }
+    }
+    if comment.content.trim().is_empty() {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    let comment = repository.create(comment).await.map_err(|e| {
+        tracing::error!("Error creating comment: {:?}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    if let Err(e) = state
+        .plugin_registry
+        .call_action_hook(
+            "action_comment_created",
+            &PluginActionEvent::OnCommentCreated(OnCommentData::from(comment.clone())),
+        )
+        .await
+    {
+        tracing::error!("Error calling action_comment_created: {:?}", e);
+    }
+
+    Ok((StatusCode::CREATED, Json(CommentResponse::from(comment))))
+}
+
+/// Fail with 404 unless the post exists and is published
+async fn ensure_published<
+    PR: PostRepository,
+    UR: UserRepository,
+    SB: crate::storage::StorageBackend,
+>(
+    state: &ExtendedAppState<PR, UR, SB>,
+    post_id: i64,
+) -> Result<(), StatusCode> {
+    match state.app_state.post_repository.find_by_id(&post_id).await {
+        Ok(Some(post)) if post.status == PostStatus::Published => Ok(()),
+        Ok(_) => Err(StatusCode::NOT_FOUND),
+        Err(e) => {
+            tracing::error!("Error looking up post: {:?}", e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+/// Trim an optional author field, treating blank values as absent
+fn optional_field(value: Option<String>) -> Result<Option<String>, StatusCode> {
+    match value.map(|value| value.trim().to_string()) {
+        Some(value) if value.chars().count() > MAX_COMMENT_AUTHOR_FIELD_LEN => {
+            Err(StatusCode::BAD_REQUEST)
+        }
+        Some(value) if !value.is_empty() => Ok(Some(value)),
+        _ => Ok(None),
+    }
+}
+
+/// Whether the `auto_publish_comments` setting is on (off when unset)
+async fn auto_publish_comments(db: &Arc<sea_orm::DatabaseConnection>) -> bool {
+    crate::api::admin_api::settings_helper::get_setting_value(db, "auto_publish_comments")
+        .await
+        .ok()
+        .flatten()
+        .is_some_and(|value| value.trim() == "true")
+}
+
+/// Client address as reported by a reverse proxy
+fn client_ip(headers: &HeaderMap) -> Option<String> {
+    headers
+        .get("x-forwarded-for")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.split(',').next())
+        .or_else(|| {
+            headers
+                .get("x-real-ip")
+                .and_then(|value| value.to_str().ok())
+        })
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+// Wrapper functions for OpenAPI documentation (non-generic)
+#[utoipa::path(
+    get,
+    path = "/api/posts/{id}/comments",
+    params(
+        ("id" = String, Path, description = "Post ID")
+    ),
+    responses(
+        (status = 200, description = "Successfully retrieved comments", body = Vec<CommentResponse>),
+        (status = 404, description = "Post not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Comments"
+)]
+#[allow(dead_code)]
+pub(crate) fn get_comments_doc(_id: String) {}
+
+#[utoipa::path(
+    post,
+    path = "/api/posts/{id}/comments",
+    params(
+        ("id" = String, Path, description = "Post ID")
+    ),
+    request_body = CreateCommentRequest,
+    responses(
+        (status = 201, description = "Successfully posted comment", body = CommentResponse),
+        (status = 400, description = "Bad request (empty content, missing name, invalid email or URL, unknown parent)"),
+        (status = 404, description = "Post not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Comments"
+)]
+#[allow(dead_code)]
+pub(crate) fn create_comment_doc(_id: String, _payload: CreateCommentRequest) {}
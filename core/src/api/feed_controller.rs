@@ -0,0 +1,431 @@
+// Feed controller: RSS and Atom feeds of published posts
+use crate::api::admin_api::settings_helper::get_setting_value;
+use crate::api::post_controller::ExtendedAppState;
+use crate::dto::pagination::MAX_PER_PAGE;
+use crate::dto::{PostListQuery, PostStatus};
+use crate::feed::{Feed, FeedEntry, FeedFormat};
+use crate::repository::{PostRepository, UserRepository};
+use crate::slug::encode_path_segment;
+use axum::{
+    extract::{Path, State},
+    http::{HeaderMap, StatusCode, header},
+    response::{IntoResponse, Response},
+};
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::sync::Arc;
+
+/// Number of posts in a feed when the `feed_items` setting is not set
+const DEFAULT_FEED_ITEMS: u64 = 20;
+
+/// Posts a feed is built from
+enum FeedScope {
+    Site,
+    /// Category slug; subcategories are included
+    Category(String),
+    /// Author username
+    Author(String),
+}
+
+/// RSS 2.0 feed of the site
+pub async fn get_rss_feed<
+    PR: PostRepository,
+    UR: UserRepository,
+    SB: crate::storage::StorageBackend,
+>(
+    State(state): State<Arc<ExtendedAppState<PR, UR, SB>>>,
+    headers: HeaderMap,
+) -> Response {
+    feed_response(&state, &headers, FeedScope::Site, FeedFormat::Rss).await
+}
+
+/// Atom feed of the site
+pub async fn get_atom_feed<
+    PR: PostRepository,
+    UR: UserRepository,
+    SB: crate::storage::StorageBackend,
+>(
+    State(state): State<Arc<ExtendedAppState<PR, UR, SB>>>,
+    headers: HeaderMap,
+) -> Response {
+    feed_response(&state, &headers, FeedScope::Site, FeedFormat::Atom).await
+}
+
+/// RSS 2.0 feed of a category and its subcategories
+pub async fn get_category_rss_feed<
+    PR: PostRepository,
+    UR: UserRepository,
+    SB: crate::storage::StorageBackend,
+>(
+    Path(slug): Path<String>,
+    State(state): State<Arc<ExtendedAppState<PR, UR, SB>>>,
+    headers: HeaderMap,
+) -> Response {
+    feed_response(&state, &headers, FeedScope::Category(slug), FeedFormat::Rss).await
+}
+
+/// Atom feed of a category and its subcategories
+pub async fn get_category_atom_feed<
+    PR: PostRepository,
+    UR: UserRepository,
+    SB: crate::storage::StorageBackend,
+>(
+    Path(slug): Path<String>,
+    State(state): State<Arc<ExtendedAppState<PR, UR, SB>>>,
+    headers: HeaderMap,
+) -> Response {
+    feed_response(
+        &state,
+        &headers,
+        FeedScope::Category(slug),
+        FeedFormat::Atom,
+    )
+    .await
+}
+
+/// RSS 2.0 feed of an author
+pub async fn get_author_rss_feed<
+    PR: PostRepository,
+    UR: UserRepository,
+    SB: crate::storage::StorageBackend,
+>(
+    Path(username): Path<String>,
+    State(state): State<Arc<ExtendedAppState<PR, UR, SB>>>,
+    headers: HeaderMap,
+) -> Response {
+    feed_response(
+        &state,
+        &headers,
+        FeedScope::Author(username),
+        FeedFormat::Rss,
+    )
+    .await
+}
+
+/// Atom feed of an author
+pub async fn get_author_atom_feed<
+    PR: PostRepository,
+    UR: UserRepository,
+    SB: crate::storage::StorageBackend,
+>(
+    Path(username): Path<String>,
+    State(state): State<Arc<ExtendedAppState<PR, UR, SB>>>,
+    headers: HeaderMap,
+) -> Response {
+    feed_response(
+        &state,
+        &headers,
+        FeedScope::Author(username),
+        FeedFormat::Atom,
+    )
+    .await
+}
+
+/// Build a feed and answer with it, or with 304 when the client's copy is current
+///
+/// The validators are computed from the posts and settings the feed is built
+/// from, so a conditional request is answered before anything is rendered.
+async fn feed_response<
+    PR: PostRepository,
+    UR: UserRepository,
+    SB: crate::storage::StorageBackend,
+>(
+    state: &ExtendedAppState<PR, UR, SB>,
+    headers: &HeaderMap,
+    scope: FeedScope,
+    format: FeedFormat,
+) -> Response {
+    let source = match load_feed(state, headers, scope).await {
+        Ok(source) => source,
+        Err(status) => return status.into_response(),
+    };
+
+    // Links built from a forgeable Host header must not end up in shared caches
+    if source.site_url.from_request {
+        let xml = source.into_feed(state, format).await.render(format);
+        return (
+            StatusCode::OK,
+            [
+                (header::CACHE_CONTROL, "no-store"),
+                (header::CONTENT_TYPE, format.content_type()),
+            ],
+            xml,
+        )
+            .into_response();
+    }
+
+    let etag = source.etag(format);
+    let updated = source.updated();
+    let cache_headers = [
+        (header::ETAG, etag.clone()),
+        (header::LAST_MODIFIED, http_date(updated)),
+        (
+            header::CACHE_CONTROL,
+            "public, max-age=0, must-revalidate".to_string(),
+        ),
+    ];
+    if not_modified(headers, &etag, updated) {
+        return (StatusCode::NOT_MODIFIED, cache_headers).into_response();
+    }
+
+    let xml = source.into_feed(state, format).await.render(format);
+    (
+        StatusCode::OK,
+        cache_headers,
+        [(header::CONTENT_TYPE, format.content_type())],
+        xml,
+    )
+        .into_response()
+}
+
+/// Posts and settings a feed is built from
+struct FeedSource {
+    site_url: SiteUrl,
+    title: String,
+    description: String,
+    /// Path of the page the feed belongs to, empty for the site feed
+    feed_path: String,
+    /// Newest first
+    posts: Vec<crate::dto::Post>,
+}
+
+impl FeedSource {
+    /// When any post of the feed last changed; an empty feed only changes when
+    /// the first post is published
+    fn updated(&self) -> DateTime<Utc> {
+        self.posts
+            .iter()
+            .map(|post| post.updated_at.with_timezone(&Utc))
+            .max()
+            .unwrap_or(DateTime::UNIX_EPOCH)
+    }
+
+    /// Weak `ETag` of the feed rendered in `format`
+    ///
+    /// Covers everything the feed is rendered from: the settings, and which
+    /// posts it lists and when each last changed.
+    fn etag(&self, format: FeedFormat) -> String {
+        let mut hasher = DefaultHasher::new();
+        (format.content_type(), &self.site_url.url).hash(&mut hasher);
+        (&self.title, &self.description, &self.feed_path).hash(&mut hasher);
+        for post in &self.posts {
+            (post.id, post.updated_at.timestamp_micros()).hash(&mut hasher);
+        }
+        format!("W/\"{:x}\"", hasher.finish())
+    }
+
+    /// Look up the authors and render the posts
+    async fn into_feed<
+        PR: PostRepository,
+        UR: UserRepository,
+        SB: crate::storage::StorageBackend,
+    >(
+        self,
+        state: &ExtendedAppState<PR, UR, SB>,
+        format: FeedFormat,
+    ) -> Feed {
+        let updated = self.updated();
+        let site_url = self.site_url.url;
+
+        let mut authors: HashMap<i64, Option<String>> = HashMap::new();
+        let mut entries = Vec::with_capacity(self.posts.len());
+        for post in self.posts {
+            let author = match authors.get(&post.author_id) {
+                Some(author) => author.clone(),
+                None => {
+                    let author = match state
+                        .app_state
+                        .user_repository
+                        .find_by_id(&post.author_id)
+                        .await
+                    {
+                        Ok(user) => user.map(|user| user.username),
+                        Err(e) => {
+                            tracing::error!("Error loading feed post author: {:?}", e);
+                            None
+                        }
+                    };
+                    authors.insert(post.author_id, author.clone());
+                    author
+                }
+            };
+            let rendered = state.render_cache.render_post(&post);
+            entries.push(FeedEntry::from_post(
+                &site_url,
+                post,
+                author,
+                rendered.html.clone(),
+            ));
+        }
+
+        Feed {
+            title: self.title,
+            description: self.description,
+            link: format!("{}{}/", site_url, self.feed_path),
+            self_link: format!("{}{}/{}", site_url, self.feed_path, format.file_name()),
+            updated,
+            entries,
+        }
+    }
+}
+
+async fn load_feed<PR: PostRepository, UR: UserRepository, SB: crate::storage::StorageBackend>(
+    state: &ExtendedAppState<PR, UR, SB>,
+    headers: &HeaderMap,
+    scope: FeedScope,
+) -> Result<FeedSource, StatusCode> {
+    let site_url = resolve_site_url(&state.db, headers).await;
+    let site_title = get_setting_value(&state.db, "site_title")
+        .await
+        .ok()
+        .flatten()
+        .unwrap_or_else(|| "RustPress".to_string());
+    let site_description = get_setting_value(&state.db, "site_description")
+        .await
+        .ok()
+        .flatten()
+        .unwrap_or_default();
+
+    let mut query = PostListQuery {
+        status: Some(PostStatus::Published),
+        ..Default::default()
+    };
+    let (title, description, feed_path) = match &scope {
+        FeedScope::Site => (site_title, site_description, String::new()),
+        FeedScope::Category(slug) => {
+            let category = state
+                .app_state
+                .post_repository
+                .find_category_by_name_or_slug(slug)
+                .await
+                .map_err(|e| {
+                    tracing::error!("Error loading feed category: {:?}", e);
+                    StatusCode::INTERNAL_SERVER_ERROR
+                })?
+                .filter(|category| category.slug == *slug)
+                .ok_or(StatusCode::NOT_FOUND)?;
+            query.category = Some(category.slug.clone());
+            (
+                format!("{} - {}", site_title, category.name),
+                category.description.unwrap_or(site_description),
+                format!("/category/{}", encode_path_segment(&category.slug)),
+            )
+        }
+        FeedScope::Author(username) => {
+            let author = state
+                .app_state
+                .user_repository
+                .find_by_username(username)
+                .await
+                .map_err(|e| {
+                    tracing::error!("Error loading feed author: {:?}", e);
+                    StatusCode::INTERNAL_SERVER_ERROR
+                })?
+                .ok_or(StatusCode::NOT_FOUND)?;
+            query.author_id = Some(author.id);
+            (
+                format!("{} - {}", site_title, author.username),
+                site_description,
+                format!("/author/{}", encode_path_segment(&author.username)),
+            )
+        }
+    };
+
+    let items = get_setting_value(&state.db, "feed_items")
+        .await
+        .ok()
+        .flatten()
+        .and_then(|value| value.trim().parse::<u64>().ok())
+        .unwrap_or(DEFAULT_FEED_ITEMS)
+        .clamp(1, MAX_PER_PAGE);
+    let (posts, _) = state
+        .app_state
+        .post_repository
+        .find_page(&query, 1, items)
+        .await
+        .map_err(|e| {
+            tracing::error!("Error loading feed posts: {:?}", e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    Ok(FeedSource {
+        site_url,
+        title,
+        description,
+        feed_path,
+        posts,
+    })
+}
+
+/// Public base URL of the site
+pub(crate) struct SiteUrl {
+    /// Without a trailing slash
+    pub url: String,
+    /// Derived from the request headers because the `site_url` setting is empty;
+    /// any client can forge those, so nothing built from it may be cached
+    pub from_request: bool,
+}
+
+/// Public base URL of the site
+///
+/// Taken from the `site_url` setting, or else from the request's `Host` header
+/// (and `X-Forwarded-Proto`, when behind a proxy).
+pub(crate) async fn resolve_site_url(
+    db: &Arc<sea_orm::DatabaseConnection>,
+    headers: &HeaderMap,
+) -> SiteUrl {
+    if let Ok(Some(url)) = get_setting_value(db, "site_url").await
+        && !url.trim().is_empty()
+    {
+        return SiteUrl {
+            url: url.trim().trim_end_matches('/').to_string(),
+            from_request: false,
+        };
+    }
+
+    let header_value = |name: &str| {
+        headers
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.split(',').next())
+            .map(|value| value.trim().to_string())
+            .filter(|value| !value.is_empty())
+    };
+    let host = header_value("x-forwarded-host")
+        .or_else(|| header_value(header::HOST.as_str()))
+        .unwrap_or_else(|| "localhost:3000".to_string());
+    let scheme = header_value("x-forwarded-proto").unwrap_or_else(|| "http".to_string());
+    SiteUrl {
+        url: format!("{}://{}", scheme, host),
+        from_request: true,
+    }
+}
+
+/// Format a time as an HTTP date (`Last-Modified`, `If-Modified-Since`)
+pub(crate) fn http_date(time: DateTime<Utc>) -> String {
+    time.format("%a, %d %b %Y %H:%M:%S GMT").to_string()
+}
+
+/// Whether a conditional GET can be answered with 304 Not Modified
+///
+/// `If-None-Match` takes precedence; `If-Modified-Since` is only used without it.
+pub(crate) fn not_modified(headers: &HeaderMap, etag: &str, last_modified: DateTime<Utc>) -> bool {
+    if let Some(value) = headers.get(header::IF_NONE_MATCH) {
+        let Ok(value) = value.to_str() else {
+            return false;
+        };
+        let etag = etag.trim_start_matches("W/");
+        return value
+            .split(',')
+            .map(str::trim)
+            .any(|tag| tag == "*" || tag.trim_start_matches("W/") == etag);
+    }
+
+    headers
+        .get(header::IF_MODIFIED_SINCE)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| DateTime::parse_from_rfc2822(value).ok())
+        // HTTP dates have no sub-second precision
+        .is_some_and(|since| last_modified.timestamp() <= since.timestamp())
+}
@@ -1,9 +1,11 @@
 pub mod admin_api;
+pub mod comment_controller;
+pub mod feed_controller;
 pub mod page_controller;
 pub mod post_controller;
 pub mod routes;
+pub mod sitemap_controller;
 pub mod upload_controller;
 pub mod user_controller;
 
 pub use routes::*;
-
@@ -1,14 +1,149 @@
 // Page controller for serving frontend SPA
+use crate::api::admin_api::settings_helper::get_setting_value;
+use crate::api::feed_controller::resolve_site_url;
+use crate::api::post_controller::{ExtendedAppState, SlugLookup, moved_permanently, resolve_slug};
+use crate::dto::{Post, PostStatus};
+use crate::repository::{PostRepository, UserRepository};
+use crate::seo::{PageMeta, inject_head};
+use crate::slug::post_path;
 use axum::{
-    http::StatusCode,
-    response::{Html, IntoResponse},
+    extract::{Path, State},
+    http::{HeaderMap, StatusCode},
+    response::{Html, IntoResponse, Response},
 };
+use std::sync::Arc;
 
 /// Serve index.html for SPA routes (fallback for non-API routes)
 pub async fn serve_spa() -> impl IntoResponse {
+    spa_index(StatusCode::OK).await
+}
+
+/// Serve index.html for a post permalink (`/p/:slug`)
+///
+/// Old slugs of renamed posts redirect to the current permalink; unknown slugs
+/// still get the SPA (so it can render its not-found page) but with a 404 status.
+pub async fn serve_post_spa<
+    PR: PostRepository,
+    UR: UserRepository,
+    SB: crate::storage::StorageBackend,
+>(
+    Path(slug): Path<String>,
+    State(state): State<Arc<ExtendedAppState<PR, UR, SB>>>,
+    headers: HeaderMap,
+) -> Response {
+    match resolve_slug(state.app_state.post_repository.as_ref(), &slug).await {
+        Ok(SlugLookup::Current(post)) => post_page(&state, &headers, post).await,
+        Ok(SlugLookup::Renamed(post)) => moved_permanently(&post_path(&post.slug)),
+        Ok(SlugLookup::NotFound) => spa_index(StatusCode::NOT_FOUND).await,
+        Err(status) => status.into_response(),
+    }
+}
+
+/// Serve index.html for a post page (`/posts/:id`)
+///
+/// Published posts get their meta tags filled in; anything else is left to the SPA.
+pub async fn serve_post_by_id_spa<
+    PR: PostRepository,
+    UR: UserRepository,
+    SB: crate::storage::StorageBackend,
+>(
+    Path(id): Path<String>,
+    State(state): State<Arc<ExtendedAppState<PR, UR, SB>>>,
+    headers: HeaderMap,
+) -> Response {
+    // Other pages under /posts/ (e.g. /posts/create)
+    let Ok(id) = id.parse::<i64>() else {
+        return spa_index(StatusCode::OK).await;
+    };
+
+    match state.app_state.post_repository.find_by_id(&id).await {
+        Ok(Some(post)) => post_page(&state, &headers, post).await,
+        Ok(None) => spa_index(StatusCode::NOT_FOUND).await,
+        Err(e) => {
+            tracing::error!("Error loading post page: {:?}", e);
+            StatusCode::INTERNAL_SERVER_ERROR.into_response()
+        }
+    }
+}
+
+/// index.html with the post's title, description, canonical link, social cards and JSON-LD
+///
+/// Unpublished posts get the plain index.html, so nothing about them leaks into the head.
+async fn post_page<PR: PostRepository, UR: UserRepository, SB: crate::storage::StorageBackend>(
+    state: &ExtendedAppState<PR, UR, SB>,
+    headers: &HeaderMap,
+    post: Post,
+) -> Response {
+    if post.status != PostStatus::Published || post.deleted_at.is_some() {
+        return spa_index(StatusCode::OK).await;
+    }
+    let Ok(template) = tokio::fs::read_to_string("frontend/dist/index.html").await else {
+        return (StatusCode::NOT_FOUND, "index.html not found").into_response();
+    };
+
+    let site_url = resolve_site_url(&state.db, headers).await;
+    let site_name = get_setting_value(&state.db, "site_title")
+        .await
+        .ok()
+        .flatten()
+        .unwrap_or_else(|| "RustPress".to_string());
+
+    // Tags with links from the request's Host header are built per request, so
+    // one forged header can't poison them for everyone
+    if site_url.from_request {
+        let tags = post_meta(state, &site_url.url, site_name, &post)
+            .await
+            .head_tags();
+        return Html(inject_head(&template, &tags)).into_response();
+    }
+
+    let fingerprint = format!("{}|{}|{}", post.revision, site_url.url, site_name);
+    let head_tags = match state.page_meta_cache.get(post.id, &fingerprint) {
+        Some(tags) => tags,
+        None => {
+            let tags = Arc::new(
+                post_meta(state, &site_url.url, site_name, &post)
+                    .await
+                    .head_tags(),
+            );
+            state
+                .page_meta_cache
+                .insert(post.id, fingerprint, tags.clone());
+            tags
+        }
+    };
+
+    Html(inject_head(&template, &head_tags)).into_response()
+}
+
+async fn post_meta<PR: PostRepository, UR: UserRepository, SB: crate::storage::StorageBackend>(
+    state: &ExtendedAppState<PR, UR, SB>,
+    site_url: &str,
+    site_name: String,
+    post: &Post,
+) -> PageMeta {
+    let author = match state
+        .app_state
+        .user_repository
+        .find_by_id(&post.author_id)
+        .await
+    {
+        Ok(user) => user.map(|user| user.username),
+        Err(e) => {
+            tracing::error!("Error loading post author: {:?}", e);
+            None
+        }
+    };
+
+    let rendered = state.render_cache.render_post(post);
+    PageMeta::for_post(site_url, site_name, post, author, &rendered.html)
+}
+
+/// Read the main frontend's index.html and serve it with `status`
+async fn spa_index(status: StatusCode) -> Response {
     // Serve index.html for SPA routing
     match tokio::fs::read_to_string("frontend/dist/index.html").await {
-        Ok(html) => Html(html).into_response(),
+        Ok(html) => (status, Html(html)).into_response(),
         Err(_) => (StatusCode::NOT_FOUND, "index.html not found").into_response(),
     }
 }
@@ -28,4 +163,3 @@ pub async fn serve_admin_spa() -> impl IntoResponse {
         }
     }
 }
-// This module is kept for future SSR support
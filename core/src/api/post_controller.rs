@@ -1,32 +1,54 @@
+use crate::dto::category::build_category_tree;
+use crate::dto::pagination::MAX_PER_PAGE;
 use crate::dto::plugin::PluginHook;
+use crate::dto::post_diff::{CURRENT_VERSION, DiffSide, PostVersionDiff, diff_text};
 use crate::dto::{
-    self, CreatePostRequest, CreateUserRequest, LoginRequest, LoginResponse, Post,
-    PostDraftResponse, PostResponse, PostVersionResponse, RefreshTokenRequest,
-    RefreshTokenResponse, SaveDraftRequest, UpdatePostRequest, UserResponse, UserRole,
+    self, Category, CategoryTreeNode, CreatePostRequest, CreateUserRequest, LoginRequest,
+    LoginResponse, Paginated, Post, PostDraftResponse, PostListQuery, PostListResponse,
+    PostRenderQuery, PostResponse, PostSearchHit, PostSearchQuery, PostSearchResponse,
+    PostSortField, PostStatus, PostTransition, PostTransitionRequest, PostVersionResponse,
+    RefreshTokenRequest, RefreshTokenResponse, SaveDraftRequest, SortOrder, TagResponse,
+    UpdatePostRequest, UserResponse, UserRole,
 };
 use crate::plugin::exports::rustpress::plugin::event_handler::{
-    OnPostPublishedData, PluginFilterEvent,
+    OnPostPublishedData, OnPostStatusChangedData, PluginActionEvent, PluginFilterEvent,
 };
-use crate::repository::{PostRepository, UserRepository};
+use crate::repository::{
+    CommentRepository, MediaRepository, PostRepository, RevisionConflict, SlugTaken, UserRepository,
+};
+use crate::slug::post_slug;
 use axum::{
-    extract::{Extension, Path, State},
-    http::StatusCode,
-    response::Json,
+    extract::{Extension, Path, Query, State},
+    http::{HeaderMap, StatusCode, header},
+    response::{IntoResponse, Json, Response},
 };
+use chrono::{DateTime, Utc};
 use std::sync::Arc;
 use utoipa::OpenApi;
 
-/// Application state, containing Post and User Repository
+/// Page size used when neither the request nor the `posts_per_page` setting provides one
+const DEFAULT_POSTS_PER_PAGE: u64 = 10;
+
+/// Application state, containing Post, User, Comment and Media Repository
 pub struct AppState<PR: PostRepository, UR: UserRepository> {
     pub post_repository: Arc<PR>,
     pub user_repository: Arc<UR>,
+    pub comment_repository: Arc<dyn CommentRepository>,
+    pub media_repository: Arc<dyn MediaRepository>,
 }
 
 impl<PR: PostRepository, UR: UserRepository> AppState<PR, UR> {
-    pub fn new(post_repository: PR, user_repository: UR) -> Self {
+    pub fn new(
+        post_repository: PR,
+        user_repository: UR,
+        comment_repository: impl CommentRepository + 'static,
+        media_repository: impl MediaRepository + 'static,
+    ) -> Self {
         Self {
             post_repository: Arc::new(post_repository),
             user_repository: Arc::new(user_repository),
+            comment_repository: Arc::new(comment_repository),
+            media_repository: Arc::new(media_repository),
         }
     }
 }
@@ -41,6 +63,10 @@ pub struct ExtendedAppState<
     pub storage: Arc<SB>,
     pub db: Arc<sea_orm::DatabaseConnection>,
     pub plugin_registry: Arc<crate::plugin::registry::PluginRegistry>,
+    /// Rendered post contents, shared by all handlers that return HTML
+    pub render_cache: Arc<crate::markdown::RenderCache>,
+    /// Head tags of server-rendered post pages
+    pub page_meta_cache: Arc<crate::seo::PageMetaCache>,
 }
 
 impl<PR: PostRepository, UR: UserRepository, SB: crate::storage::StorageBackend>
@@ -57,18 +83,32 @@ impl<PR: PostRepository, UR: UserRepository, SB: crate::storage::StorageBackend>
             storage,
             db: Arc::new(db),
             plugin_registry,
+            render_cache: Arc::new(crate::markdown::RenderCache::new()),
+            page_meta_cache: Arc::new(crate::seo::PageMetaCache::new()),
+        }
+    }
+
+    /// Response DTO for a post, with its content rendered to HTML when `render` is set
+    pub fn post_response(&self, post: Post, render: bool) -> PostResponse {
+        let rendered = render.then(|| self.render_cache.render_post(&post));
+        let response = PostResponse::from(post);
+        match rendered {
+            Some(rendered) => response.with_rendered(&rendered),
+            None => response,
         }
     }
 }
 
-/// Get all posts
+/// Get posts
 ///
-/// Returns a list of all posts, sorted by creation time in descending order
+/// Returns one page of published posts matching the filters, sorted by creation time in descending order by default
 #[utoipa::path(
     get,
     path = "/api/posts",
+    params(PostListQuery, PostRenderQuery),
     responses(
-        (status = 200, description = "Successfully retrieved post list", body = Vec<PostResponse>),
+        (status = 200, description = "Successfully retrieved post list", body = PostListResponse),
+        (status = 400, description = "Bad request"),
         (status = 500, description = "Internal server error")
     ),
     tag = "Posts"
@@ -79,21 +119,290 @@ pub async fn get_posts<
     SB: crate::storage::StorageBackend,
 >(
     State(state): State<Arc<ExtendedAppState<PR, UR, SB>>>,
-) -> Result<Json<Vec<PostResponse>>, StatusCode> {
-    match state.app_state.post_repository.find_all().await {
-        Ok(posts) => Ok(Json(posts.into_iter().map(PostResponse::from).collect())),
-        Err(_) => Err(StatusCode::INTERNAL_SERVER_ERROR),
+    Query(mut query): Query<PostListQuery>,
+    Query(render): Query<PostRenderQuery>,
+) -> Result<Json<PostListResponse>, StatusCode> {
+    // Unpublished posts are only listed through the admin API
+    query.status = Some(PostStatus::Published);
+    let (page, per_page) = resolve_pagination(&state.db, query.page, query.per_page).await;
+
+    match state
+        .app_state
+        .post_repository
+        .find_page(&query, page, per_page)
+        .await
+    {
+        Ok((posts, total)) => Ok(Json(
+            Paginated::new(posts, total, page, per_page)
+                .map(|post| state.post_response(post, render.render)),
+        )),
+        Err(e) => {
+            tracing::error!("Error listing posts: {:?}", e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+/// Resolve page and page size for a listing query
+///
+/// `per_page` falls back to the `posts_per_page` setting and is clamped to `1..=MAX_PER_PAGE`
+pub(crate) async fn resolve_pagination(
+    db: &Arc<sea_orm::DatabaseConnection>,
+    page: Option<u64>,
+    per_page: Option<u64>,
+) -> (u64, u64) {
+    let per_page = match per_page {
+        Some(per_page) => per_page,
+        None => crate::api::admin_api::settings_helper::get_setting_value(db, "posts_per_page")
+            .await
+            .ok()
+            .flatten()
+            .and_then(|value| value.trim().parse::<u64>().ok())
+            .unwrap_or(DEFAULT_POSTS_PER_PAGE),
+    };
+
+    (page.unwrap_or(1).max(1), per_page.clamp(1, MAX_PER_PAGE))
+}
+
+/// `ETag` header value for a post revision
+pub(crate) fn post_etag(revision: i32) -> String {
+    format!("\"{}\"", revision)
+}
+
+/// Whether the request's `If-Match` header, if any, matches the post's current revision
+///
+/// Accepts `*`, a list of entity tags (weak or strong) and bare revision numbers.
+fn if_match_satisfied(headers: &HeaderMap, post: &Post) -> bool {
+    let Some(value) = headers.get(header::IF_MATCH) else {
+        return true;
+    };
+    let Ok(value) = value.to_str() else {
+        return false;
+    };
+    let revision = post.revision.to_string();
+    value
+        .split(',')
+        .map(str::trim)
+        .any(|tag| tag == "*" || tag.trim_start_matches("W/").trim_matches('"') == revision)
+}
+
+/// Response with a post and its `ETag`
+fn post_with_etag(status: StatusCode, post: PostResponse) -> Response {
+    (
+        status,
+        [(header::ETAG, post_etag(post.revision))],
+        Json(post),
+    )
+        .into_response()
+}
+
+/// 409 response holding the post as currently stored, for clients whose `If-Match` is outdated
+fn revision_conflict(post: Post) -> Response {
+    post_with_etag(StatusCode::CONFLICT, PostResponse::from(post))
+}
+
+/// Resolve the category of a request, given either by id or by name/slug
+///
+/// Returns `Ok(None)` when neither is given and `Ok(Some(None))` when an empty name clears
+/// the category. Unknown categories are a bad request.
+pub(crate) async fn resolve_category<PR: PostRepository>(
+    repository: &PR,
+    category_id: Option<i64>,
+    category: Option<&str>,
+) -> Result<Option<Option<Category>>, StatusCode> {
+    let found = if let Some(id) = category_id {
+        repository.find_category_by_id(&id).await
+    } else {
+        match category.map(str::trim) {
+            None => return Ok(None),
+            Some("") => return Ok(Some(None)),
+            Some(name) => repository.find_category_by_name_or_slug(name).await,
+        }
+    };
+
+    match found {
+        Ok(Some(category)) => Ok(Some(Some(category))),
+        Ok(None) => Err(StatusCode::BAD_REQUEST),
+        Err(e) => {
+            tracing::error!("Error looking up category: {:?}", e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+/// Category referenced by the `default_category` setting, if it still exists
+pub(crate) async fn default_category<PR: PostRepository>(
+    repository: &PR,
+    db: &Arc<sea_orm::DatabaseConnection>,
+) -> Option<Category> {
+    let id = crate::api::admin_api::settings_helper::get_setting_value(db, "default_category")
+        .await
+        .ok()
+        .flatten()
+        .and_then(|value| value.trim().parse::<i64>().ok())?;
+    repository.find_category_by_id(&id).await.ok().flatten()
+}
+
+/// Search posts
+///
+/// Full-text search over post titles, descriptions and content, ranked by relevance.
+/// Matched terms are wrapped in `<mark>` in `title_highlight` and `snippet`.
+#[utoipa::path(
+    get,
+    path = "/api/posts/search",
+    params(PostSearchQuery),
+    responses(
+        (status = 200, description = "Successfully searched posts", body = PostSearchResponse),
+        (status = 400, description = "Bad request (missing or empty query)"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Posts"
+)]
+pub async fn search_posts<
+    PR: PostRepository,
+    UR: UserRepository,
+    SB: crate::storage::StorageBackend,
+>(
+    State(state): State<Arc<ExtendedAppState<PR, UR, SB>>>,
+    Query(query): Query<PostSearchQuery>,
+) -> Result<Json<PostSearchResponse>, StatusCode> {
+    if query.q.trim().is_empty() {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    let (page, per_page) = resolve_pagination(&state.db, query.page, query.per_page).await;
+
+    match state
+        .app_state
+        .post_repository
+        .search(&query, page, per_page)
+        .await
+    {
+        Ok((hits, total)) => Ok(Json(Paginated::new(hits, total, page, per_page))),
+        Err(e) => {
+            tracing::error!("Error searching posts: {:?}", e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+/// List tags
+///
+/// Get all tags with the number of posts using each, ordered by name
+#[utoipa::path(
+    get,
+    path = "/api/tags",
+    responses(
+        (status = 200, description = "Successfully retrieved tags", body = Vec<TagResponse>),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Posts"
+)]
+pub async fn get_tags<
+    PR: PostRepository,
+    UR: UserRepository,
+    SB: crate::storage::StorageBackend,
+>(
+    State(state): State<Arc<ExtendedAppState<PR, UR, SB>>>,
+) -> Result<Json<Vec<TagResponse>>, StatusCode> {
+    match state.app_state.post_repository.get_tag_stats().await {
+        Ok(stats) => Ok(Json(
+            stats
+                .into_iter()
+                .map(|(tag, count)| TagResponse::new(tag, count))
+                .collect(),
+        )),
+        Err(e) => {
+            tracing::error!("Error listing tags: {:?}", e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+/// List categories
+///
+/// Returns the category tree; each node has the number of posts directly in it
+#[utoipa::path(
+    get,
+    path = "/api/categories",
+    responses(
+        (status = 200, description = "Successfully retrieved categories", body = Vec<CategoryTreeNode>),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Posts"
+)]
+pub async fn get_categories<
+    PR: PostRepository,
+    UR: UserRepository,
+    SB: crate::storage::StorageBackend,
+>(
+    State(state): State<Arc<ExtendedAppState<PR, UR, SB>>>,
+) -> Result<Json<Vec<CategoryTreeNode>>, StatusCode> {
+    match state.app_state.post_repository.get_category_stats().await {
+        Ok(categories) => Ok(Json(build_category_tree(categories))),
+        Err(e) => {
+            tracing::error!("Error listing categories: {:?}", e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
     }
 }
 
+/// List posts by tag
+///
+/// Accepts the same pagination, filter and sort parameters as `GET /api/posts`
+#[utoipa::path(
+    get,
+    path = "/api/tags/{slug}/posts",
+    params(
+        ("slug" = String, Path, description = "Tag slug"),
+        PostListQuery,
+        PostRenderQuery
+    ),
+    responses(
+        (status = 200, description = "Successfully retrieved post list", body = PostListResponse),
+        (status = 404, description = "Tag not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Posts"
+)]
+pub async fn get_posts_by_tag<
+    PR: PostRepository,
+    UR: UserRepository,
+    SB: crate::storage::StorageBackend,
+>(
+    Path(slug): Path<String>,
+    State(state): State<Arc<ExtendedAppState<PR, UR, SB>>>,
+    Query(mut query): Query<PostListQuery>,
+    render: Query<PostRenderQuery>,
+) -> Result<Json<PostListResponse>, StatusCode> {
+    match state
+        .app_state
+        .post_repository
+        .find_tag_by_slug(&slug)
+        .await
+    {
+        Ok(Some(_)) => {}
+        Ok(None) => return Err(StatusCode::NOT_FOUND),
+        Err(e) => {
+            tracing::error!("Error looking up tag: {:?}", e);
+            return Err(StatusCode::INTERNAL_SERVER_ERROR);
+        }
+    }
+
+    query.tag = Some(slug);
+    get_posts(State(state), Query(query), render).await
+}
+
 /// Get a single post by ID
 ///
-/// Get detailed information of a single post by post ID
+/// Get detailed information of a single post by post ID. Posts that are not published
+/// are only visible to their author and admins. The `ETag` header holds the post's revision.
 #[utoipa::path(
     get,
     path = "/api/posts/{id}",
     params(
-        ("id" = String, Path, description = "Post ID")
+        ("id" = String, Path, description = "Post ID"),
+        PostRenderQuery
     ),
     responses(
         (status = 200, description = "Successfully retrieved post", body = PostResponse),
@@ -110,19 +419,130 @@ pub async fn get_post<
 >(
     Path(id): Path<String>,
     State(state): State<Arc<ExtendedAppState<PR, UR, SB>>>,
-) -> Result<Json<PostResponse>, StatusCode> {
+    current_user: Option<Extension<Arc<crate::auth::middleware::CurrentUser>>>,
+    Query(render): Query<PostRenderQuery>,
+) -> Result<Response, StatusCode> {
     let id_num: i64 = match id.parse() {
         Ok(num) => num,
         Err(_) => return Err(StatusCode::BAD_REQUEST),
     };
 
     match state.app_state.post_repository.find_by_id(&id_num).await {
-        Ok(Some(post)) => Ok(Json(PostResponse::from(post))),
+        Ok(Some(post))
+            if post.status == PostStatus::Published
+                || current_user
+                    .is_some_and(|Extension(user)| user.is_author_or_admin(post.author_id)) =>
+        {
+            Ok(post_with_etag(
+                StatusCode::OK,
+                state.post_response(post, render.render),
+            ))
+        }
+        Ok(Some(_)) => Err(StatusCode::NOT_FOUND),
         Ok(None) => Err(StatusCode::NOT_FOUND),
         Err(_) => Err(StatusCode::INTERNAL_SERVER_ERROR),
     }
 }
 
+/// Get a single post by slug
+///
+/// Answers with a 301 redirect to the current slug when the post has been renamed
+#[utoipa::path(
+    get,
+    path = "/api/posts/by-slug/{slug}",
+    params(
+        ("slug" = String, Path, description = "Post slug"),
+        PostRenderQuery
+    ),
+    responses(
+        (status = 200, description = "Successfully retrieved post", body = PostResponse),
+        (status = 301, description = "Post was renamed; Location holds its current URL"),
+        (status = 404, description = "Post not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Posts"
+)]
+pub async fn get_post_by_slug<
+    PR: PostRepository,
+    UR: UserRepository,
+    SB: crate::storage::StorageBackend,
+>(
+    Path(slug): Path<String>,
+    State(state): State<Arc<ExtendedAppState<PR, UR, SB>>>,
+    Query(render): Query<PostRenderQuery>,
+) -> Result<Response, StatusCode> {
+    match resolve_slug(state.app_state.post_repository.as_ref(), &slug).await? {
+        SlugLookup::Current(post) => {
+            Ok(Json(state.post_response(post, render.render)).into_response())
+        }
+        SlugLookup::Renamed(post) => Ok(moved_permanently(&format!(
+            "/api/posts/by-slug/{}{}",
+            post.slug,
+            if render.render { "?render=true" } else { "" }
+        ))),
+        SlugLookup::NotFound => Err(StatusCode::NOT_FOUND),
+    }
+}
+
+/// Result of looking up a post by slug
+pub(crate) enum SlugLookup {
+    /// The slug is the post's current slug
+    Current(Post),
+    /// The slug used to belong to the post, which has since been renamed
+    Renamed(Post),
+    NotFound,
+}
+
+/// Look up a published post by its current or a previous slug
+pub(crate) async fn resolve_slug<PR: PostRepository>(
+    repository: &PR,
+    slug: &str,
+) -> Result<SlugLookup, StatusCode> {
+    let lookup = match repository.find_by_slug(slug).await {
+        Ok(Some(post)) if post.status == PostStatus::Published => {
+            return Ok(SlugLookup::Current(post));
+        }
+        Ok(Some(_)) => return Ok(SlugLookup::NotFound),
+        Ok(None) => repository.find_by_previous_slug(slug).await,
+        Err(e) => Err(e),
+    };
+
+    match lookup {
+        Ok(Some(post)) if post.status == PostStatus::Published => Ok(SlugLookup::Renamed(post)),
+        Ok(_) => Ok(SlugLookup::NotFound),
+        Err(e) => {
+            tracing::error!("Error looking up post by slug: {:?}", e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+/// 301 redirect to `location`
+pub(crate) fn moved_permanently(location: &str) -> Response {
+    (
+        StatusCode::MOVED_PERMANENTLY,
+        [(header::LOCATION, location.to_string())],
+    )
+        .into_response()
+}
+
+/// Normalize a requested slug, failing with 409 if another post already uses it
+async fn claim_slug<PR: PostRepository>(
+    repository: &PR,
+    slug: &str,
+    post_id: Option<i64>,
+) -> Result<String, StatusCode> {
+    let slug = post_slug(slug);
+    match repository.is_slug_taken(&slug, post_id).await {
+        Ok(false) => Ok(slug),
+        Ok(true) => Err(StatusCode::CONFLICT),
+        Err(e) => {
+            tracing::error!("Error checking slug: {:?}", e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
 /// Create a new post
 ///
 /// Create a new post
@@ -133,6 +553,8 @@ pub async fn get_post<
     responses(
         (status = 201, description = "Successfully created post", body = PostResponse),
         (status = 400, description = "Bad request"),
+        (status = 403, description = "Not allowed to create a post in the requested status"),
+        (status = 409, description = "Another post already uses the requested slug, or the post changed since the revision in `If-Match` (body holds the current post)"),
         (status = 500, description = "Internal server error")
     ),
     tag = "Posts"
@@ -148,15 +570,131 @@ pub async fn create_post<
 ) -> Result<(axum::http::StatusCode, Json<PostResponse>), StatusCode> {
     // User must be authenticated (already checked by middleware)
 
+    let repository = state.app_state.post_repository.as_ref();
+    let category =
+        match resolve_category(repository, payload.category_id, payload.category.as_deref()).await?
+        {
+            Some(category) => category,
+            None => default_category(repository, &state.db).await,
+        };
+    let slug = match payload.slug.as_deref() {
+        Some(slug) => Some(claim_slug(repository, slug, None).await?),
+        None => None,
+    };
+    let status = initial_status(&current_user, payload.status, payload.publish_at)?;
+    check_schedule(payload.publish_at, payload.unpublish_at)?;
+    let (publish_at, unpublish_at) = (payload.publish_at, payload.unpublish_at);
+
     // Parse JSON payload into initial_post_data (dto::post::Post)
     let mut initial_post_data: CreatePostRequest = payload.into();
     initial_post_data.author_id = current_user.id;
+    initial_post_data.category = category.as_ref().map(|category| category.name.clone());
+
+    // Plugins filter posts as they go public; drafts and posts under review wait for that
+    let mut create_request = if status == PostStatus::Published {
+        let mut filtered = run_publish_filter(&state.plugin_registry, initial_post_data).await?;
+        filtered.category_id =
+            plugin_category_id(repository, filtered.category.as_deref(), category.as_ref()).await?;
+        filtered
+    } else {
+        initial_post_data.category_id = category.as_ref().map(|category| category.id);
+        initial_post_data
+    };
+    create_request.slug = slug;
+    create_request.status = Some(status);
+    create_request.publish_at = match status {
+        // Published posts record when they went live; they cannot go live in the future
+        PostStatus::Published => Some(
+            publish_at
+                .filter(|publish_at| *publish_at <= Utc::now())
+                .unwrap_or_else(Utc::now),
+        ),
+        _ => publish_at,
+    };
+    create_request.unpublish_at = unpublish_at;
+
+    let post = state
+        .app_state
+        .post_repository
+        .create(create_request)
+        .await
+        .map_err(|e| {
+            // Another post took the slug after it was checked
+            if e.is::<SlugTaken>() {
+                return StatusCode::CONFLICT;
+            }
+            tracing::error!("Error creating post: {:?}", e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
 
-    let filtered_post_data = state
-        .plugin_registry
+    let hook = match post.status {
+        PostStatus::Published => Some(PostTransition::Publish),
+        PostStatus::PendingReview => Some(PostTransition::Submit),
+        PostStatus::Scheduled => Some(PostTransition::Schedule),
+        _ => None,
+    };
+    if let Some(transition) = hook {
+        fire_status_hook(
+            &state.plugin_registry,
+            &post,
+            None,
+            transition,
+            Some(current_user.id),
+        )
+        .await;
+    }
+
+    Ok((StatusCode::CREATED, Json(PostResponse::from(post))))
+}
+
+/// Status a new post starts in
+///
+/// Admins may create posts in any status and default to `published`, or `scheduled`
+/// when `publish_at` lies in the future; other users may only create drafts or
+/// submit for review, which is the default.
+fn initial_status(
+    current_user: &crate::auth::middleware::CurrentUser,
+    requested: Option<PostStatus>,
+    publish_at: Option<DateTime<Utc>>,
+) -> Result<PostStatus, StatusCode> {
+    let in_future = publish_at.is_some_and(|publish_at| publish_at > Utc::now());
+    if current_user.is_admin() {
+        match requested {
+            Some(PostStatus::Scheduled) if !in_future => Err(StatusCode::BAD_REQUEST),
+            Some(status) => Ok(status),
+            None if in_future => Ok(PostStatus::Scheduled),
+            None => Ok(PostStatus::Published),
+        }
+    } else {
+        match requested.unwrap_or(PostStatus::PendingReview) {
+            status @ (PostStatus::Draft | PostStatus::PendingReview) => Ok(status),
+            _ => Err(StatusCode::FORBIDDEN),
+        }
+    }
+}
+
+/// Fail with 400 unless `unpublish_at` comes after `publish_at`
+fn check_schedule<T: PartialOrd>(
+    publish_at: Option<T>,
+    unpublish_at: Option<T>,
+) -> Result<(), StatusCode> {
+    match (publish_at, unpublish_at) {
+        (Some(publish_at), Some(unpublish_at)) if unpublish_at <= publish_at => {
+            Err(StatusCode::BAD_REQUEST)
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Run the `filter_post_published` plugin hook over a post about to be published
+async fn run_publish_filter(
+    plugin_registry: &crate::plugin::registry::PluginRegistry,
+    post: CreatePostRequest,
+) -> Result<CreatePostRequest, StatusCode> {
+    let filtered_post_data = plugin_registry
         .call_filter_hook(
             "filter_post_published",
-            &PluginFilterEvent::OnPostPublishedFilter(OnPostPublishedData::from(initial_post_data)),
+            &PluginFilterEvent::OnPostPublishedFilter(OnPostPublishedData::from(post)),
         )
         .await;
 
@@ -165,8 +703,8 @@ pub async fn create_post<
             PluginFilterEvent::OnPostPublishedFilter(on_post_published_data) => {
                 on_post_published_data
             }
-            PluginFilterEvent::Unknown => {
-                tracing::error!("Plugin returned unknown filter event");
+            _ => {
+                tracing::error!("Plugin returned unexpected filter event");
                 return Err(StatusCode::INTERNAL_SERVER_ERROR);
             }
         },
@@ -178,21 +716,231 @@ pub async fn create_post<
 
     tracing::info!("Filtered post data: {:?}", filtered_post_data);
 
-    let post = state
-        .app_state
-        .post_repository
-        .create(filtered_post_data.into())
+    Ok(filtered_post_data.into())
+}
+
+/// Run the `filter_post_published` hook over an existing post and apply the result
+pub(crate) async fn filter_existing_post<PR: PostRepository>(
+    repository: &PR,
+    plugin_registry: &crate::plugin::registry::PluginRegistry,
+    post: &mut Post,
+) -> Result<(), StatusCode> {
+    let original_category = match post.category_id {
+        Some(category_id) => repository
+            .find_category_by_id(&category_id)
+            .await
+            .map_err(|e| {
+                tracing::error!("Error looking up category: {:?}", e);
+                StatusCode::INTERNAL_SERVER_ERROR
+            })?,
+        None => None,
+    };
+    let filtered = run_publish_filter(
+        plugin_registry,
+        CreatePostRequest {
+            title: post.title.clone(),
+            slug: None,
+            status: Some(PostStatus::Published),
+            publish_at: None,
+            unpublish_at: None,
+            content: post.content.clone(),
+            category: post.category.clone(),
+            category_id: post.category_id,
+            author_id: post.author_id,
+            description: post.description.clone(),
+            tags: post.tags.clone(),
+        },
+    )
+    .await?;
+
+    post.category_id = plugin_category_id(
+        repository,
+        filtered.category.as_deref(),
+        original_category.as_ref(),
+    )
+    .await?;
+    post.category = filtered.category;
+    post.title = filtered.title;
+    post.content = filtered.content;
+    post.description = filtered.description;
+    post.tags = filtered.tags;
+    Ok(())
+}
+
+/// Map the category name returned by a plugin back to a category id
+///
+/// Plugins see and return the category by name; unknown names keep `original`.
+async fn plugin_category_id<PR: PostRepository>(
+    repository: &PR,
+    name: Option<&str>,
+    original: Option<&Category>,
+) -> Result<Option<i64>, StatusCode> {
+    match name.map(str::trim) {
+        None | Some("") => Ok(None),
+        Some(name) if Some(name) == original.map(|c| c.name.as_str()) => Ok(original.map(|c| c.id)),
+        Some(name) => match repository.find_category_by_name_or_slug(name).await {
+            Ok(Some(found)) => Ok(Some(found.id)),
+            Ok(None) => {
+                tracing::warn!(
+                    "Plugin returned unknown category {:?}, keeping the original",
+                    name
+                );
+                Ok(original.map(|c| c.id))
+            }
+            Err(e) => {
+                tracing::error!("Error looking up category: {:?}", e);
+                Err(StatusCode::INTERNAL_SERVER_ERROR)
+            }
+        },
+    }
+}
+
+/// Fire the action hook of a status transition
+///
+/// `from` is `None` for posts that were just created and `actor_id` is `None` for
+/// transitions made by the scheduler. Hook failures are only logged.
+pub(crate) async fn fire_status_hook(
+    plugin_registry: &crate::plugin::registry::PluginRegistry,
+    post: &Post,
+    from: Option<PostStatus>,
+    transition: PostTransition,
+    actor_id: Option<i64>,
+) {
+    let event = PluginActionEvent::OnPostStatusChanged(OnPostStatusChangedData {
+        post_id: post.id,
+        title: post.title.clone(),
+        author_id: post.author_id,
+        from_status: from.map(|status| status.as_str().to_string()),
+        to_status: post.status.as_str().to_string(),
+        action: transition.as_str().to_string(),
+        actor_id,
+        note: post.review_note.clone(),
+    });
+
+    if let Err(e) = plugin_registry
+        .call_action_hook(transition.hook_name(), &event)
         .await
-        .map_err(|e| {
-            tracing::error!("Error creating post: {:?}", e);
-            StatusCode::INTERNAL_SERVER_ERROR
-        })?;
-    Ok((StatusCode::CREATED, Json(PostResponse::from(post))))
+    {
+        tracing::error!("Error calling {}: {:?}", transition.hook_name(), e);
+    }
+}
+
+/// Change the status of a post
+///
+/// Authors can submit their drafts for review, withdraw them and unpublish their posts;
+/// every other transition requires an admin. Rejecting a post requires a note for its author.
+#[utoipa::path(
+    post,
+    path = "/api/posts/{id}/transitions",
+    params(
+        ("id" = String, Path, description = "Post ID")
+    ),
+    request_body = PostTransitionRequest,
+    responses(
+        (status = 200, description = "Successfully changed post status", body = PostResponse),
+        (status = 400, description = "Bad request (e.g. rejection without a note)"),
+        (status = 403, description = "Not allowed to perform this transition"),
+        (status = 404, description = "Post not found"),
+        (status = 409, description = "Transition not allowed from the post's current status"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Posts"
+)]
+pub async fn transition_post<
+    PR: PostRepository,
+    UR: UserRepository,
+    SB: crate::storage::StorageBackend,
+>(
+    Path(id): Path<String>,
+    State(state): State<Arc<ExtendedAppState<PR, UR, SB>>>,
+    Extension(current_user): Extension<Arc<crate::auth::middleware::CurrentUser>>,
+    axum::Json(payload): axum::Json<PostTransitionRequest>,
+) -> Result<Json<PostResponse>, StatusCode> {
+    let id_num: i64 = id.parse().map_err(|_| StatusCode::BAD_REQUEST)?;
+    let repository = state.app_state.post_repository.as_ref();
+
+    let mut post = match repository.find_by_id(&id_num).await {
+        Ok(Some(post)) => post,
+        Ok(None) => return Err(StatusCode::NOT_FOUND),
+        Err(_) => return Err(StatusCode::INTERNAL_SERVER_ERROR),
+    };
+
+    let transition = payload.action;
+    if !current_user.is_author_or_admin(post.author_id)
+        || (transition.requires_admin() && !current_user.is_admin())
+    {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    let from = post.status;
+    let mut target = transition.target(from).ok_or(StatusCode::CONFLICT)?;
+
+    let note = payload
+        .note
+        .map(|note| note.trim().to_string())
+        .filter(|note| !note.is_empty());
+    match transition {
+        PostTransition::Reject if note.is_none() => return Err(StatusCode::BAD_REQUEST),
+        PostTransition::Approve | PostTransition::Reject => post.review_note = note,
+        PostTransition::Submit | PostTransition::Withdraw => post.review_note = None,
+        _ => {}
+    }
+
+    if let Some(publish_at) = payload.publish_at {
+        post.publish_at = Some(publish_at.into());
+    }
+    let publish_in_future = post
+        .publish_at
+        .is_some_and(|publish_at| publish_at > Utc::now());
+    match transition {
+        // Scheduling needs a publication time in the future
+        PostTransition::Schedule if !publish_in_future => return Err(StatusCode::BAD_REQUEST),
+        // Approved posts wait for the publication time their author asked for
+        PostTransition::Approve if publish_in_future => target = PostStatus::Scheduled,
+        // Publishing by hand makes the post go live now
+        PostTransition::Publish => post.publish_at = Some(Utc::now().into()),
+        _ => {}
+    }
+    check_schedule(post.publish_at, post.unpublish_at)?;
+
+    if target == PostStatus::Published {
+        filter_existing_post(repository, &state.plugin_registry, &mut post).await?;
+    }
+    post.status = target;
+
+    let post = match repository
+        .update(&id_num, post, false, None, current_user.id)
+        .await
+    {
+        Ok(Some(post)) => post,
+        Ok(None) => return Err(StatusCode::NOT_FOUND),
+        Err(e) if e.is::<RevisionConflict>() => return Err(StatusCode::CONFLICT),
+        Err(e) => {
+            tracing::error!("Error changing post status: {:?}", e);
+            return Err(StatusCode::INTERNAL_SERVER_ERROR);
+        }
+    };
+
+    fire_status_hook(
+        &state.plugin_registry,
+        &post,
+        Some(from),
+        transition,
+        Some(current_user.id),
+    )
+    .await;
+
+    Ok(Json(PostResponse::from(post)))
 }
 
 /// Update a post (full update)
 ///
-/// Update post information by ID (PUT method, full update)
+/// Update post information by ID (PUT method, full update). Send the revision from
+/// the post's `ETag` in `If-Match` to have the update rejected with 409 when someone
+/// else has changed the post in the meantime.
+///
+/// Authors can change their posts until they are approved; after that, only admins
+/// can, and changes to a published post run through `filter_post_published` again.
 #[utoipa::path(
     put,
     path = "/api/posts/{id}",
@@ -202,8 +950,10 @@ pub async fn create_post<
     request_body = UpdatePostRequest,
     responses(
         (status = 200, description = "Successfully updated post", body = PostResponse),
+        (status = 403, description = "Not the author or an admin, or the post is approved and only admins may change it"),
         (status = 404, description = "Post not found"),
         (status = 400, description = "Bad request"),
+        (status = 409, description = "Another post already uses the requested slug, or the post changed since the revision in `If-Match` (body holds the current post)"),
         (status = 500, description = "Internal server error")
     ),
     tag = "Posts"
@@ -216,8 +966,9 @@ pub async fn update_post<
     Path(id): Path<String>,
     State(state): State<Arc<ExtendedAppState<PR, UR, SB>>>,
     Extension(current_user): Extension<Arc<crate::auth::middleware::CurrentUser>>,
+    headers: HeaderMap,
     axum::Json(payload): axum::Json<UpdatePostRequest>,
-) -> Result<Json<PostResponse>, StatusCode> {
+) -> Result<Response, StatusCode> {
     let id_num: i64 = match id.parse() {
         Ok(num) => num,
         Err(_) => return Err(StatusCode::BAD_REQUEST),
@@ -230,27 +981,81 @@ pub async fn update_post<
         Err(_) => return Err(StatusCode::INTERNAL_SERVER_ERROR),
     };
 
-    // Check if user is author or admin
-    if !current_user.is_author_or_admin(existing_post.author_id) {
+    // Authors can only change posts that have not been approved yet
+    if !current_user.can_edit_post(existing_post.author_id, existing_post.status) {
         return Err(StatusCode::FORBIDDEN);
     }
+    if !if_match_satisfied(&headers, &existing_post) {
+        return Ok(revision_conflict(existing_post));
+    }
 
     // Use current user ID from context
     let user_id = current_user.id;
 
+    let (category, category_id) = match resolve_category(
+        state.app_state.post_repository.as_ref(),
+        payload.category_id,
+        payload.category.as_deref(),
+    )
+    .await?
+    {
+        Some(category) => (
+            category.as_ref().map(|category| category.name.clone()),
+            category.map(|category| category.id),
+        ),
+        None => (existing_post.category, existing_post.category_id),
+    };
+    let slug = match payload.slug.as_deref() {
+        Some(slug) => {
+            claim_slug(state.app_state.post_repository.as_ref(), slug, Some(id_num)).await?
+        }
+        None => existing_post.slug,
+    };
+    let publish_at = match payload.publish_at {
+        Some(publish_at) => publish_at.map(Into::into),
+        None => existing_post.publish_at,
+    };
+    let unpublish_at = match payload.unpublish_at {
+        Some(unpublish_at) => unpublish_at.map(Into::into),
+        None => existing_post.unpublish_at,
+    };
+    // A scheduled post cannot lose its publication time
+    if existing_post.status == PostStatus::Scheduled && publish_at.is_none() {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    check_schedule(publish_at, unpublish_at)?;
+
     // Build updated post
-    let updated_post = Post {
+    let mut updated_post = Post {
         id: existing_post.id,
         title: payload.title.unwrap_or(existing_post.title),
+        slug,
         content: payload.content.unwrap_or(existing_post.content),
-        category: payload.category.or(existing_post.category),
+        category,
+        category_id,
         author_id: existing_post.author_id,
         description: payload.description.or(existing_post.description),
         created_at: existing_post.created_at,
         updated_at: existing_post.updated_at, // Placeholder value, actually updated automatically by ActiveModelBehavior
         archived_at: existing_post.archived_at,
         deleted_at: existing_post.deleted_at,
+        tags: payload.tags.unwrap_or(existing_post.tags),
+        status: existing_post.status,
+        review_note: existing_post.review_note,
+        publish_at,
+        unpublish_at,
+        // Rejected by the repository if the post changed since it was read above
+        revision: existing_post.revision,
     };
+    // Changes to a live post go through the same filter as publishing it
+    if updated_post.status == PostStatus::Published {
+        filter_existing_post(
+            state.app_state.post_repository.as_ref(),
+            &state.plugin_registry,
+            &mut updated_post,
+        )
+        .await?;
+    }
 
     match state
         .app_state
@@ -264,9 +1069,16 @@ pub async fn update_post<
         )
         .await
     {
-        Ok(Some(post)) => Ok(Json(PostResponse::from(post))),
+        Ok(Some(post)) => Ok(post_with_etag(StatusCode::OK, PostResponse::from(post))),
         Ok(None) => Err(StatusCode::NOT_FOUND),
-        Err(_) => Err(StatusCode::INTERNAL_SERVER_ERROR),
+        Err(e) => match e.downcast::<RevisionConflict>() {
+            Ok(conflict) => Ok(revision_conflict(conflict.current)),
+            Err(e) if e.is::<SlugTaken>() => Err(StatusCode::CONFLICT),
+            Err(e) => {
+                tracing::error!("Error updating post: {:?}", e);
+                Err(StatusCode::INTERNAL_SERVER_ERROR)
+            }
+        },
     }
 }
 
@@ -345,16 +1157,38 @@ type UserRepo = crate::repository::PostgresUserRepository;
 #[openapi(
     paths(
         get_posts_doc,
+        search_posts_doc,
+        get_tags_doc,
+        get_categories_doc,
+        get_posts_by_tag_doc,
         get_post_doc,
+        get_post_by_slug_doc,
         create_post_doc,
         update_post_doc,
         delete_post_doc,
+        transition_post_doc,
+        crate::api::comment_controller::get_comments_doc,
+        crate::api::comment_controller::create_comment_doc,
         health_check,
     ),
     components(schemas(
         PostResponse,
+        crate::markdown::TocEntry,
+        PostListResponse,
+        PostSearchHit,
+        PostSearchResponse,
+        PostSortField,
+        SortOrder,
+        TagResponse,
+        CategoryTreeNode,
         CreatePostRequest,
         UpdatePostRequest,
+        PostStatus,
+        PostTransition,
+        PostTransitionRequest,
+        crate::dto::CommentResponse,
+        crate::dto::CommentStatus,
+        crate::dto::CreateCommentRequest,
         UserResponse,
         CreateUserRequest,
         UserRole,
@@ -365,6 +1199,7 @@ type UserRepo = crate::repository::PostgresUserRepository;
     )),
     tags(
         (name = "Posts", description = "Post management API"),
+        (name = "Comments", description = "Post comments API"),
         (name = "Users", description = "User management API"),
         (name = "Auth", description = "Authentication API"),
         (name = "Health", description = "Health check API"),
@@ -387,8 +1222,10 @@ pub struct ApiDoc;
 #[utoipa::path(
     get,
     path = "/api/posts",
+    params(PostListQuery, PostRenderQuery),
     responses(
-        (status = 200, description = "Successfully retrieved post list", body = Vec<PostResponse>),
+        (status = 200, description = "Successfully retrieved post list", body = PostListResponse),
+        (status = 400, description = "Bad request"),
         (status = 500, description = "Internal server error")
     ),
     tag = "Posts"
@@ -396,11 +1233,68 @@ pub struct ApiDoc;
 #[allow(dead_code)]
 fn get_posts_doc() {}
 
+#[utoipa::path(
+    get,
+    path = "/api/posts/search",
+    params(PostSearchQuery),
+    responses(
+        (status = 200, description = "Successfully searched posts", body = PostSearchResponse),
+        (status = 400, description = "Bad request (missing or empty query)"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Posts"
+)]
+#[allow(dead_code)]
+fn search_posts_doc() {}
+
+#[utoipa::path(
+    get,
+    path = "/api/tags",
+    responses(
+        (status = 200, description = "Successfully retrieved tags", body = Vec<TagResponse>),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Posts"
+)]
+#[allow(dead_code)]
+fn get_tags_doc() {}
+
+#[utoipa::path(
+    get,
+    path = "/api/categories",
+    responses(
+        (status = 200, description = "Successfully retrieved categories", body = Vec<CategoryTreeNode>),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Posts"
+)]
+#[allow(dead_code)]
+fn get_categories_doc() {}
+
+#[utoipa::path(
+    get,
+    path = "/api/tags/{slug}/posts",
+    params(
+        ("slug" = String, Path, description = "Tag slug"),
+        PostListQuery,
+        PostRenderQuery
+    ),
+    responses(
+        (status = 200, description = "Successfully retrieved post list", body = PostListResponse),
+        (status = 404, description = "Tag not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Posts"
+)]
+#[allow(dead_code)]
+fn get_posts_by_tag_doc(_slug: String) {}
+
 #[utoipa::path(
     get,
     path = "/api/posts/{id}",
     params(
-        ("id" = String, Path, description = "Post ID")
+        ("id" = String, Path, description = "Post ID"),
+        PostRenderQuery
     ),
     responses(
         (status = 200, description = "Successfully retrieved post", body = PostResponse),
@@ -412,6 +1306,24 @@ fn get_posts_doc() {}
 #[allow(dead_code)]
 fn get_post_doc(_id: String) {}
 
+#[utoipa::path(
+    get,
+    path = "/api/posts/by-slug/{slug}",
+    params(
+        ("slug" = String, Path, description = "Post slug"),
+        PostRenderQuery
+    ),
+    responses(
+        (status = 200, description = "Successfully retrieved post", body = PostResponse),
+        (status = 301, description = "Post was renamed; Location holds its current URL"),
+        (status = 404, description = "Post not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Posts"
+)]
+#[allow(dead_code)]
+fn get_post_by_slug_doc(_slug: String) {}
+
 #[utoipa::path(
     post,
     path = "/api/posts",
@@ -419,6 +1331,8 @@ fn get_post_doc(_id: String) {}
     responses(
         (status = 201, description = "Successfully created post", body = PostResponse),
         (status = 400, description = "Bad request"),
+        (status = 403, description = "Not allowed to create a post in the requested status"),
+        (status = 409, description = "Another post already uses the requested slug, or the post changed since the revision in `If-Match` (body holds the current post)"),
         (status = 500, description = "Internal server error")
     ),
     tag = "Posts"
@@ -435,7 +1349,9 @@ fn create_post_doc(_payload: CreatePostRequest) {}
     request_body = UpdatePostRequest,
     responses(
         (status = 200, description = "Successfully updated post", body = PostResponse),
+        (status = 403, description = "Not the author or an admin, or the post is approved and only admins may change it"),
         (status = 404, description = "Post not found"),
+        (status = 409, description = "Another post already uses the requested slug, or the post changed since the revision in `If-Match` (body holds the current post)"),
         (status = 500, description = "Internal server error")
     ),
     tag = "Posts"
@@ -459,6 +1375,26 @@ fn update_post_doc(_id: String, _payload: UpdatePostRequest) {}
 #[allow(dead_code)]
 fn delete_post_doc(_id: String) {}
 
+#[utoipa::path(
+    post,
+    path = "/api/posts/{id}/transitions",
+    params(
+        ("id" = String, Path, description = "Post ID")
+    ),
+    request_body = PostTransitionRequest,
+    responses(
+        (status = 200, description = "Successfully changed post status", body = PostResponse),
+        (status = 400, description = "Bad request (e.g. rejection without a note)"),
+        (status = 403, description = "Not allowed to perform this transition"),
+        (status = 404, description = "Post not found"),
+        (status = 409, description = "Transition not allowed from the post's current status"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Posts"
+)]
+#[allow(dead_code)]
+fn transition_post_doc(_id: String, _payload: PostTransitionRequest) {}
+
 // Version management endpoints
 
 /// Get all versions of a post
@@ -540,6 +1476,118 @@ pub async fn get_post_version<
     }
 }
 
+/// Compare two versions of a post
+///
+/// Either side can be a version id or `current` for the post as it is now.
+/// Returns line- and word-level diffs of title, content, category and description.
+#[utoipa::path(
+    get,
+    path = "/api/posts/{post_id}/versions/{version_id}/diff/{other_id}",
+    params(
+        ("post_id" = String, Path, description = "Post ID"),
+        ("version_id" = String, Path, description = "Old side: version ID or `current`"),
+        ("other_id" = String, Path, description = "New side: version ID or `current`")
+    ),
+    responses(
+        (status = 200, description = "Successfully compared versions", body = PostVersionDiff),
+        (status = 400, description = "Bad request"),
+        (status = 403, description = "Forbidden - not the author or an admin"),
+        (status = 404, description = "Post or version not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Posts"
+)]
+pub async fn diff_post_versions<
+    PR: PostRepository,
+    UR: UserRepository,
+    SB: crate::storage::StorageBackend,
+>(
+    Path((post_id, version_id, other_id)): Path<(String, String, String)>,
+    State(state): State<Arc<ExtendedAppState<PR, UR, SB>>>,
+    Extension(current_user): Extension<Arc<crate::auth::middleware::CurrentUser>>,
+) -> Result<Json<PostVersionDiff>, StatusCode> {
+    let post_id_num: i64 = post_id.parse().map_err(|_| StatusCode::BAD_REQUEST)?;
+    let repository = state.app_state.post_repository.as_ref();
+
+    let post = match repository.find_by_id(&post_id_num).await {
+        Ok(Some(post)) => post,
+        Ok(None) => return Err(StatusCode::NOT_FOUND),
+        Err(e) => {
+            tracing::error!("Error loading post: {:?}", e);
+            return Err(StatusCode::INTERNAL_SERVER_ERROR);
+        }
+    };
+    if !current_user.is_author_or_admin(post.author_id) {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    let (from, old) = diff_side(repository, &post, &version_id).await?;
+    let (to, new) = diff_side(repository, &post, &other_id).await?;
+
+    Ok(Json(PostVersionDiff {
+        post_id: post.id,
+        from,
+        to,
+        title: diff_text(&old.title, &new.title),
+        content: diff_text(&old.content, &new.content),
+        category: diff_text(&old.category, &new.category),
+        description: diff_text(&old.description, &new.description),
+    }))
+}
+
+/// Fields of a post compared by `diff_post_versions`
+struct DiffFields {
+    title: String,
+    content: String,
+    category: String,
+    description: String,
+}
+
+/// Load one side of a version comparison
+async fn diff_side<PR: PostRepository>(
+    repository: &PR,
+    post: &Post,
+    version_id: &str,
+) -> Result<(DiffSide, DiffFields), StatusCode> {
+    if version_id == CURRENT_VERSION {
+        return Ok((
+            DiffSide {
+                version_id: None,
+                version_number: None,
+                created_at: post.updated_at.into(),
+            },
+            DiffFields {
+                title: post.title.clone(),
+                content: post.content.clone(),
+                category: post.category.clone().unwrap_or_default(),
+                description: post.description.clone().unwrap_or_default(),
+            },
+        ));
+    }
+
+    let version_id_num: i64 = version_id.parse().map_err(|_| StatusCode::BAD_REQUEST)?;
+    match repository.get_version(&version_id_num).await {
+        Ok(Some(version)) if version.post_id == post.id => Ok((
+            DiffSide {
+                version_id: Some(version.id),
+                version_number: Some(version.version_number),
+                created_at: version.created_at.into(),
+            },
+            DiffFields {
+                title: version.title,
+                content: version.content,
+                category: version.category.unwrap_or_default(),
+                description: version.description.unwrap_or_default(),
+            },
+        )),
+        Ok(_) => Err(StatusCode::NOT_FOUND),
+        Err(e) => {
+            tracing::error!("Error loading version: {:?}", e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
 /// Restore a post from a version
 #[utoipa::path(
     post,
@@ -550,6 +1598,7 @@ pub async fn get_post_version<
     ),
     responses(
         (status = 200, description = "Successfully restored post", body = PostResponse),
+        (status = 403, description = "Not the author or an admin, or the post is approved and only admins may change it"),
         (status = 404, description = "Post or version not found"),
         (status = 400, description = "Bad request"),
         (status = 500, description = "Internal server error")
@@ -586,8 +1635,8 @@ pub async fn restore_post_from_version<
         Err(_) => return Err(StatusCode::INTERNAL_SERVER_ERROR),
     };
 
-    // Check if user is author or admin
-    if !current_user.is_author_or_admin(post.author_id) {
+    // Restoring changes the content like an update
+    if !current_user.can_edit_post(post.author_id, post.status) {
         return Err(StatusCode::FORBIDDEN);
     }
 
@@ -606,6 +1655,9 @@ pub async fn restore_post_from_version<
 // Draft management endpoints
 
 /// Save or update a draft
+///
+/// For a draft of an existing post, an `If-Match` header with the post's revision
+/// makes the save fail with 409 once someone else has updated the post.
 #[utoipa::path(
     post,
     path = "/api/drafts",
@@ -613,6 +1665,7 @@ pub async fn restore_post_from_version<
     responses(
         (status = 200, description = "Successfully saved draft", body = PostDraftResponse),
         (status = 400, description = "Bad request"),
+        (status = 409, description = "The post changed since the revision in `If-Match` (body holds the current post)"),
         (status = 500, description = "Internal server error")
     ),
     tag = "Drafts"
@@ -624,18 +1677,49 @@ pub async fn save_draft<
 >(
     State(state): State<Arc<ExtendedAppState<PR, UR, SB>>>,
     Extension(current_user): Extension<Arc<crate::auth::middleware::CurrentUser>>,
+    headers: HeaderMap,
     axum::Json(payload): axum::Json<SaveDraftRequest>,
-) -> Result<Json<PostDraftResponse>, StatusCode> {
+) -> Result<Response, StatusCode> {
     // Use current user ID from context
     let author_id = current_user.id;
 
+    // A draft of an existing post is based on the revision given in If-Match
+    if let Some(post_id) = payload.post_id
+        && headers.contains_key(header::IF_MATCH)
+    {
+        match state.app_state.post_repository.find_by_id(&post_id).await {
+            Ok(Some(post)) if !if_match_satisfied(&headers, &post) => {
+                return Ok(revision_conflict(post));
+            }
+            Ok(_) => {}
+            Err(e) => {
+                tracing::error!("Error loading post for draft: {:?}", e);
+                return Err(StatusCode::INTERNAL_SERVER_ERROR);
+            }
+        }
+    }
+
+    // Drafts are saved as typed; a category that doesn't exist (yet) is simply not kept
+    let mut payload = payload;
+    payload.category_id = match resolve_category(
+        state.app_state.post_repository.as_ref(),
+        payload.category_id,
+        payload.category.as_deref(),
+    )
+    .await
+    {
+        Ok(category) => category.flatten().map(|category| category.id),
+        Err(StatusCode::BAD_REQUEST) => None,
+        Err(status) => return Err(status),
+    };
+
     match state
         .app_state
         .post_repository
         .save_draft(author_id, payload)
         .await
     {
-        Ok(draft) => Ok(Json(PostDraftResponse::from(draft))),
+        Ok(draft) => Ok(Json(PostDraftResponse::from(draft)).into_response()),
         Err(_) => Err(StatusCode::INTERNAL_SERVER_ERROR),
     }
 }
@@ -788,6 +1872,24 @@ fn get_post_versions_doc(_id: String) {}
 #[allow(dead_code)]
 fn get_post_version_doc(_post_id: String, _version_id: String) {}
 
+#[utoipa::path(
+    get,
+    path = "/api/posts/{post_id}/versions/{version_id}/diff/{other_id}",
+    params(
+        ("post_id" = String, Path, description = "Post ID"),
+        ("version_id" = String, Path, description = "Old side: version ID or `current`"),
+        ("other_id" = String, Path, description = "New side: version ID or `current`")
+    ),
+    responses(
+        (status = 200, description = "Successfully compared versions", body = PostVersionDiff),
+        (status = 404, description = "Post or version not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Posts"
+)]
+#[allow(dead_code)]
+fn diff_post_versions_doc(_post_id: String, _version_id: String, _other_id: String) {}
+
 #[utoipa::path(
     post,
     path = "/api/posts/{post_id}/versions/{version_id}/restore",
@@ -797,6 +1899,7 @@ fn get_post_version_doc(_post_id: String, _version_id: String) {}
     ),
     responses(
         (status = 200, description = "Successfully restored post", body = PostResponse),
+        (status = 403, description = "Not the author or an admin, or the post is approved and only admins may change it"),
         (status = 404, description = "Post or version not found"),
         (status = 500, description = "Internal server error")
     ),
@@ -1,14 +1,21 @@
 use crate::api::admin_api;
-use crate::api::page_controller::{serve_admin_spa, serve_spa};
+use crate::api::comment_controller::*;
+use crate::api::feed_controller::*;
+use crate::api::page_controller::{
+    serve_admin_spa, serve_post_by_id_spa, serve_post_spa, serve_spa,
+};
 use crate::api::post_controller::{ApiDoc, *};
+use crate::api::sitemap_controller::*;
 use crate::api::upload_controller::*;
 use crate::api::user_controller::*;
-use crate::auth::middleware::auth_middleware;
+use crate::auth::middleware::{auth_middleware, optional_auth_middleware};
 use crate::repository::{PostRepository, UserRepository};
 use crate::storage::StorageBackend;
 #[allow(unused_imports)] // post, put, delete, patch are used via method chaining
 use axum::{
-    Router, middleware,
+    Router,
+    extract::DefaultBodyLimit,
+    middleware,
     routing::{delete, get, patch, post, put},
 };
 use std::sync::Arc;
@@ -49,7 +56,60 @@ pub fn create_router<
         .route("/api/auth/refresh", post(refresh_token::<PR, UR, SB>))
         // Public posts endpoints
         .route("/api/posts", get(get_posts::<PR, UR, SB>))
-        .route("/api/posts/:id", get(get_post::<PR, UR, SB>));
+        .route("/api/posts/search", get(search_posts::<PR, UR, SB>))
+        // Unpublished posts are visible to their author and admins when a token is sent
+        .route(
+            "/api/posts/:id",
+            get(get_post::<PR, UR, SB>).layer(middleware::from_fn(optional_auth_middleware)),
+        )
+        .route(
+            "/api/posts/by-slug/:slug",
+            get(get_post_by_slug::<PR, UR, SB>),
+        )
+        // Comments (approved comments only; a token makes the comment count as the user's)
+        // GET  /api/posts/:id/comments - Get threaded comments
+        // POST /api/posts/:id/comments - Post a comment or reply
+        .route(
+            "/api/posts/:id/comments",
+            get(get_comments::<PR, UR, SB>).merge(
+                post(create_comment::<PR, UR, SB>)
+                    .layer(middleware::from_fn(optional_auth_middleware)),
+            ),
+        )
+        // Public categories endpoint
+        .route("/api/categories", get(get_categories::<PR, UR, SB>))
+        // Public tags endpoints
+        .route("/api/tags", get(get_tags::<PR, UR, SB>))
+        .route("/api/tags/:slug/posts", get(get_posts_by_tag::<PR, UR, SB>))
+        // Feeds of published posts (conditional GET with ETag / Last-Modified)
+        // GET /feed.xml, /atom.xml                                 - Site feeds
+        // GET /category/:slug/feed.xml, /category/:slug/atom.xml   - Category feeds
+        // GET /author/:username/feed.xml, /author/:username/atom.xml - Author feeds
+        .route("/feed.xml", get(get_rss_feed::<PR, UR, SB>))
+        .route("/atom.xml", get(get_atom_feed::<PR, UR, SB>))
+        .route(
+            "/category/:slug/feed.xml",
+            get(get_category_rss_feed::<PR, UR, SB>),
+        )
+        .route(
+            "/category/:slug/atom.xml",
+            get(get_category_atom_feed::<PR, UR, SB>),
+        )
+        .route(
+            "/author/:username/feed.xml",
+            get(get_author_rss_feed::<PR, UR, SB>),
+        )
+        .route(
+            "/author/:username/atom.xml",
+            get(get_author_atom_feed::<PR, UR, SB>),
+        )
+        // Search engines (must not fall through to the SPA fallback)
+        // GET /sitemap.xml    - Sitemap, or sitemap index above 50k URLs
+        // GET /sitemaps/:file - Sitemap n of the index (`n.xml`)
+        // GET /robots.txt     - robots.txt from the `robots_txt` setting
+        .route("/sitemap.xml", get(get_sitemap::<PR, UR, SB>))
+        .route("/sitemaps/:file", get(get_sitemap_page::<PR, UR, SB>))
+        .route("/robots.txt", get(get_robots_txt::<PR, UR, SB>));
 
     // Protected routes (authentication required)
     let protected_routes = Router::new()
@@ -62,6 +122,12 @@ pub fn create_router<
             "/api/posts/:id",
             put(update_post::<PR, UR, SB>).delete(delete_post::<PR, UR, SB>),
         )
+        // Post status workflow
+        // POST /api/posts/:id/transitions - Submit, approve, reject, publish, ...
+        .route(
+            "/api/posts/:id/transitions",
+            post(transition_post::<PR, UR, SB>),
+        )
         // Post version management
         .route(
             "/api/posts/:id/versions",
@@ -71,6 +137,11 @@ pub fn create_router<
             "/api/posts/:post_id/versions/:version_id",
             get(get_post_version::<PR, UR, SB>),
         )
+        // GET /api/posts/:post_id/versions/:version_id/diff/:other_id - Compare two versions (or `current`)
+        .route(
+            "/api/posts/:post_id/versions/:version_id/diff/:other_id",
+            get(diff_post_versions::<PR, UR, SB>),
+        )
         .route(
             "/api/posts/:post_id/versions/:version_id/restore",
             post(restore_post_from_version::<PR, UR, SB>),
@@ -101,7 +172,16 @@ pub fn create_router<
         )
         // Upload API
         // POST /api/upload/image - Upload image
-        .route("/api/upload/image", post(upload_image::<PR, UR, SB>))
+        // POST /api/upload/file  - Upload file (images, PDFs, audio, video, ...)
+        // Size limits come from the upload_size_limits setting
+        .route(
+            "/api/upload/image",
+            post(upload_image::<PR, UR, SB>).layer(DefaultBodyLimit::disable()),
+        )
+        .route(
+            "/api/upload/file",
+            post(upload_file::<PR, UR, SB>).layer(DefaultBodyLimit::disable()),
+        )
         .layer(middleware::from_fn(auth_middleware));
 
     // Admin API router (all routes under /api/admin/*)
@@ -147,14 +227,19 @@ pub fn create_router<
         .route("/admin", get(serve_admin_spa))
         .route("/admin/", get(serve_admin_spa))
         .route("/admin/*path", get(serve_admin_spa))
-        // Serve uploaded files
-        // GET /uploads/* - Serve uploaded files
-        .nest_service("/uploads", ServeDir::new("uploads"))
+        // Serve uploaded files from the storage backend
+        // GET /uploads/:file - Serve an uploaded file
+        .route("/uploads/:file", get(serve_upload::<PR, UR, SB>))
         // Swagger UI
         .merge(SwaggerUi::new("/swagger-ui").url("/api-doc/openapi.json", ApiDoc::openapi()))
         // Serve main frontend static files (only assets directory)
         // This ensures static assets (JS, CSS) are served, but SPA routes fall through to fallback
         .nest_service("/assets", ServeDir::new("frontend/dist/assets"))
+        // Post pages - serve index.html with the post's meta tags, redirecting old slugs to the current one
+        // GET /p/:slug - Post page by slug
+        .route("/p/:slug", get(serve_post_spa::<PR, UR, SB>))
+        // GET /posts/:id - Post page by id
+        .route("/posts/:id", get(serve_post_by_id_spa::<PR, UR, SB>))
         // SPA fallback - serve index.html for all non-API routes
         // This must be last so API routes take precedence
         // When user visits /posts/123, this handler will return index.html
@@ -173,7 +258,11 @@ pub fn create_router<
                 .allow_headers([
                     axum::http::header::CONTENT_TYPE,
                     axum::http::header::AUTHORIZATION,
-                ]),
+                    axum::http::header::IF_MATCH,
+                    axum::http::header::IF_NONE_MATCH,
+                    axum::http::header::IF_MODIFIED_SINCE,
+                ])
+                .expose_headers([axum::http::header::ETAG, axum::http::header::LAST_MODIFIED]),
         )
         .layer(TraceLayer::new_for_http())
         .layer(middleware::from_fn(
@@ -0,0 +1,153 @@
+// Sitemap controller: XML sitemaps and robots.txt for search engines
+use crate::api::admin_api::settings_helper::get_setting_value;
+use crate::api::feed_controller::resolve_site_url;
+use crate::api::post_controller::ExtendedAppState;
+use crate::repository::{PostRepository, UserRepository};
+use crate::sitemap::{MAX_SITEMAP_URLS, SitemapUrl, robots_txt, sitemap_index, urlset};
+use axum::{
+    extract::{Path, State},
+    http::{HeaderMap, StatusCode, header},
+    response::{IntoResponse, Response},
+};
+use std::sync::Arc;
+
+const XML_CONTENT_TYPE: &str = "application/xml; charset=utf-8";
+
+/// Sitemap of the site
+///
+/// Lists the home page, categories and authors with published posts, and published
+/// posts. Above [`MAX_SITEMAP_URLS`] URLs this is a sitemap index of `/sitemaps/{n}.xml`.
+pub async fn get_sitemap<
+    PR: PostRepository,
+    UR: UserRepository,
+    SB: crate::storage::StorageBackend,
+>(
+    State(state): State<Arc<ExtendedAppState<PR, UR, SB>>>,
+    headers: HeaderMap,
+) -> Result<Response, StatusCode> {
+    let site_url = resolve_site_url(&state.db, &headers).await.url;
+    let total = count_urls(&state).await?;
+
+    let xml = if total <= MAX_SITEMAP_URLS {
+        urlset(&sitemap_urls(&state, &site_url, 0, total).await?)
+    } else {
+        let sitemaps: Vec<SitemapUrl> = (1..=total.div_ceil(MAX_SITEMAP_URLS))
+            .map(|page| SitemapUrl {
+                loc: format!("{}/sitemaps/{}.xml", site_url, page),
+                lastmod: None,
+            })
+            .collect();
+        sitemap_index(&sitemaps)
+    };
+
+    Ok(([(header::CONTENT_TYPE, XML_CONTENT_TYPE)], xml).into_response())
+}
+
+/// One sitemap of the sitemap index (`/sitemaps/{n}.xml`, 1-based)
+pub async fn get_sitemap_page<
+    PR: PostRepository,
+    UR: UserRepository,
+    SB: crate::storage::StorageBackend,
+>(
+    Path(file): Path<String>,
+    State(state): State<Arc<ExtendedAppState<PR, UR, SB>>>,
+    headers: HeaderMap,
+) -> Result<Response, StatusCode> {
+    let page: u64 = file
+        .strip_suffix(".xml")
+        .and_then(|page| page.parse().ok())
+        .filter(|page| *page >= 1)
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    let total = count_urls(&state).await?;
+    let start = (page - 1) * MAX_SITEMAP_URLS;
+    if start >= total {
+        return Err(StatusCode::NOT_FOUND);
+    }
+    let end = total.min(start + MAX_SITEMAP_URLS);
+
+    let site_url = resolve_site_url(&state.db, &headers).await.url;
+    let xml = urlset(&sitemap_urls(&state, &site_url, start, end).await?);
+    Ok(([(header::CONTENT_TYPE, XML_CONTENT_TYPE)], xml).into_response())
+}
+
+/// robots.txt from the `robots_txt` setting, pointing crawlers at the sitemap
+pub async fn get_robots_txt<
+    PR: PostRepository,
+    UR: UserRepository,
+    SB: crate::storage::StorageBackend,
+>(
+    State(state): State<Arc<ExtendedAppState<PR, UR, SB>>>,
+    headers: HeaderMap,
+) -> Response {
+    let setting = get_setting_value(&state.db, "robots_txt")
+        .await
+        .ok()
+        .flatten();
+    let site_url = resolve_site_url(&state.db, &headers).await.url;
+    let robots = robots_txt(setting, &site_url);
+
+    (
+        [(header::CONTENT_TYPE, "text/plain; charset=utf-8")],
+        robots,
+    )
+        .into_response()
+}
+
+/// Number of URLs in the sitemap, counting the home page
+async fn count_urls<PR: PostRepository, UR: UserRepository, SB: crate::storage::StorageBackend>(
+    state: &ExtendedAppState<PR, UR, SB>,
+) -> Result<u64, StatusCode> {
+    state
+        .app_state
+        .post_repository
+        .count_sitemap_entries()
+        .await
+        .map(|count| count + 1)
+        .map_err(|e| {
+            tracing::error!("Error counting sitemap entries: {:?}", e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })
+}
+
+/// URLs `start..end` of the sitemap; the home page comes first
+async fn sitemap_urls<
+    PR: PostRepository,
+    UR: UserRepository,
+    SB: crate::storage::StorageBackend,
+>(
+    state: &ExtendedAppState<PR, UR, SB>,
+    site_url: &str,
+    start: u64,
+    end: u64,
+) -> Result<Vec<SitemapUrl>, StatusCode> {
+    let mut urls = Vec::new();
+    if start == 0 {
+        urls.push(SitemapUrl {
+            loc: format!("{}/", site_url),
+            lastmod: None,
+        });
+    }
+
+    let offset = start.saturating_sub(1);
+    let limit = end.saturating_sub(1) - offset;
+    if limit == 0 {
+        return Ok(urls);
+    }
+    let entries = state
+        .app_state
+        .post_repository
+        .find_sitemap_entries(offset, limit)
+        .await
+        .map_err(|e| {
+            tracing::error!("Error loading sitemap entries: {:?}", e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    urls.extend(
+        entries
+            .into_iter()
+            .map(|entry| SitemapUrl::from_entry(site_url, entry)),
+    );
+    Ok(urls)
+}
@@ -1,43 +1,148 @@
-//! Image upload controller
+//! Upload controller
 //! 
-//! Handles image upload requests and delegates to storage backend
+//! Handles image and file upload requests and delegates to storage backend
 
+use crate::api::feed_controller::{http_date, not_modified};
+use crate::dto::{Media, MediaVariant, SourceSet};
+use crate::image_variants::{VariantError, VariantSettings};
+use crate::media::{FileNaming, StagedFile};
 use crate::storage::StorageBackend;
+use crate::upload_policy::{CheckedType, SNIFF_LEN, UploadPolicy, UploadRejection};
 use axum::{
-    extract::{Extension, Multipart, State},
-    http::StatusCode,
-    response::Json,
+    body::Body,
+    extract::{Extension, Multipart, Path, State},
+    http::{HeaderMap, HeaderValue, StatusCode, header},
+    response::{IntoResponse, Json, Response},
 };
 use serde::Serialize;
+use sha2::{Digest, Sha256};
 use std::sync::Arc;
+use tokio::io::AsyncWriteExt;
 
 /// Upload response DTO
 #[derive(Debug, Serialize)]
 pub struct UploadResponse {
     pub url: String,
     pub filename: String,
+    /// Id of the file in the media library
+    pub media_id: i64,
+    pub width: Option<i32>,
+    pub height: Option<i32>,
+    pub thumbnail_url: Option<String>,
+    /// Resized copies of the image, including the thumbnail
+    pub variants: Vec<MediaVariant>,
+    /// `srcset` values per format, for `<picture>` sources and the `<img>` fallback
+    pub srcset: Vec<SourceSet>,
+    /// An identical file was uploaded before; it is returned instead of a new copy
+    pub deduplicated: bool,
+}
+
+impl UploadResponse {
+    fn new(
+        filename: String,
+        media: Media,
+        variants: Vec<MediaVariant>,
+        deduplicated: bool,
+    ) -> Self {
+        let media = crate::dto::MediaResponse::new(media, 0, variants);
+        Self {
+            url: media.url,
+            filename,
+            media_id: media.id,
+            width: media.width,
+            height: media.height,
+            thumbnail_url: media.thumbnail_url,
+            variants: media.variants,
+            srcset: media.srcset,
+            deduplicated,
+        }
+    }
 }
 
 /// Upload image endpoint
 /// 
 /// Accepts multipart/form-data with an "image" field
-/// Returns the URL where the uploaded image can be accessed
+/// Records the image in the media library with its responsive variants and thumbnail,
+/// and returns the URLs where they can be accessed. With `deduplicate_uploads` on,
+/// an image already in the library is returned instead of being stored again.
+/// The image is checked like any other upload (see [`upload_file`]).
 pub async fn upload_image<PR, UR, SB>(
     State(state): State<Arc<crate::api::post_controller::ExtendedAppState<PR, UR, SB>>>,
-    Extension(_current_user): Extension<Arc<crate::auth::middleware::CurrentUser>>,
-    mut multipart: Multipart,
+    Extension(current_user): Extension<Arc<crate::auth::middleware::CurrentUser>>,
+    multipart: Multipart,
+) -> Result<Json<UploadResponse>, StatusCode>
+where
+    PR: crate::repository::PostRepository,
+    UR: crate::repository::UserRepository,
+    SB: StorageBackend,
+{
+    let upload = receive_upload(&state.db, multipart, "image", true).await?;
+    store_upload(&state, current_user.id, upload).await
+}
+
+/// Upload file endpoint
+///
+/// Accepts multipart/form-data with a "file" field: images, PDFs, audio, video or
+/// anything else `upload_allowed_types` allows. The type is taken from the file's
+/// content rather than trusted from the client, and files that are not what they
+/// claim to be are rejected with 415, as are HTML files and SVG images with
+/// scripts. Files over their type's limit in `upload_size_limits` are rejected
+/// with 413. Images get variants like with [`upload_image`].
+pub async fn upload_file<PR, UR, SB>(
+    State(state): State<Arc<crate::api::post_controller::ExtendedAppState<PR, UR, SB>>>,
+    Extension(current_user): Extension<Arc<crate::auth::middleware::CurrentUser>>,
+    multipart: Multipart,
 ) -> Result<Json<UploadResponse>, StatusCode>
 where
     PR: crate::repository::PostRepository,
     UR: crate::repository::UserRepository,
     SB: StorageBackend,
 {
-    // Find the image field in the multipart data
-    while let Some(field) = multipart.next_field().await.map_err(|_| StatusCode::BAD_REQUEST)? {
-        let field_name = field.name();
-        
-        // Only process fields named "image"
-        if field_name != Some("image") {
+    let upload = receive_upload(&state.db, multipart, "file", false).await?;
+    store_upload(&state, current_user.id, upload).await
+}
+
+/// An upload streamed to a temporary file and checked against the upload policy
+struct ReceivedUpload {
+    file_name: String,
+    /// The type detected from the content
+    mime_type: CheckedType,
+    /// Removed when the upload is dropped
+    file: tempfile::NamedTempFile,
+    size: u64,
+    checksum: String,
+}
+
+fn rejection_status(file_name: &str, rejection: UploadRejection) -> StatusCode {
+    tracing::warn!("Rejected upload {}: {}", file_name, rejection);
+    match rejection {
+        UploadRejection::TooLarge { .. } => StatusCode::PAYLOAD_TOO_LARGE,
+        _ => StatusCode::UNSUPPORTED_MEDIA_TYPE,
+    }
+}
+
+/// Stream the multipart field `field_name` to a temporary file
+///
+/// The type is detected from the first bytes before the rest is read, so files
+/// of types that are not allowed and files over their size limit are rejected
+/// without being read in full.
+async fn receive_upload(
+    db: &Arc<sea_orm::DatabaseConnection>,
+    mut multipart: Multipart,
+    field_name: &str,
+    images_only: bool,
+) -> Result<ReceivedUpload, StatusCode> {
+    let policy = UploadPolicy::load(db).await.map_err(|e| {
+        tracing::error!("Failed to load upload settings: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    while let Some(mut field) = multipart
+        .next_field()
+        .await
+        .map_err(|_| StatusCode::BAD_REQUEST)?
+    {
+        if field.name() != Some(field_name) {
             continue;
         }
 
@@ -45,39 +150,464 @@ where
             .file_name()
             .ok_or(StatusCode::BAD_REQUEST)?
             .to_string();
-        
-        let content_type = field
+        let declared_type = field
             .content_type()
             .unwrap_or("application/octet-stream")
             .to_string();
-
-        // Validate content type (only images)
-        if !content_type.starts_with("image/") {
+        if images_only && !declared_type.starts_with("image/") {
             return Err(StatusCode::BAD_REQUEST);
         }
 
-        // Read file data
-        let file_data = field
-            .bytes()
-            .await
-            .map_err(|_| StatusCode::BAD_REQUEST)?
-            .to_vec();
+        // The start of the file, to detect its type from and reject the upload
+        // early; the complete file is checked once it is staged
+        let mut head = Vec::with_capacity(SNIFF_LEN);
+        let mut rest = None;
+        while head.len() < SNIFF_LEN {
+            match field.chunk().await.map_err(|_| StatusCode::BAD_REQUEST)? {
+                Some(chunk) => {
+                    let split = chunk.len().min(SNIFF_LEN - head.len());
+                    head.extend_from_slice(&chunk[..split]);
+                    if split < chunk.len() {
+                        rest = Some(chunk.slice(split..));
+                    }
+                }
+                None => break,
+            }
+        }
+
+        let mime_type = crate::upload_policy::content_type(&declared_type, &head)
+            .and_then(|mime_type| {
+                if images_only && !mime_type.starts_with("image/") {
+                    return Err(UploadRejection::Mismatch {
+                        declared: declared_type.clone(),
+                        detected: mime_type,
+                    });
+                }
+                policy.check_allowed(&mime_type)?;
+                Ok(mime_type)
+            })
+            .map_err(|rejection| rejection_status(&file_name, rejection))?;
+        let limit = policy.size_limit(&mime_type);
 
-        // Validate file size (max 10MB)
-        const MAX_FILE_SIZE: usize = 10 * 1024 * 1024; // 10MB
-        if file_data.len() > MAX_FILE_SIZE {
-            return Err(StatusCode::PAYLOAD_TOO_LARGE);
+        let staging_error = |e: std::io::Error| {
+            tracing::error!("Failed to stage upload {}: {}", file_name, e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        };
+        let temp_file = tempfile::NamedTempFile::new().map_err(staging_error)?;
+        let mut writer = tokio::fs::File::create(temp_file.path())
+            .await
+            .map_err(staging_error)?;
+        let mut hasher = Sha256::new();
+        let mut size = 0u64;
+        // Nothing is left over unless the head is full
+        let mut chunk = Some(bytes::Bytes::from(head)).filter(|head| !head.is_empty());
+        while let Some(data) = chunk {
+            size += data.len() as u64;
+            if size > limit {
+                return Err(rejection_status(
+                    &file_name,
+                    UploadRejection::TooLarge { mime_type, limit },
+                ));
+            }
+            hasher.update(&data);
+            writer.write_all(&data).await.map_err(staging_error)?;
+            chunk = match rest.take() {
+                Some(rest) => Some(rest),
+                None => field.chunk().await.map_err(|_| StatusCode::BAD_REQUEST)?,
+            };
         }
+        writer.flush().await.map_err(staging_error)?;
 
-        // Upload to storage backend
-        let url = state.storage
-            .upload_file(file_data, file_name.clone(), content_type)
+        let mime_type = policy
+            .check_file(&declared_type, temp_file.path())
             .await
-            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+            .map_err(staging_error)?
+            .map_err(|rejection| rejection_status(&file_name, rejection))?;
 
-        return Ok(Json(UploadResponse { url, filename: file_name }));
+        return Ok(ReceivedUpload {
+            file_name,
+            mime_type,
+            file: temp_file,
+            size,
+            checksum: format!("{:x}", hasher.finalize()),
+        });
     }
 
     Err(StatusCode::BAD_REQUEST)
 }
 
+/// Store a received upload and record it in the media library
+///
+/// Images are read into memory to generate their variants; other files are
+/// streamed from the temporary file to the storage backend.
+async fn store_upload<PR, UR, SB>(
+    state: &crate::api::post_controller::ExtendedAppState<PR, UR, SB>,
+    uploader_id: i64,
+    upload: ReceivedUpload,
+) -> Result<Json<UploadResponse>, StatusCode>
+where
+    PR: crate::repository::PostRepository,
+    UR: crate::repository::UserRepository,
+    SB: StorageBackend,
+{
+    let ReceivedUpload {
+        file_name,
+        mime_type,
+        file,
+        size,
+        checksum,
+    } = upload;
+
+    // An identical file in the library is returned as it is
+    let storage = state.storage.as_ref();
+    let media_repository = state.app_state.media_repository.as_ref();
+    let naming = FileNaming::load(&state.db).await.map_err(|e| {
+        tracing::error!("Failed to load upload settings: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+    let duplicate = crate::media::find_duplicate(media_repository, naming, &checksum)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to look up duplicates of {}: {}", file_name, e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+    if let Some(media) = duplicate {
+        return existing_upload(media_repository, file_name, media).await;
+    }
+
+    if !mime_type.as_str().starts_with("image/") {
+        let staged = StagedFile {
+            path: file.path(),
+            size,
+            checksum,
+        };
+        let stored = crate::media::store_staged(
+            storage,
+            media_repository,
+            &staged,
+            file_name.clone(),
+            mime_type,
+            Some(uploader_id),
+            naming,
+        )
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to store upload {}: {}", file_name, e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+        return Ok(Json(UploadResponse::new(
+            file_name,
+            stored.media,
+            Vec::new(),
+            stored.existing,
+        )));
+    }
+
+    let file_data = tokio::fs::read(file.path()).await.map_err(|e| {
+        tracing::error!("Failed to read staged upload {}: {}", file_name, e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+    drop(file);
+
+    // Decoding and resizing is CPU-bound; keep it off the async workers
+    let settings = VariantSettings::load(&state.db).await.map_err(|e| {
+        tracing::error!("Failed to load image variant settings: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+    let variant_type = mime_type.as_str().to_string();
+    let (file_data, variants) = tokio::task::spawn_blocking(move || {
+        let variants = crate::image_variants::generate(&file_data, &variant_type, &settings);
+        (file_data, variants)
+    })
+    .await
+    .map_err(|e| {
+        tracing::error!("Image processing task failed: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+    let variants = variants.map_err(|e| match e {
+        VariantError::TooLarge { .. } => StatusCode::PAYLOAD_TOO_LARGE,
+        VariantError::Decode(_) => StatusCode::BAD_REQUEST,
+        VariantError::Encode { .. } => {
+            tracing::error!("Failed to generate variants of {}: {}", file_name, e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        }
+    })?;
+
+    // Upload to storage backend and record it in the media library
+    let stored = crate::media::store(
+        storage,
+        media_repository,
+        file_data,
+        file_name.clone(),
+        mime_type,
+        Some(uploader_id),
+        naming,
+    )
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to store upload {}: {}", file_name, e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+    // Uploaded at the same time by someone else, with its own variants
+    if stored.existing {
+        return existing_upload(media_repository, file_name, stored.media).await;
+    }
+    let media = stored.media;
+    let variants =
+        match crate::media::store_variants(storage, media_repository, &media, variants).await {
+            Ok(variants) => variants,
+            Err(e) => {
+                tracing::error!("Failed to store variants of {}: {}", file_name, e);
+                if let Err(e) = crate::media::delete(storage, media_repository, &media).await {
+                    tracing::error!("Failed to delete upload {}: {}", media.url, e);
+                }
+                return Err(StatusCode::INTERNAL_SERVER_ERROR);
+            }
+        };
+
+    Ok(Json(UploadResponse::new(file_name, media, variants, false)))
+}
+
+/// Response for an upload identical to a file already in the library
+async fn existing_upload(
+    media_repository: &dyn crate::repository::MediaRepository,
+    file_name: String,
+    media: Media,
+) -> Result<Json<UploadResponse>, StatusCode> {
+    let variants = media_repository
+        .find_variants(&[media.id])
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to load variants of {}: {}", media.url, e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+    Ok(Json(UploadResponse::new(file_name, media, variants, true)))
+}
+
+/// Cache lifetime of uploaded files; their names are never reused for other content
+const UPLOAD_CACHE_CONTROL: &str = "public, max-age=31536000, immutable";
+
+/// Byte range asked for with a `Range` header
+#[derive(Debug, PartialEq)]
+enum RangeRequest {
+    /// No usable range; the whole file is sent
+    Full,
+    Partial(std::ops::Range<u64>),
+    /// The range starts after the end of the file
+    Unsatisfiable,
+}
+
+/// Parse a `Range` header for a file of `size` bytes
+///
+/// Only single ranges (`bytes=0-99`, `bytes=100-`, `bytes=-100`) are supported;
+/// multiple ranges and malformed values fall back to the whole file.
+fn parse_range(value: &str, size: u64) -> RangeRequest {
+    let Some(spec) = value.trim().strip_prefix("bytes=") else {
+        return RangeRequest::Full;
+    };
+    let Some((start, end)) = spec.trim().split_once('-') else {
+        return RangeRequest::Full;
+    };
+    if spec.contains(',') {
+        return RangeRequest::Full;
+    }
+
+    let (start, end) = (start.trim(), end.trim());
+    let range = if start.is_empty() {
+        // Suffix range: the last `end` bytes
+        match end.parse::<u64>() {
+            Ok(length) if length > 0 && size > 0 => size.saturating_sub(length)..size,
+            Ok(_) => return RangeRequest::Unsatisfiable,
+            Err(_) => return RangeRequest::Full,
+        }
+    } else {
+        let Ok(start) = start.parse::<u64>() else {
+            return RangeRequest::Full;
+        };
+        let end = if end.is_empty() {
+            size
+        } else {
+            match end.parse::<u64>() {
+                Ok(end) if end >= start => end.saturating_add(1).min(size),
+                _ => return RangeRequest::Full,
+            }
+        };
+        if start >= size {
+            return RangeRequest::Unsatisfiable;
+        }
+        start..end
+    };
+    RangeRequest::Partial(range)
+}
+
+/// Serve an uploaded file (`/uploads/:file`) from the storage backend
+///
+/// Backends that can hand out their own URLs (e.g. S3 with redirects enabled) get a
+/// temporary redirect; everything else is streamed through the server, with
+/// support for single `Range` requests and conditional requests.
+pub async fn serve_upload<PR, UR, SB>(
+    State(state): State<Arc<crate::api::post_controller::ExtendedAppState<PR, UR, SB>>>,
+    Path(file): Path<String>,
+    headers: HeaderMap,
+) -> Response
+where
+    PR: crate::repository::PostRepository,
+    UR: crate::repository::UserRepository,
+    SB: StorageBackend,
+{
+    if crate::storage::stored_file_name(&file) != Some(file.as_str()) {
+        return StatusCode::NOT_FOUND.into_response();
+    }
+
+    match state.storage.redirect_url(&file).await {
+        Ok(Some(location)) => {
+            return (
+                StatusCode::TEMPORARY_REDIRECT,
+                [(header::LOCATION, location)],
+            )
+                .into_response();
+        }
+        Ok(None) => {}
+        Err(e) => {
+            tracing::error!("Failed to resolve upload {}: {}", file, e);
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        }
+    }
+
+    let metadata = match state.storage.head(&file).await {
+        Ok(Some(metadata)) => metadata,
+        Ok(None) => return StatusCode::NOT_FOUND.into_response(),
+        Err(e) => {
+            tracing::error!("Failed to read upload {}: {}", file, e);
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        }
+    };
+
+    let mut cache_headers = HeaderMap::new();
+    cache_headers.insert(
+        header::CACHE_CONTROL,
+        HeaderValue::from_static(UPLOAD_CACHE_CONTROL),
+    );
+    cache_headers.insert(header::ACCEPT_RANGES, HeaderValue::from_static("bytes"));
+    if let Some(last_modified) = metadata.last_modified {
+        let etag = format!("\"{:x}-{:x}\"", metadata.size, last_modified.timestamp());
+        if let Ok(value) = HeaderValue::from_str(&etag) {
+            cache_headers.insert(header::ETAG, value);
+        }
+        if let Ok(value) = HeaderValue::from_str(&http_date(last_modified)) {
+            cache_headers.insert(header::LAST_MODIFIED, value);
+        }
+        if not_modified(&headers, &etag, last_modified) {
+            return (StatusCode::NOT_MODIFIED, cache_headers).into_response();
+        }
+    }
+
+    let range = match headers
+        .get(header::RANGE)
+        .and_then(|value| value.to_str().ok())
+        .map(|value| parse_range(value, metadata.size))
+        .unwrap_or(RangeRequest::Full)
+    {
+        RangeRequest::Full => None,
+        RangeRequest::Partial(range) => Some(range),
+        RangeRequest::Unsatisfiable => {
+            return (
+                StatusCode::RANGE_NOT_SATISFIABLE,
+                cache_headers,
+                [(header::CONTENT_RANGE, format!("bytes */{}", metadata.size))],
+            )
+                .into_response();
+        }
+    };
+
+    let stored = match state.storage.get(&file, range.clone()).await {
+        Ok(Some(stored)) => stored,
+        Ok(None) => return StatusCode::NOT_FOUND.into_response(),
+        Err(e) => {
+            tracing::error!("Failed to read upload {}: {}", file, e);
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        }
+    };
+
+    let mut response = (cache_headers, Body::from_stream(stored.body)).into_response();
+    let headers = response.headers_mut();
+    let content_type = metadata
+        .content_type
+        .unwrap_or_else(|| "application/octet-stream".to_string());
+    if let Ok(value) = HeaderValue::from_str(&content_type) {
+        headers.insert(header::CONTENT_TYPE, value);
+    }
+    // Browsers must not take a file for something other than its checked type
+    headers.insert(
+        header::X_CONTENT_TYPE_OPTIONS,
+        HeaderValue::from_static("nosniff"),
+    );
+    match range {
+        Some(range) => {
+            *response.status_mut() = StatusCode::PARTIAL_CONTENT;
+            let headers = response.headers_mut();
+            headers.insert(header::CONTENT_LENGTH, (range.end - range.start).into());
+            if let Ok(value) = HeaderValue::from_str(&format!(
+                "bytes {}-{}/{}",
+                range.start,
+                range.end.saturating_sub(1),
+                metadata.size
+            )) {
+                headers.insert(header::CONTENT_RANGE, value);
+            }
+        }
+        None => {
+            headers.insert(header::CONTENT_LENGTH, metadata.size.into());
+        }
+    }
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_single_ranges() {
+        assert_eq!(
+            parse_range("bytes=0-99", 1000),
+            RangeRequest::Partial(0..100)
+        );
+        assert_eq!(
+            parse_range("bytes=900-", 1000),
+            RangeRequest::Partial(900..1000)
+        );
+        assert_eq!(
+            parse_range("bytes=-100", 1000),
+            RangeRequest::Partial(900..1000)
+        );
+        // Cut off at the end of the file
+        assert_eq!(
+            parse_range("bytes=500-5000", 1000),
+            RangeRequest::Partial(500..1000)
+        );
+        assert_eq!(
+            parse_range("bytes=-5000", 1000),
+            RangeRequest::Partial(0..1000)
+        );
+    }
+
+    #[test]
+    fn rejects_ranges_past_the_end() {
+        assert_eq!(
+            parse_range("bytes=1000-", 1000),
+            RangeRequest::Unsatisfiable
+        );
+        assert_eq!(parse_range("bytes=-0", 1000), RangeRequest::Unsatisfiable);
+        assert_eq!(parse_range("bytes=0-", 0), RangeRequest::Unsatisfiable);
+        assert_eq!(parse_range("bytes=-100", 0), RangeRequest::Unsatisfiable);
+    }
+
+    #[test]
+    fn ignores_unsupported_ranges() {
+        assert_eq!(parse_range("bytes=0-9,20-29", 1000), RangeRequest::Full);
+        assert_eq!(parse_range("bytes=9-0", 1000), RangeRequest::Full);
+        assert_eq!(parse_range("items=0-9", 1000), RangeRequest::Full);
+        assert_eq!(parse_range("bytes=abc", 1000), RangeRequest::Full);
+    }
+}
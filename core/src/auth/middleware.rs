@@ -1,5 +1,5 @@
 use crate::auth::jwt::{Claims, JwtUtil};
-use crate::dto::UserRole;
+use crate::dto::{PostStatus, UserRole};
 use axum::{
     extract::Request,
     http::{HeaderMap, StatusCode},
@@ -120,6 +120,11 @@ impl CurrentUser {
     pub fn is_author_or_admin(&self, author_id: i64) -> bool {
         self.id == author_id || self.is_admin()
     }
+
+    /// Whether the user may change the content of a post in this status
+    pub fn can_edit_post(&self, author_id: i64, status: PostStatus) -> bool {
+        self.is_admin() || (self.id == author_id && status.editable_by_author())
+    }
 }
 
 /// Admin middleware
@@ -138,4 +143,35 @@ pub async fn admin_middleware(
     }
 
     Ok(next.run(request).await)
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dto::PostTransition;
+
+    fn user(id: i64, role: UserRole) -> CurrentUser {
+        CurrentUser {
+            id,
+            username: format!("user{}", id),
+            role,
+        }
+    }
+
+    #[test]
+    fn test_author_cannot_edit_approved_post() {
+        let author = user(1, UserRole::User);
+        let approved = PostTransition::Approve
+            .target(PostStatus::PendingReview)
+            .unwrap();
+        assert!(!author.can_edit_post(1, approved));
+        assert!(!author.can_edit_post(1, PostStatus::Scheduled));
+        assert!(!author.can_edit_post(1, PostStatus::Private));
+        assert!(author.can_edit_post(1, PostStatus::Draft));
+        assert!(author.can_edit_post(1, PostStatus::PendingReview));
+        assert!(!author.can_edit_post(2, PostStatus::Draft));
+
+        assert!(user(3, UserRole::Admin).can_edit_post(1, approved));
+        assert!(user(4, UserRole::Root).can_edit_post(1, PostStatus::Scheduled));
+    }
+}
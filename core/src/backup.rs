@@ -0,0 +1,844 @@
+//! Backup and restore of a whole site
+//!
+//! A backup is one zip archive holding:
+//! - `manifest.json`: a [`BackupManifest`] with the archive format and the schema
+//!   migrations the data was written with
+//! - `data/<table>.json`: the rows of every table, including users' password hashes
+//! - `plugins/<id>-<version>.rpk`: the installed plugin packages
+//! - `media/<name>`: the files of the storage backend, local or S3
+//!
+//! OpenAI API keys are saved without the key itself; restored keys have to be
+//! entered again. A backup can only be restored by a build whose migrations match
+//! the ones it was written with, into a database migrated by that build.
+
+use crate::dto::{BackupManifest, RestoreReport};
+use crate::entity::{
+    categories, comments, import_records, media, media_variants, openai_api_keys,
+    plugin_permissions, plugins, post_drafts, post_slug_history, post_tags, post_versions, posts,
+    settings, tags, users,
+};
+use crate::storage::{StorageBackend, stored_file_name};
+use bytes::Bytes;
+use chrono::Utc;
+use futures::StreamExt;
+use rustpress_migration::Migrator;
+use sea_orm::{
+    ActiveModelTrait, ConnectionTrait, DatabaseConnection, EntityName, EntityTrait,
+    IntoActiveModel, Statement, TransactionTrait,
+};
+use sea_orm_migration::MigratorTrait;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use std::collections::{HashMap, HashSet};
+use std::fs::File;
+use std::io::{Read, Write};
+use std::path::{Component, Path, PathBuf};
+use tokio::sync::mpsc;
+
+type BackupError = Box<dyn std::error::Error + Send + Sync>;
+
+/// Version of the archive layout written by [`create_backup`]
+pub const BACKUP_FORMAT: u32 = 1;
+
+const MANIFEST_FILE: &str = "manifest.json";
+const DATA_DIR: &str = "data/";
+const PLUGINS_DIR: &str = "plugins/";
+const MEDIA_DIR: &str = "media/";
+
+/// Rows inserted per statement, well below PostgreSQL's limit on bind parameters
+const INSERT_BATCH: usize = 500;
+
+/// Chunks of media read ahead of the thread writing the archive
+const MEDIA_CHUNKS_AHEAD: usize = 16;
+
+/// Tables with a serial `id`, whose sequences are moved past the restored rows
+const SERIAL_TABLES: &[&str] = &[
+    "users",
+    "openai_api_keys",
+    "plugins",
+    "plugin_permissions",
+    "categories",
+    "tags",
+    "posts",
+    "post_versions",
+    "post_drafts",
+    "comments",
+    "import_records",
+    "media",
+    "media_variants",
+];
+
+/// Where the files included in backups live, besides the storage backend
+#[derive(Debug, Clone)]
+pub struct BackupPaths {
+    /// Directory of the installed `.rpk` packages
+    pub plugins_dir: PathBuf,
+}
+
+/// The archive is not a readable backup
+#[derive(Debug, thiserror::Error)]
+#[error("Invalid backup archive: {0}")]
+pub struct InvalidBackup(pub String);
+
+/// The backup was written with other schema migrations than this build has
+#[derive(Debug, thiserror::Error)]
+#[error(
+    "Backup schema does not match this build (migrations missing from the backup: {missing:?}, unknown migrations: {unknown:?})"
+)]
+pub struct SchemaMismatch {
+    /// Migrations of this build that the backup predates
+    pub missing: Vec<String>,
+    /// Migrations in the backup that this build does not know
+    pub unknown: Vec<String>,
+}
+
+/// Check that a backup was written with exactly the migrations this build has
+fn check_schema(backup: &[String], known: &[String]) -> Result<(), SchemaMismatch> {
+    if backup == known {
+        return Ok(());
+    }
+    Err(SchemaMismatch {
+        missing: known
+            .iter()
+            .filter(|name| !backup.contains(name))
+            .cloned()
+            .collect(),
+        unknown: backup
+            .iter()
+            .filter(|name| !known.contains(name))
+            .cloned()
+            .collect(),
+    })
+}
+
+/// Names of the migrations this build runs, oldest first
+pub fn known_migrations() -> Vec<String> {
+    Migrator::migrations()
+        .iter()
+        .map(|migration| migration.name().to_string())
+        .collect()
+}
+
+/// Names of the migrations applied to the database, oldest first
+pub async fn applied_migrations(db: &DatabaseConnection) -> Result<Vec<String>, BackupError> {
+    Ok(Migrator::get_applied_migrations(db)
+        .await?
+        .iter()
+        .map(|migration| migration.name().to_string())
+        .collect())
+}
+
+/// Write a backup of the database, plugin packages and stored media to `out`
+pub async fn create_backup<SB: StorageBackend + ?Sized>(
+    db: &DatabaseConnection,
+    storage: &SB,
+    paths: &BackupPaths,
+    out: &Path,
+) -> Result<BackupManifest, BackupError> {
+    // One read-only snapshot, so rows written meanwhile can't break references
+    let txn = db.begin().await?;
+    txn.execute_unprepared("SET TRANSACTION ISOLATION LEVEL REPEATABLE READ READ ONLY")
+        .await?;
+    let mut tables = Vec::new();
+    dump::<users::Entity>(&txn, &mut tables).await?;
+    dump::<settings::Entity>(&txn, &mut tables).await?;
+    let keys = openai_api_keys::Entity::find()
+        .all(&txn)
+        .await?
+        .into_iter()
+        .map(|key| openai_api_keys::Model {
+            api_key: String::new(),
+            ..key
+        })
+        .collect::<Vec<_>>();
+    add_table(&mut tables, openai_api_keys::Entity, &keys)?;
+    dump::<plugins::Entity>(&txn, &mut tables).await?;
+    dump::<plugin_permissions::Entity>(&txn, &mut tables).await?;
+    dump::<categories::Entity>(&txn, &mut tables).await?;
+    dump::<tags::Entity>(&txn, &mut tables).await?;
+    dump::<posts::Entity>(&txn, &mut tables).await?;
+    dump::<post_tags::Entity>(&txn, &mut tables).await?;
+    dump::<post_versions::Entity>(&txn, &mut tables).await?;
+    dump::<post_drafts::Entity>(&txn, &mut tables).await?;
+    dump::<post_slug_history::Entity>(&txn, &mut tables).await?;
+    dump::<comments::Entity>(&txn, &mut tables).await?;
+    dump::<import_records::Entity>(&txn, &mut tables).await?;
+    // media_references is left out; it is rebuilt from post content on restore
+    dump::<media::Entity>(&txn, &mut tables).await?;
+    dump::<media_variants::Entity>(&txn, &mut tables).await?;
+    txn.commit().await?;
+
+    let manifest = BackupManifest {
+        format: BACKUP_FORMAT,
+        rustpress_version: env!("CARGO_PKG_VERSION").to_string(),
+        created_at: Utc::now(),
+        migrations: applied_migrations(db).await?,
+        tables: tables
+            .iter()
+            .map(|table| (table.name.clone(), table.rows))
+            .collect(),
+        plugin_files: 0,
+        media_files: 0,
+    };
+
+    write_backup(storage, paths, tables, manifest, out).await
+}
+
+/// Write the archive of a backup, with the media read from the storage backend
+async fn write_backup<SB: StorageBackend + ?Sized>(
+    storage: &SB,
+    paths: &BackupPaths,
+    tables: Vec<TableData>,
+    mut manifest: BackupManifest,
+    out: &Path,
+) -> Result<BackupManifest, BackupError> {
+    let (media_sender, media_receiver) = mpsc::channel(MEDIA_CHUNKS_AHEAD);
+    let paths = paths.clone();
+    let out = out.to_path_buf();
+    let writer = tokio::task::spawn_blocking(move || {
+        write_archive(&out, &paths, &tables, &mut manifest, media_receiver)?;
+        Ok::<_, BackupError>(manifest)
+    });
+
+    let sent = send_media(storage, &media_sender).await;
+    drop(media_sender);
+    // A writer that failed stops receiving, so its error comes first
+    let manifest = writer.await??;
+    sent?;
+    Ok(manifest)
+}
+
+/// A media file for the archive: its name and size, then its contents
+enum MediaMessage {
+    File { name: String, size: u64 },
+    Data(Bytes),
+}
+
+/// Read every stored file and send it to the thread writing the archive
+async fn send_media<SB: StorageBackend + ?Sized>(
+    storage: &SB,
+    sender: &mpsc::Sender<MediaMessage>,
+) -> Result<(), BackupError> {
+    const WRITER_STOPPED: &str = "The backup archive writer stopped";
+    for file in storage.list("").await? {
+        // Removed from storage since it was listed
+        let Some(stored) = storage.get(&file.name, None).await? else {
+            continue;
+        };
+        sender
+            .send(MediaMessage::File {
+                name: file.name,
+                size: stored.metadata.size,
+            })
+            .await
+            .map_err(|_| WRITER_STOPPED)?;
+        let mut body = stored.body;
+        while let Some(chunk) = body.next().await {
+            sender
+                .send(MediaMessage::Data(chunk?))
+                .await
+                .map_err(|_| WRITER_STOPPED)?;
+        }
+    }
+    Ok(())
+}
+
+/// Replace all content, users, settings, plugins and media with a backup
+///
+/// The database rows are replaced in one transaction; files are written after it
+/// commits, media to the storage backend, overwriting files of the same name.
+pub async fn restore_backup<SB: StorageBackend + ?Sized>(
+    db: &DatabaseConnection,
+    storage: &SB,
+    paths: &BackupPaths,
+    archive: &Path,
+) -> Result<RestoreReport, BackupError> {
+    let archive_path = archive.to_path_buf();
+    let (manifest, data) =
+        tokio::task::spawn_blocking(move || read_archive_data(&archive_path)).await??;
+
+    let known = known_migrations();
+    if applied_migrations(db).await? != known {
+        return Err("The database is not migrated to this build's schema; start the server or run the migrations first".into());
+    }
+    check_schema(&manifest.migrations, &known)?;
+
+    let users = rows::<users::Entity>(&data)?;
+    let settings = rows::<settings::Entity>(&data)?;
+    let keys = rows::<openai_api_keys::Entity>(&data)?;
+    let plugins = rows::<plugins::Entity>(&data)?;
+    let plugin_permissions = rows::<plugin_permissions::Entity>(&data)?;
+    let categories = parents_first(
+        rows::<categories::Entity>(&data)?,
+        |category| category.id,
+        |category| category.parent_id,
+    );
+    let tags = rows::<tags::Entity>(&data)?;
+    let posts = rows::<posts::Entity>(&data)?;
+    let post_tags = rows::<post_tags::Entity>(&data)?;
+    let post_versions = rows::<post_versions::Entity>(&data)?;
+    let post_drafts = rows::<post_drafts::Entity>(&data)?;
+    let post_slug_history = rows::<post_slug_history::Entity>(&data)?;
+    let comments = parents_first(
+        rows::<comments::Entity>(&data)?,
+        |comment| comment.id,
+        |comment| comment.parent_id,
+    );
+    let import_records = rows::<import_records::Entity>(&data)?;
+    let media = rows::<media::Entity>(&data)?;
+    let media_variants = rows::<media_variants::Entity>(&data)?;
+
+    let mut warnings = Vec::new();
+    if !keys.is_empty() {
+        warnings.push(format!(
+            "{} OpenAI API keys were restored without the key itself; enter them again",
+            keys.len()
+        ));
+    }
+    if !plugins.is_empty() {
+        warnings.push("Restart the server to load the restored plugins".to_string());
+    }
+
+    let txn = db.begin().await?;
+    // Children first, so that no delete is blocked by a foreign key
+    media_variants::Entity::delete_many().exec(&txn).await?;
+    media::Entity::delete_many().exec(&txn).await?;
+    import_records::Entity::delete_many().exec(&txn).await?;
+    comments::Entity::delete_many().exec(&txn).await?;
+    post_slug_history::Entity::delete_many().exec(&txn).await?;
+    post_drafts::Entity::delete_many().exec(&txn).await?;
+    post_versions::Entity::delete_many().exec(&txn).await?;
+    post_tags::Entity::delete_many().exec(&txn).await?;
+    posts::Entity::delete_many().exec(&txn).await?;
+    tags::Entity::delete_many().exec(&txn).await?;
+    categories::Entity::delete_many().exec(&txn).await?;
+    plugin_permissions::Entity::delete_many().exec(&txn).await?;
+    plugins::Entity::delete_many().exec(&txn).await?;
+    openai_api_keys::Entity::delete_many().exec(&txn).await?;
+    settings::Entity::delete_many().exec(&txn).await?;
+    users::Entity::delete_many().exec(&txn).await?;
+
+    insert_rows::<users::ActiveModel, _>(&txn, users).await?;
+    insert_rows::<settings::ActiveModel, _>(&txn, settings).await?;
+    insert_rows::<openai_api_keys::ActiveModel, _>(&txn, keys).await?;
+    insert_rows::<plugins::ActiveModel, _>(&txn, plugins).await?;
+    insert_rows::<plugin_permissions::ActiveModel, _>(&txn, plugin_permissions).await?;
+    insert_rows::<categories::ActiveModel, _>(&txn, categories).await?;
+    insert_rows::<tags::ActiveModel, _>(&txn, tags).await?;
+    insert_rows::<posts::ActiveModel, _>(&txn, posts).await?;
+    insert_rows::<post_tags::ActiveModel, _>(&txn, post_tags).await?;
+    insert_rows::<post_versions::ActiveModel, _>(&txn, post_versions).await?;
+    insert_rows::<post_drafts::ActiveModel, _>(&txn, post_drafts).await?;
+    insert_rows::<post_slug_history::ActiveModel, _>(&txn, post_slug_history).await?;
+    insert_rows::<comments::ActiveModel, _>(&txn, comments).await?;
+    insert_rows::<import_records::ActiveModel, _>(&txn, import_records).await?;
+    // After the posts, whose content the database scans for references to each file
+    insert_rows::<media::ActiveModel, _>(&txn, media).await?;
+    insert_rows::<media_variants::ActiveModel, _>(&txn, media_variants).await?;
+
+    for table in SERIAL_TABLES {
+        txn.execute(Statement::from_string(
+            txn.get_database_backend(),
+            format!(
+                "SELECT setval(pg_get_serial_sequence('{table}', 'id'), \
+                 COALESCE((SELECT MAX(id) FROM {table}), 0) + 1, false)"
+            ),
+        ))
+        .await?;
+    }
+    txn.commit().await?;
+
+    restore_files(storage, paths, archive).await?;
+
+    Ok(RestoreReport { manifest, warnings })
+}
+
+/// Write the plugin packages of an archive to their directory and its media
+/// files to the storage backend
+async fn restore_files<SB: StorageBackend + ?Sized>(
+    storage: &SB,
+    paths: &BackupPaths,
+    archive: &Path,
+) -> Result<(), BackupError> {
+    // One media file at a time is staged on disk
+    let (media_sender, mut media_receiver) = mpsc::channel(1);
+    let paths = paths.clone();
+    let archive_path = archive.to_path_buf();
+    let extractor =
+        tokio::task::spawn_blocking(move || extract_files(&archive_path, &paths, media_sender));
+
+    while let Some((name, file)) = media_receiver.recv().await {
+        let content_type = mime_guess::from_path(&name)
+            .first_or_octet_stream()
+            .to_string();
+        storage
+            .put_file_from_path(file.path(), &name, content_type)
+            .await?;
+    }
+    extractor.await?
+}
+
+/// Rows of one table, serialized as JSON
+struct TableData {
+    name: String,
+    rows: u64,
+    json: Vec<u8>,
+}
+
+async fn dump<E>(db: &impl ConnectionTrait, tables: &mut Vec<TableData>) -> Result<(), BackupError>
+where
+    E: EntityTrait,
+    E::Model: Serialize,
+{
+    let rows = E::find().all(db).await?;
+    add_table(tables, E::default(), &rows)
+}
+
+fn add_table<E: EntityName, M: Serialize>(
+    tables: &mut Vec<TableData>,
+    entity: E,
+    rows: &[M],
+) -> Result<(), BackupError> {
+    tables.push(TableData {
+        name: entity.table_name().to_string(),
+        rows: rows.len() as u64,
+        json: serde_json::to_vec(rows)?,
+    });
+    Ok(())
+}
+
+fn rows<E>(data: &HashMap<String, Vec<u8>>) -> Result<Vec<E::Model>, BackupError>
+where
+    E: EntityTrait,
+    E::Model: DeserializeOwned,
+{
+    let entity = E::default();
+    let name = entity.table_name();
+    let json = data
+        .get(name)
+        .ok_or_else(|| InvalidBackup(format!("no data for table {}", name)))?;
+    serde_json::from_slice(json)
+        .map_err(|e| InvalidBackup(format!("data of table {}: {}", name, e)).into())
+}
+
+async fn insert_rows<A, C>(
+    db: &C,
+    rows: Vec<<A::Entity as EntityTrait>::Model>,
+) -> Result<(), BackupError>
+where
+    A: ActiveModelTrait,
+    <A::Entity as EntityTrait>::Model: IntoActiveModel<A>,
+    C: ConnectionTrait,
+{
+    let mut rows = rows.into_iter().peekable();
+    while rows.peek().is_some() {
+        let batch: Vec<A> = rows
+            .by_ref()
+            .take(INSERT_BATCH)
+            .map(IntoActiveModel::into_active_model)
+            .collect();
+        A::Entity::insert_many(batch)
+            .exec_without_returning(db)
+            .await?;
+    }
+    Ok(())
+}
+
+/// Order rows of a self-referencing table so that parents come before their children
+fn parents_first<M>(
+    mut rows: Vec<M>,
+    id: impl Fn(&M) -> i64,
+    parent: impl Fn(&M) -> Option<i64>,
+) -> Vec<M> {
+    rows.sort_by_key(&id);
+    let ids: HashSet<i64> = rows.iter().map(&id).collect();
+    let mut placed = HashSet::new();
+    let mut ordered = Vec::with_capacity(rows.len());
+    while !rows.is_empty() {
+        let (ready, waiting): (Vec<M>, Vec<M>) = rows.into_iter().partition(|row| {
+            parent(row).is_none_or(|parent| placed.contains(&parent) || !ids.contains(&parent))
+        });
+        if ready.is_empty() {
+            // A cycle; leave it to the foreign key check to reject
+            ordered.extend(waiting);
+            break;
+        }
+        placed.extend(ready.iter().map(&id));
+        ordered.extend(ready);
+        rows = waiting;
+    }
+    ordered
+}
+
+fn write_archive(
+    out: &Path,
+    paths: &BackupPaths,
+    tables: &[TableData],
+    manifest: &mut BackupManifest,
+    mut media_receiver: mpsc::Receiver<MediaMessage>,
+) -> Result<(), BackupError> {
+    if let Some(parent) = out.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent)?;
+    }
+    let mut writer = zip::ZipWriter::new(File::create(out)?);
+    let deflated =
+        zip::write::FileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+
+    for table in tables {
+        writer.start_file(format!("{}{}.json", DATA_DIR, table.name), deflated)?;
+        writer.write_all(&table.json)?;
+    }
+
+    if paths.plugins_dir.is_dir() {
+        for entry in std::fs::read_dir(&paths.plugins_dir)? {
+            let entry = entry?;
+            let name = entry.file_name().to_string_lossy().into_owned();
+            if entry.file_type()?.is_file() && name.ends_with(".rpk") {
+                add_file(
+                    &mut writer,
+                    &format!("{}{}", PLUGINS_DIR, name),
+                    &entry.path(),
+                )?;
+                manifest.plugin_files += 1;
+            }
+        }
+    }
+
+    // Media files arrive from the storage backend as they are read
+    while let Some(message) = media_receiver.blocking_recv() {
+        match message {
+            MediaMessage::File { name, size } => {
+                writer.start_file(format!("{}{}", MEDIA_DIR, name), stored(size))?;
+                manifest.media_files += 1;
+            }
+            MediaMessage::Data(data) => writer.write_all(&data)?,
+        }
+    }
+
+    writer.start_file(MANIFEST_FILE, deflated)?;
+    writer.write_all(&serde_json::to_vec_pretty(manifest)?)?;
+    writer.finish()?;
+    Ok(())
+}
+
+/// Options for a file stored uncompressed; media and plugin packages are
+/// compressed already
+fn stored(size: u64) -> zip::write::FileOptions {
+    zip::write::FileOptions::default()
+        .compression_method(zip::CompressionMethod::Stored)
+        .large_file(size >= u32::MAX as u64)
+}
+
+fn add_file(writer: &mut zip::ZipWriter<File>, name: &str, path: &Path) -> Result<(), BackupError> {
+    let mut file = File::open(path)?;
+    writer.start_file(name, stored(file.metadata()?.len()))?;
+    std::io::copy(&mut file, writer)?;
+    Ok(())
+}
+
+fn open_archive(path: &Path) -> Result<zip::ZipArchive<File>, BackupError> {
+    zip::ZipArchive::new(File::open(path)?).map_err(|e| InvalidBackup(e.to_string()).into())
+}
+
+/// Manifest of a backup archive, to show what it holds before restoring it
+pub fn read_manifest(path: &Path) -> Result<BackupManifest, BackupError> {
+    manifest_of(&mut open_archive(path)?)
+}
+
+fn manifest_of(archive: &mut zip::ZipArchive<File>) -> Result<BackupManifest, BackupError> {
+    let manifest: BackupManifest = {
+        let entry = archive
+            .by_name(MANIFEST_FILE)
+            .map_err(|_| InvalidBackup(format!("no {}", MANIFEST_FILE)))?;
+        serde_json::from_reader(entry)
+            .map_err(|e| InvalidBackup(format!("{}: {}", MANIFEST_FILE, e)))?
+    };
+    if manifest.format > BACKUP_FORMAT {
+        return Err(InvalidBackup(format!(
+            "archive format {} is newer than this build supports ({})",
+            manifest.format, BACKUP_FORMAT
+        ))
+        .into());
+    }
+    Ok(manifest)
+}
+
+/// The manifest and the table data of an archive
+fn read_archive_data(
+    path: &Path,
+) -> Result<(BackupManifest, HashMap<String, Vec<u8>>), BackupError> {
+    let mut archive = open_archive(path)?;
+    let manifest = manifest_of(&mut archive)?;
+
+    let mut data = HashMap::new();
+    for index in 0..archive.len() {
+        let mut entry = archive.by_index(index)?;
+        let Some(table) = entry
+            .name()
+            .strip_prefix(DATA_DIR)
+            .and_then(|name| name.strip_suffix(".json"))
+            .map(str::to_string)
+        else {
+            continue;
+        };
+        let mut json = Vec::with_capacity(entry.size() as usize);
+        entry.read_to_end(&mut json)?;
+        data.insert(table, json);
+    }
+    Ok((manifest, data))
+}
+
+/// What an archive entry is restored as
+#[derive(Debug, PartialEq, Eq)]
+enum ExtractTarget {
+    /// A plugin package, written to this path
+    Plugin(PathBuf),
+    /// A media file, stored under this name
+    Media(String),
+}
+
+/// What an archive entry is restored as; `None` for entries that are not plugin
+/// packages or media files, or whose name would leave their directory
+fn extract_target(name: &Path, paths: &BackupPaths) -> Option<ExtractTarget> {
+    if let Ok(file) = name.strip_prefix(PLUGINS_DIR) {
+        // Packages are kept flat, as the plugin registry expects
+        match file.components().collect::<Vec<_>>().as_slice() {
+            [Component::Normal(file)] => Some(ExtractTarget::Plugin(paths.plugins_dir.join(file))),
+            _ => None,
+        }
+    } else if let Ok(file) = name.strip_prefix(MEDIA_DIR) {
+        // Stored files have plain names; `enclosed_name` lets `media/../x` through
+        let file = file.to_str()?;
+        (stored_file_name(file) == Some(file)).then(|| ExtractTarget::Media(file.to_string()))
+    } else {
+        None
+    }
+}
+
+/// Write the plugin packages of an archive to their directory, and send its
+/// media files, staged in temporary files, to be stored
+fn extract_files(
+    path: &Path,
+    paths: &BackupPaths,
+    media_sender: mpsc::Sender<(String, tempfile::NamedTempFile)>,
+) -> Result<(), BackupError> {
+    let mut archive = open_archive(path)?;
+    for index in 0..archive.len() {
+        let mut entry = archive.by_index(index)?;
+        if entry.is_dir() {
+            continue;
+        }
+        let Some(target) = entry
+            .enclosed_name()
+            .and_then(|name| extract_target(name, paths))
+        else {
+            continue;
+        };
+        match target {
+            ExtractTarget::Plugin(target) => {
+                if let Some(parent) = target.parent() {
+                    std::fs::create_dir_all(parent)?;
+                }
+                std::io::copy(&mut entry, &mut File::create(&target)?)?;
+            }
+            ExtractTarget::Media(name) => {
+                let mut file = tempfile::NamedTempFile::new()?;
+                std::io::copy(&mut entry, &mut file)?;
+                media_sender
+                    .blocking_send((name, file))
+                    .map_err(|_| "Storing the restored media stopped")?;
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::LocalStorage;
+    use chrono::FixedOffset;
+
+    fn paths(dir: &Path) -> BackupPaths {
+        BackupPaths {
+            plugins_dir: dir.join("plugins"),
+        }
+    }
+
+    fn manifest() -> BackupManifest {
+        BackupManifest {
+            format: BACKUP_FORMAT,
+            rustpress_version: "test".to_string(),
+            created_at: Utc::now(),
+            migrations: known_migrations(),
+            tables: Default::default(),
+            plugin_files: 0,
+            media_files: 0,
+        }
+    }
+
+    /// An archive with a manifest and the given files
+    fn write_zip(path: &Path, manifest: &BackupManifest, files: &[(&str, &[u8])]) {
+        let mut writer = zip::ZipWriter::new(File::create(path).unwrap());
+        for (name, data) in files {
+            writer
+                .start_file(*name, zip::write::FileOptions::default())
+                .unwrap();
+            writer.write_all(data).unwrap();
+        }
+        writer
+            .start_file(MANIFEST_FILE, zip::write::FileOptions::default())
+            .unwrap();
+        writer
+            .write_all(&serde_json::to_vec(manifest).unwrap())
+            .unwrap();
+        writer.finish().unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_archive_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let source = paths(&dir.path().join("source"));
+        let source_storage = LocalStorage::new(dir.path().join("source/uploads"), String::new());
+        for (name, data) in [("cat.png", b"cat"), ("dog.jpg", b"dog")] {
+            source_storage
+                .put_file(data.to_vec(), name, String::new())
+                .await
+                .unwrap();
+        }
+        std::fs::create_dir_all(&source.plugins_dir).unwrap();
+        std::fs::write(source.plugins_dir.join("hello-1.0.0.rpk"), b"package").unwrap();
+        std::fs::write(source.plugins_dir.join("notes.txt"), b"not a package").unwrap();
+
+        let time = Utc::now().with_timezone(&FixedOffset::east_opt(0).unwrap());
+        let settings = vec![settings::Model {
+            key: "site_title".to_string(),
+            value: "My blog".to_string(),
+            description: None,
+            created_at: time,
+            updated_at: time,
+        }];
+        let mut tables = Vec::new();
+        add_table(&mut tables, settings::Entity, &settings).unwrap();
+        add_table(&mut tables, tags::Entity, &Vec::<tags::Model>::new()).unwrap();
+
+        let archive = dir.path().join("backups/site.zip");
+        let mut manifest = manifest();
+        manifest.tables = tables
+            .iter()
+            .map(|table| (table.name.clone(), table.rows))
+            .collect();
+        let written = write_backup(&source_storage, &source, tables, manifest, &archive)
+            .await
+            .unwrap();
+        assert_eq!(written.plugin_files, 1);
+        assert_eq!(written.media_files, 2);
+        assert_eq!(read_manifest(&archive).unwrap(), written);
+
+        let (manifest, data) = read_archive_data(&archive).unwrap();
+        assert_eq!(manifest, written);
+        assert_eq!(rows::<settings::Entity>(&data).unwrap(), settings);
+        assert!(rows::<tags::Entity>(&data).unwrap().is_empty());
+        assert!(rows::<posts::Entity>(&data).is_err());
+
+        let target = paths(&dir.path().join("target"));
+        let target_storage = LocalStorage::new(dir.path().join("target/uploads"), String::new());
+        restore_files(&target_storage, &target, &archive)
+            .await
+            .unwrap();
+        let names = target_storage
+            .list("")
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|file| file.name)
+            .collect::<Vec<_>>();
+        assert_eq!(names, ["cat.png", "dog.jpg"]);
+        assert_eq!(
+            std::fs::read(dir.path().join("target/uploads/dog.jpg")).unwrap(),
+            b"dog"
+        );
+        assert_eq!(
+            std::fs::read(target.plugins_dir.join("hello-1.0.0.rpk")).unwrap(),
+            b"package"
+        );
+        assert!(!target.plugins_dir.join("notes.txt").exists());
+    }
+
+    #[test]
+    fn test_schema_mismatch() {
+        let known = known_migrations();
+        assert!(check_schema(&known, &known).is_ok());
+
+        let mut backup = known[..known.len() - 1].to_vec();
+        backup.push("m29990101_000000_from_the_future".to_string());
+        let mismatch = check_schema(&backup, &known).unwrap_err();
+        assert_eq!(mismatch.missing, vec![known.last().unwrap().clone()]);
+        assert_eq!(mismatch.unknown, vec!["m29990101_000000_from_the_future"]);
+
+        // Archives of a newer layout are refused before anything is read
+        let dir = tempfile::tempdir().unwrap();
+        let archive = dir.path().join("backup.zip");
+        let newer = BackupManifest {
+            format: BACKUP_FORMAT + 1,
+            ..manifest()
+        };
+        write_zip(&archive, &newer, &[]);
+        let error = read_archive_data(&archive).unwrap_err();
+        assert!(error.downcast_ref::<InvalidBackup>().is_some(), "{}", error);
+    }
+
+    #[tokio::test]
+    async fn test_restore_rejects_traversal() {
+        let dir = tempfile::tempdir().unwrap();
+        let target = paths(&dir.path().join("site"));
+        let storage = LocalStorage::new(dir.path().join("site/uploads"), String::new());
+        let archive_dir = tempfile::tempdir().unwrap();
+        let archive = archive_dir.path().join("backup.zip");
+        write_zip(
+            &archive,
+            &manifest(),
+            &[
+                ("media/ok.png", b"ok"),
+                ("../escaped.txt", b"evil"),
+                ("media/../../escaped.txt", b"evil"),
+                ("media/../outside.txt", b"evil"),
+                ("media/.env", b"evil"),
+                ("/tmp/absolute.txt", b"evil"),
+                ("plugins/../escaped.rpk", b"evil"),
+                ("plugins/nested/evil.rpk", b"evil"),
+            ],
+        );
+
+        restore_files(&storage, &target, &archive).await.unwrap();
+        let mut written = Vec::new();
+        let mut pending = vec![dir.path().to_path_buf()];
+        while let Some(dir) = pending.pop() {
+            for entry in std::fs::read_dir(dir).unwrap() {
+                let path = entry.unwrap().path();
+                if path.is_dir() {
+                    pending.push(path);
+                } else {
+                    written.push(path);
+                }
+            }
+        }
+        assert_eq!(written, vec![dir.path().join("site/uploads/ok.png")]);
+
+        let name = |name: &str| extract_target(Path::new(name), &target);
+        assert_eq!(
+            name("media/cat.png"),
+            Some(ExtractTarget::Media("cat.png".to_string()))
+        );
+        assert_eq!(
+            name("plugins/hello-1.0.0.rpk"),
+            Some(ExtractTarget::Plugin(
+                target.plugins_dir.join("hello-1.0.0.rpk")
+            ))
+        );
+        assert_eq!(name("media/../outside.txt"), None);
+        assert_eq!(name("media/2024/cat.png"), None);
+        assert_eq!(name("plugins/nested/evil.rpk"), None);
+        assert_eq!(name("data/posts.json"), None);
+    }
+}
@@ -2,7 +2,7 @@ use crate::dto::{PostResponse, UserResponse};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use serde_json::Value as JsonValue;
-use utoipa::ToSchema;
+use utoipa::{IntoParams, ToSchema};
 
 /// Setting item in a settings tab
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
@@ -48,6 +48,36 @@ pub struct AdminPostListResponse {
     pub post: PostResponse,
 }
 
+/// Archived or trashed post in the admin lists
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AdminRemovedPostResponse {
+    pub post: PostResponse,
+    pub archived_at: Option<DateTime<Utc>>,
+    /// When the post was moved to the trash
+    pub deleted_at: Option<DateTime<Utc>>,
+    /// When the post will be purged from the trash; null when trash is kept until emptied
+    pub purge_at: Option<DateTime<Utc>>,
+}
+
+/// Query parameters for listing the trash
+#[derive(Debug, Clone, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct TrashListQuery {
+    /// Page number (1-based, default 1)
+    pub page: Option<u64>,
+    /// Items per page (defaults to the `posts_per_page` setting, max 100)
+    pub per_page: Option<u64>,
+}
+
+/// What purging posts from the trash removed
+#[derive(Debug, Default, Serialize, ToSchema)]
+pub struct PurgeTrashResponse {
+    /// Posts deleted permanently, with their versions and drafts
+    pub posts: u64,
+    /// Uploaded files deleted because no other post linked to them
+    pub media_files: u64,
+}
+
 /// Admin ban user request
 #[derive(Debug, Deserialize, ToSchema)]
 pub struct AdminBanUserRequest {
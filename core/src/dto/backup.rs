@@ -0,0 +1,39 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use utoipa::{IntoParams, ToSchema};
+
+/// Description of a backup archive, stored in it as `manifest.json`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct BackupManifest {
+    /// Version of the archive layout
+    pub format: u32,
+    /// Version of RustPress that wrote the backup
+    pub rustpress_version: String,
+    pub created_at: DateTime<Utc>,
+    /// Schema migrations applied to the backed-up database, oldest first
+    pub migrations: Vec<String>,
+    /// Number of rows saved per table
+    pub tables: BTreeMap<String, u64>,
+    /// Installed plugin packages (`.rpk` files)
+    pub plugin_files: u64,
+    /// Uploaded media files
+    pub media_files: u64,
+}
+
+/// Query parameters of the restore endpoint
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct RestoreQuery {
+    /// Must be `true`: restoring replaces all posts, users, settings and plugins
+    #[serde(default)]
+    pub confirm: bool,
+}
+
+/// Outcome of a restore
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct RestoreReport {
+    /// Manifest of the restored backup
+    pub manifest: BackupManifest,
+    pub warnings: Vec<String>,
+}
@@ -0,0 +1,204 @@
+use crate::entity::categories;
+use chrono::{DateTime, Utc};
+use sea_orm::prelude::DateTimeWithTimeZone;
+use serde::{Deserialize, Deserializer, Serialize};
+use std::collections::HashMap;
+use utoipa::{IntoParams, ToSchema};
+
+/// Category business entity (DTO)
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct Category {
+    pub id: i64,
+    pub name: String,
+    pub slug: String,
+    pub description: Option<String>,
+    pub parent_id: Option<i64>,
+    pub created_at: DateTimeWithTimeZone,
+    pub updated_at: DateTimeWithTimeZone,
+}
+
+/// Category tree node with its direct post count and child categories
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct CategoryTreeNode {
+    pub id: i64,
+    pub name: String,
+    pub slug: String,
+    pub description: Option<String>,
+    pub parent_id: Option<i64>,
+    /// Number of published posts directly in this category (children not included)
+    pub post_count: i64,
+    pub children: Vec<CategoryTreeNode>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Request DTO for creating a category
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CreateCategoryRequest {
+    pub name: String,
+    /// Generated from the name when omitted
+    pub slug: Option<String>,
+    pub description: Option<String>,
+    pub parent_id: Option<i64>,
+}
+
+/// Request DTO for updating a category
+///
+/// Omitted fields are left unchanged; `description` and `parent_id` can be cleared with `null`.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdateCategoryRequest {
+    pub name: Option<String>,
+    pub slug: Option<String>,
+    #[serde(default, deserialize_with = "deserialize_present")]
+    #[schema(value_type = Option<String>)]
+    pub description: Option<Option<String>>,
+    #[serde(default, deserialize_with = "deserialize_present")]
+    #[schema(value_type = Option<i64>)]
+    pub parent_id: Option<Option<i64>>,
+}
+
+/// Query parameters for deleting a category
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct DeleteCategoryQuery {
+    /// Category that receives the deleted category's posts (default: none)
+    pub reassign_to: Option<i64>,
+}
+
+/// Distinguish an explicit `null` (`Some(None)`) from an omitted field (`None`)
+pub(crate) fn deserialize_present<'de, D, T>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    Option::<T>::deserialize(deserializer).map(Some)
+}
+
+/// Convert from database Model to business DTO
+impl From<categories::Model> for Category {
+    fn from(model: categories::Model) -> Self {
+        Category {
+            id: model.id,
+            name: model.name,
+            slug: model.slug,
+            description: model.description,
+            parent_id: model.parent_id,
+            created_at: model.created_at,
+            updated_at: model.updated_at,
+        }
+    }
+}
+
+impl CategoryTreeNode {
+    fn new(category: Category, post_count: i64) -> Self {
+        CategoryTreeNode {
+            id: category.id,
+            name: category.name,
+            slug: category.slug,
+            description: category.description,
+            parent_id: category.parent_id,
+            post_count,
+            children: Vec::new(),
+            created_at: category.created_at.into(),
+            updated_at: category.updated_at.into(),
+        }
+    }
+}
+
+/// Build the category tree from a flat list of categories with post counts
+///
+/// Siblings keep the order of the input. Categories whose parent is missing from
+/// the list are treated as roots.
+pub fn build_category_tree(categories: Vec<(Category, i64)>) -> Vec<CategoryTreeNode> {
+    let ids: Vec<i64> = categories.iter().map(|(category, _)| category.id).collect();
+    let mut children_of: HashMap<Option<i64>, Vec<CategoryTreeNode>> = HashMap::new();
+    for (category, count) in categories {
+        let parent = category.parent_id.filter(|parent| ids.contains(parent));
+        children_of
+            .entry(parent)
+            .or_default()
+            .push(CategoryTreeNode::new(category, count));
+    }
+
+    fn attach(
+        mut node: CategoryTreeNode,
+        children_of: &mut HashMap<Option<i64>, Vec<CategoryTreeNode>>,
+    ) -> CategoryTreeNode {
+        let children = children_of.remove(&Some(node.id)).unwrap_or_default();
+        node.children = children
+            .into_iter()
+            .map(|child| attach(child, children_of))
+            .collect();
+        node
+    }
+
+    let roots = children_of.remove(&None).unwrap_or_default();
+    roots
+        .into_iter()
+        .map(|root| attach(root, &mut children_of))
+        .collect()
+}
+
+/// Ids of a category and all of its descendants
+pub fn descendant_ids(categories: &[Category], id: i64) -> Vec<i64> {
+    let mut ids = vec![id];
+    let mut i = 0;
+    while i < ids.len() {
+        let current = ids[i];
+        let children: Vec<i64> = categories
+            .iter()
+            .filter(|category| category.parent_id == Some(current))
+            .map(|category| category.id)
+            .filter(|child| !ids.contains(child))
+            .collect();
+        ids.extend(children);
+        i += 1;
+    }
+    ids
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn category(id: i64, parent_id: Option<i64>) -> Category {
+        Category {
+            id,
+            name: format!("Category {}", id),
+            slug: format!("category-{}", id),
+            description: None,
+            parent_id,
+            created_at: Utc::now().into(),
+            updated_at: Utc::now().into(),
+        }
+    }
+
+    #[test]
+    fn test_build_category_tree() {
+        let tree = build_category_tree(vec![
+            (category(1, None), 2),
+            (category(2, Some(1)), 1),
+            (category(3, Some(2)), 0),
+            (category(4, None), 5),
+        ]);
+
+        assert_eq!(tree.len(), 2);
+        assert_eq!(tree[0].id, 1);
+        assert_eq!(tree[0].children[0].id, 2);
+        assert_eq!(tree[0].children[0].children[0].id, 3);
+        assert_eq!(tree[1].post_count, 5);
+    }
+
+    #[test]
+    fn test_descendant_ids() {
+        let categories = vec![
+            category(1, None),
+            category(2, Some(1)),
+            category(3, Some(2)),
+            category(4, None),
+        ];
+
+        assert_eq!(descendant_ids(&categories, 1), vec![1, 2, 3]);
+        assert_eq!(descendant_ids(&categories, 4), vec![4]);
+    }
+}
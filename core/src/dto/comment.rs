@@ -0,0 +1,274 @@
+use crate::entity::comments;
+use chrono::{DateTime, Utc};
+use sea_orm::prelude::DateTimeWithTimeZone;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use utoipa::{IntoParams, ToSchema};
+
+/// Maximum length of a comment, in characters
+pub const MAX_COMMENT_LEN: usize = 10_000;
+
+/// Maximum length of a guest's name, email and URL, in characters
+pub const MAX_COMMENT_AUTHOR_FIELD_LEN: usize = 200;
+
+/// Moderation status of a comment
+///
+/// Only `approved` comments are visible on public endpoints.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CommentStatus {
+    /// Waiting in the moderation queue
+    Pending,
+    Approved,
+    Rejected,
+    Spam,
+}
+
+impl CommentStatus {
+    /// Value stored in the `comments.status` column
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CommentStatus::Pending => "pending",
+            CommentStatus::Approved => "approved",
+            CommentStatus::Rejected => "rejected",
+            CommentStatus::Spam => "spam",
+        }
+    }
+
+    /// Parse a stored `comments.status` value
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "pending" => Some(CommentStatus::Pending),
+            "approved" => Some(CommentStatus::Approved),
+            "rejected" => Some(CommentStatus::Rejected),
+            "spam" => Some(CommentStatus::Spam),
+            _ => None,
+        }
+    }
+}
+
+/// Comment business entity (DTO), as shown to admins
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct Comment {
+    pub id: i64,
+    pub post_id: i64,
+    pub parent_id: Option<i64>,
+    /// Author's user id; `null` for guest comments
+    pub author_id: Option<i64>,
+    pub author_name: String,
+    pub author_email: Option<String>,
+    pub author_url: Option<String>,
+    pub content: String,
+    pub status: CommentStatus,
+    pub ip_address: Option<String>,
+    pub user_agent: Option<String>,
+    pub created_at: DateTimeWithTimeZone,
+    pub updated_at: DateTimeWithTimeZone,
+}
+
+/// A comment about to be stored
+#[derive(Debug, Clone)]
+pub struct NewComment {
+    pub post_id: i64,
+    pub parent_id: Option<i64>,
+    pub author_id: Option<i64>,
+    pub author_name: String,
+    pub author_email: Option<String>,
+    pub author_url: Option<String>,
+    pub content: String,
+    pub status: CommentStatus,
+    pub ip_address: Option<String>,
+    pub user_agent: Option<String>,
+}
+
+/// Public comment response with its replies
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct CommentResponse {
+    pub id: i64,
+    pub post_id: i64,
+    pub parent_id: Option<i64>,
+    pub author_id: Option<i64>,
+    pub author_name: String,
+    pub author_url: Option<String>,
+    pub content: String,
+    /// Always `approved` in listings; a new comment may still be `pending`
+    pub status: CommentStatus,
+    pub replies: Vec<CommentResponse>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Request DTO for posting a comment
+///
+/// Guests must give a name; signed-in users comment under their username.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CreateCommentRequest {
+    /// Comment being replied to
+    pub parent_id: Option<i64>,
+    pub author_name: Option<String>,
+    pub author_email: Option<String>,
+    pub author_url: Option<String>,
+    pub content: String,
+}
+
+/// Query parameters for the admin comment listing
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct CommentListQuery {
+    /// Page number (1-based, default 1)
+    pub page: Option<u64>,
+    /// Items per page (default: the `posts_per_page` setting)
+    pub per_page: Option<u64>,
+    /// Only comments with this status (e.g. `pending` for the moderation queue)
+    #[param(value_type = Option<CommentStatus>)]
+    pub status: Option<CommentStatus>,
+    /// Only comments on this post
+    pub post_id: Option<i64>,
+}
+
+/// Moderation action on one or more comments
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CommentModeration {
+    Approve,
+    Reject,
+    Spam,
+    Delete,
+}
+
+impl CommentModeration {
+    /// Status the comments end up in, or `None` when they are deleted
+    pub fn status(&self) -> Option<CommentStatus> {
+        match self {
+            CommentModeration::Approve => Some(CommentStatus::Approved),
+            CommentModeration::Reject => Some(CommentStatus::Rejected),
+            CommentModeration::Spam => Some(CommentStatus::Spam),
+            CommentModeration::Delete => None,
+        }
+    }
+}
+
+/// Request DTO for moderating several comments at once
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct BulkModerateCommentsRequest {
+    pub ids: Vec<i64>,
+    pub action: CommentModeration,
+}
+
+/// Response DTO for bulk moderation
+#[derive(Debug, Serialize, ToSchema)]
+pub struct BulkModerateCommentsResponse {
+    /// Number of comments that were changed or deleted
+    pub affected: u64,
+}
+
+/// Convert from database Model to business DTO
+impl From<comments::Model> for Comment {
+    fn from(model: comments::Model) -> Self {
+        Comment {
+            id: model.id,
+            post_id: model.post_id,
+            parent_id: model.parent_id,
+            author_id: model.author_id,
+            author_name: model.author_name,
+            author_email: model.author_email,
+            author_url: model.author_url,
+            content: model.content,
+            status: CommentStatus::parse(&model.status).unwrap_or(CommentStatus::Pending),
+            ip_address: model.ip_address,
+            user_agent: model.user_agent,
+            created_at: model.created_at,
+            updated_at: model.updated_at,
+        }
+    }
+}
+
+/// Convert from business DTO to public response DTO (without replies)
+impl From<Comment> for CommentResponse {
+    fn from(comment: Comment) -> Self {
+        CommentResponse {
+            id: comment.id,
+            post_id: comment.post_id,
+            parent_id: comment.parent_id,
+            author_id: comment.author_id,
+            author_name: comment.author_name,
+            author_url: comment.author_url,
+            content: comment.content,
+            status: comment.status,
+            replies: Vec::new(),
+            created_at: comment.created_at.into(),
+        }
+    }
+}
+
+/// Build comment threads from a flat list of comments
+///
+/// Replies keep the order of the input. Replies whose parent is missing from the
+/// list (e.g. not approved) become top-level comments.
+pub fn build_comment_tree(comments: Vec<Comment>) -> Vec<CommentResponse> {
+    let ids: HashSet<i64> = comments.iter().map(|comment| comment.id).collect();
+    let mut replies_to: HashMap<Option<i64>, Vec<CommentResponse>> = HashMap::new();
+    for comment in comments {
+        let parent = comment.parent_id.filter(|parent| ids.contains(parent));
+        replies_to
+            .entry(parent)
+            .or_default()
+            .push(CommentResponse::from(comment));
+    }
+
+    fn attach(
+        mut node: CommentResponse,
+        replies_to: &mut HashMap<Option<i64>, Vec<CommentResponse>>,
+    ) -> CommentResponse {
+        let replies = replies_to.remove(&Some(node.id)).unwrap_or_default();
+        node.replies = replies
+            .into_iter()
+            .map(|reply| attach(reply, replies_to))
+            .collect();
+        node
+    }
+
+    let roots = replies_to.remove(&None).unwrap_or_default();
+    roots
+        .into_iter()
+        .map(|root| attach(root, &mut replies_to))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn comment(id: i64, parent_id: Option<i64>) -> Comment {
+        Comment {
+            id,
+            post_id: 1,
+            parent_id,
+            author_id: None,
+            author_name: format!("Guest {}", id),
+            author_email: None,
+            author_url: None,
+            content: "Hello".to_string(),
+            status: CommentStatus::Approved,
+            ip_address: None,
+            user_agent: None,
+            created_at: Utc::now().into(),
+            updated_at: Utc::now().into(),
+        }
+    }
+
+    #[test]
+    fn test_build_comment_tree() {
+        let tree = build_comment_tree(vec![
+            comment(1, None),
+            comment(2, Some(1)),
+            comment(3, Some(2)),
+            comment(4, Some(99)),
+        ]);
+
+        assert_eq!(tree.len(), 2);
+        assert_eq!(tree[0].replies[0].id, 2);
+        assert_eq!(tree[0].replies[0].replies[0].id, 3);
+        // Reply to a hidden comment is shown at the top level
+        assert_eq!(tree[1].id, 4);
+    }
+}
@@ -0,0 +1,108 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+use super::PostStatus;
+
+/// Item of an import source, identifying what an import created for it
+///
+/// Stored in `import_records`; an item that already has a record is skipped when
+/// the same source is imported again.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportKey {
+    /// Where the item comes from, e.g. the URL of the exported WordPress site
+    pub source: String,
+    /// Kind of item (`post`, `attachment`, ...)
+    pub kind: String,
+    /// Id of the item in the source
+    pub source_id: String,
+}
+
+/// What an earlier import created for an item
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportRecord {
+    pub target_id: Option<i64>,
+    pub target_url: Option<String>,
+}
+
+/// Post to import with its history, written in one transaction
+///
+/// Unlike [`super::CreatePostRequest`], timestamps are kept from the source.
+#[derive(Debug, Clone)]
+pub struct ImportPostRequest {
+    pub key: ImportKey,
+    pub title: String,
+    /// Preferred slug; made unique like the slugs of new posts
+    pub slug: Option<String>,
+    pub status: PostStatus,
+    pub publish_at: Option<DateTime<Utc>>,
+    pub content: String,
+    pub category_id: Option<i64>,
+    pub author_id: i64,
+    pub description: Option<String>,
+    pub tags: Vec<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    /// Earlier revisions, oldest first; stored as versions 1, 2, ...
+    pub versions: Vec<ImportPostVersion>,
+    /// Unsaved edits, stored as a draft of the post
+    pub draft: Option<ImportPostDraft>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ImportPostVersion {
+    pub title: String,
+    pub content: String,
+    pub description: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub created_by: i64,
+}
+
+#[derive(Debug, Clone)]
+pub struct ImportPostDraft {
+    pub title: String,
+    pub content: String,
+    pub author_id: i64,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Query parameters of import endpoints
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ImportQuery {
+    /// Only report what would be imported, without changing anything
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+/// Number of items of one kind that an import created or found already present
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, ToSchema)]
+pub struct ImportCounts {
+    pub created: u64,
+    /// Existing records changed to match the source
+    pub updated: u64,
+    /// Matched to existing records (users by email, categories by slug) or
+    /// imported by an earlier run
+    pub existing: u64,
+}
+
+/// Outcome of an import; for dry runs, what the import would do
+#[derive(Debug, Clone, Default, Serialize, ToSchema)]
+pub struct ImportReport {
+    pub dry_run: bool,
+    pub users: ImportCounts,
+    pub categories: ImportCounts,
+    pub posts: ImportCounts,
+    pub attachments: ImportCounts,
+    /// Revisions stored as post versions (for updates, the replaced content)
+    pub versions: u64,
+    /// Autosaves stored as post drafts
+    pub drafts: u64,
+    /// Items that are not imported (pages, menus, trashed posts, unreadable files, ...)
+    pub skipped: u64,
+    /// Attachments whose file was not found in the uploads directory
+    pub missing_files: Vec<String>,
+    /// Attachments refused by the upload settings, with the reason
+    pub rejected_files: Vec<String>,
+    pub warnings: Vec<String>,
+}
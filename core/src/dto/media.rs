@@ -0,0 +1,418 @@
+use crate::dto::PostStatus;
+use crate::entity::{media, media_variants};
+use chrono::{DateTime, Utc};
+use sea_orm::prelude::DateTimeWithTimeZone;
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+/// Maximum length of a file's alt text, in characters
+pub const MAX_ALT_TEXT_LEN: usize = 1000;
+
+/// Maximum length of a file's original name, in characters
+pub const MAX_ORIGINAL_NAME_LEN: usize = 255;
+
+/// Uploaded file business entity (DTO)
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct Media {
+    pub id: i64,
+    /// URL returned by the storage backend, as linked from posts
+    pub url: String,
+    /// Name the storage backend gave the file
+    pub file_name: String,
+    /// Name of the file on the uploader's computer
+    pub original_name: String,
+    pub mime_type: String,
+    /// Size in bytes
+    pub size: i64,
+    /// Pixel dimensions, for images
+    pub width: Option<i32>,
+    pub height: Option<i32>,
+    /// Hex-encoded SHA-256 digest of the contents
+    pub checksum: String,
+    pub alt_text: Option<String>,
+    /// `null` once the uploader is deleted
+    pub uploader_id: Option<i64>,
+    pub created_at: DateTimeWithTimeZone,
+    pub updated_at: DateTimeWithTimeZone,
+}
+
+/// An uploaded file about to be recorded
+#[derive(Debug, Clone)]
+pub struct NewMedia {
+    pub url: String,
+    pub file_name: String,
+    pub original_name: String,
+    pub mime_type: String,
+    pub size: i64,
+    pub width: Option<i32>,
+    pub height: Option<i32>,
+    pub checksum: String,
+    pub alt_text: Option<String>,
+    pub uploader_id: Option<i64>,
+}
+
+/// What a resized copy of an image is for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum MediaVariantKind {
+    /// Scaled to one of the configured widths, for `srcset`
+    Responsive,
+    /// Cropped to a square for listings
+    Thumbnail,
+}
+
+impl MediaVariantKind {
+    /// Value stored in the `media_variants.kind` column
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MediaVariantKind::Responsive => "responsive",
+            MediaVariantKind::Thumbnail => "thumbnail",
+        }
+    }
+
+    /// Parse a stored `media_variants.kind` value
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "responsive" => Some(MediaVariantKind::Responsive),
+            "thumbnail" => Some(MediaVariantKind::Thumbnail),
+            _ => None,
+        }
+    }
+}
+
+/// Resized copy of an uploaded image
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct MediaVariant {
+    pub id: i64,
+    pub media_id: i64,
+    pub kind: MediaVariantKind,
+    pub url: String,
+    pub file_name: String,
+    pub mime_type: String,
+    pub width: i32,
+    pub height: i32,
+    /// Size in bytes
+    pub size: i64,
+}
+
+/// A resized copy about to be recorded
+#[derive(Debug, Clone)]
+pub struct NewMediaVariant {
+    pub media_id: i64,
+    pub kind: MediaVariantKind,
+    pub url: String,
+    pub file_name: String,
+    pub mime_type: String,
+    pub width: i32,
+    pub height: i32,
+    pub size: i64,
+}
+
+/// The sizes of an image available in one format, ready for a `<source>` or `<img>`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+pub struct SourceSet {
+    pub mime_type: String,
+    /// `srcset` attribute value, narrowest first (e.g. `/uploads/a.webp 480w, /uploads/b.webp 768w`)
+    pub srcset: String,
+}
+
+/// Media library entry
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct MediaResponse {
+    pub id: i64,
+    pub url: String,
+    pub file_name: String,
+    pub original_name: String,
+    pub mime_type: String,
+    pub size: i64,
+    pub width: Option<i32>,
+    pub height: Option<i32>,
+    pub checksum: String,
+    pub alt_text: Option<String>,
+    pub uploader_id: Option<i64>,
+    /// Number of posts (including trashed ones) whose content links to the file
+    pub reference_count: u64,
+    pub thumbnail_url: Option<String>,
+    /// Resized copies of an image, including the thumbnail
+    pub variants: Vec<MediaVariant>,
+    /// One entry per format, the image's own format last as the `<img>` fallback
+    pub srcset: Vec<SourceSet>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// A post whose content links to a file
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct MediaUsage {
+    pub post_id: i64,
+    pub title: String,
+    pub slug: String,
+    pub status: PostStatus,
+    /// Whether the post is in the trash
+    pub deleted: bool,
+}
+
+/// Media library entry with the posts that use it
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct MediaDetailResponse {
+    pub media: MediaResponse,
+    pub used_by: Vec<MediaUsage>,
+}
+
+/// Query parameters for the media library listing
+#[derive(Debug, Clone, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct MediaListQuery {
+    /// Page number (1-based, default 1)
+    pub page: Option<u64>,
+    /// Items per page (default: the `posts_per_page` setting, max 100)
+    pub per_page: Option<u64>,
+    /// Only files whose original name, file name or alt text contains this text (case-insensitive)
+    pub q: Option<String>,
+    /// Only files of this MIME type, or of this kind when it ends with `/` (e.g. `image/`)
+    pub mime_type: Option<String>,
+    /// Only files uploaded by this user
+    pub uploader_id: Option<i64>,
+    /// `true` for files no post links to, `false` for files in use (default: both)
+    pub orphaned: Option<bool>,
+}
+
+/// Request DTO for editing a file's details; missing fields are left unchanged
+#[derive(Debug, Clone, Default, Deserialize, ToSchema)]
+pub struct UpdateMediaRequest {
+    /// New alt text; an empty string removes it
+    pub alt_text: Option<String>,
+    pub original_name: Option<String>,
+}
+
+/// Query parameters for deleting a file
+#[derive(Debug, Clone, Copy, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct DeleteMediaQuery {
+    /// Delete the file even though posts still link to it
+    #[serde(default)]
+    pub force: bool,
+}
+
+/// Convert from database Model to business DTO
+impl From<media::Model> for Media {
+    fn from(model: media::Model) -> Self {
+        Media {
+            id: model.id,
+            url: model.url,
+            file_name: model.file_name,
+            original_name: model.original_name,
+            mime_type: model.mime_type,
+            size: model.size,
+            width: model.width,
+            height: model.height,
+            checksum: model.checksum,
+            alt_text: model.alt_text,
+            uploader_id: model.uploader_id,
+            created_at: model.created_at,
+            updated_at: model.updated_at,
+        }
+    }
+}
+
+/// Convert from database Model to business DTO
+impl From<media_variants::Model> for MediaVariant {
+    fn from(model: media_variants::Model) -> Self {
+        MediaVariant {
+            id: model.id,
+            media_id: model.media_id,
+            kind: MediaVariantKind::parse(&model.kind).unwrap_or(MediaVariantKind::Responsive),
+            url: model.url,
+            file_name: model.file_name,
+            mime_type: model.mime_type,
+            width: model.width,
+            height: model.height,
+            size: model.size,
+        }
+    }
+}
+
+/// Group the responsive variants of an image by format into `srcset` values
+///
+/// The original is a candidate of its own format, which comes last as the `<img>`
+/// fallback; AVIF and WebP come first, so `<source>` elements can be emitted in order.
+pub fn source_sets(media: &Media, variants: &[MediaVariant]) -> Vec<SourceSet> {
+    let mut candidates: Vec<(&str, i32, &str)> = variants
+        .iter()
+        .filter(|variant| variant.kind == MediaVariantKind::Responsive)
+        .map(|variant| {
+            (
+                variant.mime_type.as_str(),
+                variant.width,
+                variant.url.as_str(),
+            )
+        })
+        .collect();
+    if let Some(width) = media.width
+        && media.mime_type.starts_with("image/")
+    {
+        candidates.push((media.mime_type.as_str(), width, media.url.as_str()));
+    }
+    if candidates.is_empty() {
+        return Vec::new();
+    }
+
+    let rank = |mime_type: &str| {
+        if mime_type == media.mime_type {
+            3
+        } else {
+            match mime_type {
+                "image/avif" => 0,
+                "image/webp" => 1,
+                _ => 2,
+            }
+        }
+    };
+    candidates.sort_by(|a, b| (rank(a.0), a.0, a.1).cmp(&(rank(b.0), b.0, b.1)));
+
+    let mut sets: Vec<SourceSet> = Vec::new();
+    for (mime_type, width, url) in candidates {
+        let candidate = format!("{} {}w", url, width);
+        match sets.last_mut() {
+            Some(set) if set.mime_type == mime_type => {
+                set.srcset.push_str(", ");
+                set.srcset.push_str(&candidate);
+            }
+            _ => sets.push(SourceSet {
+                mime_type: mime_type.to_string(),
+                srcset: candidate,
+            }),
+        }
+    }
+    sets
+}
+
+impl MediaResponse {
+    pub fn new(media: Media, reference_count: u64, variants: Vec<MediaVariant>) -> Self {
+        let srcset = source_sets(&media, &variants);
+        let thumbnail_url = variants
+            .iter()
+            .find(|variant| variant.kind == MediaVariantKind::Thumbnail)
+            .map(|variant| variant.url.clone());
+        MediaResponse {
+            id: media.id,
+            url: media.url,
+            file_name: media.file_name,
+            original_name: media.original_name,
+            mime_type: media.mime_type,
+            size: media.size,
+            width: media.width,
+            height: media.height,
+            checksum: media.checksum,
+            alt_text: media.alt_text,
+            uploader_id: media.uploader_id,
+            reference_count,
+            thumbnail_url,
+            variants,
+            srcset,
+            created_at: media.created_at.into(),
+            updated_at: media.updated_at.into(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn media() -> Media {
+        let now = Utc::now().fixed_offset();
+        Media {
+            id: 1,
+            url: "/uploads/photo.jpg".to_string(),
+            file_name: "photo.jpg".to_string(),
+            original_name: "IMG_0001.jpg".to_string(),
+            mime_type: "image/jpeg".to_string(),
+            size: 1000,
+            width: Some(2000),
+            height: Some(1000),
+            checksum: String::new(),
+            alt_text: None,
+            uploader_id: None,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    fn variant(kind: MediaVariantKind, mime_type: &str, width: i32, url: &str) -> MediaVariant {
+        MediaVariant {
+            id: 0,
+            media_id: 1,
+            kind,
+            url: url.to_string(),
+            file_name: url.trim_start_matches("/uploads/").to_string(),
+            mime_type: mime_type.to_string(),
+            width,
+            height: width / 2,
+            size: 0,
+        }
+    }
+
+    #[test]
+    fn test_source_sets_group_by_format() {
+        let variants = vec![
+            variant(
+                MediaVariantKind::Responsive,
+                "image/jpeg",
+                1024,
+                "/uploads/c.jpg",
+            ),
+            variant(
+                MediaVariantKind::Responsive,
+                "image/jpeg",
+                480,
+                "/uploads/a.jpg",
+            ),
+            variant(
+                MediaVariantKind::Responsive,
+                "image/webp",
+                480,
+                "/uploads/a.webp",
+            ),
+            variant(
+                MediaVariantKind::Thumbnail,
+                "image/jpeg",
+                150,
+                "/uploads/t.jpg",
+            ),
+            variant(
+                MediaVariantKind::Responsive,
+                "image/avif",
+                480,
+                "/uploads/a.avif",
+            ),
+        ];
+
+        assert_eq!(
+            source_sets(&media(), &variants),
+            vec![
+                SourceSet {
+                    mime_type: "image/avif".to_string(),
+                    srcset: "/uploads/a.avif 480w".to_string(),
+                },
+                SourceSet {
+                    mime_type: "image/webp".to_string(),
+                    srcset: "/uploads/a.webp 480w".to_string(),
+                },
+                SourceSet {
+                    mime_type: "image/jpeg".to_string(),
+                    srcset: "/uploads/a.jpg 480w, /uploads/c.jpg 1024w, /uploads/photo.jpg 2000w"
+                        .to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_source_sets_skip_files_without_dimensions() {
+        let mut media = media();
+        media.mime_type = "application/pdf".to_string();
+        media.width = None;
+        assert!(source_sets(&media, &[]).is_empty());
+    }
+}
@@ -6,30 +6,63 @@
 //! - Conversion with database Entities
 
 pub mod admin;
+pub mod backup;
+pub mod category;
+pub mod comment;
+pub mod import;
+pub mod media;
 pub mod openai;
+pub mod pagination;
 pub mod plugin;
 pub mod post;
+pub mod post_diff;
+pub mod post_status;
+pub mod sitemap;
+pub mod tag;
 pub mod user;
 
 // Re-export commonly used DTOs
 pub use admin::{
     AdminBanUserRequest, AdminPluginEnableResponse, AdminPluginListResponse, AdminPluginUpdateRequest, AdminPostListResponse,
-    AdminResetPasswordRequest, AdminResetPasswordResponse,
-    AdminUserListResponse,
+    AdminRemovedPostResponse, AdminResetPasswordRequest, AdminResetPasswordResponse,
+    AdminUserListResponse, PurgeTrashResponse, TrashListQuery,
+};
+pub use backup::{BackupManifest, RestoreQuery, RestoreReport};
+pub use category::{
+    Category, CategoryTreeNode, CreateCategoryRequest, DeleteCategoryQuery, UpdateCategoryRequest,
+};
+pub use comment::{
+    BulkModerateCommentsRequest, BulkModerateCommentsResponse, Comment, CommentListQuery,
+    CommentModeration, CommentResponse, CommentStatus, CreateCommentRequest, NewComment,
+};
+pub use import::{
+    ImportCounts, ImportKey, ImportPostDraft, ImportPostRequest, ImportPostVersion, ImportQuery,
+    ImportRecord, ImportReport,
+};
+pub use media::{
+    DeleteMediaQuery, Media, MediaDetailResponse, MediaListQuery, MediaResponse, MediaUsage,
+    MediaVariant, MediaVariantKind, NewMedia, NewMediaVariant, SourceSet, UpdateMediaRequest,
 };
 pub use openai::{
     ChatCompletionRequest, ChatCompletionResponse, ChatMessage, CreateOpenAIApiKeyRequest,
     ListOpenAIModelsResponse, OpenAIApiKeyResponse, OpenAIModel, SetDefaultModelRequest,
     TestOpenAIApiKeyResponse, TestTokenUsage, UpdateOpenAIApiKeyRequest,
 };
+pub use pagination::{
+    AdminPostPageResponse, AdminRemovedPostPageResponse, CommentPageResponse, MediaPageResponse, Paginated, PostListResponse, PostSearchResponse,
+};
 pub use plugin::{
     ApprovePluginPermissionsRequest,
     PluginInstallRequest, PluginPermissionsResponse, UpdatePluginPermissionsRequest,
 };
 pub use post::{
-    CreatePostRequest, Post, PostDraft, PostDraftResponse, PostResponse, PostVersion,
-    PostVersionResponse, SaveDraftRequest, UpdatePostRequest,
+    CreatePostRequest, Post, PostDraft, PostDraftResponse, PostListQuery, PostResponse,
+    PostRenderQuery, PostSearchHit, PostSearchQuery, PostSortField, PostVersion, PostVersionResponse,
+    SaveDraftRequest, SortOrder, UpdatePostRequest,
 };
+pub use post_status::{PostStatus, PostTransition, PostTransitionRequest};
+pub use sitemap::{SitemapEntry, SitemapEntryKind};
+pub use tag::{MergeTagsRequest, RenameTagRequest, Tag, TagResponse};
 pub use user::{
     CreateUserRequest, LoginRequest, LoginResponse, RefreshTokenRequest, RefreshTokenResponse,
     User, UserResponse, UserRole,
@@ -0,0 +1,89 @@
+use crate::dto::{
+    AdminPostListResponse, AdminRemovedPostResponse, Comment, MediaResponse, PostResponse,
+    PostSearchHit,
+};
+use serde::Serialize;
+use utoipa::ToSchema;
+
+/// Upper bound for `per_page` on list endpoints
+pub const MAX_PER_PAGE: u64 = 100;
+
+/// Paginated list response with total-count and next-page metadata
+#[derive(Debug, Serialize, ToSchema)]
+#[aliases(
+    PostListResponse = Paginated<PostResponse>,
+    AdminPostPageResponse = Paginated<AdminPostListResponse>,
+    AdminRemovedPostPageResponse = Paginated<AdminRemovedPostResponse>,
+    PostSearchResponse = Paginated<PostSearchHit>,
+    CommentPageResponse = Paginated<Comment>,
+    MediaPageResponse = Paginated<MediaResponse>
+)]
+pub struct Paginated<T> {
+    pub items: Vec<T>,
+    /// Total number of items matching the query (across all pages)
+    pub total: u64,
+    /// Current page (1-based)
+    pub page: u64,
+    pub per_page: u64,
+    pub total_pages: u64,
+    /// Next page number, or null if this is the last page
+    pub next_page: Option<u64>,
+}
+
+impl<T> Paginated<T> {
+    pub fn new(items: Vec<T>, total: u64, page: u64, per_page: u64) -> Self {
+        let total_pages = total.div_ceil(per_page.max(1));
+        let next_page = if page < total_pages {
+            Some(page + 1)
+        } else {
+            None
+        };
+
+        Self {
+            items,
+            total,
+            page,
+            per_page,
+            total_pages,
+            next_page,
+        }
+    }
+
+    /// Convert items while keeping pagination metadata
+    pub fn map<U>(self, f: impl FnMut(T) -> U) -> Paginated<U> {
+        Paginated {
+            items: self.items.into_iter().map(f).collect(),
+            total: self.total,
+            page: self.page,
+            per_page: self.per_page,
+            total_pages: self.total_pages,
+            next_page: self.next_page,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_paginated_next_page() {
+        let page = Paginated::new(vec![1, 2, 3], 25, 1, 10);
+        assert_eq!(page.total_pages, 3);
+        assert_eq!(page.next_page, Some(2));
+    }
+
+    #[test]
+    fn test_paginated_last_page() {
+        let page = Paginated::new(vec![1], 21, 3, 10);
+        assert_eq!(page.total_pages, 3);
+        assert_eq!(page.next_page, None);
+    }
+
+    #[test]
+    fn test_paginated_empty() {
+        let page: Paginated<i32> = Paginated::new(vec![], 0, 1, 10);
+        assert_eq!(page.total_pages, 0);
+        assert_eq!(page.next_page, None);
+    }
+}
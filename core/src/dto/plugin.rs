@@ -11,6 +11,12 @@ pub enum PluginPermission {
     /// Permission to create/modify posts
     #[serde(rename = "post:write")]
     PostWrite,
+    /// Permission to read comments
+    #[serde(rename = "comment:read")]
+    CommentRead,
+    /// Permission to modify or moderate new comments
+    #[serde(rename = "comment:write")]
+    CommentWrite,
     /// Permission to read users
     #[serde(rename = "user:read")]
     UserRead,
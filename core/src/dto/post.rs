@@ -1,42 +1,97 @@
+use crate::dto::PostStatus;
+use crate::dto::category::deserialize_present;
 use crate::entity::{post_drafts, post_versions, posts};
+use crate::markdown::{RenderedMarkdown, TocEntry};
 use chrono::{DateTime, Utc};
 use sea_orm::prelude::DateTimeWithTimeZone;
 use serde::{Deserialize, Serialize};
-use utoipa::ToSchema;
+use utoipa::{IntoParams, ToSchema};
 
 /// Post business entity (DTO)
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Default)]
 pub struct Post {
     pub id: i64,
     pub title: String,
+    /// Unique permalink slug
+    pub slug: String,
+    pub status: PostStatus,
+    /// Note left by the admin who last approved or rejected the post
+    pub review_note: Option<String>,
+    /// When a scheduled post goes live
+    pub publish_at: Option<DateTimeWithTimeZone>,
+    /// When a published post is turned back into a draft
+    pub unpublish_at: Option<DateTimeWithTimeZone>,
     pub content: String,
+    /// Category name (resolved from `category_id`)
     pub category: Option<String>,
+    pub category_id: Option<i64>,
     pub author_id: i64,
     pub description: Option<String>,
     pub created_at: DateTimeWithTimeZone,
     pub updated_at: DateTimeWithTimeZone,
     pub archived_at: Option<DateTimeWithTimeZone>,
     pub deleted_at: Option<DateTimeWithTimeZone>,
+    /// Tag names, sorted alphabetically
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Incremented on every write; updates based on an older revision are rejected
+    #[serde(default)]
+    pub revision: i32,
 }
 
 /// Request DTO for creating a post
 #[derive(Debug, Deserialize, ToSchema)]
 pub struct CreatePostRequest {
     pub title: String,
+    /// Permalink slug; generated from the title when omitted
+    #[serde(default)]
+    pub slug: Option<String>,
+    /// Initial status. Admins default to `published`; other users may only create
+    /// `draft` or `pending_review` posts and default to `pending_review`.
+    #[serde(default)]
+    pub status: Option<PostStatus>,
+    /// Publication time for `scheduled` posts. A future time without an explicit
+    /// status schedules the post (admins) or is kept for when it is approved.
+    #[serde(default)]
+    pub publish_at: Option<DateTime<Utc>>,
+    /// Time at which the post is automatically unpublished again
+    #[serde(default)]
+    pub unpublish_at: Option<DateTime<Utc>>,
     pub content: String,
+    /// Name or slug of an existing category; ignored when `category_id` is set
     #[serde(default)]
     pub category: Option<String>,
+    /// Category id; defaults to the `default_category` setting when no category is given
+    #[serde(default)]
+    pub category_id: Option<i64>,
     pub author_id: i64,
     pub description: Option<String>,
+    /// Tag names; tags that don't exist yet are created
+    #[serde(default)]
+    pub tags: Vec<String>,
 }
 
 /// Request DTO for updating a post
 #[derive(Debug, Deserialize, ToSchema)]
 pub struct UpdatePostRequest {
     pub title: Option<String>,
+    /// New permalink slug; the old one keeps redirecting to the post
+    pub slug: Option<String>,
     pub content: Option<String>,
+    /// Name or slug of an existing category (empty string clears it); ignored when `category_id` is set
     pub category: Option<String>,
+    pub category_id: Option<i64>,
     pub description: Option<String>,
+    /// Replaces the post's tags when present
+    pub tags: Option<Vec<String>>,
+    /// Reschedules the post; `null` clears it (not allowed for `scheduled` posts)
+    #[serde(default, deserialize_with = "deserialize_present")]
+    #[schema(value_type = Option<DateTime<Utc>>)]
+    pub publish_at: Option<Option<DateTime<Utc>>>,
+    /// Changes when the post is unpublished; `null` clears it
+    #[serde(default, deserialize_with = "deserialize_present")]
+    #[schema(value_type = Option<DateTime<Utc>>)]
+    pub unpublish_at: Option<Option<DateTime<Utc>>>,
     #[serde(default)]
     pub create_version: bool,
     pub change_note: Option<String>,
@@ -47,10 +102,130 @@ pub struct UpdatePostRequest {
 pub struct PostResponse {
     pub id: i64,
     pub title: String,
+    pub slug: String,
+    pub status: PostStatus,
+    pub review_note: Option<String>,
+    pub publish_at: Option<DateTime<Utc>>,
+    pub unpublish_at: Option<DateTime<Utc>>,
     pub content: String,
     pub category: Option<String>,
+    pub category_id: Option<i64>,
     pub description: Option<String>,
+    pub tags: Vec<String>,
     pub author_id: i64,
+    /// Current revision, also sent as the `ETag` header; send it back in `If-Match` when updating
+    pub revision: i32,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    /// Sanitised HTML rendering of `content`; only present when requested with `render=true`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content_html: Option<String>,
+    /// Headings of `content`, linking to the anchors in `content_html`; only present with `render=true`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub toc: Option<Vec<TocEntry>>,
+}
+
+impl PostResponse {
+    /// Attach the rendered content of the post
+    pub fn with_rendered(mut self, rendered: &RenderedMarkdown) -> Self {
+        self.content_html = Some(rendered.html.clone());
+        self.toc = Some(rendered.toc.clone());
+        self
+    }
+}
+
+/// Query parameter for getting posts with their content rendered to HTML
+#[derive(Debug, Clone, Copy, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct PostRenderQuery {
+    /// Include `content_html` and `toc` in the response (default false)
+    #[serde(default)]
+    pub render: bool,
+}
+
+/// Sort field for post listings
+#[derive(Debug, Clone, Copy, Default, Deserialize, ToSchema, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum PostSortField {
+    #[default]
+    CreatedAt,
+    UpdatedAt,
+    Title,
+}
+
+/// Sort direction for listings
+#[derive(Debug, Clone, Copy, Default, Deserialize, ToSchema, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SortOrder {
+    Asc,
+    #[default]
+    Desc,
+}
+
+/// Query parameters for paginated, filterable post listings
+#[derive(Debug, Clone, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct PostListQuery {
+    /// Page number (1-based, default 1)
+    pub page: Option<u64>,
+    /// Items per page (defaults to the `posts_per_page` setting, max 100)
+    pub per_page: Option<u64>,
+    /// Only posts in this category (slug), including its subcategories
+    pub category: Option<String>,
+    /// Only posts by this author
+    pub author_id: Option<i64>,
+    /// Only posts with this tag (slug)
+    pub tag: Option<String>,
+    /// Only posts created at or after this time (RFC 3339)
+    pub from: Option<DateTime<Utc>>,
+    /// Only posts created at or before this time (RFC 3339)
+    pub to: Option<DateTime<Utc>>,
+    /// `true` for archived posts only, `false` to exclude them (default: both)
+    pub archived: Option<bool>,
+    /// Only posts with this status (admin listing; public listings only show published posts)
+    #[param(value_type = Option<PostStatus>)]
+    pub status: Option<PostStatus>,
+    /// Sort field (default `created_at`)
+    #[param(value_type = Option<PostSortField>)]
+    #[serde(default)]
+    pub sort: PostSortField,
+    /// Sort direction (default `desc`)
+    #[param(value_type = Option<SortOrder>)]
+    #[serde(default)]
+    pub order: SortOrder,
+}
+
+/// Query parameters for full-text post search
+#[derive(Debug, Clone, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct PostSearchQuery {
+    /// Search terms (web search syntax: quoted phrases, `or`, `-term`)
+    pub q: String,
+    /// Page number (1-based, default 1)
+    pub page: Option<u64>,
+    /// Items per page (defaults to the `posts_per_page` setting, max 100)
+    pub per_page: Option<u64>,
+    /// Only posts in this category (slug), including its subcategories
+    pub category: Option<String>,
+    /// Only posts by this author
+    pub author_id: Option<i64>,
+}
+
+/// A ranked full-text search result
+///
+/// `title_highlight` and `snippet` are HTML-escaped, with matched terms wrapped in `<mark>`
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct PostSearchHit {
+    pub id: i64,
+    pub title: String,
+    pub slug: String,
+    pub title_highlight: String,
+    pub snippet: String,
+    pub category: Option<String>,
+    pub category_id: Option<i64>,
+    pub description: Option<String>,
+    pub author_id: i64,
+    pub rank: f32,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -61,14 +236,22 @@ impl From<posts::Model> for Post {
         Post {
             id: model.id,
             title: model.title,
+            slug: model.slug,
+            status: PostStatus::parse(&model.status).unwrap_or_default(),
+            review_note: model.review_note,
+            publish_at: model.publish_at,
+            unpublish_at: model.unpublish_at,
             content: model.content,
-            category: model.category,
+            category: None,
+            category_id: model.category_id,
             author_id: model.author_id,
             description: model.description,
             created_at: model.created_at,
             updated_at: model.updated_at,
             archived_at: model.archived_at,
             deleted_at: model.deleted_at,
+            tags: Vec::new(),
+            revision: model.revision,
         }
     }
 }
@@ -79,12 +262,22 @@ impl From<Post> for PostResponse {
         PostResponse {
             id: post.id,
             title: post.title,
+            slug: post.slug,
+            status: post.status,
+            review_note: post.review_note,
+            publish_at: post.publish_at.map(Into::into),
+            unpublish_at: post.unpublish_at.map(Into::into),
             content: post.content,
             category: post.category,
+            category_id: post.category_id,
             description: post.description,
+            tags: post.tags,
             author_id: post.author_id,
+            revision: post.revision,
             created_at: post.created_at.into(),
             updated_at: post.updated_at.into(),
+            content_html: None,
+            toc: None,
         }
     }
 }
@@ -96,7 +289,9 @@ pub struct PostVersion {
     pub post_id: i64,
     pub title: String,
     pub content: String,
-    pub category: String,
+    pub category: Option<String>,
+    pub category_id: Option<i64>,
+    pub description: Option<String>,
     pub version_number: i32,
     pub created_at: DateTimeWithTimeZone,
     pub created_by: i64,
@@ -110,7 +305,9 @@ pub struct PostVersionResponse {
     pub post_id: i64,
     pub title: String,
     pub content: String,
-    pub category: String,
+    pub category: Option<String>,
+    pub category_id: Option<i64>,
+    pub description: Option<String>,
     pub version_number: i32,
     pub created_at: DateTime<Utc>,
     pub created_by: i64,
@@ -130,8 +327,10 @@ pub struct PostDraft {
     pub post_id: Option<i64>,
     pub title: String,
     pub content: String,
-    pub category: String,
+    pub category: Option<String>,
+    pub category_id: Option<i64>,
     pub description: Option<String>,
+    pub tags: Vec<String>,
     pub author_id: i64,
     pub created_at: DateTimeWithTimeZone,
     pub updated_at: DateTimeWithTimeZone,
@@ -144,8 +343,10 @@ pub struct PostDraftResponse {
     pub post_id: Option<i64>,
     pub title: String,
     pub content: String,
-    pub category: String,
+    pub category: Option<String>,
+    pub category_id: Option<i64>,
     pub description: Option<String>,
+    pub tags: Vec<String>,
     pub author_id: i64,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
@@ -157,9 +358,15 @@ pub struct SaveDraftRequest {
     pub post_id: Option<i64>,
     pub title: String,
     pub content: String,
-    pub category: String,
+    /// Name or slug of an existing category; ignored when `category_id` is set
+    #[serde(default)]
+    pub category: Option<String>,
+    #[serde(default)]
+    pub category_id: Option<i64>,
     #[serde(default)]
     pub description: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
 }
 
 /// Convert from database Model to PostVersion DTO
@@ -170,7 +377,9 @@ impl From<post_versions::Model> for PostVersion {
             post_id: model.post_id,
             title: model.title,
             content: model.content,
-            category: model.category,
+            category: None,
+            category_id: model.category_id,
+            description: model.description,
             version_number: model.version_number,
             created_at: model.created_at,
             created_by: model.created_by,
@@ -188,6 +397,8 @@ impl From<PostVersion> for PostVersionResponse {
             title: version.title,
             content: version.content,
             category: version.category,
+            category_id: version.category_id,
+            description: version.description,
             version_number: version.version_number,
             created_at: version.created_at.into(),
             created_by: version.created_by,
@@ -204,8 +415,10 @@ impl From<post_drafts::Model> for PostDraft {
             post_id: model.post_id,
             title: model.title,
             content: model.content,
-            category: model.category,
+            category: None,
+            category_id: model.category_id,
             description: model.description,
+            tags: serde_json::from_value(model.tags).unwrap_or_default(),
             author_id: model.author_id,
             created_at: model.created_at,
             updated_at: model.updated_at,
@@ -222,7 +435,9 @@ impl From<PostDraft> for PostDraftResponse {
             title: draft.title,
             content: draft.content,
             category: draft.category,
+            category_id: draft.category_id,
             description: draft.description,
+            tags: draft.tags,
             author_id: draft.author_id,
             created_at: draft.created_at.into(),
             updated_at: draft.updated_at.into(),
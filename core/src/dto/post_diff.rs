@@ -0,0 +1,252 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use similar::{Algorithm, ChangeTag, DiffOp, TextDiff};
+use utoipa::ToSchema;
+
+/// Path value that selects the current state of the post instead of a saved version
+pub const CURRENT_VERSION: &str = "current";
+
+/// One side of a version comparison
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct DiffSide {
+    /// Version id, or `null` for the current post
+    pub version_id: Option<i64>,
+    /// Version number, or `null` for the current post
+    pub version_number: Option<i32>,
+    /// When the version was saved, or when the post was last updated
+    pub created_at: DateTime<Utc>,
+}
+
+/// Kind of a row in a side-by-side diff
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum DiffTag {
+    /// Same line on both sides
+    Equal,
+    /// Line only on the old side
+    Delete,
+    /// Line only on the new side
+    Insert,
+    /// Old line replaced by the new line
+    Replace,
+}
+
+/// Piece of a line; `changed` marks the words that differ from the other side
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+pub struct DiffSegment {
+    pub text: String,
+    pub changed: bool,
+}
+
+/// One line on one side of a diff
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct DiffLine {
+    /// Line number on its side (1-based)
+    pub number: usize,
+    pub segments: Vec<DiffSegment>,
+}
+
+/// Row of a side-by-side diff
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct DiffRow {
+    pub tag: DiffTag,
+    /// Left-hand line; `null` for inserted lines
+    pub old: Option<DiffLine>,
+    /// Right-hand line; `null` for deleted lines
+    pub new: Option<DiffLine>,
+}
+
+/// Line- and word-level diff of one field
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct FieldDiff {
+    pub changed: bool,
+    /// Number of lines only on the new side
+    pub added_lines: usize,
+    /// Number of lines only on the old side
+    pub removed_lines: usize,
+    pub rows: Vec<DiffRow>,
+}
+
+/// Response DTO comparing two versions of a post
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct PostVersionDiff {
+    pub post_id: i64,
+    pub from: DiffSide,
+    pub to: DiffSide,
+    pub title: FieldDiff,
+    pub content: FieldDiff,
+    /// Category names; a missing category compares as empty
+    pub category: FieldDiff,
+    /// A missing description compares as empty
+    pub description: FieldDiff,
+}
+
+/// Diff two texts line by line, marking changed words within replaced lines
+///
+/// Replaced lines are paired up in order, so each row can be shown side by side.
+/// Lines that differ too much to align word by word are marked as changed as a whole.
+pub fn diff_text(old: &str, new: &str) -> FieldDiff {
+    let diff = TextDiff::configure()
+        .algorithm(Algorithm::Patience)
+        .diff_lines(old, new);
+
+    let mut rows = Vec::new();
+    let mut added_lines = 0;
+    let mut removed_lines = 0;
+
+    for op in diff.ops() {
+        let changes: Vec<_> = diff
+            .iter_inline_changes(op)
+            .map(|change| {
+                let mut segments: Vec<DiffSegment> = change
+                    .iter_strings_lossy()
+                    .map(|(changed, text)| DiffSegment {
+                        text: text.into_owned(),
+                        changed,
+                    })
+                    .collect();
+                trim_line_ending(&mut segments);
+                (
+                    change.tag(),
+                    change.old_index(),
+                    change.new_index(),
+                    segments,
+                )
+            })
+            .collect();
+
+        // Without any aligned words, replaced lines count as changed as a whole
+        let whole_line = !matches!(op, DiffOp::Replace { .. })
+            || changes
+                .iter()
+                .all(|(_, _, _, segments)| segments.iter().all(|segment| !segment.changed));
+
+        let mut deleted = Vec::new();
+        let mut inserted = Vec::new();
+        for (tag, old_index, new_index, segments) in changes {
+            let number = |index: Option<usize>| index.unwrap_or_default() + 1;
+            match tag {
+                ChangeTag::Equal => rows.push(DiffRow {
+                    tag: DiffTag::Equal,
+                    old: Some(DiffLine {
+                        number: number(old_index),
+                        segments: segments.clone(),
+                    }),
+                    new: Some(DiffLine {
+                        number: number(new_index),
+                        segments,
+                    }),
+                }),
+                ChangeTag::Delete => deleted.push(DiffLine {
+                    number: number(old_index),
+                    segments: mark_whole_line(segments, whole_line),
+                }),
+                ChangeTag::Insert => inserted.push(DiffLine {
+                    number: number(new_index),
+                    segments: mark_whole_line(segments, whole_line),
+                }),
+            }
+        }
+
+        removed_lines += deleted.len();
+        added_lines += inserted.len();
+
+        let mut deleted = deleted.into_iter();
+        let mut inserted = inserted.into_iter();
+        loop {
+            let row = match (deleted.next(), inserted.next()) {
+                (None, None) => break,
+                (old, new) => DiffRow {
+                    tag: match (&old, &new) {
+                        (Some(_), Some(_)) => DiffTag::Replace,
+                        (Some(_), None) => DiffTag::Delete,
+                        _ => DiffTag::Insert,
+                    },
+                    old,
+                    new,
+                },
+            };
+            rows.push(row);
+        }
+    }
+
+    FieldDiff {
+        changed: old != new,
+        added_lines,
+        removed_lines,
+        rows,
+    }
+}
+
+/// Drop the line break from the end of a line's last segment
+fn trim_line_ending(segments: &mut Vec<DiffSegment>) {
+    if let Some(last) = segments.last_mut() {
+        let trimmed = last.text.trim_end_matches(['\n', '\r']).len();
+        last.text.truncate(trimmed);
+        if last.text.is_empty() {
+            segments.pop();
+        }
+    }
+}
+
+/// Merge a line into a single changed segment when no words were aligned
+fn mark_whole_line(segments: Vec<DiffSegment>, whole_line: bool) -> Vec<DiffSegment> {
+    if !whole_line {
+        return segments;
+    }
+    let text: String = segments.into_iter().map(|segment| segment.text).collect();
+    if text.is_empty() {
+        return Vec::new();
+    }
+    vec![DiffSegment {
+        text,
+        changed: true,
+    }]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diff_text_pairs_replaced_lines() {
+        let diff = diff_text(
+            "Intro\nThe quick brown fox\nOutro\n",
+            "Intro\nThe quick red fox\nNew line\nOutro\n",
+        );
+
+        assert!(diff.changed);
+        assert_eq!(diff.removed_lines, 1);
+        assert_eq!(diff.added_lines, 2);
+
+        let tags: Vec<DiffTag> = diff.rows.iter().map(|row| row.tag).collect();
+        assert_eq!(
+            tags,
+            [
+                DiffTag::Equal,
+                DiffTag::Replace,
+                DiffTag::Insert,
+                DiffTag::Equal
+            ]
+        );
+
+        // Only the changed word is marked in a replaced line
+        let new = diff.rows[1].new.as_ref().unwrap();
+        let changed: Vec<&str> = new
+            .segments
+            .iter()
+            .filter(|segment| segment.changed)
+            .map(|segment| segment.text.as_str())
+            .collect();
+        assert_eq!(changed, ["red"]);
+        assert_eq!(diff.rows[3].new.as_ref().unwrap().number, 4);
+    }
+
+    #[test]
+    fn test_diff_text_unchanged() {
+        let diff = diff_text("Same", "Same");
+        assert!(!diff.changed);
+        assert_eq!(diff.rows.len(), 1);
+        assert_eq!(diff.rows[0].tag, DiffTag::Equal);
+    }
+}
@@ -0,0 +1,179 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Publication status of a post
+///
+/// Only `published` posts are visible on public endpoints.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum PostStatus {
+    /// Work in progress, visible to its author and admins
+    Draft,
+    /// Submitted by its author, waiting for an admin to approve or reject it
+    PendingReview,
+    /// Waiting for its `publish_at` time to be published
+    Scheduled,
+    #[default]
+    Published,
+    /// Taken off the public site by an admin
+    Private,
+}
+
+impl PostStatus {
+    /// Value stored in the `posts.status` column
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PostStatus::Draft => "draft",
+            PostStatus::PendingReview => "pending_review",
+            PostStatus::Scheduled => "scheduled",
+            PostStatus::Published => "published",
+            PostStatus::Private => "private",
+        }
+    }
+
+    /// Parse a stored `posts.status` value
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "draft" => Some(PostStatus::Draft),
+            "pending_review" => Some(PostStatus::PendingReview),
+            "scheduled" => Some(PostStatus::Scheduled),
+            "published" => Some(PostStatus::Published),
+            "private" => Some(PostStatus::Private),
+            _ => None,
+        }
+    }
+
+    /// Whether a post's author may still change it; approved, scheduled and
+    /// private posts are only changed by admins, or after unpublishing them,
+    /// so that no change goes live without review
+    pub fn editable_by_author(&self) -> bool {
+        matches!(self, PostStatus::Draft | PostStatus::PendingReview)
+    }
+}
+
+/// A change of a post's status
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum PostTransition {
+    /// Author submits a draft for review
+    Submit,
+    /// Author takes a post back out of review
+    Withdraw,
+    /// Admin accepts a post under review and publishes it, or schedules it if
+    /// its `publish_at` lies in the future
+    Approve,
+    /// Admin sends a post under review back to its author as a draft
+    Reject,
+    /// Admin publishes a post directly
+    Publish,
+    /// Admin schedules a draft for publication at its `publish_at` time
+    Schedule,
+    /// Admin hides a post from the public site
+    MakePrivate,
+    /// Author or admin turns a post back into a draft
+    Unpublish,
+}
+
+impl PostTransition {
+    /// Name of the transition as used in requests
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PostTransition::Submit => "submit",
+            PostTransition::Withdraw => "withdraw",
+            PostTransition::Approve => "approve",
+            PostTransition::Reject => "reject",
+            PostTransition::Publish => "publish",
+            PostTransition::Schedule => "schedule",
+            PostTransition::MakePrivate => "make_private",
+            PostTransition::Unpublish => "unpublish",
+        }
+    }
+
+    /// Status a post in status `from` ends up in, or `None` if the transition is not allowed from there
+    pub fn target(&self, from: PostStatus) -> Option<PostStatus> {
+        use PostStatus::*;
+        match (self, from) {
+            (PostTransition::Submit, Draft) => Some(PendingReview),
+            (PostTransition::Withdraw, PendingReview) => Some(Draft),
+            (PostTransition::Approve, PendingReview) => Some(Published),
+            (PostTransition::Reject, PendingReview) => Some(Draft),
+            (PostTransition::Publish, Draft | Scheduled | Private) => Some(Published),
+            (PostTransition::Schedule, Draft) => Some(Scheduled),
+            (PostTransition::MakePrivate, Draft | Published) => Some(Private),
+            (PostTransition::Unpublish, Scheduled | Published | Private) => Some(Draft),
+            _ => None,
+        }
+    }
+
+    /// Whether only Admin/Root may perform this transition (otherwise the post's author may too)
+    pub fn requires_admin(&self) -> bool {
+        !matches!(
+            self,
+            PostTransition::Submit | PostTransition::Withdraw | PostTransition::Unpublish
+        )
+    }
+
+    /// Plugin action hook fired after this transition
+    pub fn hook_name(&self) -> &'static str {
+        match self {
+            PostTransition::Submit => "action_post_submitted",
+            PostTransition::Withdraw => "action_post_withdrawn",
+            PostTransition::Approve => "action_post_approved",
+            PostTransition::Reject => "action_post_rejected",
+            PostTransition::Publish => "action_post_published",
+            PostTransition::Schedule => "action_post_scheduled",
+            PostTransition::MakePrivate => "action_post_made_private",
+            PostTransition::Unpublish => "action_post_unpublished",
+        }
+    }
+}
+
+/// Request DTO for changing a post's status
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct PostTransitionRequest {
+    pub action: PostTransition,
+    /// Review note shown to the author; required when rejecting
+    pub note: Option<String>,
+    /// Publication time; required when scheduling a post that has none yet
+    pub publish_at: Option<DateTime<Utc>>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_review_flow() {
+        let pending = PostTransition::Submit.target(PostStatus::Draft).unwrap();
+        assert_eq!(pending, PostStatus::PendingReview);
+        assert_eq!(
+            PostTransition::Approve.target(pending),
+            Some(PostStatus::Published)
+        );
+        assert_eq!(
+            PostTransition::Reject.target(pending),
+            Some(PostStatus::Draft)
+        );
+    }
+
+    #[test]
+    fn test_invalid_transitions() {
+        assert_eq!(PostTransition::Approve.target(PostStatus::Draft), None);
+        assert_eq!(PostTransition::Submit.target(PostStatus::Published), None);
+        assert_eq!(PostTransition::Publish.target(PostStatus::Published), None);
+    }
+
+    #[test]
+    fn test_status_round_trip() {
+        for status in [
+            PostStatus::Draft,
+            PostStatus::PendingReview,
+            PostStatus::Scheduled,
+            PostStatus::Published,
+            PostStatus::Private,
+        ] {
+            assert_eq!(PostStatus::parse(status.as_str()), Some(status));
+        }
+    }
+}
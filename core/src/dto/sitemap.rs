@@ -0,0 +1,20 @@
+use sea_orm::prelude::DateTimeWithTimeZone;
+
+/// Kind of page listed in a sitemap
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SitemapEntryKind {
+    /// Category page, keyed by category slug
+    Category,
+    /// Author page, keyed by username
+    Author,
+    /// Post permalink, keyed by post slug
+    Post,
+}
+
+/// Page listed in a sitemap, with when its content last changed
+#[derive(Debug, Clone)]
+pub struct SitemapEntry {
+    pub kind: SitemapEntryKind,
+    pub key: String,
+    pub updated_at: DateTimeWithTimeZone,
+}
@@ -0,0 +1,82 @@
+use crate::entity::tags;
+use crate::slug::slugify;
+use chrono::{DateTime, Utc};
+use sea_orm::prelude::DateTimeWithTimeZone;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Tag business entity (DTO)
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct Tag {
+    pub id: i64,
+    pub name: String,
+    pub slug: String,
+    pub created_at: DateTimeWithTimeZone,
+}
+
+/// Tag response DTO with the number of (non-deleted) posts using it
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct TagResponse {
+    pub id: i64,
+    pub name: String,
+    pub slug: String,
+    pub post_count: i64,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Request DTO for renaming a tag (the slug is regenerated from the new name)
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct RenameTagRequest {
+    pub name: String,
+}
+
+/// Request DTO for merging tags
+///
+/// Posts tagged with any of `source_ids` are re-tagged with `target_id`,
+/// then the source tags are deleted.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct MergeTagsRequest {
+    pub source_ids: Vec<i64>,
+    pub target_id: i64,
+}
+
+/// Convert from database Model to business DTO
+impl From<tags::Model> for Tag {
+    fn from(model: tags::Model) -> Self {
+        Tag {
+            id: model.id,
+            name: model.name,
+            slug: model.slug,
+            created_at: model.created_at,
+        }
+    }
+}
+
+impl TagResponse {
+    pub fn new(tag: Tag, post_count: i64) -> Self {
+        TagResponse {
+            id: tag.id,
+            name: tag.name,
+            slug: tag.slug,
+            post_count,
+            created_at: tag.created_at.into(),
+        }
+    }
+}
+
+/// Normalize user-supplied tag names
+///
+/// Trims and collapses whitespace, drops names that produce an empty slug and
+/// removes duplicates (by slug), keeping the first spelling. Returns `(name, slug)` pairs.
+pub fn normalize_tag_names(names: &[String]) -> Vec<(String, String)> {
+    let mut normalized: Vec<(String, String)> = Vec::new();
+    for name in names {
+        let name = name.split_whitespace().collect::<Vec<_>>().join(" ");
+        let slug = slugify(&name);
+        if slug.is_empty() || normalized.iter().any(|(_, s)| *s == slug) {
+            continue;
+        }
+        normalized.push((name, slug));
+    }
+    normalized
+}
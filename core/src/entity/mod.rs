@@ -2,11 +2,20 @@
 
 pub mod prelude;
 
+pub mod categories;
+pub mod comments;
+pub mod import_records;
+pub mod media;
+pub mod media_references;
+pub mod media_variants;
 pub mod openai_api_keys;
 pub mod plugin_permissions;
 pub mod plugins;
 pub mod post_drafts;
+pub mod post_slug_history;
+pub mod post_tags;
 pub mod post_versions;
 pub mod posts;
 pub mod settings;
+pub mod tags;
 pub mod users;
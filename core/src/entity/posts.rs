@@ -11,19 +11,41 @@ pub struct Model {
     pub title: String,
     #[sea_orm(column_type = "Text")]
     pub content: String,
-    pub category: Option<String>,
     pub author_id: i64,
     pub created_at: DateTimeWithTimeZone,
     pub updated_at: DateTimeWithTimeZone,
     pub archived_at: Option<DateTimeWithTimeZone>,
     pub deleted_at: Option<DateTimeWithTimeZone>,
     pub description: Option<String>,
+    pub category_id: Option<i64>,
+    #[sea_orm(unique)]
+    pub slug: String,
+    pub status: String,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub review_note: Option<String>,
+    pub publish_at: Option<DateTimeWithTimeZone>,
+    pub unpublish_at: Option<DateTimeWithTimeZone>,
+    pub revision: i32,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
 pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::categories::Entity",
+        from = "Column::CategoryId",
+        to = "super::categories::Column::Id",
+        on_update = "Cascade",
+        on_delete = "SetNull"
+    )]
+    Categories,
+    #[sea_orm(has_many = "super::comments::Entity")]
+    Comments,
     #[sea_orm(has_many = "super::post_drafts::Entity")]
     PostDrafts,
+    #[sea_orm(has_many = "super::post_slug_history::Entity")]
+    PostSlugHistory,
+    #[sea_orm(has_many = "super::post_tags::Entity")]
+    PostTags,
     #[sea_orm(has_many = "super::post_versions::Entity")]
     PostVersions,
     #[sea_orm(
@@ -36,12 +58,36 @@ pub enum Relation {
     Users,
 }
 
+impl Related<super::categories::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Categories.def()
+    }
+}
+
+impl Related<super::comments::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Comments.def()
+    }
+}
+
 impl Related<super::post_drafts::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::PostDrafts.def()
     }
 }
 
+impl Related<super::post_slug_history::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::PostSlugHistory.def()
+    }
+}
+
+impl Related<super::post_tags::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::PostTags.def()
+    }
+}
+
 impl Related<super::post_versions::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::PostVersions.def()
@@ -54,4 +100,13 @@ impl Related<super::users::Entity> for Entity {
     }
 }
 
+impl Related<super::tags::Entity> for Entity {
+    fn to() -> RelationDef {
+        super::post_tags::Relation::Tags.def()
+    }
+    fn via() -> Option<RelationDef> {
+        Some(super::post_tags::Relation::Posts.def().rev())
+    }
+}
+
 impl ActiveModelBehavior for ActiveModel {}
@@ -1,10 +1,19 @@
 //! `SeaORM` Entity, @generated by sea-orm-codegen 1.1.19
 
+pub use super::categories::Entity as Categories;
+pub use super::comments::Entity as Comments;
+pub use super::import_records::Entity as ImportRecords;
+pub use super::media::Entity as Media;
+pub use super::media_references::Entity as MediaReferences;
+pub use super::media_variants::Entity as MediaVariants;
 pub use super::openai_api_keys::Entity as OpenaiApiKeys;
 pub use super::plugin_permissions::Entity as PluginPermissions;
 pub use super::plugins::Entity as Plugins;
 pub use super::post_drafts::Entity as PostDrafts;
+pub use super::post_slug_history::Entity as PostSlugHistory;
+pub use super::post_tags::Entity as PostTags;
 pub use super::post_versions::Entity as PostVersions;
 pub use super::posts::Entity as Posts;
 pub use super::settings::Entity as Settings;
+pub use super::tags::Entity as Tags;
 pub use super::users::Entity as Users;
@@ -0,0 +1,1049 @@
+//! Static export of the published site
+//!
+//! Renders post pages, paginated home/category/author listings, feeds, the sitemap
+//! and robots.txt into a directory that any static web server can host, and copies
+//! the uploaded media from the storage backend next to them. A manifest in the output directory records what
+//! each post page was built from, so re-exports only rewrite posts whose
+//! `updated_at` changed (listings, feeds and the sitemap are always rebuilt).
+//!
+//! [`markdown`] exports the posts' sources instead.
+
+pub mod markdown;
+
+use crate::api::admin_api::settings_helper::get_setting_value;
+use crate::dto::{Post, PostListQuery, PostStatus};
+use crate::feed::{Feed, FeedEntry, FeedFormat, escape};
+use crate::markdown::{RenderCache, excerpt};
+use crate::repository::{
+    PostRepository, PostgresPostRepository, PostgresUserRepository, UserRepository,
+};
+use crate::seo::PageMeta;
+use crate::sitemap::{MAX_SITEMAP_URLS, SitemapUrl, robots_txt, sitemap_index, urlset};
+use crate::slug::encode_path_segment;
+use crate::storage::{FileMetadata, StorageBackend};
+use chrono::{DateTime, Utc};
+use futures::StreamExt;
+use sea_orm::DatabaseConnection;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::SystemTime;
+use tokio::io::AsyncWriteExt;
+
+type ExportError = Box<dyn std::error::Error + Send + Sync>;
+
+/// File in the output directory recording the last export
+const MANIFEST_FILE: &str = ".rustpress-export.json";
+
+/// Bumped when the page layout changes, so that older exports are rebuilt in full
+const EXPORT_FORMAT: u32 = 1;
+
+/// Posts loaded per repository query
+const POSTS_PER_QUERY: u64 = 100;
+
+/// Defaults of the settings the server uses for the same pages
+const DEFAULT_POSTS_PER_PAGE: u64 = 10;
+const DEFAULT_FEED_ITEMS: usize = 20;
+
+/// Length of post excerpts on listing pages
+const EXCERPT_CHARS: usize = 200;
+
+/// Where and how to export the site
+#[derive(Debug, Clone)]
+pub struct ExportOptions {
+    pub out_dir: PathBuf,
+    /// Public base URL of the exported site; defaults to the `site_url` setting
+    pub site_url: Option<String>,
+    /// Rewrite every post page, ignoring the previous export
+    pub full: bool,
+}
+
+/// What an export wrote
+#[derive(Debug, Default)]
+pub struct ExportReport {
+    pub posts_written: usize,
+    pub posts_unchanged: usize,
+    pub posts_removed: usize,
+    pub listings_written: usize,
+    pub feeds_written: usize,
+    pub sitemaps_written: usize,
+    pub media_copied: usize,
+}
+
+/// Record of the last export, stored as [`MANIFEST_FILE`]
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Manifest {
+    format: u32,
+    /// Settings every page depends on; a change forces a full export
+    site: String,
+    posts: BTreeMap<i64, ManifestPost>,
+    /// Former slugs of exported posts, which hold redirect pages
+    #[serde(default)]
+    redirects: BTreeMap<String, i64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct ManifestPost {
+    slug: String,
+    /// Everything the post page was rendered from besides the site settings
+    fingerprint: String,
+}
+
+/// Site-wide settings of an export
+struct Site {
+    url: String,
+    title: String,
+    description: String,
+    posts_per_page: u64,
+    feed_items: usize,
+}
+
+/// Published posts with the names their pages show
+struct Content {
+    /// Newest first
+    posts: Vec<Post>,
+    authors: HashMap<i64, String>,
+    categories: Vec<CategoryPosts>,
+    render_cache: RenderCache,
+}
+
+/// Category with the ids of itself and all of its descendants
+struct CategoryPosts {
+    slug: String,
+    name: String,
+    description: Option<String>,
+    ids: HashSet<i64>,
+}
+
+/// Export the published site to `options.out_dir`, with the files of `storage`
+/// copied to `uploads/`
+pub async fn export_site<SB: StorageBackend + ?Sized>(
+    db: DatabaseConnection,
+    storage: &SB,
+    options: &ExportOptions,
+) -> Result<ExportReport, ExportError> {
+    let db = Arc::new(db);
+    let post_repository = PostgresPostRepository::new((*db).clone());
+    let user_repository = PostgresUserRepository::new((*db).clone());
+
+    let site = load_site(&db, options).await?;
+    let content = load_content(&post_repository, &user_repository).await?;
+    let out = options.out_dir.as_path();
+    tokio::fs::create_dir_all(out).await?;
+
+    let mut report = ExportReport::default();
+    let mut manifest = write_posts(out, &site, &content, options.full, &mut report).await?;
+    write_listings(out, &site, &content, &mut report).await?;
+    write_sitemap(out, &site, &db, &post_repository, &mut report).await?;
+
+    report.media_copied = copy_media(storage, &out.join("uploads")).await?;
+
+    manifest.format = EXPORT_FORMAT;
+    manifest.site = site_fingerprint(&site);
+    tokio::fs::write(
+        out.join(MANIFEST_FILE),
+        serde_json::to_vec_pretty(&manifest)?,
+    )
+    .await?;
+
+    Ok(report)
+}
+
+async fn load_site(
+    db: &Arc<DatabaseConnection>,
+    options: &ExportOptions,
+) -> Result<Site, ExportError> {
+    let setting = |key: &'static str| async move {
+        get_setting_value(db, key)
+            .await
+            .map(|value| value.filter(|value| !value.trim().is_empty()))
+    };
+
+    let url = match &options.site_url {
+        Some(url) => Some(url.clone()),
+        None => setting("site_url").await?,
+    }
+    .map(|url| url.trim().trim_end_matches('/').to_string())
+    .filter(|url| !url.is_empty())
+    .ok_or("no site URL: pass a base URL or set the `site_url` setting")?;
+
+    Ok(Site {
+        url,
+        title: setting("site_title")
+            .await?
+            .unwrap_or_else(|| "RustPress".to_string()),
+        description: setting("site_description").await?.unwrap_or_default(),
+        posts_per_page: setting("posts_per_page")
+            .await?
+            .and_then(|value| value.trim().parse::<u64>().ok())
+            .unwrap_or(DEFAULT_POSTS_PER_PAGE)
+            .max(1),
+        feed_items: setting("feed_items")
+            .await?
+            .and_then(|value| value.trim().parse::<usize>().ok())
+            .unwrap_or(DEFAULT_FEED_ITEMS)
+            .max(1),
+    })
+}
+
+async fn load_content(
+    post_repository: &PostgresPostRepository,
+    user_repository: &PostgresUserRepository,
+) -> Result<Content, ExportError> {
+    let query = PostListQuery {
+        status: Some(PostStatus::Published),
+        ..Default::default()
+    };
+    let mut posts = Vec::new();
+    for page in 1.. {
+        let (batch, total) = post_repository
+            .find_page(&query, page, POSTS_PER_QUERY)
+            .await?;
+        let done = batch.is_empty() || page * POSTS_PER_QUERY >= total;
+        posts.extend(batch);
+        if done {
+            break;
+        }
+    }
+
+    let mut authors = HashMap::new();
+    for author_id in posts.iter().map(|post| post.author_id) {
+        if authors.contains_key(&author_id) {
+            continue;
+        }
+        if let Some(user) = user_repository.find_by_id(&author_id).await? {
+            authors.insert(author_id, user.username);
+        }
+    }
+
+    let all_categories: Vec<_> = post_repository
+        .get_category_stats()
+        .await?
+        .into_iter()
+        .map(|(category, _)| category)
+        .collect();
+    let parents: HashMap<i64, Option<i64>> = all_categories
+        .iter()
+        .map(|category| (category.id, category.parent_id))
+        .collect();
+    let categories = all_categories
+        .into_iter()
+        .map(|category| {
+            // Every category whose chain of parents reaches this one
+            let ids = parents
+                .keys()
+                .copied()
+                .filter(|&id| {
+                    let mut current = Some(id);
+                    let mut seen = HashSet::new();
+                    while let Some(id) = current {
+                        if id == category.id {
+                            return true;
+                        }
+                        if !seen.insert(id) {
+                            break;
+                        }
+                        current = parents.get(&id).copied().flatten();
+                    }
+                    false
+                })
+                .collect();
+            CategoryPosts {
+                slug: category.slug,
+                name: category.name,
+                description: category.description,
+                ids,
+            }
+        })
+        .collect();
+
+    Ok(Content {
+        posts,
+        authors,
+        categories,
+        render_cache: RenderCache::new(),
+    })
+}
+
+/// Write the pages of new and changed posts and remove those of posts that are gone
+async fn write_posts(
+    out: &Path,
+    site: &Site,
+    content: &Content,
+    full: bool,
+    report: &mut ExportReport,
+) -> Result<Manifest, ExportError> {
+    let previous = match tokio::fs::read(out.join(MANIFEST_FILE)).await {
+        Ok(bytes) => serde_json::from_slice::<Manifest>(&bytes).unwrap_or_default(),
+        Err(_) => Manifest::default(),
+    };
+    let reuse =
+        !full && previous.format == EXPORT_FORMAT && previous.site == site_fingerprint(site);
+
+    let mut manifest = Manifest::default();
+    let current_slugs: HashMap<&str, &Post> = content
+        .posts
+        .iter()
+        .map(|post| (post.slug.as_str(), post))
+        .collect();
+
+    // Pages of posts that are no longer published
+    for (id, entry) in &previous.posts {
+        if !content.posts.iter().any(|post| post.id == *id)
+            && !current_slugs.contains_key(entry.slug.as_str())
+        {
+            remove_post_dir(out, &entry.slug).await?;
+            report.posts_removed += 1;
+        }
+    }
+
+    for post in &content.posts {
+        let Some(dir) = post_dir(out, &post.slug) else {
+            tracing::warn!(
+                "Skipping post {} with unusable slug {:?}",
+                post.id,
+                post.slug
+            );
+            continue;
+        };
+        let author = content.authors.get(&post.author_id).cloned();
+        let entry = ManifestPost {
+            slug: post.slug.clone(),
+            fingerprint: format!(
+                "{}|{}|{}|{}",
+                post.updated_at.to_rfc3339(),
+                post.revision,
+                author.as_deref().unwrap_or_default(),
+                post.category.as_deref().unwrap_or_default()
+            ),
+        };
+
+        let unchanged = reuse
+            && previous.posts.get(&post.id) == Some(&entry)
+            && tokio::fs::try_exists(dir.join("index.html")).await?;
+        if unchanged {
+            report.posts_unchanged += 1;
+        } else {
+            let rendered = content.render_cache.render_post(post);
+            let html = post_page(site, post, author, &rendered.html);
+            write_file(&dir.join("index.html"), html.as_bytes()).await?;
+            report.posts_written += 1;
+        }
+
+        if let Some(old) = previous.posts.get(&post.id)
+            && old.slug != post.slug
+        {
+            manifest.redirects.insert(old.slug.clone(), post.id);
+        }
+        manifest.posts.insert(post.id, entry);
+    }
+
+    // Former slugs point at the post's current page, unless another post took them over
+    for (slug, id) in previous.redirects {
+        manifest.redirects.entry(slug).or_insert(id);
+    }
+    let mut redirects = BTreeMap::new();
+    for (slug, id) in std::mem::take(&mut manifest.redirects) {
+        if current_slugs.contains_key(slug.as_str()) {
+            continue;
+        }
+        let target = content.posts.iter().find(|post| post.id == id);
+        match (target, post_dir(out, &slug)) {
+            (Some(post), Some(dir)) if post.slug != slug => {
+                let html = redirect_page(&format!("/p/{}/", encode_path_segment(&post.slug)));
+                write_file(&dir.join("index.html"), html.as_bytes()).await?;
+                redirects.insert(slug, id);
+            }
+            _ => remove_post_dir(out, &slug).await?,
+        }
+    }
+    manifest.redirects = redirects;
+
+    Ok(manifest)
+}
+
+/// Write the home, category and author listings with their feeds
+async fn write_listings(
+    out: &Path,
+    site: &Site,
+    content: &Content,
+    report: &mut ExportReport,
+) -> Result<(), ExportError> {
+    // Listings are rebuilt from scratch, so stale categories and pages disappear
+    for dir in ["page", "category", "author"] {
+        remove_dir(&out.join(dir)).await?;
+    }
+
+    let all: Vec<&Post> = content.posts.iter().collect();
+    let home = Listing {
+        path: String::new(),
+        title: site.title.clone(),
+        description: site.description.clone(),
+    };
+    write_listing(out, site, content, &home, &all, report).await?;
+
+    for category in &content.categories {
+        let posts: Vec<&Post> = content
+            .posts
+            .iter()
+            .filter(|post| {
+                post.category_id
+                    .is_some_and(|id| category.ids.contains(&id))
+            })
+            .collect();
+        if posts.is_empty() || !is_safe_segment(&category.slug) {
+            continue;
+        }
+        let listing = Listing {
+            path: format!("category/{}", category.slug),
+            title: format!("{} - {}", site.title, category.name),
+            description: category
+                .description
+                .clone()
+                .unwrap_or_else(|| site.description.clone()),
+        };
+        write_listing(out, site, content, &listing, &posts, report).await?;
+    }
+
+    let mut authors: Vec<(&i64, &String)> = content.authors.iter().collect();
+    authors.sort();
+    for (author_id, username) in authors {
+        let posts: Vec<&Post> = content
+            .posts
+            .iter()
+            .filter(|post| post.author_id == *author_id)
+            .collect();
+        if !is_safe_segment(username) {
+            continue;
+        }
+        let listing = Listing {
+            path: format!("author/{}", username),
+            title: format!("{} - {}", site.title, username),
+            description: site.description.clone(),
+        };
+        write_listing(out, site, content, &listing, &posts, report).await?;
+    }
+
+    Ok(())
+}
+
+/// Page of posts and its feeds, relative to the output directory
+struct Listing {
+    /// Directory of the listing (`""`, `category/{slug}`, `author/{username}`)
+    path: String,
+    title: String,
+    description: String,
+}
+
+impl Listing {
+    /// URL path of the listing, with leading and trailing slash
+    fn url_path(&self) -> String {
+        if self.path.is_empty() {
+            "/".to_string()
+        } else {
+            let segments: Vec<String> = self.path.split('/').map(encode_path_segment).collect();
+            format!("/{}/", segments.join("/"))
+        }
+    }
+
+    /// URL path of page `page` (1-based) of the listing
+    fn page_path(&self, page: u64) -> String {
+        if page == 1 {
+            self.url_path()
+        } else {
+            format!("{}page/{}/", self.url_path(), page)
+        }
+    }
+}
+
+async fn write_listing(
+    out: &Path,
+    site: &Site,
+    content: &Content,
+    listing: &Listing,
+    posts: &[&Post],
+    report: &mut ExportReport,
+) -> Result<(), ExportError> {
+    let dir = out.join(&listing.path);
+    let pages = (posts.len() as u64).div_ceil(site.posts_per_page).max(1);
+    for page in 1..=pages {
+        let start = ((page - 1) * site.posts_per_page) as usize;
+        let end = posts.len().min(start + site.posts_per_page as usize);
+        let html = listing_page(site, content, listing, &posts[start..end], page, pages);
+        let page_dir = if page == 1 {
+            dir.clone()
+        } else {
+            dir.join("page").join(page.to_string())
+        };
+        write_file(&page_dir.join("index.html"), html.as_bytes()).await?;
+        report.listings_written += 1;
+    }
+
+    let link = format!("{}{}", site.url, listing.url_path());
+    let entries: Vec<FeedEntry> = posts
+        .iter()
+        .take(site.feed_items)
+        .map(|post| {
+            FeedEntry::from_post(
+                &site.url,
+                (*post).clone(),
+                content.authors.get(&post.author_id).cloned(),
+                content.render_cache.render_post(post).html.clone(),
+            )
+        })
+        .collect();
+    let updated = entries
+        .iter()
+        .map(|entry| entry.updated)
+        .max()
+        .unwrap_or(DateTime::UNIX_EPOCH);
+    for format in [FeedFormat::Rss, FeedFormat::Atom] {
+        let feed = Feed {
+            title: listing.title.clone(),
+            description: listing.description.clone(),
+            link: link.clone(),
+            self_link: format!("{}{}", link, format.file_name()),
+            updated,
+            entries: entries.clone(),
+        };
+        write_file(
+            &dir.join(format.file_name()),
+            feed.render(format).as_bytes(),
+        )
+        .await?;
+        report.feeds_written += 1;
+    }
+
+    Ok(())
+}
+
+/// Write `sitemap.xml` (an index of `sitemaps/{n}.xml` for large sites) and robots.txt
+async fn write_sitemap(
+    out: &Path,
+    site: &Site,
+    db: &Arc<DatabaseConnection>,
+    post_repository: &PostgresPostRepository,
+    report: &mut ExportReport,
+) -> Result<(), ExportError> {
+    remove_dir(&out.join("sitemaps")).await?;
+
+    let mut urls = vec![SitemapUrl {
+        loc: format!("{}/", site.url),
+        lastmod: None,
+    }];
+    let total = post_repository.count_sitemap_entries().await?;
+    let mut offset = 0;
+    while offset < total {
+        let entries = post_repository
+            .find_sitemap_entries(offset, MAX_SITEMAP_URLS)
+            .await?;
+        if entries.is_empty() {
+            break;
+        }
+        offset += entries.len() as u64;
+        urls.extend(
+            entries
+                .into_iter()
+                .map(|entry| SitemapUrl::from_entry(&site.url, entry)),
+        );
+    }
+
+    if urls.len() as u64 <= MAX_SITEMAP_URLS {
+        write_file(&out.join("sitemap.xml"), urlset(&urls).as_bytes()).await?;
+        report.sitemaps_written += 1;
+    } else {
+        let mut sitemaps = Vec::new();
+        for (index, chunk) in urls.chunks(MAX_SITEMAP_URLS as usize).enumerate() {
+            let file = format!("sitemaps/{}.xml", index + 1);
+            write_file(&out.join(&file), urlset(chunk).as_bytes()).await?;
+            sitemaps.push(SitemapUrl {
+                loc: format!("{}/{}", site.url, file),
+                lastmod: None,
+            });
+            report.sitemaps_written += 1;
+        }
+        write_file(
+            &out.join("sitemap.xml"),
+            sitemap_index(&sitemaps).as_bytes(),
+        )
+        .await?;
+    }
+
+    let setting = get_setting_value(db, "robots_txt").await?;
+    write_file(
+        &out.join("robots.txt"),
+        robots_txt(setting, &site.url).as_bytes(),
+    )
+    .await?;
+    Ok(())
+}
+
+fn post_page(site: &Site, post: &Post, author: Option<String>, content_html: &str) -> String {
+    let meta = PageMeta::for_post(
+        &site.url,
+        site.title.clone(),
+        post,
+        author.clone(),
+        content_html,
+    );
+
+    let mut body = String::new();
+    let _ = write!(body, "<article><h1>{}</h1>", escape(&post.title));
+    body.push_str(r#"<p class="meta">"#);
+    body.push_str(&time_element(post.created_at.with_timezone(&Utc)));
+    if let Some(author) = &author {
+        let _ = write!(
+            body,
+            r#" · <a href="/author/{}/">{}</a>"#,
+            encode_path_segment(author),
+            escape(author)
+        );
+    }
+    if let Some(category) = &post.category {
+        let _ = write!(body, " · {}", escape(category));
+    }
+    if !post.tags.is_empty() {
+        let _ = write!(body, " · {}", escape(&post.tags.join(", ")));
+    }
+    body.push_str("</p>");
+    body.push_str(content_html);
+    body.push_str("</article>");
+
+    layout(site, &meta.head_tags(), "/", &body)
+}
+
+fn listing_page(
+    site: &Site,
+    content: &Content,
+    listing: &Listing,
+    posts: &[&Post],
+    page: u64,
+    pages: u64,
+) -> String {
+    let meta = PageMeta {
+        title: listing.title.clone(),
+        description: listing.description.clone(),
+        canonical_url: format!("{}{}", site.url, listing.page_path(page)),
+        site_name: site.title.clone(),
+        image: None,
+        article: None,
+    };
+
+    let mut body = String::new();
+    let _ = write!(body, "<h1>{}</h1>", escape(&listing.title));
+    body.push_str(r#"<ul class="posts">"#);
+    for post in posts {
+        let summary = post
+            .description
+            .clone()
+            .filter(|description| !description.trim().is_empty())
+            .unwrap_or_else(|| excerpt(&post.content, EXCERPT_CHARS));
+        let _ = write!(
+            body,
+            r#"<li><a href="/p/{}/">{}</a> <span class="meta">{}"#,
+            encode_path_segment(&post.slug),
+            escape(&post.title),
+            time_element(post.created_at.with_timezone(&Utc))
+        );
+        if let Some(author) = content.authors.get(&post.author_id) {
+            let _ = write!(body, " · {}", escape(author));
+        }
+        let _ = write!(body, "</span><p>{}</p></li>", escape(&summary));
+    }
+    body.push_str("</ul>");
+
+    if pages > 1 {
+        body.push_str(r#"<nav class="pagination">"#);
+        if page > 1 {
+            let _ = write!(
+                body,
+                r#"<a href="{}" rel="prev">Newer posts</a> "#,
+                listing.page_path(page - 1)
+            );
+        }
+        if page < pages {
+            let _ = write!(
+                body,
+                r#"<a href="{}" rel="next">Older posts</a>"#,
+                listing.page_path(page + 1)
+            );
+        }
+        body.push_str("</nav>");
+    }
+
+    layout(site, &meta.head_tags(), &listing.url_path(), &body)
+}
+
+/// HTML document around a page's body; `feed_base` is the path its feeds live under
+fn layout(site: &Site, head_tags: &str, feed_base: &str, body: &str) -> String {
+    format!(
+        concat!(
+            "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n",
+            "<meta charset=\"UTF-8\">\n",
+            "<meta name=\"viewport\" content=\"width=device-width, initial-scale=1.0\">\n",
+            "{head}\n",
+            "<link rel=\"alternate\" type=\"application/rss+xml\" href=\"{feed_base}feed.xml\">\n",
+            "<link rel=\"alternate\" type=\"application/atom+xml\" href=\"{feed_base}atom.xml\">\n",
+            "<style>{style}</style>\n",
+            "</head>\n<body>\n<header><a href=\"/\">{site}</a></header>\n",
+            "<main>{body}</main>\n</body>\n</html>\n"
+        ),
+        head = head_tags,
+        feed_base = feed_base,
+        style = STYLE,
+        site = escape(&site.title),
+        body = body,
+    )
+}
+
+const STYLE: &str = "body{max-width:46rem;margin:0 auto;padding:1rem;font-family:system-ui,sans-serif;line-height:1.6}\
+header{margin-bottom:2rem;font-weight:bold}img{max-width:100%}pre{overflow-x:auto;padding:1rem}\
+.meta{color:#666;font-size:.9rem}.posts{list-style:none;padding:0}.posts li{margin-bottom:1.5rem}";
+
+/// Page sending visitors (and crawlers) on to a post's new slug
+fn redirect_page(target: &str) -> String {
+    let target = escape(target);
+    format!(
+        concat!(
+            "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"UTF-8\">\n",
+            "<meta http-equiv=\"refresh\" content=\"0; url={target}\">\n",
+            "<link rel=\"canonical\" href=\"{target}\">\n",
+            "</head>\n<body><a href=\"{target}\">{target}</a></body>\n</html>\n"
+        ),
+        target = target
+    )
+}
+
+fn time_element(time: DateTime<Utc>) -> String {
+    format!(
+        r#"<time datetime="{}">{}</time>"#,
+        time.to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+        time.format("%B %-d, %Y")
+    )
+}
+
+fn site_fingerprint(site: &Site) -> String {
+    format!(
+        "{}|{}|{}|{}",
+        EXPORT_FORMAT, site.url, site.title, site.description
+    )
+}
+
+/// Whether a slug or username can be used as a single directory name
+fn is_safe_segment(value: &str) -> bool {
+    !value.is_empty() && value != "." && value != ".." && !value.contains(['/', '\\', '\0'])
+}
+
+fn post_dir(out: &Path, slug: &str) -> Option<PathBuf> {
+    is_safe_segment(slug).then(|| out.join("p").join(slug))
+}
+
+async fn remove_post_dir(out: &Path, slug: &str) -> Result<(), ExportError> {
+    match post_dir(out, slug) {
+        Some(dir) => remove_dir(&dir).await,
+        None => Ok(()),
+    }
+}
+
+async fn remove_dir(dir: &Path) -> Result<(), ExportError> {
+    match tokio::fs::remove_dir_all(dir).await {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.into()),
+        _ => Ok(()),
+    }
+}
+
+async fn write_file(path: &Path, contents: &[u8]) -> Result<(), ExportError> {
+    if let Some(parent) = path.parent() {
+        tokio::fs::create_dir_all(parent).await?;
+    }
+    tokio::fs::write(path, contents).await?;
+    Ok(())
+}
+
+/// Copy new and changed files from the storage backend into `to`, returning how
+/// many were copied
+async fn copy_media<SB: StorageBackend + ?Sized>(
+    storage: &SB,
+    to: &Path,
+) -> Result<usize, ExportError> {
+    let files = storage.list("").await?;
+    tokio::fs::create_dir_all(to).await?;
+
+    let mut copied = 0;
+    for file in files {
+        let target = to.join(&file.name);
+        let current = match tokio::fs::metadata(&target).await {
+            Ok(existing) => is_current_copy(existing.len(), existing.modified().ok(), &file),
+            Err(_) => false,
+        };
+        if current {
+            continue;
+        }
+
+        // Removed from storage since it was listed
+        let Some(stored) = storage.get(&file.name, None).await? else {
+            continue;
+        };
+        let mut body = stored.body;
+        let mut writer = tokio::fs::File::create(&target).await?;
+        while let Some(chunk) = body.next().await {
+            writer.write_all(&chunk?).await?;
+        }
+        writer.flush().await?;
+        copied += 1;
+    }
+    Ok(copied)
+}
+
+/// Whether an exported copy of a file (its size and modification time) is up to
+/// date: it has the same size and is not older than the stored file
+fn is_current_copy(size: u64, modified: Option<SystemTime>, original: &FileMetadata) -> bool {
+    if size != original.size {
+        return false;
+    }
+    match (modified, original.last_modified) {
+        (Some(copy), Some(original)) => DateTime::<Utc>::from(copy) >= original,
+        // Without a time to compare, the same size has to do
+        (_, None) => true,
+        (None, Some(_)) => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::LocalStorage;
+
+    fn site() -> Site {
+        Site {
+            url: "https://example.com".to_string(),
+            title: "Example".to_string(),
+            description: "A test site".to_string(),
+            posts_per_page: 10,
+            feed_items: 20,
+        }
+    }
+
+    fn post(id: i64, slug: &str, updated_at: &str) -> Post {
+        let time = DateTime::parse_from_rfc3339(updated_at).unwrap();
+        Post {
+            id,
+            title: format!("Post {}", id),
+            slug: slug.to_string(),
+            status: PostStatus::Published,
+            review_note: None,
+            publish_at: None,
+            unpublish_at: None,
+            content: "Hello *world*".to_string(),
+            category: None,
+            category_id: None,
+            author_id: 1,
+            description: None,
+            created_at: time,
+            updated_at: time,
+            archived_at: None,
+            deleted_at: None,
+            tags: Vec::new(),
+            revision: 1,
+        }
+    }
+
+    fn content(posts: Vec<Post>) -> Content {
+        Content {
+            posts,
+            authors: HashMap::from([(1, "admin".to_string())]),
+            categories: Vec::new(),
+            render_cache: RenderCache::new(),
+        }
+    }
+
+    /// Export the posts and save the manifest, like [`export_site`] does
+    async fn export_posts(out: &Path, posts: Vec<Post>, full: bool) -> ExportReport {
+        let site = site();
+        let mut report = ExportReport::default();
+        let mut manifest = write_posts(out, &site, &content(posts), full, &mut report)
+            .await
+            .unwrap();
+        manifest.format = EXPORT_FORMAT;
+        manifest.site = site_fingerprint(&site);
+        tokio::fs::write(
+            out.join(MANIFEST_FILE),
+            serde_json::to_vec(&manifest).unwrap(),
+        )
+        .await
+        .unwrap();
+        report
+    }
+
+    #[test]
+    fn test_manifest_without_redirects_parses() {
+        let manifest: Manifest = serde_json::from_str(
+            r#"{"format":1,"site":"s","posts":{"3":{"slug":"hello","fingerprint":"f"}}}"#,
+        )
+        .unwrap();
+        assert_eq!(manifest.posts[&3].slug, "hello");
+        assert!(manifest.redirects.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_reexport_only_rewrites_changed_posts() {
+        let out = tempfile::tempdir().unwrap();
+        let first = post(1, "first", "2026-01-01T00:00:00Z");
+        let second = post(2, "second", "2026-01-02T00:00:00Z");
+
+        let report = export_posts(out.path(), vec![first.clone(), second.clone()], false).await;
+        assert_eq!((report.posts_written, report.posts_unchanged), (2, 0));
+
+        let report = export_posts(out.path(), vec![first.clone(), second.clone()], false).await;
+        assert_eq!((report.posts_written, report.posts_unchanged), (0, 2));
+
+        let changed = post(2, "second", "2026-02-01T00:00:00Z");
+        let report = export_posts(out.path(), vec![first.clone(), changed.clone()], false).await;
+        assert_eq!((report.posts_written, report.posts_unchanged), (1, 1));
+
+        let report = export_posts(out.path(), vec![first.clone(), changed.clone()], true).await;
+        assert_eq!((report.posts_written, report.posts_unchanged), (2, 0));
+
+        // A deleted page is written again even though the post didn't change
+        std::fs::remove_file(out.path().join("p/first/index.html")).unwrap();
+        let report = export_posts(out.path(), vec![first, changed], false).await;
+        assert_eq!((report.posts_written, report.posts_unchanged), (1, 1));
+    }
+
+    #[tokio::test]
+    async fn test_reexport_redirects_renamed_and_removes_unpublished_posts() {
+        let out = tempfile::tempdir().unwrap();
+        export_posts(
+            out.path(),
+            vec![
+                post(1, "old-name", "2026-01-01T00:00:00Z"),
+                post(2, "gone", "2026-01-01T00:00:00Z"),
+            ],
+            false,
+        )
+        .await;
+
+        let report = export_posts(
+            out.path(),
+            vec![post(1, "new-name", "2026-01-03T00:00:00Z")],
+            false,
+        )
+        .await;
+        assert_eq!(report.posts_removed, 1);
+        assert!(!out.path().join("p/gone").exists());
+        assert!(out.path().join("p/new-name/index.html").exists());
+        let redirect = std::fs::read_to_string(out.path().join("p/old-name/index.html")).unwrap();
+        assert!(redirect.contains(r#"url=/p/new-name/""#));
+
+        // The redirect survives later exports, until another post takes the slug
+        export_posts(
+            out.path(),
+            vec![post(1, "new-name", "2026-01-03T00:00:00Z")],
+            false,
+        )
+        .await;
+        assert!(out.path().join("p/old-name/index.html").exists());
+        export_posts(
+            out.path(),
+            vec![
+                post(1, "new-name", "2026-01-03T00:00:00Z"),
+                post(3, "old-name", "2026-01-04T00:00:00Z"),
+            ],
+            false,
+        )
+        .await;
+        let page = std::fs::read_to_string(out.path().join("p/old-name/index.html")).unwrap();
+        assert!(page.contains("Post 3"));
+    }
+
+    #[test]
+    fn test_listing_urls() {
+        let home = Listing {
+            path: String::new(),
+            title: String::new(),
+            description: String::new(),
+        };
+        assert_eq!(home.page_path(1), "/");
+        assert_eq!(home.page_path(2), "/page/2/");
+
+        let category = Listing {
+            path: "category/c#".to_string(),
+            title: String::new(),
+            description: String::new(),
+        };
+        assert_eq!(category.page_path(1), "/category/c%23/");
+        assert_eq!(category.page_path(3), "/category/c%23/page/3/");
+    }
+
+    #[test]
+    fn test_unsafe_slugs_have_no_page() {
+        let out = Path::new("/out");
+        assert_eq!(post_dir(out, "hello"), Some(PathBuf::from("/out/p/hello")));
+        for slug in ["", ".", "..", "a/b", "a\\b"] {
+            assert_eq!(post_dir(out, slug), None, "{:?}", slug);
+        }
+    }
+
+    #[test]
+    fn test_redirect_page_escapes_target() {
+        let html = redirect_page("/p/a\"b/");
+        assert!(html.contains("url=/p/a&quot;b/"));
+        assert!(!html.contains("a\"b"));
+    }
+
+    #[test]
+    fn test_is_current_copy() {
+        let modified = DateTime::parse_from_rfc3339("2026-01-01T00:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let original = FileMetadata {
+            name: "a.png".to_string(),
+            size: 10,
+            content_type: None,
+            last_modified: Some(modified),
+        };
+        let later = SystemTime::from(modified + chrono::Duration::seconds(1));
+        let earlier = SystemTime::from(modified - chrono::Duration::seconds(1));
+        assert!(is_current_copy(10, Some(later), &original));
+        assert!(!is_current_copy(10, Some(earlier), &original));
+        assert!(!is_current_copy(11, Some(later), &original));
+        assert!(is_current_copy(
+            10,
+            None,
+            &FileMetadata {
+                last_modified: None,
+                ..original
+            }
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_copy_media_from_storage() {
+        let uploads = tempfile::tempdir().unwrap();
+        let out = tempfile::tempdir().unwrap();
+        let storage = LocalStorage::new(uploads.path(), String::new());
+        storage
+            .put_file(b"one".to_vec(), "a.png", "image/png".to_string())
+            .await
+            .unwrap();
+        storage
+            .put_file(b"two".to_vec(), "b.pdf", "application/pdf".to_string())
+            .await
+            .unwrap();
+
+        let target = out.path().join("uploads");
+        assert_eq!(copy_media(&storage, &target).await.unwrap(), 2);
+        assert_eq!(std::fs::read(target.join("b.pdf")).unwrap(), b"two");
+        // Unchanged files are not copied again
+        assert_eq!(copy_media(&storage, &target).await.unwrap(), 0);
+
+        storage
+            .put_file(b"three".to_vec(), "a.png", "image/png".to_string())
+            .await
+            .unwrap();
+        assert_eq!(copy_media(&storage, &target).await.unwrap(), 1);
+        assert_eq!(std::fs::read(target.join("a.png")).unwrap(), b"three");
+    }
+}
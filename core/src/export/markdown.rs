@@ -0,0 +1,54 @@
+//! Export of posts as Markdown files with front matter
+//!
+//! Writes one `<slug>.md` file per post, in the layout read by
+//! [`crate::import::markdown`], Hugo and Jekyll. Posts that are not published are
+//! marked as drafts so that static site generators leave them out.
+
+use crate::dto::PostStatus;
+use crate::front_matter::{self, FrontMatter, FrontMatterFormat, MarkdownFile};
+use crate::repository::{PostRepository, UserRepository};
+use chrono::Utc;
+use std::collections::HashMap;
+
+type ExportError = Box<dyn std::error::Error + Send + Sync>;
+
+/// All posts that are not deleted, as Markdown files
+pub async fn export_posts<PR: PostRepository, UR: UserRepository>(
+    post_repository: &PR,
+    user_repository: &UR,
+    format: FrontMatterFormat,
+) -> Result<Vec<MarkdownFile>, ExportError> {
+    let usernames: HashMap<i64, String> = user_repository
+        .find_all()
+        .await?
+        .into_iter()
+        .map(|user| (user.id, user.username))
+        .collect();
+
+    let mut files = Vec::new();
+    for post in post_repository.find_all().await? {
+        let front_matter = FrontMatter {
+            title: Some(post.title),
+            slug: Some(post.slug.clone()),
+            status: Some(post.status),
+            draft: (post.status != PostStatus::Published).then_some(true),
+            published: None,
+            date: Some(post.created_at.with_timezone(&Utc)),
+            lastmod: Some(post.updated_at.with_timezone(&Utc)),
+            publish_at: post
+                .publish_at
+                .filter(|_| post.status == PostStatus::Scheduled)
+                .map(|date| date.with_timezone(&Utc)),
+            author: usernames.get(&post.author_id).cloned(),
+            categories: post.category.into_iter().collect(),
+            tags: post.tags,
+            description: post.description,
+        };
+        files.push(MarkdownFile {
+            path: format!("{}.md", post.slug),
+            contents: front_matter::render(&front_matter, &post.content, format)?,
+        });
+    }
+    files.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(files)
+}
@@ -0,0 +1,288 @@
+//! RSS 2.0 and Atom feeds
+//!
+//! Builds the XML of syndication feeds from already resolved entries; choosing the
+//! posts and turning them into absolute links happens in the feed controller.
+
+use crate::dto::Post;
+use crate::slug::post_path;
+use chrono::{DateTime, SecondsFormat, Utc};
+use std::fmt::Write;
+
+/// Name and version written to the feeds' `generator` element
+const GENERATOR: &str = concat!("RustPress ", env!("CARGO_PKG_VERSION"));
+
+/// Syndication format of a feed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FeedFormat {
+    Rss,
+    Atom,
+}
+
+impl FeedFormat {
+    pub fn content_type(self) -> &'static str {
+        match self {
+            FeedFormat::Rss => "application/rss+xml; charset=utf-8",
+            FeedFormat::Atom => "application/atom+xml; charset=utf-8",
+        }
+    }
+
+    /// File name of the feed under its base path (`feed.xml`, `atom.xml`)
+    pub fn file_name(self) -> &'static str {
+        match self {
+            FeedFormat::Rss => "feed.xml",
+            FeedFormat::Atom => "atom.xml",
+        }
+    }
+}
+
+/// Entry of a feed; links are absolute
+#[derive(Debug, Clone)]
+pub struct FeedEntry {
+    pub title: String,
+    /// Permalink of the post
+    pub link: String,
+    /// Stable identifier of the post, kept when its slug changes
+    pub id: String,
+    pub author: Option<String>,
+    /// Category name followed by the tags
+    pub categories: Vec<String>,
+    pub summary: Option<String>,
+    /// Rendered (sanitised) HTML content
+    pub content_html: String,
+    pub published: DateTime<Utc>,
+    pub updated: DateTime<Utc>,
+}
+
+impl FeedEntry {
+    /// Entry for a post, given its author's name and rendered content
+    pub fn from_post(
+        site_url: &str,
+        post: Post,
+        author: Option<String>,
+        content_html: String,
+    ) -> Self {
+        FeedEntry {
+            link: format!("{}{}", site_url, post_path(&post.slug)),
+            id: format!("{}/posts/{}", site_url, post.id),
+            author,
+            categories: post.category.into_iter().chain(post.tags).collect(),
+            summary: post
+                .description
+                .filter(|description| !description.is_empty()),
+            content_html,
+            published: post.created_at.with_timezone(&Utc),
+            updated: post.updated_at.with_timezone(&Utc),
+            title: post.title,
+        }
+    }
+}
+
+/// Feed with its entries, newest first; links are absolute
+#[derive(Debug, Clone)]
+pub struct Feed {
+    pub title: String,
+    pub description: String,
+    /// Page the feed belongs to
+    pub link: String,
+    /// URL of the feed itself
+    pub self_link: String,
+    /// When any entry of the feed last changed
+    pub updated: DateTime<Utc>,
+    pub entries: Vec<FeedEntry>,
+}
+
+impl Feed {
+    pub fn render(&self, format: FeedFormat) -> String {
+        match format {
+            FeedFormat::Rss => self.to_rss(),
+            FeedFormat::Atom => self.to_atom(),
+        }
+    }
+
+    /// RSS 2.0 document, with `content:encoded` for the full content
+    pub fn to_rss(&self) -> String {
+        let mut xml = String::new();
+        xml.push_str(r#"<?xml version="1.0" encoding="UTF-8"?>"#);
+        xml.push_str(concat!(
+            r#"<rss version="2.0" xmlns:atom="http://www.w3.org/2005/Atom""#,
+            r#" xmlns:content="http://purl.org/rss/1.0/modules/content/""#,
+            r#" xmlns:dc="http://purl.org/dc/elements/1.1/">"#,
+        ));
+        xml.push_str("<channel>");
+        element(&mut xml, "title", &self.title);
+        element(&mut xml, "link", &self.link);
+        element(&mut xml, "description", &self.description);
+        let _ = write!(
+            xml,
+            r#"<atom:link href="{}" rel="self" type="application/rss+xml"/>"#,
+            escape(&self.self_link)
+        );
+        element(&mut xml, "lastBuildDate", &self.updated.to_rfc2822());
+        element(&mut xml, "generator", GENERATOR);
+
+        for entry in &self.entries {
+            xml.push_str("<item>");
+            element(&mut xml, "title", &entry.title);
+            element(&mut xml, "link", &entry.link);
+            let _ = write!(
+                xml,
+                r#"<guid isPermaLink="false">{}</guid>"#,
+                escape(&entry.id)
+            );
+            element(&mut xml, "pubDate", &entry.published.to_rfc2822());
+            if let Some(author) = &entry.author {
+                element(&mut xml, "dc:creator", author);
+            }
+            for category in &entry.categories {
+                element(&mut xml, "category", category);
+            }
+            if let Some(summary) = &entry.summary {
+                element(&mut xml, "description", summary);
+            }
+            element(&mut xml, "content:encoded", &entry.content_html);
+            xml.push_str("</item>");
+        }
+
+        xml.push_str("</channel></rss>");
+        xml
+    }
+
+    /// Atom 1.0 document
+    pub fn to_atom(&self) -> String {
+        let mut xml = String::new();
+        xml.push_str(r#"<?xml version="1.0" encoding="UTF-8"?>"#);
+        xml.push_str(r#"<feed xmlns="http://www.w3.org/2005/Atom">"#);
+        element(&mut xml, "title", &self.title);
+        if !self.description.is_empty() {
+            element(&mut xml, "subtitle", &self.description);
+        }
+        element(&mut xml, "id", &self.self_link);
+        let _ = write!(
+            xml,
+            r#"<link rel="alternate" type="text/html" href="{}"/>"#,
+            escape(&self.link)
+        );
+        let _ = write!(
+            xml,
+            r#"<link rel="self" type="application/atom+xml" href="{}"/>"#,
+            escape(&self.self_link)
+        );
+        element(&mut xml, "updated", &rfc3339(self.updated));
+        element(&mut xml, "generator", GENERATOR);
+
+        for entry in &self.entries {
+            xml.push_str("<entry>");
+            element(&mut xml, "title", &entry.title);
+            let _ = write!(
+                xml,
+                r#"<link rel="alternate" type="text/html" href="{}"/>"#,
+                escape(&entry.link)
+            );
+            element(&mut xml, "id", &entry.id);
+            element(&mut xml, "published", &rfc3339(entry.published));
+            element(&mut xml, "updated", &rfc3339(entry.updated));
+            if let Some(author) = &entry.author {
+                xml.push_str("<author>");
+                element(&mut xml, "name", author);
+                xml.push_str("</author>");
+            }
+            for category in &entry.categories {
+                let _ = write!(xml, r#"<category term="{}"/>"#, escape(category));
+            }
+            if let Some(summary) = &entry.summary {
+                element(&mut xml, "summary", summary);
+            }
+            let _ = write!(
+                xml,
+                r#"<content type="html">{}</content>"#,
+                escape(&entry.content_html)
+            );
+            xml.push_str("</entry>");
+        }
+
+        xml.push_str("</feed>");
+        xml
+    }
+}
+
+/// Append `<name>text</name>` with the text escaped
+fn element(xml: &mut String, name: &str, text: &str) {
+    let _ = write!(xml, "<{name}>{}</{name}>", escape(text));
+}
+
+fn rfc3339(time: DateTime<Utc>) -> String {
+    time.to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+/// Escape text for XML element content and attribute values
+///
+/// Control characters other than tab and line breaks are not allowed in XML 1.0 and are dropped.
+pub fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            '\t' | '\n' | '\r' => escaped.push(c),
+            c if c.is_control() => {}
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_feed() -> Feed {
+        let time = DateTime::parse_from_rfc3339("2026-01-02T03:04:05Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        Feed {
+            title: "Tom & Jerry's blog".to_string(),
+            description: "Cats <and> mice".to_string(),
+            link: "https://example.com/".to_string(),
+            self_link: "https://example.com/feed.xml".to_string(),
+            updated: time,
+            entries: vec![FeedEntry {
+                title: "Hello".to_string(),
+                link: "https://example.com/p/hello".to_string(),
+                id: "https://example.com/posts/1".to_string(),
+                author: Some("admin".to_string()),
+                categories: vec!["News".to_string()],
+                summary: None,
+                content_html: "<p>Hi\u{1}</p>".to_string(),
+                published: time,
+                updated: time,
+            }],
+        }
+    }
+
+    #[test]
+    fn test_rss_feed() {
+        let xml = sample_feed().to_rss();
+
+        assert!(xml.contains("<title>Tom &amp; Jerry&apos;s blog</title>"));
+        assert!(xml.contains("<description>Cats &lt;and&gt; mice</description>"));
+        assert!(xml.contains("<pubDate>Fri, 2 Jan 2026 03:04:05 +0000</pubDate>"));
+        assert!(xml.contains("<content:encoded>&lt;p&gt;Hi&lt;/p&gt;</content:encoded>"));
+        assert!(xml.contains("<dc:creator>admin</dc:creator>"));
+    }
+
+    #[test]
+    fn test_atom_feed() {
+        let xml = sample_feed().to_atom();
+
+        assert!(xml.contains("<updated>2026-01-02T03:04:05Z</updated>"));
+        assert!(xml.contains(
+            r#"<link rel="self" type="application/atom+xml" href="https://example.com/feed.xml"/>"#
+        ));
+        assert!(xml.contains(r#"<category term="News"/>"#));
+        assert!(xml.contains(r#"<content type="html">&lt;p&gt;Hi&lt;/p&gt;</content>"#));
+        assert!(!xml.contains("<summary>"));
+    }
+}
@@ -0,0 +1,432 @@
+//! Markdown files with YAML or TOML front matter
+//!
+//! The layout used by Hugo and Jekyll: a metadata block between `---` (YAML) or
+//! `+++` (TOML) lines, followed by the Markdown body. Key names of both generators
+//! are accepted when reading (`lastmod` or `last_modified_at`, `summary` or
+//! `description`, ...); files are written with Hugo's names.
+
+use crate::dto::PostStatus;
+use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
+use serde::{Deserialize, Deserializer, Serialize};
+use std::io::{Cursor, Read, Write};
+use std::path::{Component, Path};
+
+type FrontMatterError = Box<dyn std::error::Error + Send + Sync>;
+
+/// Largest Markdown file read from a directory or archive
+pub const MAX_MARKDOWN_FILE_BYTES: u64 = 8 * 1024 * 1024;
+
+/// Syntax of a front matter block
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum FrontMatterFormat {
+    /// Between `---` lines
+    #[default]
+    Yaml,
+    /// Between `+++` lines
+    Toml,
+}
+
+impl FrontMatterFormat {
+    fn delimiter(&self) -> &'static str {
+        match self {
+            FrontMatterFormat::Yaml => "---",
+            FrontMatterFormat::Toml => "+++",
+        }
+    }
+}
+
+/// Post metadata in a front matter block
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct FrontMatter {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub slug: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<PostStatus>,
+    /// Hugo's draft flag
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub draft: Option<bool>,
+    /// Jekyll's inverse of `draft`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub published: Option<bool>,
+    /// Creation date; for generators, the publication date
+    #[serde(
+        default,
+        deserialize_with = "deserialize_date",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub date: Option<DateTime<Utc>>,
+    #[serde(
+        default,
+        alias = "last_modified_at",
+        alias = "updated",
+        deserialize_with = "deserialize_date",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub lastmod: Option<DateTime<Utc>>,
+    /// When a scheduled post goes live
+    #[serde(
+        default,
+        alias = "publishDate",
+        deserialize_with = "deserialize_date",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub publish_at: Option<DateTime<Utc>>,
+    /// Username or email of the author
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub author: Option<String>,
+    /// Category names; posts have one category, so only the first is used
+    #[serde(
+        default,
+        alias = "category",
+        deserialize_with = "deserialize_list",
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    pub categories: Vec<String>,
+    #[serde(
+        default,
+        deserialize_with = "deserialize_list",
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    pub tags: Vec<String>,
+    #[serde(
+        default,
+        alias = "summary",
+        alias = "excerpt",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub description: Option<String>,
+}
+
+impl FrontMatter {
+    /// Status given by `status`, `draft` or `published`, if any
+    pub fn post_status(&self) -> Option<PostStatus> {
+        self.status.or_else(|| {
+            if self.draft == Some(true) || self.published == Some(false) {
+                Some(PostStatus::Draft)
+            } else if self.draft == Some(false) || self.published == Some(true) {
+                Some(PostStatus::Published)
+            } else {
+                None
+            }
+        })
+    }
+}
+
+/// A Markdown file and its path relative to the imported or exported directory
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MarkdownFile {
+    /// Path with `/` separators
+    pub path: String,
+    pub contents: String,
+}
+
+impl MarkdownFile {
+    pub fn is_markdown(path: &str) -> bool {
+        let path = path.to_ascii_lowercase();
+        path.ends_with(".md") || path.ends_with(".markdown")
+    }
+}
+
+/// Split a file into its front matter and Markdown body
+///
+/// Files without a front matter block have empty metadata.
+pub fn parse(text: &str) -> Result<(FrontMatter, String), FrontMatterError> {
+    let text = text.strip_prefix('\u{feff}').unwrap_or(text);
+    let format = if text.starts_with("---") {
+        FrontMatterFormat::Yaml
+    } else if text.starts_with("+++") {
+        FrontMatterFormat::Toml
+    } else {
+        return Ok((FrontMatter::default(), text.trim_end().to_string()));
+    };
+
+    let delimiter = format.delimiter();
+    let mut lines = text.split_inclusive('\n');
+    let start = match lines.next() {
+        Some(line) if line.trim_end() == delimiter => line.len(),
+        _ => return Ok((FrontMatter::default(), text.trim_end().to_string())),
+    };
+    let mut end = start;
+    let body_start = loop {
+        let Some(line) = lines.next() else {
+            return Err(format!("front matter is not closed with {}", delimiter).into());
+        };
+        if line.trim_end() == delimiter {
+            break end + line.len();
+        }
+        end += line.len();
+    };
+    let block = &text[start..end];
+    let body = text[body_start..]
+        .trim_start_matches(['\r', '\n'])
+        .trim_end();
+
+    let value = match format {
+        FrontMatterFormat::Yaml if block.trim().is_empty() => serde_json::Value::Null,
+        FrontMatterFormat::Yaml => serde_yaml::from_str(block)?,
+        FrontMatterFormat::Toml => toml_to_json(toml::from_str::<toml::Value>(block)?),
+    };
+    let front_matter = if value.is_null() {
+        FrontMatter::default()
+    } else {
+        serde_json::from_value(value)?
+    };
+    Ok((front_matter, body.to_string()))
+}
+
+/// Write a front matter block followed by the body
+pub fn render(
+    front_matter: &FrontMatter,
+    body: &str,
+    format: FrontMatterFormat,
+) -> Result<String, FrontMatterError> {
+    let block = match format {
+        FrontMatterFormat::Yaml => serde_yaml::to_string(front_matter)?,
+        FrontMatterFormat::Toml => toml::to_string(front_matter)?,
+    };
+    let delimiter = format.delimiter();
+    Ok(format!(
+        "{delimiter}\n{}\n{delimiter}\n\n{}\n",
+        block.trim_end(),
+        body.trim_end()
+    ))
+}
+
+/// TOML dates have no JSON counterpart; they are turned into their string form
+fn toml_to_json(value: toml::Value) -> serde_json::Value {
+    match value {
+        toml::Value::String(value) => serde_json::Value::String(value),
+        toml::Value::Integer(value) => value.into(),
+        toml::Value::Float(value) => value.into(),
+        toml::Value::Boolean(value) => value.into(),
+        toml::Value::Datetime(value) => serde_json::Value::String(value.to_string()),
+        toml::Value::Array(values) => values.into_iter().map(toml_to_json).collect(),
+        toml::Value::Table(table) => table
+            .into_iter()
+            .map(|(key, value)| (key, toml_to_json(value)))
+            .collect::<serde_json::Map<_, _>>()
+            .into(),
+    }
+}
+
+/// Parse the date formats Hugo and Jekyll accept; dates without an offset are UTC
+pub fn parse_date(value: &str) -> Option<DateTime<Utc>> {
+    let value = value.trim();
+    if let Ok(date) = DateTime::parse_from_rfc3339(value) {
+        return Some(date.with_timezone(&Utc));
+    }
+    for format in [
+        "%Y-%m-%d %H:%M:%S %z",
+        "%Y-%m-%d %H:%M:%S%.f %z",
+        "%Y-%m-%d %H:%M %z",
+    ] {
+        if let Ok(date) = DateTime::parse_from_str(value, format) {
+            return Some(date.with_timezone(&Utc));
+        }
+    }
+    for format in [
+        "%Y-%m-%dT%H:%M:%S%.f",
+        "%Y-%m-%d %H:%M:%S%.f",
+        "%Y-%m-%d %H:%M",
+    ] {
+        if let Ok(date) = NaiveDateTime::parse_from_str(value, format) {
+            return Some(date.and_utc());
+        }
+    }
+    NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .ok()
+        .and_then(|date| date.and_hms_opt(0, 0, 0))
+        .map(|date| date.and_utc())
+}
+
+fn deserialize_date<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<DateTime<Utc>>, D::Error> {
+    let value = Option::<String>::deserialize(deserializer)?;
+    value
+        .filter(|value| !value.trim().is_empty())
+        .map(|value| {
+            parse_date(&value)
+                .ok_or_else(|| serde::de::Error::custom(format!("invalid date: {}", value)))
+        })
+        .transpose()
+}
+
+/// A list, or a string of space-separated items as Jekyll allows for tags and categories
+fn deserialize_list<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<String>, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum List {
+        One(String),
+        Many(Vec<String>),
+    }
+    Ok(match Option::<List>::deserialize(deserializer)? {
+        None => Vec::new(),
+        Some(List::One(value)) => value.split_whitespace().map(str::to_string).collect(),
+        Some(List::Many(values)) => values,
+    })
+}
+
+/// Markdown files in a directory and its subdirectories
+///
+/// Hidden entries (such as `.git`) are skipped, as are other kinds of files.
+pub fn read_dir(dir: &Path) -> Result<Vec<MarkdownFile>, FrontMatterError> {
+    let mut files = Vec::new();
+    let mut pending = vec![dir.to_path_buf()];
+    while let Some(current) = pending.pop() {
+        for entry in std::fs::read_dir(&current)? {
+            let entry = entry?;
+            let path = entry.path();
+            if entry.file_name().to_string_lossy().starts_with('.') {
+                continue;
+            }
+            let file_type = entry.file_type()?;
+            if file_type.is_dir() {
+                pending.push(path);
+                continue;
+            }
+            let relative = path
+                .strip_prefix(dir)?
+                .components()
+                .map(|component| component.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/");
+            if !file_type.is_file() || !MarkdownFile::is_markdown(&relative) {
+                continue;
+            }
+            if entry.metadata()?.len() > MAX_MARKDOWN_FILE_BYTES {
+                return Err(format!("{} is too large", relative).into());
+            }
+            files.push(MarkdownFile {
+                path: relative,
+                contents: std::fs::read_to_string(&path)?,
+            });
+        }
+    }
+    files.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(files)
+}
+
+/// Markdown files in a zip archive
+pub fn read_zip(data: &[u8]) -> Result<Vec<MarkdownFile>, FrontMatterError> {
+    let mut archive = zip::ZipArchive::new(Cursor::new(data))?;
+    let mut files = Vec::new();
+    for index in 0..archive.len() {
+        let entry = archive.by_index(index)?;
+        let Some(path) = entry.enclosed_name().map(Path::to_path_buf) else {
+            continue;
+        };
+        let path = path
+            .components()
+            .filter_map(|component| match component {
+                Component::Normal(part) => Some(part.to_string_lossy()),
+                _ => None,
+            })
+            .collect::<Vec<_>>()
+            .join("/");
+        if entry.is_dir()
+            || !MarkdownFile::is_markdown(&path)
+            || path.split('/').any(|part| part.starts_with('.'))
+        {
+            continue;
+        }
+        if entry.size() > MAX_MARKDOWN_FILE_BYTES {
+            return Err(format!("{} is too large", path).into());
+        }
+        let mut contents = String::new();
+        entry
+            .take(MAX_MARKDOWN_FILE_BYTES)
+            .read_to_string(&mut contents)?;
+        files.push(MarkdownFile { path, contents });
+    }
+    files.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(files)
+}
+
+/// Write files below a directory, creating subdirectories as needed
+pub fn write_dir(dir: &Path, files: &[MarkdownFile]) -> Result<(), FrontMatterError> {
+    for file in files {
+        let path = dir.join(&file.path);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, &file.contents)?;
+    }
+    Ok(())
+}
+
+/// Pack files into a zip archive
+pub fn write_zip(files: &[MarkdownFile]) -> Result<Vec<u8>, FrontMatterError> {
+    let mut writer = zip::ZipWriter::new(Cursor::new(Vec::new()));
+    let options =
+        zip::write::FileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+    for file in files {
+        writer.start_file(file.path.as_str(), options)?;
+        writer.write_all(file.contents.as_bytes())?;
+    }
+    Ok(writer.finish()?.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_yaml() {
+        let text = "---\ntitle: \"Hello: World\"\ndate: 2024-03-01 10:30:00 +0100\n\
+                    categories: rust web\ntags: [a, b]\ndraft: true\nsummary: Short\n---\n\n# Body\n";
+        let (front_matter, body) = parse(text).unwrap();
+        assert_eq!(front_matter.title.as_deref(), Some("Hello: World"));
+        assert_eq!(
+            front_matter.date,
+            Some(parse_date("2024-03-01T09:30:00Z").unwrap())
+        );
+        assert_eq!(front_matter.categories, vec!["rust", "web"]);
+        assert_eq!(front_matter.tags, vec!["a", "b"]);
+        assert_eq!(front_matter.description.as_deref(), Some("Short"));
+        assert_eq!(front_matter.post_status(), Some(PostStatus::Draft));
+        assert_eq!(body, "# Body");
+    }
+
+    #[test]
+    fn test_parse_toml() {
+        let text = "+++\r\ntitle = \"Hello\"\r\ndate = 2024-03-01\r\nlastmod = 2024-03-02T08:00:00Z\r\n\
+                    category = \"Rust\"\r\n+++\r\nBody +++ text\r\n";
+        let (front_matter, body) = parse(text).unwrap();
+        assert_eq!(front_matter.title.as_deref(), Some("Hello"));
+        assert_eq!(front_matter.date, parse_date("2024-03-01"));
+        assert_eq!(front_matter.lastmod, parse_date("2024-03-02 08:00:00"));
+        assert_eq!(front_matter.categories, vec!["Rust"]);
+        assert_eq!(front_matter.post_status(), None);
+        assert_eq!(body, "Body +++ text");
+    }
+
+    #[test]
+    fn test_parse_without_front_matter() {
+        let (front_matter, body) = parse("---- not front matter\n").unwrap();
+        assert_eq!(front_matter, FrontMatter::default());
+        assert_eq!(body, "---- not front matter");
+        assert!(parse("---\ntitle: x\n").is_err());
+    }
+
+    #[test]
+    fn test_render_round_trip() {
+        let front_matter = FrontMatter {
+            title: Some("Hello".to_string()),
+            slug: Some("hello".to_string()),
+            status: Some(PostStatus::Published),
+            date: parse_date("2024-03-01T10:00:00Z"),
+            tags: vec!["a b".to_string()],
+            ..Default::default()
+        };
+        for format in [FrontMatterFormat::Yaml, FrontMatterFormat::Toml] {
+            let text = render(&front_matter, "Body\n---\nmore", format).unwrap();
+            assert_eq!(
+                parse(&text).unwrap(),
+                (front_matter.clone(), "Body\n---\nmore".to_string())
+            );
+        }
+    }
+}
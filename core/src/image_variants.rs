@@ -0,0 +1,446 @@
+//! Responsive variants and thumbnails of uploaded images
+//!
+//! JPEG, PNG and WebP uploads are decoded and scaled down to each configured
+//! width (`image_variant_widths`) and to a square thumbnail
+//! (`image_thumbnail_size`), in their own format and, for the widths, optionally
+//! as WebP and AVIF too (`image_variant_formats`). GIFs are left alone so that
+//! animations survive. The pixel count read from the header is checked against
+//! `image_max_megapixels` before anything is decoded, so a small file can't make
+//! the server allocate gigabytes. Encoding is CPU-bound: call [`generate`] from
+//! `spawn_blocking`.
+
+use crate::api::admin_api::settings_helper::get_setting_value;
+use crate::dto::MediaVariantKind;
+use image::codecs::avif::AvifEncoder;
+use image::codecs::jpeg::JpegEncoder;
+use image::codecs::png::PngEncoder;
+use image::imageops::FilterType;
+use image::{DynamicImage, ImageDecoder, ImageFormat, ImageReader, Limits};
+use sea_orm::DatabaseConnection;
+use std::io::Cursor;
+use std::sync::Arc;
+
+/// Widths generated when `image_variant_widths` is not set
+pub const DEFAULT_WIDTHS: &[u32] = &[480, 768, 1024, 1600];
+
+/// Thumbnail edge length when `image_thumbnail_size` is not set
+pub const DEFAULT_THUMBNAIL_SIZE: u32 = 150;
+
+/// Largest accepted image when `image_max_megapixels` is not set
+pub const DEFAULT_MAX_MEGAPIXELS: u64 = 50;
+
+/// Bounds of a configured width or thumbnail size
+pub const MIN_WIDTH: u32 = 16;
+pub const MAX_WIDTH: u32 = 4096;
+
+/// Bound of `image_max_megapixels`
+pub const MAX_MEGAPIXELS: u64 = 500;
+
+const JPEG_QUALITY: u8 = 82;
+const WEBP_QUALITY: f32 = 80.0;
+const AVIF_QUALITY: u8 = 70;
+/// rav1e speed preset (1-10); the slow presets take seconds per megapixel
+const AVIF_SPEED: u8 = 8;
+
+/// Format that variants are generated in besides the image's own
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExtraFormat {
+    WebP,
+    Avif,
+}
+
+impl ExtraFormat {
+    pub fn mime_type(&self) -> &'static str {
+        match self {
+            ExtraFormat::WebP => "image/webp",
+            ExtraFormat::Avif => "image/avif",
+        }
+    }
+
+    /// Name used in the `image_variant_formats` setting
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ExtraFormat::WebP => "webp",
+            ExtraFormat::Avif => "avif",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "webp" => Some(ExtraFormat::WebP),
+            "avif" => Some(ExtraFormat::Avif),
+            _ => None,
+        }
+    }
+}
+
+/// What to generate for an uploaded image
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VariantSettings {
+    /// Target widths, ascending; only those narrower than the image are generated
+    pub widths: Vec<u32>,
+    /// Edge length of the square thumbnail; 0 disables it
+    pub thumbnail_size: u32,
+    pub formats: Vec<ExtraFormat>,
+    pub max_pixels: u64,
+}
+
+impl Default for VariantSettings {
+    fn default() -> Self {
+        Self {
+            widths: DEFAULT_WIDTHS.to_vec(),
+            thumbnail_size: DEFAULT_THUMBNAIL_SIZE,
+            formats: Vec::new(),
+            max_pixels: DEFAULT_MAX_MEGAPIXELS * 1_000_000,
+        }
+    }
+}
+
+impl VariantSettings {
+    /// Read the settings; values that don't parse fall back to the defaults
+    pub async fn load(db: &Arc<DatabaseConnection>) -> Result<Self, sea_orm::DbErr> {
+        let defaults = Self::default();
+        let widths = get_setting_value(db, "image_variant_widths")
+            .await?
+            .and_then(|value| parse_widths(&value))
+            .unwrap_or(defaults.widths);
+        let thumbnail_size = get_setting_value(db, "image_thumbnail_size")
+            .await?
+            .and_then(|value| parse_thumbnail_size(&value))
+            .unwrap_or(defaults.thumbnail_size);
+        let formats = get_setting_value(db, "image_variant_formats")
+            .await?
+            .and_then(|value| parse_formats(&value))
+            .unwrap_or(defaults.formats);
+        let max_pixels = get_setting_value(db, "image_max_megapixels")
+            .await?
+            .and_then(|value| parse_max_megapixels(&value))
+            .map(|megapixels| megapixels * 1_000_000)
+            .unwrap_or(defaults.max_pixels);
+        Ok(Self {
+            widths,
+            thumbnail_size,
+            formats,
+            max_pixels,
+        })
+    }
+}
+
+/// Parse a comma-separated list of widths, sorted and without duplicates;
+/// an empty list disables responsive variants
+pub fn parse_widths(value: &str) -> Option<Vec<u32>> {
+    let mut widths = value
+        .split(',')
+        .map(str::trim)
+        .filter(|width| !width.is_empty())
+        .map(|width| {
+            width
+                .parse::<u32>()
+                .ok()
+                .filter(|width| (MIN_WIDTH..=MAX_WIDTH).contains(width))
+        })
+        .collect::<Option<Vec<u32>>>()?;
+    widths.sort_unstable();
+    widths.dedup();
+    Some(widths)
+}
+
+/// Parse a thumbnail size; 0 disables thumbnails
+pub fn parse_thumbnail_size(value: &str) -> Option<u32> {
+    value
+        .trim()
+        .parse::<u32>()
+        .ok()
+        .filter(|size| *size == 0 || (MIN_WIDTH..=MAX_WIDTH).contains(size))
+}
+
+/// Parse a comma-separated list of extra formats (`webp`, `avif`)
+pub fn parse_formats(value: &str) -> Option<Vec<ExtraFormat>> {
+    let mut formats = Vec::new();
+    for format in value.split(',').filter(|format| !format.trim().is_empty()) {
+        let format = ExtraFormat::parse(format)?;
+        if !formats.contains(&format) {
+            formats.push(format);
+        }
+    }
+    Some(formats)
+}
+
+pub fn parse_max_megapixels(value: &str) -> Option<u64> {
+    value
+        .trim()
+        .parse::<u64>()
+        .ok()
+        .filter(|megapixels| (1..=MAX_MEGAPIXELS).contains(megapixels))
+}
+
+/// Whether variants are generated for images of this MIME type
+pub fn is_supported(mime_type: &str) -> bool {
+    image_format(mime_type).is_some()
+}
+
+fn image_format(mime_type: &str) -> Option<ImageFormat> {
+    match mime_type {
+        "image/jpeg" | "image/jpg" => Some(ImageFormat::Jpeg),
+        "image/png" => Some(ImageFormat::Png),
+        "image/webp" => Some(ImageFormat::WebP),
+        _ => None,
+    }
+}
+
+/// A generated variant, ready to be stored
+#[derive(Debug, Clone)]
+pub struct EncodedVariant {
+    pub kind: MediaVariantKind,
+    pub mime_type: &'static str,
+    pub width: u32,
+    pub height: u32,
+    pub data: Vec<u8>,
+}
+
+impl EncodedVariant {
+    /// File name to hand to the storage backend, for its extension
+    pub fn file_name(&self) -> String {
+        let extension = match self.mime_type {
+            "image/jpeg" => "jpg",
+            "image/png" => "png",
+            "image/webp" => "webp",
+            _ => "avif",
+        };
+        format!("{}-{}.{}", self.kind.as_str(), self.width, extension)
+    }
+}
+
+/// The upload can't be turned into variants
+#[derive(Debug, thiserror::Error)]
+pub enum VariantError {
+    /// More pixels than `image_max_megapixels` allows
+    #[error("Image of {width}x{height} pixels exceeds the limit of {max_pixels} pixels")]
+    TooLarge {
+        width: u32,
+        height: u32,
+        max_pixels: u64,
+    },
+    /// Not a readable image of the claimed type
+    #[error("Failed to decode image: {0}")]
+    Decode(#[from] image::ImageError),
+    #[error("Failed to encode {mime_type} variant: {message}")]
+    Encode {
+        mime_type: &'static str,
+        message: String,
+    },
+}
+
+/// Decode an image and generate its variants; an image of an unsupported type
+/// has none
+pub fn generate(
+    data: &[u8],
+    mime_type: &str,
+    settings: &VariantSettings,
+) -> Result<Vec<EncodedVariant>, VariantError> {
+    let Some(format) = image_format(mime_type) else {
+        return Ok(Vec::new());
+    };
+
+    let (width, height) = ImageReader::with_format(Cursor::new(data), format).into_dimensions()?;
+    if u64::from(width) * u64::from(height) > settings.max_pixels {
+        return Err(VariantError::TooLarge {
+            width,
+            height,
+            max_pixels: settings.max_pixels,
+        });
+    }
+
+    // The header could lie about the size; the limits hold the decoder to it
+    let mut reader = ImageReader::with_format(Cursor::new(data), format);
+    let mut limits = Limits::default();
+    limits.max_image_width = Some(width);
+    limits.max_image_height = Some(height);
+    reader.limits(limits);
+    let mut decoder = reader.into_decoder()?;
+    let orientation = decoder.orientation()?;
+    let mut image = DynamicImage::from_decoder(decoder)?;
+    // Browsers rotate the original by its EXIF orientation; the copies carry no EXIF
+    image.apply_orientation(orientation);
+
+    let own_mime_type = match format {
+        ImageFormat::Png => "image/png",
+        ImageFormat::WebP => "image/webp",
+        _ => "image/jpeg",
+    };
+    let mut mime_types = vec![own_mime_type];
+    for format in &settings.formats {
+        if !mime_types.contains(&format.mime_type()) {
+            mime_types.push(format.mime_type());
+        }
+    }
+
+    let mut variants = Vec::new();
+    // Widest first, each scaled from the previous one to keep large photos cheap
+    let mut scaled_images: Vec<DynamicImage> = Vec::new();
+    for &target in settings.widths.iter().rev() {
+        if target >= image.width() {
+            continue;
+        }
+        let source = scaled_images.last().unwrap_or(&image);
+        let target_height = scaled_height(image.width(), image.height(), target);
+        let scaled = source.resize_exact(target, target_height, FilterType::Lanczos3);
+        scaled_images.push(scaled);
+    }
+    for scaled in scaled_images.iter().rev() {
+        for &mime_type in &mime_types {
+            variants.push(EncodedVariant {
+                kind: MediaVariantKind::Responsive,
+                mime_type,
+                width: scaled.width(),
+                height: scaled.height(),
+                data: encode(scaled, mime_type)?,
+            });
+        }
+    }
+
+    let size = settings.thumbnail_size;
+    if size > 0 && (image.width() > size || image.height() > size) {
+        let thumbnail = image.resize_to_fill(size, size, FilterType::Lanczos3);
+        variants.push(EncodedVariant {
+            kind: MediaVariantKind::Thumbnail,
+            mime_type: own_mime_type,
+            width: thumbnail.width(),
+            height: thumbnail.height(),
+            data: encode(&thumbnail, own_mime_type)?,
+        });
+    }
+
+    Ok(variants)
+}
+
+/// Height of an image scaled to `target_width`, keeping the aspect ratio
+fn scaled_height(width: u32, height: u32, target_width: u32) -> u32 {
+    let scaled =
+        (u64::from(height) * u64::from(target_width) + u64::from(width) / 2) / u64::from(width);
+    scaled.clamp(1, u64::from(u32::MAX)) as u32
+}
+
+fn encode(image: &DynamicImage, mime_type: &'static str) -> Result<Vec<u8>, VariantError> {
+    let encode_error = |message: String| VariantError::Encode { mime_type, message };
+    let mut data = Vec::new();
+    match mime_type {
+        "image/png" => image
+            .write_with_encoder(PngEncoder::new(&mut data))
+            .map_err(|e| encode_error(e.to_string()))?,
+        "image/webp" => {
+            // image only writes lossless WebP, which is larger than the JPEG it replaces
+            let rgba = image.to_rgba8();
+            let encoded = webp::Encoder::from_rgba(rgba.as_raw(), rgba.width(), rgba.height())
+                .encode_simple(false, WEBP_QUALITY)
+                .map_err(|e| encode_error(format!("{:?}", e)))?;
+            data.extend_from_slice(&encoded);
+        }
+        "image/avif" => image
+            .write_with_encoder(AvifEncoder::new_with_speed_quality(
+                &mut data,
+                AVIF_SPEED,
+                AVIF_QUALITY,
+            ))
+            .map_err(|e| encode_error(e.to_string()))?,
+        // JPEG has no alpha channel
+        _ => DynamicImage::ImageRgb8(image.to_rgb8())
+            .write_with_encoder(JpegEncoder::new_with_quality(&mut data, JPEG_QUALITY))
+            .map_err(|e| encode_error(e.to_string()))?,
+    }
+    Ok(data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{Rgb, RgbImage};
+
+    fn jpeg(width: u32, height: u32) -> Vec<u8> {
+        let image = RgbImage::from_fn(width, height, |x, y| Rgb([x as u8, y as u8, 128]));
+        let mut data = Vec::new();
+        DynamicImage::ImageRgb8(image)
+            .write_with_encoder(JpegEncoder::new_with_quality(&mut data, 90))
+            .unwrap();
+        data
+    }
+
+    #[test]
+    fn test_parse_settings() {
+        assert_eq!(parse_widths("1024, 480,480"), Some(vec![480, 1024]));
+        assert_eq!(parse_widths(""), Some(vec![]));
+        assert_eq!(parse_widths("480,wide"), None);
+        assert_eq!(parse_widths("8"), None);
+        assert_eq!(
+            parse_formats("AVIF, webp"),
+            Some(vec![ExtraFormat::Avif, ExtraFormat::WebP])
+        );
+        assert_eq!(parse_formats("gif"), None);
+        assert_eq!(parse_thumbnail_size("0"), Some(0));
+        assert_eq!(parse_max_megapixels("0"), None);
+    }
+
+    #[test]
+    fn test_generate_scales_down_only() {
+        let settings = VariantSettings {
+            widths: vec![100, 200, 800],
+            thumbnail_size: 50,
+            formats: vec![ExtraFormat::WebP],
+            max_pixels: 1_000_000,
+        };
+        let variants = generate(&jpeg(400, 200), "image/jpeg", &settings).unwrap();
+
+        let summary: Vec<_> = variants
+            .iter()
+            .map(|variant| {
+                (
+                    variant.kind,
+                    variant.mime_type,
+                    variant.width,
+                    variant.height,
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (MediaVariantKind::Responsive, "image/jpeg", 100, 50),
+                (MediaVariantKind::Responsive, "image/webp", 100, 50),
+                (MediaVariantKind::Responsive, "image/jpeg", 200, 100),
+                (MediaVariantKind::Responsive, "image/webp", 200, 100),
+                (MediaVariantKind::Thumbnail, "image/jpeg", 50, 50),
+            ]
+        );
+        assert!(variants[1].data.starts_with(b"RIFF"));
+    }
+
+    #[test]
+    fn test_generate_rejects_too_many_pixels() {
+        let settings = VariantSettings {
+            max_pixels: 10_000,
+            ..VariantSettings::default()
+        };
+        assert!(matches!(
+            generate(&jpeg(200, 100), "image/jpeg", &settings),
+            Err(VariantError::TooLarge {
+                width: 200,
+                height: 100,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_generate_skips_unsupported_types() {
+        let settings = VariantSettings::default();
+        assert!(
+            generate(b"GIF89a", "image/gif", &settings)
+                .unwrap()
+                .is_empty()
+        );
+        assert!(matches!(
+            generate(b"not a jpeg", "image/jpeg", &settings),
+            Err(VariantError::Decode(_))
+        ));
+    }
+}
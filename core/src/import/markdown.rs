@@ -0,0 +1,383 @@
+//! Import of Markdown files with front matter (Hugo and Jekyll content)
+//!
+//! Each file is one post, identified by its slug: the `slug` key, or the file name
+//! without Jekyll's date prefix (the directory name for Hugo's `index.md` page
+//! bundles). Posts that already exist are updated to match their file, keeping the
+//! replaced content as a version, so a directory kept in git can be imported again
+//! after every change. The counterpart is [`crate::export::markdown`].
+
+use crate::dto::{
+    CreateCategoryRequest, ImportKey, ImportPostRequest, ImportReport, Post, PostStatus,
+};
+use crate::front_matter::{self, FrontMatter, MarkdownFile};
+use crate::repository::{PostRepository, UserRepository};
+use crate::slug::post_slug;
+use chrono::{DateTime, NaiveDate, Utc};
+use std::collections::HashMap;
+
+type ImportError = Box<dyn std::error::Error + Send + Sync>;
+
+/// Source of the import records of Markdown posts
+const SOURCE: &str = "markdown";
+
+/// Change note of the version kept when an import updates a post
+const CHANGE_NOTE: &str = "Imported from Markdown";
+
+/// How to import Markdown files
+#[derive(Debug, Clone)]
+pub struct MarkdownImportOptions {
+    /// Author of new posts whose `author` is missing or unknown
+    pub fallback_author_id: i64,
+    /// Only report what would be imported
+    pub dry_run: bool,
+}
+
+/// Import Markdown files, creating missing posts and updating changed ones
+///
+/// Authors are matched by username or email; categories by slug or name, and
+/// created when missing. Files that cannot be read are skipped with a warning.
+pub async fn import<PR: PostRepository, UR: UserRepository>(
+    post_repository: &PR,
+    user_repository: &UR,
+    files: &[MarkdownFile],
+    options: &MarkdownImportOptions,
+) -> Result<ImportReport, ImportError> {
+    let mut importer = Importer {
+        post_repository,
+        user_repository,
+        options,
+        authors: HashMap::new(),
+        categories: HashMap::new(),
+        report: ImportReport {
+            dry_run: options.dry_run,
+            ..Default::default()
+        },
+    };
+    for file in files {
+        importer.import_file(file).await?;
+    }
+    Ok(importer.report)
+}
+
+struct Importer<'a, PR, UR> {
+    post_repository: &'a PR,
+    user_repository: &'a UR,
+    options: &'a MarkdownImportOptions,
+    /// User ids by the `author` values seen so far
+    authors: HashMap<String, Option<i64>>,
+    /// Category ids by name; categories that a dry run would create have none
+    categories: HashMap<String, Option<i64>>,
+    report: ImportReport,
+}
+
+impl<PR: PostRepository, UR: UserRepository> Importer<'_, PR, UR> {
+    async fn import_file(&mut self, file: &MarkdownFile) -> Result<(), ImportError> {
+        let Some((name, file_date)) = file_name(&file.path) else {
+            self.report.skipped += 1;
+            return Ok(());
+        };
+        let (front_matter, body) = match front_matter::parse(&file.contents) {
+            Ok(parsed) => parsed,
+            Err(e) => {
+                self.report.skipped += 1;
+                self.report.warnings.push(format!("{}: {}", file.path, e));
+                return Ok(());
+            }
+        };
+        let Some(title) = front_matter
+            .title
+            .clone()
+            .filter(|title| !title.trim().is_empty())
+        else {
+            self.report.skipped += 1;
+            self.report
+                .warnings
+                .push(format!("{}: no title in front matter", file.path));
+            return Ok(());
+        };
+        let slug = post_slug(front_matter.slug.as_deref().unwrap_or(&name));
+
+        let key = ImportKey {
+            source: SOURCE.to_string(),
+            kind: "post".to_string(),
+            source_id: slug.clone(),
+        };
+        let existing = match self.post_repository.find_import_record(&key).await? {
+            Some(record) => {
+                let post = match record.target_id {
+                    Some(id) => self.post_repository.find_by_id(&id).await?,
+                    None => None,
+                };
+                match post {
+                    Some(post) if post.deleted_at.is_none() => Some(post),
+                    _ => {
+                        self.report.skipped += 1;
+                        self.report.warnings.push(format!(
+                            "{}: the post imported from it has been deleted",
+                            file.path
+                        ));
+                        return Ok(());
+                    }
+                }
+            }
+            None => self.post_repository.find_by_slug(&slug).await?,
+        };
+
+        let author_id = match &front_matter.author {
+            Some(author) => self.author_id(&file.path, author).await?,
+            None => None,
+        };
+        let category_id = match front_matter.categories.first() {
+            Some(name) => self.category_id(name).await?,
+            None => None,
+        };
+        let mut tags = front_matter.tags.clone();
+        tags.sort();
+        tags.dedup();
+        let description = front_matter
+            .description
+            .clone()
+            .filter(|description| !description.trim().is_empty());
+
+        match existing {
+            Some(post) => {
+                self.update_post(
+                    file,
+                    post,
+                    &front_matter,
+                    Post {
+                        title,
+                        slug,
+                        content: body,
+                        category_id,
+                        description,
+                        tags,
+                        ..Default::default()
+                    },
+                    author_id,
+                )
+                .await
+            }
+            None => {
+                let Some((status, publish_at)) =
+                    self.status(file, &front_matter, PostStatus::Published)
+                else {
+                    return Ok(());
+                };
+                // Posts dated in the future are scheduled, but created now
+                let now = Utc::now();
+                let created_at = front_matter
+                    .date
+                    .or(file_date)
+                    .filter(|date| *date <= now)
+                    .unwrap_or(now);
+                self.report.posts.created += 1;
+                if self.options.dry_run {
+                    return Ok(());
+                }
+                let request = ImportPostRequest {
+                    key,
+                    title,
+                    slug: Some(slug),
+                    status,
+                    publish_at,
+                    content: body,
+                    category_id,
+                    author_id: author_id.unwrap_or(self.options.fallback_author_id),
+                    description,
+                    tags,
+                    created_at,
+                    updated_at: front_matter.lastmod.unwrap_or(created_at),
+                    versions: Vec::new(),
+                    draft: None,
+                };
+                self.post_repository.import_post(request).await?;
+                Ok(())
+            }
+        }
+    }
+
+    /// Bring an existing post in line with its file; `changes` holds the values
+    /// read from the file
+    async fn update_post(
+        &mut self,
+        file: &MarkdownFile,
+        post: Post,
+        front_matter: &FrontMatter,
+        changes: Post,
+        author_id: Option<i64>,
+    ) -> Result<(), ImportError> {
+        let Some((status, publish_at)) = self.status(file, front_matter, post.status) else {
+            return Ok(());
+        };
+        let mut updated = post.clone();
+        if changes.slug != post.slug {
+            if self
+                .post_repository
+                .is_slug_taken(&changes.slug, Some(post.id))
+                .await?
+            {
+                self.report.warnings.push(format!(
+                    "{}: slug {} is used by another post; keeping {}",
+                    file.path, changes.slug, post.slug
+                ));
+            } else {
+                updated.slug = changes.slug;
+            }
+        }
+        updated.title = changes.title;
+        updated.content = changes.content;
+        updated.category_id = changes.category_id;
+        updated.description = changes.description;
+        updated.tags = changes.tags;
+        updated.author_id = author_id.unwrap_or(post.author_id);
+        updated.status = status;
+        if status == PostStatus::Scheduled {
+            updated.publish_at = publish_at.map(Into::into);
+        }
+
+        let unchanged = updated.slug == post.slug
+            && updated.title == post.title
+            && updated.content.trim_end() == post.content.trim_end()
+            && updated.category_id == post.category_id
+            && updated.description == post.description
+            && updated.tags == post.tags
+            && updated.author_id == post.author_id
+            && updated.status == post.status
+            && updated.publish_at == post.publish_at;
+        if unchanged {
+            self.report.posts.existing += 1;
+            return Ok(());
+        }
+
+        self.report.posts.updated += 1;
+        self.report.versions += 1;
+        if self.options.dry_run {
+            return Ok(());
+        }
+        let id = post.id;
+        self.post_repository
+            .update(
+                &id,
+                updated,
+                true,
+                Some(CHANGE_NOTE.to_string()),
+                self.options.fallback_author_id,
+            )
+            .await?;
+        Ok(())
+    }
+
+    /// Status and publication time from the front matter, `default` when it has
+    /// none; `None` (with a warning) for scheduled posts without a date
+    ///
+    /// Like static site generators, a published post dated in the future is
+    /// scheduled for that date.
+    fn status(
+        &mut self,
+        file: &MarkdownFile,
+        front_matter: &FrontMatter,
+        default: PostStatus,
+    ) -> Option<(PostStatus, Option<DateTime<Utc>>)> {
+        let mut status = front_matter.post_status().unwrap_or(default);
+        let mut publish_at = front_matter.publish_at;
+        if front_matter.status.is_none()
+            && status == PostStatus::Published
+            && let Some(date) = front_matter.date.filter(|date| *date > Utc::now())
+        {
+            status = PostStatus::Scheduled;
+            publish_at.get_or_insert(date);
+        }
+        if status == PostStatus::Scheduled {
+            publish_at = publish_at.or(front_matter.date);
+            if publish_at.is_none() {
+                self.report.skipped += 1;
+                self.report.warnings.push(format!(
+                    "{}: scheduled post without publish_at or date",
+                    file.path
+                ));
+                return None;
+            }
+        }
+        Some((status, publish_at))
+    }
+
+    /// Id of the user named by an `author` value (username or email)
+    async fn author_id(&mut self, path: &str, author: &str) -> Result<Option<i64>, ImportError> {
+        if let Some(id) = self.authors.get(author) {
+            return Ok(*id);
+        }
+        let user = match self.user_repository.find_by_username(author).await? {
+            Some(user) => Some(user),
+            None => self.user_repository.find_by_email(author).await?,
+        };
+        if user.is_none() {
+            self.report.warnings.push(format!(
+                "{}: unknown author {}; new posts are assigned to the importing user",
+                path, author
+            ));
+        }
+        let id = user.map(|user| user.id);
+        self.authors.insert(author.to_string(), id);
+        Ok(id)
+    }
+
+    async fn category_id(&mut self, name: &str) -> Result<Option<i64>, ImportError> {
+        if let Some(id) = self.categories.get(name) {
+            return Ok(*id);
+        }
+        let id = match self
+            .post_repository
+            .find_category_by_name_or_slug(name)
+            .await?
+        {
+            Some(category) => {
+                self.report.categories.existing += 1;
+                Some(category.id)
+            }
+            None if self.options.dry_run => {
+                self.report.categories.created += 1;
+                None
+            }
+            None => {
+                self.report.categories.created += 1;
+                let category = self
+                    .post_repository
+                    .create_category(CreateCategoryRequest {
+                        name: name.trim().to_string(),
+                        slug: None,
+                        description: None,
+                        parent_id: None,
+                    })
+                    .await?;
+                Some(category.id)
+            }
+        };
+        self.categories.insert(name.to_string(), id);
+        Ok(id)
+    }
+}
+
+/// Name a post is known by when its front matter has no slug, and the date from
+/// Jekyll's `YYYY-MM-DD-name.md` file names; `None` for Hugo's section pages
+fn file_name(path: &str) -> Option<(String, Option<DateTime<Utc>>)> {
+    let mut parts = path.rsplit('/');
+    let file = parts.next()?;
+    let stem = file.rsplit_once('.').map_or(file, |(stem, _)| stem);
+    let name = match stem {
+        "_index" => return None,
+        "index" => parts.next().unwrap_or(stem),
+        _ => stem,
+    };
+    if let Some((date, rest)) = name.get(..10).zip(name.get(11..))
+        && name.as_bytes()[10] == b'-'
+        && !rest.is_empty()
+        && let Ok(date) = NaiveDate::parse_from_str(date, "%Y-%m-%d")
+    {
+        return Some((
+            rest.to_string(),
+            date.and_hms_opt(0, 0, 0).map(|date| date.and_utc()),
+        ));
+    }
+    Some((name.to_string(), None))
+}
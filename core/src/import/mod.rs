@@ -0,0 +1,29 @@
+//! Importing content from other blogging systems
+//!
+//! Importers record what they create in `import_records`, keyed by the source and
+//! the id of each item there, so that running an import again only adds items that
+//! are still missing.
+
+pub mod markdown;
+pub mod wxr;
+
+/// Decode `%XX` escapes (as in WordPress slugs of non-ASCII titles)
+pub(crate) fn percent_decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%'
+            && let Some(byte) = value
+                .get(i + 1..i + 3)
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+        {
+            decoded.push(byte);
+            i += 3;
+            continue;
+        }
+        decoded.push(bytes[i]);
+        i += 1;
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
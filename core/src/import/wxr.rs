@@ -0,0 +1,992 @@
+//! WordPress eXtended RSS (WXR) import
+//!
+//! Reads the file written by WordPress' "Tools → Export" and imports its authors,
+//! categories, posts (with their revisions and autosaves) and attachments.
+//! Attachment files are read from a copy of the site's `wp-content/uploads`
+//! directory, stored through the storage backend and recorded in the media
+//! library; links to them in post content are rewritten to the new URLs.
+
+use super::percent_decode;
+use crate::dto::{
+    CreateCategoryRequest, CreateUserRequest, ImportKey, ImportPostDraft, ImportPostRequest,
+    ImportPostVersion, ImportRecord, ImportReport, PostStatus, UserRole,
+};
+use crate::media::FileNaming;
+use crate::repository::{MediaRepository, PostRepository, UserRepository};
+use crate::storage::StorageBackend;
+use crate::upload_policy::{CheckedType, UploadPolicy, UploadRejection};
+use chrono::{DateTime, NaiveDateTime, Utc};
+use lazy_static::lazy_static;
+use regex::{Captures, Regex};
+use roxmltree::Node;
+use std::collections::{HashMap, HashSet};
+use std::path::{Component, Path, PathBuf};
+
+type ImportError = Box<dyn std::error::Error + Send + Sync>;
+
+/// Namespaces of WXR elements; the version suffix (`1.0/`, `1.1/`, `1.2/`) varies
+const WP_NAMESPACE: &str = "http://wordpress.org/export/";
+const CONTENT_NAMESPACE: &str = "http://purl.org/rss/1.0/modules/content/";
+const DC_NAMESPACE: &str = "http://purl.org/dc/elements/1.1/";
+
+lazy_static! {
+    /// Link into a WordPress uploads directory, capturing the path below it
+    static ref UPLOAD_URL: Regex = Regex::new(
+        r#"(?:https?:)?//[^\s"'<>()\[\]]+?/wp-content/uploads/([^\s"'<>()\[\]?#]+)"#
+    )
+    .unwrap();
+    /// Size suffix of the scaled copies WordPress generates (`photo-300x200.jpg`)
+    static ref SIZE_SUFFIX: Regex = Regex::new(r"-\d+x\d+(\.[A-Za-z0-9]+)$").unwrap();
+    /// Site directory of multisite uploads (`sites/2/`)
+    static ref SITE_PREFIX: Regex = Regex::new(r"^sites/\d+/").unwrap();
+}
+
+/// Contents of a WXR file
+#[derive(Debug, Clone, Default)]
+pub struct WxrDocument {
+    /// URL of the exported site; identifies the source of imported items
+    pub site_url: String,
+    pub authors: Vec<WxrAuthor>,
+    pub categories: Vec<WxrCategory>,
+    pub items: Vec<WxrItem>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct WxrAuthor {
+    pub login: String,
+    pub email: String,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct WxrCategory {
+    pub slug: String,
+    pub name: String,
+    /// Slug of the parent category
+    pub parent: Option<String>,
+    pub description: Option<String>,
+}
+
+/// Post, page, attachment, revision, ... of the export
+#[derive(Debug, Clone, Default)]
+pub struct WxrItem {
+    pub id: String,
+    pub post_type: String,
+    pub status: String,
+    pub title: String,
+    /// Login of the author
+    pub creator: String,
+    pub content: String,
+    pub excerpt: String,
+    pub slug: String,
+    /// Id of the parent item (the post of a revision), `0` for none
+    pub parent: String,
+    pub date: Option<DateTime<Utc>>,
+    pub modified: Option<DateTime<Utc>>,
+    /// Slugs and names of the categories, the primary one first
+    pub categories: Vec<(String, String)>,
+    /// Tag names
+    pub tags: Vec<String>,
+    pub attachment_url: Option<String>,
+    /// Path of an attachment's file below the uploads directory
+    pub attached_file: Option<String>,
+}
+
+/// Parse a WXR file
+pub fn parse(xml: &str) -> Result<WxrDocument, ImportError> {
+    let document = roxmltree::Document::parse(xml)?;
+    let channel = document
+        .root_element()
+        .children()
+        .find(|node| is(*node, Ns::Rss, "channel"))
+        .ok_or("not a WordPress export: missing <channel>")?;
+
+    let mut wxr = WxrDocument {
+        site_url: child_text(channel, Ns::Wp, "base_blog_url")
+            .or_else(|| child_text(channel, Ns::Wp, "base_site_url"))
+            .or_else(|| child_text(channel, Ns::Rss, "link"))
+            .unwrap_or_default()
+            .trim_end_matches('/')
+            .to_string(),
+        ..Default::default()
+    };
+    if wxr.site_url.is_empty() {
+        return Err("not a WordPress export: missing site URL".into());
+    }
+
+    for node in channel.children().filter(|node| node.is_element()) {
+        if is(node, Ns::Wp, "author") {
+            wxr.authors.push(WxrAuthor {
+                login: child_text(node, Ns::Wp, "author_login").unwrap_or_default(),
+                email: child_text(node, Ns::Wp, "author_email").unwrap_or_default(),
+            });
+        } else if is(node, Ns::Wp, "category") {
+            let slug = child_text(node, Ns::Wp, "category_nicename").unwrap_or_default();
+            wxr.categories.push(WxrCategory {
+                name: child_text(node, Ns::Wp, "cat_name").unwrap_or_else(|| slug.clone()),
+                slug: percent_decode(&slug),
+                parent: child_text(node, Ns::Wp, "category_parent")
+                    .map(|parent| percent_decode(&parent)),
+                description: child_text(node, Ns::Wp, "category_description"),
+            });
+        } else if is(node, Ns::Rss, "item") {
+            wxr.items.push(parse_item(node));
+        }
+    }
+
+    Ok(wxr)
+}
+
+fn parse_item(node: Node) -> WxrItem {
+    let text = |ns, name| child_text(node, ns, name).unwrap_or_default();
+    let date = |gmt, local| {
+        child_text(node, Ns::Wp, gmt)
+            .and_then(|value| parse_date(&value))
+            .or_else(|| child_text(node, Ns::Wp, local).and_then(|value| parse_date(&value)))
+    };
+
+    let mut item = WxrItem {
+        id: text(Ns::Wp, "post_id"),
+        post_type: text(Ns::Wp, "post_type"),
+        status: text(Ns::Wp, "status"),
+        title: text(Ns::Rss, "title"),
+        creator: text(Ns::Dc, "creator"),
+        content: text(Ns::Content, "encoded"),
+        excerpt: text(Ns::Excerpt, "encoded"),
+        slug: percent_decode(&text(Ns::Wp, "post_name")),
+        parent: text(Ns::Wp, "post_parent"),
+        date: date("post_date_gmt", "post_date"),
+        modified: date("post_modified_gmt", "post_modified"),
+        attachment_url: child_text(node, Ns::Wp, "attachment_url"),
+        ..Default::default()
+    };
+
+    for child in node.children().filter(|child| child.is_element()) {
+        if is(child, Ns::Rss, "category") {
+            let name = element_text(child);
+            match child.attribute("domain") {
+                Some("category") => item.categories.push((
+                    percent_decode(child.attribute("nicename").unwrap_or(&name)),
+                    name,
+                )),
+                Some("post_tag") => item.tags.push(name),
+                _ => {}
+            }
+        } else if is(child, Ns::Wp, "postmeta")
+            && child_text(child, Ns::Wp, "meta_key").as_deref() == Some("_wp_attached_file")
+        {
+            item.attached_file = child_text(child, Ns::Wp, "meta_value");
+        }
+    }
+    item
+}
+
+/// Namespace of a WXR element
+#[derive(Clone, Copy)]
+enum Ns {
+    Rss,
+    Wp,
+    Excerpt,
+    Content,
+    Dc,
+}
+
+fn is(node: Node, ns: Ns, name: &str) -> bool {
+    if !node.is_element() || node.tag_name().name() != name {
+        return false;
+    }
+    let uri = node.tag_name().namespace();
+    match ns {
+        Ns::Rss => uri.is_none(),
+        Ns::Wp => {
+            uri.is_some_and(|uri| uri.starts_with(WP_NAMESPACE) && !uri.ends_with("/excerpt/"))
+        }
+        Ns::Excerpt => {
+            uri.is_some_and(|uri| uri.starts_with(WP_NAMESPACE) && uri.ends_with("/excerpt/"))
+        }
+        Ns::Content => uri == Some(CONTENT_NAMESPACE),
+        Ns::Dc => uri == Some(DC_NAMESPACE),
+    }
+}
+
+/// Trimmed text of the first matching child element, if not empty
+fn child_text(node: Node, ns: Ns, name: &str) -> Option<String> {
+    node.children()
+        .find(|child| is(*child, ns, name))
+        .map(element_text)
+        .map(|text| text.trim().to_string())
+        .filter(|text| !text.is_empty())
+}
+
+/// Text of an element, joining text and CDATA sections
+fn element_text(node: Node) -> String {
+    node.children()
+        .filter(|child| child.is_text())
+        .filter_map(|child| child.text())
+        .collect()
+}
+
+/// WordPress dates are `YYYY-MM-DD HH:MM:SS`; unset dates are all zeros
+fn parse_date(value: &str) -> Option<DateTime<Utc>> {
+    NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S")
+        .ok()
+        .map(|date| date.and_utc())
+}
+
+/// How to import a WXR file
+#[derive(Debug, Clone)]
+pub struct WxrImportOptions {
+    /// Copy of the site's `wp-content/uploads` directory; without it attachments are skipped
+    pub uploads_dir: Option<PathBuf>,
+    /// Author of items whose author is not listed in the file
+    pub fallback_author_id: i64,
+    /// Only report what would be imported
+    pub dry_run: bool,
+    /// How attachments are named in storage
+    pub file_naming: FileNaming,
+    /// Which attachments are accepted, as for uploads
+    pub upload_policy: UploadPolicy,
+}
+
+/// Import a parsed WXR file
+///
+/// Users are matched by email and categories by slug (or name); posts and
+/// attachments imported from the same site before are skipped.
+pub async fn import<PR: PostRepository, UR: UserRepository, SB: StorageBackend>(
+    post_repository: &PR,
+    user_repository: &UR,
+    media_repository: &dyn MediaRepository,
+    storage: &SB,
+    document: &WxrDocument,
+    options: &WxrImportOptions,
+) -> Result<ImportReport, ImportError> {
+    let mut importer = Importer {
+        post_repository,
+        user_repository,
+        media_repository,
+        storage,
+        document,
+        options,
+        report: ImportReport {
+            dry_run: options.dry_run,
+            ..Default::default()
+        },
+    };
+
+    let authors = importer.import_authors().await?;
+    let categories = importer.import_categories().await?;
+    let uploads = importer.import_attachments().await?;
+    importer
+        .import_posts(&authors, &categories, &uploads)
+        .await?;
+    Ok(importer.report)
+}
+
+struct Importer<'a, PR, UR, SB> {
+    post_repository: &'a PR,
+    user_repository: &'a UR,
+    media_repository: &'a dyn MediaRepository,
+    storage: &'a SB,
+    document: &'a WxrDocument,
+    options: &'a WxrImportOptions,
+    report: ImportReport,
+}
+
+impl<PR: PostRepository, UR: UserRepository, SB: StorageBackend> Importer<'_, PR, UR, SB> {
+    fn key(&self, kind: &str, source_id: &str) -> ImportKey {
+        ImportKey {
+            source: self.document.site_url.clone(),
+            kind: kind.to_string(),
+            source_id: source_id.to_string(),
+        }
+    }
+
+    /// User ids by login; users that a dry run would create have none
+    async fn import_authors(&mut self) -> Result<HashMap<String, Option<i64>>, ImportError> {
+        let mut authors = HashMap::new();
+        for author in &self.document.authors {
+            if author.email.is_empty() {
+                self.report.warnings.push(format!(
+                    "Author {} has no email; their posts are assigned to the importing user",
+                    author.login
+                ));
+                continue;
+            }
+
+            if let Some(user) = self.user_repository.find_by_email(&author.email).await? {
+                authors.insert(author.login.clone(), Some(user.id));
+                self.report.users.existing += 1;
+                continue;
+            }
+
+            self.report.users.created += 1;
+            if self.options.dry_run {
+                authors.insert(author.login.clone(), None);
+                continue;
+            }
+            let user = self
+                .user_repository
+                .create(CreateUserRequest {
+                    username: self.available_username(&author.login).await?,
+                    email: author.email.clone(),
+                    // Imported users set a password through a reset
+                    password: uuid::Uuid::new_v4().to_string(),
+                    role: UserRole::User,
+                })
+                .await?;
+            authors.insert(author.login.clone(), Some(user.id));
+        }
+        Ok(authors)
+    }
+
+    /// `login`, or `login-2`, `login-3`, ... when it is taken
+    async fn available_username(&self, login: &str) -> Result<String, ImportError> {
+        let base = if login.trim().is_empty() {
+            "author"
+        } else {
+            login.trim()
+        };
+        let mut candidate = base.to_string();
+        let mut n = 1;
+        while self
+            .user_repository
+            .find_by_username(&candidate)
+            .await?
+            .is_some()
+        {
+            n += 1;
+            candidate = format!("{}-{}", base, n);
+        }
+        Ok(candidate)
+    }
+
+    /// Category ids by slug; categories that a dry run would create have none
+    async fn import_categories(&mut self) -> Result<HashMap<String, Option<i64>>, ImportError> {
+        // Categories of posts may be missing from the list of an incomplete export
+        let mut pending: Vec<WxrCategory> = self.document.categories.clone();
+        let mut declared: HashSet<String> = pending
+            .iter()
+            .map(|category| category.slug.clone())
+            .collect();
+        for item in &self.document.items {
+            for (slug, name) in &item.categories {
+                if declared.insert(slug.clone()) {
+                    pending.push(WxrCategory {
+                        slug: slug.clone(),
+                        name: name.clone(),
+                        ..Default::default()
+                    });
+                }
+            }
+        }
+
+        let mut categories: HashMap<String, Option<i64>> = HashMap::new();
+        while !pending.is_empty() {
+            // Parents go first; categories whose parent never shows up are imported at the top level
+            let (ready, waiting): (Vec<_>, Vec<_>) = pending.into_iter().partition(|category| {
+                category.parent.as_ref().is_none_or(|parent| {
+                    categories.contains_key(parent) || !declared.contains(parent)
+                })
+            });
+            let (ready, waiting) = if ready.is_empty() {
+                (waiting, Vec::new())
+            } else {
+                (ready, waiting)
+            };
+            pending = waiting;
+
+            for category in ready {
+                let id = self.import_category(&category, &categories).await?;
+                categories.insert(category.slug.clone(), id);
+            }
+        }
+        Ok(categories)
+    }
+
+    async fn import_category(
+        &mut self,
+        category: &WxrCategory,
+        categories: &HashMap<String, Option<i64>>,
+    ) -> Result<Option<i64>, ImportError> {
+        for key in [&category.slug, &category.name] {
+            if let Some(existing) = self
+                .post_repository
+                .find_category_by_name_or_slug(key)
+                .await?
+            {
+                self.report.categories.existing += 1;
+                return Ok(Some(existing.id));
+            }
+        }
+
+        self.report.categories.created += 1;
+        if self.options.dry_run {
+            return Ok(None);
+        }
+        let parent_id = category
+            .parent
+            .as_ref()
+            .and_then(|parent| categories.get(parent).copied().flatten());
+        let created = self
+            .post_repository
+            .create_category(CreateCategoryRequest {
+                name: category.name.clone(),
+                slug: Some(crate::slug::slugify(&category.slug)).filter(|slug| !slug.is_empty()),
+                description: category.description.clone(),
+                parent_id,
+            })
+            .await?;
+        Ok(Some(created.id))
+    }
+
+    /// New URLs of attachment files, by path below the uploads directory
+    async fn import_attachments(&mut self) -> Result<HashMap<String, String>, ImportError> {
+        let mut uploads = HashMap::new();
+        let attachments: Vec<&WxrItem> = self
+            .document
+            .items
+            .iter()
+            .filter(|item| item.post_type == "attachment")
+            .collect();
+        if !attachments.is_empty() && self.options.uploads_dir.is_none() {
+            self.report.warnings.push(format!(
+                "No uploads directory given; {} attachments are not imported",
+                attachments.len()
+            ));
+        }
+
+        // Attachment paths are checked against the real directory, so symlinks
+        // inside it can't point elsewhere
+        let uploads_root = match &self.options.uploads_dir {
+            Some(dir) => Some(tokio::fs::canonicalize(dir).await?),
+            None => None,
+        };
+
+        for item in attachments {
+            let Some(path) = attachment_path(item) else {
+                self.report
+                    .warnings
+                    .push(format!("Attachment {} has no file", item.id));
+                continue;
+            };
+
+            let key = self.key("attachment", &item.id);
+            if let Some(record) = self.post_repository.find_import_record(&key).await? {
+                if let Some(url) = record.target_url {
+                    uploads.insert(path, url);
+                }
+                self.report.attachments.existing += 1;
+                continue;
+            }
+
+            let Some(uploads_root) = &uploads_root else {
+                continue;
+            };
+            let (file_name, data, content_type) =
+                match read_attachment(uploads_root, &path, &self.options.upload_policy).await? {
+                    AttachmentFile::Accepted {
+                        file_name,
+                        data,
+                        mime_type,
+                    } => (file_name, data, mime_type),
+                    AttachmentFile::InvalidPath => {
+                        self.report.warnings.push(format!(
+                            "Attachment {} has an invalid path: {}",
+                            item.id, path
+                        ));
+                        continue;
+                    }
+                    AttachmentFile::Missing => {
+                        self.report.missing_files.push(path);
+                        continue;
+                    }
+                    AttachmentFile::Outside => {
+                        self.report.warnings.push(format!(
+                            "Attachment {} links outside the uploads directory: {}",
+                            item.id, path
+                        ));
+                        continue;
+                    }
+                    AttachmentFile::Rejected(rejection) => {
+                        self.report
+                            .rejected_files
+                            .push(format!("{}: {}", path, rejection));
+                        continue;
+                    }
+                };
+
+            self.report.attachments.created += 1;
+            if self.options.dry_run {
+                continue;
+            }
+            let url = crate::media::store(
+                self.storage,
+                self.media_repository,
+                data,
+                file_name,
+                content_type,
+                Some(self.options.fallback_author_id),
+                self.options.file_naming,
+            )
+            .await?
+            .media
+            .url;
+            self.post_repository
+                .save_import_record(
+                    &key,
+                    ImportRecord {
+                        target_id: None,
+                        target_url: Some(url.clone()),
+                    },
+                )
+                .await?;
+            uploads.insert(path, url);
+        }
+        Ok(uploads)
+    }
+
+    async fn import_posts(
+        &mut self,
+        authors: &HashMap<String, Option<i64>>,
+        categories: &HashMap<String, Option<i64>>,
+        uploads: &HashMap<String, String>,
+    ) -> Result<(), ImportError> {
+        let mut revisions: HashMap<&str, Vec<&WxrItem>> = HashMap::new();
+        for item in &self.document.items {
+            if item.post_type == "revision" {
+                revisions
+                    .entry(item.parent.as_str())
+                    .or_default()
+                    .push(item);
+            }
+        }
+        let author_id = |login: &str| {
+            authors
+                .get(login)
+                .copied()
+                .flatten()
+                .unwrap_or(self.options.fallback_author_id)
+        };
+
+        for item in &self.document.items {
+            match item.post_type.as_str() {
+                "post" => {}
+                "attachment" | "revision" => continue,
+                _ => {
+                    self.report.skipped += 1;
+                    continue;
+                }
+            }
+            let status = match item.status.as_str() {
+                "publish" => PostStatus::Published,
+                "future" => PostStatus::Scheduled,
+                "draft" => PostStatus::Draft,
+                "pending" => PostStatus::PendingReview,
+                "private" => PostStatus::Private,
+                _ => {
+                    self.report.skipped += 1;
+                    continue;
+                }
+            };
+
+            let key = self.key("post", &item.id);
+            if self
+                .post_repository
+                .find_import_record(&key)
+                .await?
+                .is_some()
+            {
+                self.report.posts.existing += 1;
+                continue;
+            }
+
+            let mut history = revisions.remove(item.id.as_str()).unwrap_or_default();
+            history.sort_by_key(|revision| revision.date);
+            let (autosaves, history): (Vec<_>, Vec<_>) = history
+                .into_iter()
+                .partition(|revision| revision.slug.contains("autosave"));
+
+            let created_at = item.date.unwrap_or_else(Utc::now);
+            let draft = autosaves
+                .last()
+                .filter(|autosave| autosave.date >= item.modified)
+                .map(|autosave| ImportPostDraft {
+                    title: autosave.title.clone(),
+                    content: rewrite_upload_urls(&autosave.content, uploads),
+                    author_id: author_id(&autosave.creator),
+                    updated_at: autosave.date.unwrap_or(created_at),
+                });
+
+            self.report.posts.created += 1;
+            self.report.versions += history.len() as u64;
+            self.report.drafts += draft.is_some() as u64;
+            if self.options.dry_run {
+                continue;
+            }
+
+            let description = Some(item.excerpt.clone()).filter(|excerpt| !excerpt.is_empty());
+            let request = ImportPostRequest {
+                key,
+                title: item.title.clone(),
+                slug: Some(item.slug.clone()).filter(|slug| !slug.is_empty()),
+                publish_at: (status == PostStatus::Scheduled).then_some(created_at),
+                status,
+                content: rewrite_upload_urls(&item.content, uploads),
+                category_id: item
+                    .categories
+                    .first()
+                    .and_then(|(slug, _)| categories.get(slug).copied().flatten()),
+                author_id: author_id(&item.creator),
+                description,
+                tags: item.tags.clone(),
+                created_at,
+                updated_at: item.modified.unwrap_or(created_at),
+                versions: history
+                    .iter()
+                    .map(|revision| ImportPostVersion {
+                        title: revision.title.clone(),
+                        content: rewrite_upload_urls(&revision.content, uploads),
+                        description: Some(revision.excerpt.clone())
+                            .filter(|excerpt| !excerpt.is_empty()),
+                        created_at: revision.date.unwrap_or(created_at),
+                        created_by: author_id(&revision.creator),
+                    })
+                    .collect(),
+                draft,
+            };
+            self.post_repository.import_post(request).await?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Path of an attachment's file below the uploads directory
+fn attachment_path(item: &WxrItem) -> Option<String> {
+    item.attached_file
+        .clone()
+        .or_else(|| {
+            item.attachment_url.as_ref().and_then(|url| {
+                UPLOAD_URL
+                    .captures(url)
+                    .map(|captures| captures[1].to_string())
+            })
+        })
+        .map(|path| percent_decode(&path))
+        .filter(|path| !path.is_empty())
+}
+
+/// `path` below `dir`, unless it would leave it or names a hidden file or
+/// directory (`.env`, `.git/config`), which WordPress never uploads
+fn safe_join(dir: &Path, path: &str) -> Option<PathBuf> {
+    let relative = Path::new(path);
+    relative
+        .components()
+        .all(|component| match component {
+            Component::Normal(name) => !name.to_string_lossy().starts_with('.'),
+            _ => false,
+        })
+        .then(|| dir.join(relative))
+}
+
+/// An attachment's file in the uploads directory
+#[derive(Debug)]
+enum AttachmentFile {
+    Accepted {
+        file_name: String,
+        data: Vec<u8>,
+        mime_type: CheckedType,
+    },
+    /// The path leaves the uploads directory or names a hidden file
+    InvalidPath,
+    Missing,
+    /// A symlink to a file outside the uploads directory
+    Outside,
+    /// Refused by the upload settings, like an upload of the same file
+    Rejected(UploadRejection),
+}
+
+/// Read an attachment's file below `uploads_root` (with symlinks resolved) and
+/// check it like an upload; the type its name suggests must match the content
+async fn read_attachment(
+    uploads_root: &Path,
+    path: &str,
+    policy: &UploadPolicy,
+) -> std::io::Result<AttachmentFile> {
+    let Some(file) = safe_join(uploads_root, path) else {
+        return Ok(AttachmentFile::InvalidPath);
+    };
+    let file = match tokio::fs::canonicalize(&file).await {
+        Ok(file) => file,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            return Ok(AttachmentFile::Missing);
+        }
+        Err(e) => return Err(e),
+    };
+    if !file.starts_with(uploads_root) {
+        return Ok(AttachmentFile::Outside);
+    }
+
+    let data = tokio::fs::read(&file).await?;
+    let file_name = Path::new(path)
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or("attachment")
+        .to_string();
+    let declared = mime_guess::from_path(&file_name).first_or_octet_stream();
+    Ok(match policy.check(declared.essence_str(), &data) {
+        Ok(mime_type) => AttachmentFile::Accepted {
+            file_name,
+            data,
+            mime_type,
+        },
+        Err(rejection) => AttachmentFile::Rejected(rejection),
+    })
+}
+
+/// Environment variable naming the directory that uploads directories given to
+/// the import endpoint must be inside; without it, the endpoint reads no files
+pub const IMPORT_DIR_ENV: &str = "WXR_IMPORT_DIR";
+
+/// The directory `requested` names below `root`, with symlinks resolved; `None`
+/// if it is missing, not a directory or outside `root`
+pub fn resolve_uploads_dir(root: &Path, requested: &str) -> Option<PathBuf> {
+    let root = root.canonicalize().ok()?;
+    let dir = root.join(requested.trim()).canonicalize().ok()?;
+    (dir.starts_with(&root) && dir.is_dir()).then_some(dir)
+}
+
+/// Point links into the WordPress uploads directory at the imported files
+///
+/// Scaled copies (`photo-300x200.jpg`) are pointed at the original file.
+pub fn rewrite_upload_urls(content: &str, uploads: &HashMap<String, String>) -> String {
+    if uploads.is_empty() {
+        return content.to_string();
+    }
+    UPLOAD_URL
+        .replace_all(content, |captures: &Captures| {
+            let path = percent_decode(&captures[1]);
+            let path = SITE_PREFIX.replace(&path, "");
+            let original = SIZE_SUFFIX.replace(&path, "$1");
+            uploads
+                .get(path.as_ref())
+                .or_else(|| uploads.get(original.as_ref()))
+                .cloned()
+                .unwrap_or_else(|| captures[0].to_string())
+        })
+        .into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<rss version="2.0"
+    xmlns:excerpt="http://wordpress.org/export/1.2/excerpt/"
+    xmlns:content="http://purl.org/rss/1.0/modules/content/"
+    xmlns:dc="http://purl.org/dc/elements/1.1/"
+    xmlns:wp="http://wordpress.org/export/1.2/">
+<channel>
+    <title>Old blog</title>
+    <link>https://old.example.com</link>
+    <wp:base_site_url>https://old.example.com</wp:base_site_url>
+    <wp:base_blog_url>https://old.example.com</wp:base_blog_url>
+    <wp:author><wp:author_login><![CDATA[alice]]></wp:author_login><wp:author_email><![CDATA[alice@example.com]]></wp:author_email></wp:author>
+    <wp:category><wp:term_id>2</wp:term_id><wp:category_nicename><![CDATA[news]]></wp:category_nicename><wp:category_parent><![CDATA[]]></wp:category_parent><wp:cat_name><![CDATA[News]]></wp:cat_name></wp:category>
+    <item>
+        <title><![CDATA[Hello & welcome]]></title>
+        <dc:creator><![CDATA[alice]]></dc:creator>
+        <content:encoded><![CDATA[<p><img src="https://old.example.com/wp-content/uploads/2020/01/cat-300x200.jpg"></p>]]></content:encoded>
+        <excerpt:encoded><![CDATA[]]></excerpt:encoded>
+        <wp:post_id>7</wp:post_id>
+        <wp:post_date_gmt><![CDATA[2020-01-02 03:04:05]]></wp:post_date_gmt>
+        <wp:post_name><![CDATA[hello-welcome]]></wp:post_name>
+        <wp:status><![CDATA[publish]]></wp:status>
+        <wp:post_parent>0</wp:post_parent>
+        <wp:post_type><![CDATA[post]]></wp:post_type>
+        <category domain="category" nicename="news"><![CDATA[News]]></category>
+        <category domain="post_tag" nicename="cats"><![CDATA[Cats]]></category>
+    </item>
+    <item>
+        <title>cat</title>
+        <wp:post_id>8</wp:post_id>
+        <wp:post_date_gmt>0000-00-00 00:00:00</wp:post_date_gmt>
+        <wp:post_type><![CDATA[attachment]]></wp:post_type>
+        <wp:attachment_url><![CDATA[https://old.example.com/wp-content/uploads/2020/01/cat.jpg]]></wp:attachment_url>
+        <wp:postmeta><wp:meta_key><![CDATA[_wp_attached_file]]></wp:meta_key><wp:meta_value><![CDATA[2020/01/cat.jpg]]></wp:meta_value></wp:postmeta>
+    </item>
+</channel>
+</rss>"#;
+
+    #[test]
+    fn test_parse() {
+        let document = parse(SAMPLE).unwrap();
+
+        assert_eq!(document.site_url, "https://old.example.com");
+        assert_eq!(document.authors[0].email, "alice@example.com");
+        assert_eq!(document.categories[0].name, "News");
+        assert_eq!(document.categories[0].parent, None);
+
+        let post = &document.items[0];
+        assert_eq!(post.title, "Hello & welcome");
+        assert_eq!(post.creator, "alice");
+        assert_eq!(post.post_type, "post");
+        assert_eq!(
+            post.categories,
+            vec![("news".to_string(), "News".to_string())]
+        );
+        assert_eq!(post.tags, vec!["Cats".to_string()]);
+        assert_eq!(post.date.unwrap().to_rfc3339(), "2020-01-02T03:04:05+00:00");
+
+        let attachment = &document.items[1];
+        assert_eq!(attachment.date, None);
+        assert_eq!(
+            attachment_path(attachment).as_deref(),
+            Some("2020/01/cat.jpg")
+        );
+    }
+
+    #[test]
+    fn test_rewrite_upload_urls() {
+        let uploads = HashMap::from([(
+            "2020/01/cat.jpg".to_string(),
+            "/uploads/abc.jpg".to_string(),
+        )]);
+        let content = r#"<img src="https://old.example.com/wp-content/uploads/2020/01/cat-300x200.jpg"> <a href="//cdn.example.com/wp-content/uploads/sites/2/2020/01/cat.jpg">x</a> https://old.example.com/wp-content/uploads/2020/01/dog.jpg"#;
+
+        assert_eq!(
+            rewrite_upload_urls(content, &uploads),
+            r#"<img src="/uploads/abc.jpg"> <a href="/uploads/abc.jpg">x</a> https://old.example.com/wp-content/uploads/2020/01/dog.jpg"#
+        );
+        assert!(safe_join(Path::new("/tmp"), "../etc/passwd").is_none());
+    }
+
+    #[test]
+    fn test_safe_join() {
+        assert_eq!(
+            safe_join(Path::new("/wp"), "2020/01/cat.jpg"),
+            Some(PathBuf::from("/wp/2020/01/cat.jpg"))
+        );
+        for path in [
+            "/etc/passwd",
+            "../.env",
+            ".env",
+            "2020/.git/config",
+            "a/../../b",
+        ] {
+            assert!(safe_join(Path::new("/wp"), path).is_none(), "{}", path);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_read_attachment() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().canonicalize().unwrap();
+        std::fs::create_dir_all(root.join("2020/01")).unwrap();
+        let png = b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR";
+        std::fs::write(root.join("2020/01/cat.png"), png).unwrap();
+        std::fs::write(root.join("2020/01/cat.jpg"), png).unwrap();
+        std::fs::write(
+            root.join("2020/01/page.png"),
+            "<!DOCTYPE html><script>alert(1)</script>",
+        )
+        .unwrap();
+        std::fs::write(
+            root.join("2020/01/logo.svg"),
+            r#"<svg xmlns="http://www.w3.org/2000/svg" onload="alert(1)"/>"#,
+        )
+        .unwrap();
+        std::fs::write(root.join(".env"), "SECRET=1").unwrap();
+        let policy = UploadPolicy {
+            allowed_types: crate::upload_policy::parse_allowed_types("image/*").unwrap(),
+            ..UploadPolicy::default()
+        };
+
+        let read = |path: &'static str| {
+            let (root, policy) = (root.clone(), policy.clone());
+            async move { read_attachment(&root, path, &policy).await.unwrap() }
+        };
+        match read("2020/01/cat.png").await {
+            AttachmentFile::Accepted {
+                file_name,
+                data,
+                mime_type,
+            } => {
+                assert_eq!(file_name, "cat.png");
+                assert_eq!(data, png);
+                assert_eq!(mime_type.as_str(), "image/png");
+            }
+            other => panic!("{:?}", other),
+        }
+        // HTML and scripted SVG are refused like uploads, whatever their name
+        assert!(matches!(
+            read("2020/01/page.png").await,
+            AttachmentFile::Rejected(UploadRejection::ActiveContent)
+        ));
+        assert!(matches!(
+            read("2020/01/logo.svg").await,
+            AttachmentFile::Rejected(UploadRejection::ActiveContent)
+        ));
+        assert!(matches!(
+            read("2020/01/cat.jpg").await,
+            AttachmentFile::Rejected(UploadRejection::Mismatch { .. })
+        ));
+        assert!(matches!(read(".env").await, AttachmentFile::InvalidPath));
+        assert!(matches!(
+            read("../etc/passwd").await,
+            AttachmentFile::InvalidPath
+        ));
+        assert!(matches!(
+            read("2020/01/dog.png").await,
+            AttachmentFile::Missing
+        ));
+
+        #[cfg(unix)]
+        {
+            let outside = tempfile::tempdir().unwrap();
+            std::fs::write(outside.path().join("secret.png"), png).unwrap();
+            std::os::unix::fs::symlink(
+                outside.path().join("secret.png"),
+                root.join("2020/01/link.png"),
+            )
+            .unwrap();
+            assert!(matches!(
+                read("2020/01/link.png").await,
+                AttachmentFile::Outside
+            ));
+        }
+    }
+
+    #[test]
+    fn test_resolve_uploads_dir() {
+        let root = tempfile::tempdir().unwrap();
+        let uploads = root.path().join("site/uploads");
+        std::fs::create_dir_all(&uploads).unwrap();
+        std::fs::write(root.path().join("file.txt"), "").unwrap();
+        let uploads = uploads.canonicalize().unwrap();
+
+        assert_eq!(
+            resolve_uploads_dir(root.path(), "site/uploads"),
+            Some(uploads.clone())
+        );
+        assert_eq!(
+            resolve_uploads_dir(root.path(), " site/./uploads/ "),
+            Some(uploads)
+        );
+        assert!(resolve_uploads_dir(root.path(), ".").is_some());
+        for requested in ["..", "/", "/etc", "site/../..", "missing", "file.txt"] {
+            assert!(
+                resolve_uploads_dir(root.path(), requested).is_none(),
+                "{}",
+                requested
+            );
+        }
+
+        #[cfg(unix)]
+        {
+            let outside = tempfile::tempdir().unwrap();
+            std::os::unix::fs::symlink(outside.path(), root.path().join("link")).unwrap();
+            assert!(resolve_uploads_dir(root.path(), "link").is_none());
+        }
+    }
+}
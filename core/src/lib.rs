@@ -0,0 +1,31 @@
+//! RustPress core: the blog server's API, storage, plugin system and rendering
+//!
+//! Built as a library so that `rustpress-cli` can use the same code for
+//! offline tasks (such as static exports) as the `rustpress` server binary.
+
+pub mod ai;
+pub mod api;
+pub mod auth;
+pub mod backup;
+pub mod dto;
+pub mod entity;
+pub mod export;
+pub mod feed;
+pub mod front_matter;
+pub mod image_variants;
+pub mod import;
+pub mod markdown;
+pub mod media;
+pub mod metrics;
+pub mod metrics_middleware;
+pub mod plugin;
+pub mod repository;
+pub mod rpk;
+pub mod scheduler;
+pub mod seed;
+pub mod seo;
+pub mod sitemap;
+pub mod slug;
+pub mod storage;
+pub mod trash;
+pub mod upload_policy;
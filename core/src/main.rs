@@ -1,21 +1,9 @@
-use crate::api::create_router;
-use crate::repository::{
+use rustpress_core::api::create_router;
+use rustpress_core::repository::{
     PostRepository, PostgresPostRepository, PostgresUserRepository, UserRepository,
 };
 use sea_orm::{Database, DbErr};
 use std::sync::Arc;
-mod ai;
-mod api;
-mod auth;
-mod dto;
-mod entity;
-mod metrics;
-mod metrics_middleware;
-mod plugin;
-mod repository;
-mod rpk;
-mod seed;
-mod storage;
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
     // Initialize color-eyre for better error reporting with stack traces
@@ -42,7 +30,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         .init();
 
     // Initialize Prometheus metrics
-    crate::metrics::init_metrics();
+    rustpress_core::metrics::init_metrics();
     tracing::info!("✅ Prometheus metrics initialized");
 
     // Get database URL
@@ -87,19 +75,20 @@ async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         init_sample_post(&post_repository, user_id).await;
     }
 
-    // Create application state (contains Post and User Repository)
-    let app_state = Arc::new(crate::api::post_controller::AppState::new(
+    // Create application state (contains Post, User, Comment and Media Repository)
+    let app_state = Arc::new(rustpress_core::api::post_controller::AppState::new(
         post_repository,
         user_repository,
+        rustpress_core::repository::PostgresCommentRepository::new(db.clone()),
+        rustpress_core::repository::PostgresMediaRepository::new(db.clone()),
     ));
 
     // Create OpenAI API key repository
-    let openai_api_key_repository = Arc::new(
-        crate::repository::PostgresOpenAIApiKeyRepository::new(db.clone()),
-    );
+    let openai_api_key_repository =
+        Arc::new(rustpress_core::repository::PostgresOpenAIApiKeyRepository::new(db.clone()));
 
     // Create RPK processor
-    let rpk_processor = Arc::new(crate::rpk::RpkProcessor::new(
+    let rpk_processor = Arc::new(rustpress_core::rpk::RpkProcessor::new(
         std::env::current_dir().unwrap().join("installed_plugins"),
         std::env::current_dir().unwrap().join(".rpk_cache"),
     ));
@@ -110,13 +99,16 @@ async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         .expect("Failed to initialize RPK processor directories");
 
     let plugin_engine = Arc::new(
-        crate::plugin::engine::PluginEngine::new().expect("Failed to create plugin engine"),
+        rustpress_core::plugin::engine::PluginEngine::new()
+            .expect("Failed to create plugin engine"),
     );
 
-    let ai_service = Arc::new(crate::ai::AiService::new(openai_api_key_repository));
+    let ai_service = Arc::new(rustpress_core::ai::AiService::new(
+        openai_api_key_repository,
+    ));
 
     // Initialize plugin system first
-    let plugin_registry = Arc::new(crate::plugin::registry::PluginRegistry::new(
+    let plugin_registry = Arc::new(rustpress_core::plugin::registry::PluginRegistry::new(
         plugin_engine,
         Arc::new(db.clone()),
         rpk_processor.clone(),
@@ -131,26 +123,48 @@ async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
 
     tracing::info!("✅ Plugin system initialized");
 
-    // Initialize storage backend (local filesystem for now, can be switched to S3 later)
+    // Initialize storage backend (local filesystem, or an S3-compatible bucket)
     let storage_dir = std::env::var("STORAGE_DIR").unwrap_or_else(|_| "uploads".to_string());
     let storage_base_url = std::env::var("STORAGE_BASE_URL")
         .unwrap_or_else(|_| "http://localhost:3000/uploads".to_string());
 
-    let storage = Arc::new(crate::storage::LocalStorage::new(
-        &storage_dir,
-        storage_base_url.clone(),
-    ));
-
-    // Ensure storage directory exists
-    storage
-        .ensure_directory()
+    let storage = Arc::new(
+        rustpress_core::storage::ConfiguredStorage::from_env(
+            &storage_dir,
+            storage_base_url.clone(),
+        )
         .await
-        .expect("Failed to create storage directory");
+        .map_err(|e| format!("Failed to initialize storage: {}", e))?,
+    );
 
     tracing::info!("✅ Storage initialized");
-    tracing::info!("   Directory: {}", storage_dir);
+    match storage.as_ref() {
+        rustpress_core::storage::ConfiguredStorage::Local(_) => {
+            tracing::info!("   Directory: {}", storage_dir)
+        }
+        rustpress_core::storage::ConfiguredStorage::S3(s3) => {
+            tracing::info!("   S3 bucket: {}", s3.bucket())
+        }
+    }
     tracing::info!("   Base URL: {}", storage_base_url);
 
+    // Start the background scheduler for timed publishing and unpublishing
+    rustpress_core::scheduler::spawn(
+        app_state.post_repository.clone(),
+        db.clone(),
+        plugin_registry.clone(),
+    );
+    tracing::info!("✅ Post scheduler started");
+
+    // Start the background job that purges posts kept in the trash for too long
+    rustpress_core::trash::spawn(
+        app_state.post_repository.clone(),
+        app_state.media_repository.clone(),
+        storage.clone(),
+        Arc::new(db.clone()),
+    );
+    tracing::info!("✅ Trash purge job started");
+
     // Create routes (API Controller layer)
     let app = create_router(app_state, storage, db, plugin_registry);
 
@@ -166,11 +180,28 @@ async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
     tracing::info!("");
     tracing::info!("📝 API endpoints:");
     tracing::info!("   GET    /api/health        - Health check");
-    tracing::info!("   GET    /api/posts         - Get all posts");
+    tracing::info!("   GET    /api/posts         - List posts (paginated)");
+    tracing::info!("   GET    /api/posts/search  - Full-text search posts");
     tracing::info!("   GET    /api/posts/:id     - Get post by id");
+    tracing::info!("   GET    /api/posts/by-slug/:slug - Get post by slug (301 for old slugs)");
     tracing::info!("   POST   /api/posts         - Create new post");
     tracing::info!("   PUT    /api/posts/:id     - Update post");
     tracing::info!("   DELETE /api/posts/:id     - Delete post");
+    tracing::info!(
+        "   POST   /api/posts/:id/transitions - Change post status (submit, approve, reject, ...)"
+    );
+    tracing::info!("   GET    /api/posts/:id/comments - List approved comments (threaded)");
+    tracing::info!("   POST   /api/posts/:id/comments - Post a comment");
+    tracing::info!("   GET    /api/categories    - List category tree");
+    tracing::info!("   GET    /api/tags          - List tags with post counts");
+    tracing::info!("   GET    /api/tags/:slug/posts - List posts by tag");
+    tracing::info!("   GET    /feed.xml, /atom.xml - RSS and Atom feeds");
+    tracing::info!("   GET    /category/:slug/feed.xml, /category/:slug/atom.xml - Category feeds");
+    tracing::info!(
+        "   GET    /author/:username/feed.xml, /author/:username/atom.xml - Author feeds"
+    );
+    tracing::info!("   GET    /sitemap.xml       - XML sitemap (index above 50k URLs)");
+    tracing::info!("   GET    /robots.txt        - robots.txt");
     tracing::info!("   GET    /api/users         - Get all users");
     tracing::info!("   GET    /api/users/:id     - Get user by id");
     tracing::info!("   POST   /api/users         - Create new user");
@@ -205,7 +236,7 @@ async fn run_migrations(db: &sea_orm::DatabaseConnection) -> Result<(), DbErr> {
 /// Password is read from ROOT_PASSWORD environment variable, or randomly generated if not set
 /// Returns the root user's ID (whether newly created or already existing)
 async fn init_root_user<UR: UserRepository>(user_repository: &UR) -> Option<i64> {
-    use crate::dto::{CreateUserRequest, user::UserRole};
+    use rustpress_core::dto::{CreateUserRequest, user::UserRole};
     use uuid::Uuid;
 
     // Check if root user already exists (find user with Root role)
@@ -270,7 +301,7 @@ async fn init_root_user<UR: UserRepository>(user_repository: &UR) -> Option<i64>
 /// Initialize sample post
 /// Creates a sample post if no posts exist
 async fn init_sample_post<PR: PostRepository>(post_repository: &PR, author_id: i64) {
-    use crate::dto::post::CreatePostRequest;
+    use rustpress_core::dto::{CreateCategoryRequest, post::CreatePostRequest};
 
     // Check if posts already exist
     let posts = match post_repository.find_all().await {
@@ -287,13 +318,47 @@ async fn init_sample_post<PR: PostRepository>(post_repository: &PR, author_id: i
     }
 
     // Create sample post
-    let sample_post = crate::seed::get_sample_post();
+    let sample_post = rustpress_core::seed::get_sample_post();
+
+    // Find or create the sample post's category
+    let category_id = match sample_post.category.as_deref() {
+        Some(name) => match post_repository.find_category_by_name_or_slug(name).await {
+            Ok(Some(category)) => Some(category.id),
+            Ok(None) => {
+                let request = CreateCategoryRequest {
+                    name: name.to_string(),
+                    slug: None,
+                    description: None,
+                    parent_id: None,
+                };
+                match post_repository.create_category(request).await {
+                    Ok(category) => Some(category.id),
+                    Err(e) => {
+                        tracing::error!("❌ Failed to create sample category: {}", e);
+                        None
+                    }
+                }
+            }
+            Err(e) => {
+                tracing::error!("❌ Failed to look up sample category: {}", e);
+                None
+            }
+        },
+        None => None,
+    };
+
     let create_request = CreatePostRequest {
         title: sample_post.title,
+        slug: None,
         content: sample_post.content,
         category: sample_post.category.clone(),
+        category_id,
         author_id,
         description: sample_post.description.clone(),
+        tags: Vec::new(),
+        status: None,
+        publish_at: None,
+        unpublish_at: None,
     };
 
     match post_repository.create(create_request).await {
@@ -0,0 +1,335 @@
+//! Markdown rendering
+//!
+//! Post content is stored as Markdown (CommonMark with GFM tables, strikethrough and
+//! task lists). It is rendered to HTML here, with anchors on headings, a table of
+//! contents and syntax-highlighted code blocks, and then sanitised so the result can
+//! be embedded in pages, feeds and emails as is.
+
+use crate::dto::Post;
+use crate::slug::{slugify, unique_slug};
+use lazy_static::lazy_static;
+use pulldown_cmark::{CodeBlockKind, CowStr, Event, Options, Parser, Tag, TagEnd, html};
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+use syntect::html::{ClassStyle, ClassedHTMLGenerator};
+use syntect::parsing::SyntaxSet;
+use syntect::util::LinesWithEndings;
+use utoipa::ToSchema;
+
+/// Prefix of the CSS classes on highlighted code (`hl-keyword`, `hl-string`, ...)
+const HIGHLIGHT_CLASS_PREFIX: &str = "hl-";
+
+/// Number of rendered posts kept in a [`RenderCache`]
+const RENDER_CACHE_CAPACITY: usize = 512;
+
+/// Id used for headings without letters or digits
+const FALLBACK_HEADING_ID: &str = "section";
+
+lazy_static! {
+    static ref SYNTAX_SET: SyntaxSet = SyntaxSet::load_defaults_newlines();
+    static ref SANITIZER: ammonia::Builder<'static> = sanitizer();
+}
+
+/// Heading in a post's table of contents
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+pub struct TocEntry {
+    /// Heading level (1-6)
+    pub level: u8,
+    /// Anchor id of the heading, for `#id` links
+    pub id: String,
+    pub text: String,
+}
+
+/// Sanitised HTML of a Markdown document with its table of contents
+#[derive(Debug, Clone)]
+pub struct RenderedMarkdown {
+    pub html: String,
+    pub toc: Vec<TocEntry>,
+}
+
+/// Render Markdown to sanitised HTML
+pub fn render(markdown: &str) -> RenderedMarkdown {
+    let options =
+        Options::ENABLE_TABLES | Options::ENABLE_STRIKETHROUGH | Options::ENABLE_TASKLISTS;
+    let mut events = Vec::new();
+    let mut toc = Vec::new();
+    let mut ids = HashSet::new();
+
+    // Headings and code blocks are buffered until their end, then rewritten
+    let mut heading: Option<Vec<Event>> = None;
+    let mut code_block: Option<(String, Vec<Event>)> = None;
+
+    for event in Parser::new_ext(markdown, options) {
+        if let Some(buffer) = heading.as_mut() {
+            let end = matches!(event, Event::End(TagEnd::Heading(_)));
+            buffer.push(event);
+            if end {
+                events.extend(anchor_heading(
+                    heading.take().unwrap_or_default(),
+                    &mut ids,
+                    &mut toc,
+                ));
+            }
+            continue;
+        }
+        if let Some((_, buffer)) = code_block.as_mut() {
+            let end = matches!(event, Event::End(TagEnd::CodeBlock));
+            buffer.push(event);
+            if end {
+                let (language, buffer) = code_block.take().unwrap_or_default();
+                events.extend(highlight_code_block(&language, buffer));
+            }
+            continue;
+        }
+
+        match event {
+            Event::Start(Tag::Heading { .. }) => heading = Some(vec![event]),
+            Event::Start(Tag::CodeBlock(CodeBlockKind::Fenced(ref info))) => {
+                let language = info.split_whitespace().next().unwrap_or("").to_string();
+                code_block = Some((language, vec![event]));
+            }
+            event => events.push(event),
+        }
+    }
+
+    let mut unsafe_html = String::with_capacity(markdown.len() * 3 / 2);
+    html::push_html(&mut unsafe_html, events.into_iter());
+
+    RenderedMarkdown {
+        html: SANITIZER.clean(&unsafe_html).to_string(),
+        toc,
+    }
+}
+
+/// Plain-text summary of Markdown, cut at a word boundary after at most `max_chars` characters
+///
+/// Used where a post has no description, e.g. for meta descriptions.
+pub fn excerpt(markdown: &str, max_chars: usize) -> String {
+    let options =
+        Options::ENABLE_TABLES | Options::ENABLE_STRIKETHROUGH | Options::ENABLE_TASKLISTS;
+    let mut text = String::new();
+    for event in Parser::new_ext(markdown, options) {
+        match event {
+            Event::Text(part) | Event::Code(part) => text.push_str(&part),
+            Event::SoftBreak | Event::HardBreak | Event::End(_) => text.push(' '),
+            _ => {}
+        }
+    }
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+
+    if text.chars().count() <= max_chars {
+        return text;
+    }
+    let cut: String = text.chars().take(max_chars).collect();
+    let cut = match cut.rfind(' ') {
+        Some(space) if space > 0 => &cut[..space],
+        _ => cut.as_str(),
+    };
+    format!(
+        "{}…",
+        cut.trim_end_matches(|c: char| c.is_ascii_punctuation())
+    )
+}
+
+/// Give a buffered heading a unique id and a self-link, and record it in the table of contents
+fn anchor_heading<'a>(
+    mut events: Vec<Event<'a>>,
+    ids: &mut HashSet<String>,
+    toc: &mut Vec<TocEntry>,
+) -> Vec<Event<'a>> {
+    let text: String = events
+        .iter()
+        .filter_map(|event| match event {
+            Event::Text(text) | Event::Code(text) => Some(text.as_ref()),
+            _ => None,
+        })
+        .collect();
+
+    let Some(Event::Start(Tag::Heading { level, id, .. })) = events.first_mut() else {
+        return events;
+    };
+
+    let base = match slugify(&text) {
+        slug if slug.is_empty() => FALLBACK_HEADING_ID.to_string(),
+        slug => slug,
+    };
+    let anchor = unique_slug(&base, ids);
+    ids.insert(anchor.clone());
+    *id = Some(CowStr::from(anchor.clone()));
+
+    toc.push(TocEntry {
+        level: *level as u8,
+        id: anchor.clone(),
+        text: text.trim().to_string(),
+    });
+
+    let end = events.pop();
+    events.push(Event::Html(CowStr::from(format!(
+        r##"<a class="heading-anchor" href="#{}" aria-hidden="true">#</a>"##,
+        anchor
+    ))));
+    events.extend(end);
+    events
+}
+
+/// Replace a buffered fenced code block with highlighted HTML when its language is known
+fn highlight_code_block<'a>(language: &str, events: Vec<Event<'a>>) -> Vec<Event<'a>> {
+    let Some(syntax) = (!language.is_empty())
+        .then(|| SYNTAX_SET.find_syntax_by_token(language))
+        .flatten()
+    else {
+        return events;
+    };
+
+    let code: String = events
+        .iter()
+        .filter_map(|event| match event {
+            Event::Text(text) => Some(text.as_ref()),
+            _ => None,
+        })
+        .collect();
+
+    let mut generator = ClassedHTMLGenerator::new_with_class_style(
+        syntax,
+        &SYNTAX_SET,
+        ClassStyle::SpacedPrefixed {
+            prefix: HIGHLIGHT_CLASS_PREFIX,
+        },
+    );
+    for line in LinesWithEndings::from(&code) {
+        if generator
+            .parse_html_for_line_which_includes_newline(line)
+            .is_err()
+        {
+            return events;
+        }
+    }
+
+    vec![Event::Html(CowStr::from(format!(
+        "<pre><code class=\"language-{}\">{}</code></pre>\n",
+        slugify(language),
+        generator.finalize()
+    )))]
+}
+
+/// HTML sanitiser allowing what the renderer produces, on top of ammonia's defaults
+fn sanitizer() -> ammonia::Builder<'static> {
+    let mut builder = ammonia::Builder::default();
+    builder
+        .add_tags(["input"])
+        .add_tag_attributes("input", ["type", "checked", "disabled"])
+        .add_tag_attributes("a", ["class", "aria-hidden"])
+        .add_tag_attributes("code", ["class"])
+        .add_tag_attributes("span", ["class"])
+        .add_tag_attributes("th", ["style"])
+        .add_tag_attributes("td", ["style"])
+        .attribute_filter(|element, attribute, value| match (element, attribute) {
+            // Only checkboxes (task lists)
+            ("input", "type") if value != "checkbox" => None,
+            // Only table column alignment
+            ("th" | "td", "style") if !value.starts_with("text-align:") || value.contains(';') => {
+                None
+            }
+            _ => Some(value.into()),
+        });
+    for heading in ["h1", "h2", "h3", "h4", "h5", "h6"] {
+        builder.add_tag_attributes(heading, ["id"]);
+    }
+    builder
+}
+
+/// Rendered post contents, cached per post revision
+///
+/// Every update bumps a post's revision, so entries never go stale; older revisions
+/// of a post are dropped when a newer one is rendered.
+#[derive(Default)]
+pub struct RenderCache {
+    entries: Mutex<HashMap<i64, (i32, Arc<RenderedMarkdown>)>>,
+}
+
+impl RenderCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Rendered content of the post at its current revision
+    pub fn render_post(&self, post: &Post) -> Arc<RenderedMarkdown> {
+        if let Some((revision, rendered)) = self.lock().get(&post.id)
+            && *revision == post.revision
+        {
+            return rendered.clone();
+        }
+
+        let rendered = Arc::new(render(&post.content));
+        let mut entries = self.lock();
+        if entries.len() >= RENDER_CACHE_CAPACITY && !entries.contains_key(&post.id) {
+            entries.clear();
+        }
+        entries.insert(post.id, (post.revision, rendered.clone()));
+        rendered
+    }
+
+    /// Drop all entries, e.g. after posts were replaced without bumping their revision
+    pub fn clear(&self) {
+        self.lock().clear();
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<i64, (i32, Arc<RenderedMarkdown>)>> {
+        // A panic while holding the lock can't leave the map half-updated
+        self.entries
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_headings_and_toc() {
+        let rendered = render("# Intro\n\nText\n\n## Details\n\n## Details\n");
+
+        let ids: Vec<&str> = rendered.toc.iter().map(|entry| entry.id.as_str()).collect();
+        assert_eq!(ids, ["intro", "details", "details-2"]);
+        assert_eq!(rendered.toc[1].level, 2);
+        assert!(rendered.html.contains(r#"<h2 id="details-2">"#));
+        assert!(rendered.html.contains("href=\"#intro\""));
+    }
+
+    #[test]
+    fn test_render_sanitises_html() {
+        let rendered = render(
+            "Hi <script>alert(1)</script><img src=x onerror=alert(1)> [x](javascript:alert(1))",
+        );
+
+        assert!(!rendered.html.contains("<script"));
+        assert!(!rendered.html.contains("onerror"));
+        assert!(!rendered.html.contains("javascript:"));
+    }
+
+    #[test]
+    fn test_excerpt() {
+        let markdown = "# Title\n\nSome *emphasised* text with `code`.\n\n- one\n- two";
+        assert_eq!(
+            excerpt(markdown, 100),
+            "Title Some emphasised text with code. one two"
+        );
+        assert_eq!(excerpt(markdown, 22), "Title Some emphasised…");
+    }
+
+    #[test]
+    fn test_render_tables_and_code() {
+        let rendered = render(
+            "| a | b |\n|:-:|---|\n| 1 | 2 |\n\n```rust\nfn main() {}\n```\n\n```nope\n<x>\n```\n",
+        );
+
+        assert!(rendered.html.contains("<table>"));
+        assert!(rendered.html.contains(r#"style="text-align: center""#));
+        assert!(rendered.html.contains(r#"<code class="language-rust">"#));
+        assert!(rendered.html.contains("hl-"));
+        // Unknown languages are left as plain, escaped code
+        assert!(rendered.html.contains("&lt;x&gt;"));
+    }
+}
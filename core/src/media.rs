@@ -0,0 +1,345 @@
+//! Media library: uploaded files and what is known about them
+//!
+//! Files are written through the storage backend and recorded in the `media`
+//! table with their uploader, checksum and, for images, pixel dimensions. Which
+//! posts link to a file is tracked by the database from the file name, so the
+//! library can show where a file is used and which files are orphans. Resized
+//! copies of images (see [`crate::image_variants`]) are recorded with the image
+//! and deleted with it.
+//!
+//! With the `deduplicate_uploads` setting on, new files are stored under their
+//! SHA-256 digest and an upload identical to a file already in the library
+//! returns that file instead of storing another copy. Posts sharing the file all
+//! count as references to it, so it is kept until none of them links to it.
+
+use crate::api::admin_api::settings_helper::get_setting_value;
+use crate::dto::{Media, MediaVariant, NewMedia, NewMediaVariant};
+use crate::image_variants::EncodedVariant;
+use crate::repository::MediaRepository;
+use crate::storage::{StorageBackend, content_addressed_file_name, generate_file_name};
+use crate::upload_policy::CheckedType;
+use sea_orm::DatabaseConnection;
+use sha2::{Digest, Sha256};
+use std::path::Path;
+use std::sync::Arc;
+
+type MediaError = Box<dyn std::error::Error + Send + Sync>;
+
+/// How [`store`] names new files in storage
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum FileNaming {
+    /// A random name for every upload
+    #[default]
+    Unique,
+    /// The file's SHA-256 digest; identical files are stored and recorded once
+    ContentAddressed,
+}
+
+impl FileNaming {
+    /// The naming chosen by the `deduplicate_uploads` setting (unique names when unset)
+    pub async fn load(db: &Arc<DatabaseConnection>) -> Result<Self, sea_orm::DbErr> {
+        let deduplicate = get_setting_value(db, "deduplicate_uploads")
+            .await?
+            .is_some_and(|value| value.trim() == "true");
+        Ok(if deduplicate {
+            FileNaming::ContentAddressed
+        } else {
+            FileNaming::Unique
+        })
+    }
+}
+
+/// A file passed to [`store`]
+#[derive(Debug)]
+pub struct StoredMedia {
+    pub media: Media,
+    /// An identical file was in the library already and is returned instead;
+    /// nothing was written
+    pub existing: bool,
+}
+
+/// Hex-encoded SHA-256 digest of a file
+pub fn checksum(data: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(data))
+}
+
+/// Width and height of an image, read from its header; `None` for other files
+pub fn image_dimensions(data: &[u8]) -> Option<(i32, i32)> {
+    let size = imagesize::blob_size(data).ok()?;
+    Some((
+        i32::try_from(size.width).ok()?,
+        i32::try_from(size.height).ok()?,
+    ))
+}
+
+/// Name a storage backend gave a file, the last segment of its URL
+pub fn file_name_of(url: &str) -> &str {
+    url.rsplit('/').next().unwrap_or(url)
+}
+
+/// The file in the library with this checksum, when files are content-addressed
+pub async fn find_duplicate(
+    media_repository: &dyn MediaRepository,
+    naming: FileNaming,
+    checksum: &str,
+) -> Result<Option<Media>, MediaError> {
+    match naming {
+        FileNaming::Unique => Ok(None),
+        FileNaming::ContentAddressed => media_repository.find_by_checksum(checksum).await,
+    }
+}
+
+/// A file staged on local disk, such as an upload streamed to a temporary file,
+/// with the size and checksum computed while it was written
+#[derive(Debug)]
+pub struct StagedFile<'a> {
+    pub path: &'a Path,
+    pub size: u64,
+    pub checksum: String,
+}
+
+/// Write a file to storage and record it in the media library
+///
+/// The type comes from [`UploadPolicy::check`](crate::upload_policy::UploadPolicy::check),
+/// so only files the upload settings accept are stored. With [`FileNaming::ContentAddressed`], a file identical to one in the library
+/// is not written again; the existing file is returned.
+pub async fn store<SB: StorageBackend + ?Sized>(
+    storage: &SB,
+    media_repository: &dyn MediaRepository,
+    data: Vec<u8>,
+    original_name: String,
+    mime_type: CheckedType,
+    uploader_id: Option<i64>,
+    naming: FileNaming,
+) -> Result<StoredMedia, MediaError> {
+    let mime_type = mime_type.into_string();
+    let checksum = checksum(&data);
+    if let Some(media) = find_duplicate(media_repository, naming, &checksum).await? {
+        return Ok(StoredMedia {
+            media,
+            existing: true,
+        });
+    }
+    let dimensions = if mime_type.starts_with("image/") {
+        image_dimensions(&data)
+    } else {
+        None
+    };
+    let size = data.len() as i64;
+
+    let url = match naming {
+        FileNaming::Unique => {
+            storage
+                .upload_file(data, original_name.clone(), mime_type.clone())
+                .await?
+        }
+        FileNaming::ContentAddressed => {
+            let file_name = content_addressed_file_name(&checksum, &original_name);
+            storage
+                .put_file(data, &file_name, mime_type.clone())
+                .await?
+        }
+    };
+    record(
+        storage,
+        media_repository,
+        NewMedia {
+            file_name: file_name_of(&url).to_string(),
+            url,
+            original_name,
+            mime_type,
+            size,
+            width: dimensions.map(|(width, _)| width),
+            height: dimensions.map(|(_, height)| height),
+            checksum,
+            alt_text: None,
+            uploader_id,
+        },
+        naming,
+    )
+    .await
+}
+
+/// Write a staged file to storage without reading it into memory and record it
+/// in the media library
+///
+/// Like [`store`], but no dimensions are recorded; images go through [`store`].
+pub async fn store_staged<SB: StorageBackend + ?Sized>(
+    storage: &SB,
+    media_repository: &dyn MediaRepository,
+    staged: &StagedFile<'_>,
+    original_name: String,
+    mime_type: CheckedType,
+    uploader_id: Option<i64>,
+    naming: FileNaming,
+) -> Result<StoredMedia, MediaError> {
+    let mime_type = mime_type.into_string();
+    if let Some(media) = find_duplicate(media_repository, naming, &staged.checksum).await? {
+        return Ok(StoredMedia {
+            media,
+            existing: true,
+        });
+    }
+
+    let file_name = match naming {
+        FileNaming::Unique => generate_file_name(&original_name),
+        FileNaming::ContentAddressed => {
+            content_addressed_file_name(&staged.checksum, &original_name)
+        }
+    };
+    let url = storage
+        .put_file_from_path(staged.path, &file_name, mime_type.clone())
+        .await?;
+    record(
+        storage,
+        media_repository,
+        NewMedia {
+            file_name: file_name_of(&url).to_string(),
+            url,
+            original_name,
+            mime_type,
+            size: i64::try_from(staged.size)?,
+            width: None,
+            height: None,
+            checksum: staged.checksum.clone(),
+            alt_text: None,
+            uploader_id,
+        },
+        naming,
+    )
+    .await
+}
+
+/// Record a file just written to storage, deleting it again when that fails
+async fn record<SB: StorageBackend + ?Sized>(
+    storage: &SB,
+    media_repository: &dyn MediaRepository,
+    new_media: NewMedia,
+    naming: FileNaming,
+) -> Result<StoredMedia, MediaError> {
+    let file_name = new_media.file_name.clone();
+    let url = new_media.url.clone();
+    match media_repository.create(new_media).await {
+        Ok(media) => Ok(StoredMedia {
+            media,
+            existing: false,
+        }),
+        Err(e) => {
+            // The same file uploaded at the same time was recorded first; what
+            // was written is that file, which must stay
+            if naming == FileNaming::ContentAddressed
+                && let Ok(Some(media)) = media_repository.find_by_file_name(&file_name).await
+            {
+                return Ok(StoredMedia {
+                    media,
+                    existing: true,
+                });
+            }
+            // Don't leave a file behind that the library doesn't know about
+            if let Err(delete_error) = storage.delete_file(&url).await {
+                tracing::error!(
+                    "Failed to delete unrecorded upload {}: {}",
+                    url,
+                    delete_error
+                );
+            }
+            Err(e)
+        }
+    }
+}
+
+/// Write the generated variants of an image to storage and record them with it
+///
+/// When this fails, the variants recorded so far are deleted with the image by
+/// [`delete`].
+pub async fn store_variants<SB: StorageBackend + ?Sized>(
+    storage: &SB,
+    media_repository: &dyn MediaRepository,
+    media: &Media,
+    variants: Vec<EncodedVariant>,
+) -> Result<Vec<MediaVariant>, MediaError> {
+    let mut stored = Vec::with_capacity(variants.len());
+    for variant in variants {
+        let file_name = variant.file_name();
+        let size = variant.data.len() as i64;
+        let url = storage
+            .upload_file(variant.data, file_name, variant.mime_type.to_string())
+            .await?;
+        let recorded = media_repository
+            .create_variant(NewMediaVariant {
+                media_id: media.id,
+                kind: variant.kind,
+                file_name: file_name_of(&url).to_string(),
+                url: url.clone(),
+                mime_type: variant.mime_type.to_string(),
+                width: i32::try_from(variant.width)?,
+                height: i32::try_from(variant.height)?,
+                size,
+            })
+            .await;
+        match recorded {
+            Ok(recorded) => stored.push(recorded),
+            Err(e) => {
+                if let Err(delete_error) = storage.delete_file(&url).await {
+                    tracing::error!(
+                        "Failed to delete unrecorded variant {}: {}",
+                        url,
+                        delete_error
+                    );
+                }
+                return Err(e);
+            }
+        }
+    }
+    Ok(stored)
+}
+
+/// Delete a file and its variants from storage, then from the media library
+///
+/// The files go first, so a failure leaves the entry listed in the library.
+pub async fn delete<SB: StorageBackend + ?Sized>(
+    storage: &SB,
+    media_repository: &dyn MediaRepository,
+    media: &Media,
+) -> Result<(), MediaError> {
+    for variant in media_repository.find_variants(&[media.id]).await? {
+        storage.delete_file(&variant.url).await?;
+    }
+    storage.delete_file(&media.url).await?;
+    media_repository.delete(&media.id).await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 1x1 transparent PNG
+    const PNG: &[u8] = &[
+        0x89, 0x50, 0x4e, 0x47, 0x0d, 0x0a, 0x1a, 0x0a, 0x00, 0x00, 0x00, 0x0d, 0x49, 0x48, 0x44,
+        0x52, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x01, 0x08, 0x06, 0x00, 0x00, 0x00, 0x1f,
+        0x15, 0xc4, 0x89, 0x00, 0x00, 0x00, 0x0a, 0x49, 0x44, 0x41, 0x54, 0x78, 0x9c, 0x63, 0x00,
+        0x01, 0x00, 0x00, 0x05, 0x00, 0x01, 0x0d, 0x0a, 0x2d, 0xb4, 0x00, 0x00, 0x00, 0x00, 0x49,
+        0x45, 0x4e, 0x44, 0xae, 0x42, 0x60, 0x82,
+    ];
+
+    #[test]
+    fn test_checksum_is_hex_sha256() {
+        assert_eq!(
+            checksum(b"abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+
+    #[test]
+    fn test_image_dimensions() {
+        assert_eq!(image_dimensions(PNG), Some((1, 1)));
+        assert_eq!(image_dimensions(b"%PDF-1.7"), None);
+    }
+
+    #[test]
+    fn test_file_name_of() {
+        assert_eq!(file_name_of("/uploads/a.png"), "a.png");
+        assert_eq!(file_name_of("https://cdn.example.com/media/b.jpg"), "b.jpg");
+    }
+}
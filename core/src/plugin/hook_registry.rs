@@ -42,6 +42,81 @@ impl HookRegistry {
             },
         );
 
+        map.insert(
+            "list_tags".into(),
+            HookDef {
+                name: "list_tags",
+                required_perm: Some("post:list_tag"), // Must be able to list tags
+                description: "Allows listing of all tags, ordered by post count",
+            },
+        );
+
+        // === Post Status Hooks ===
+        // Fired after each status transition (receives post id, title and review note)
+        for (name, description) in [
+            (
+                "action_post_submitted",
+                "Triggered when a post is submitted for review",
+            ),
+            (
+                "action_post_withdrawn",
+                "Triggered when a post is withdrawn from review",
+            ),
+            (
+                "action_post_approved",
+                "Triggered when a post under review is approved",
+            ),
+            (
+                "action_post_rejected",
+                "Triggered when a post under review is rejected",
+            ),
+            (
+                "action_post_published",
+                "Triggered when a post is published",
+            ),
+            (
+                "action_post_scheduled",
+                "Triggered when a post is scheduled",
+            ),
+            (
+                "action_post_made_private",
+                "Triggered when a post is made private",
+            ),
+            (
+                "action_post_unpublished",
+                "Triggered when a post is turned back into a draft",
+            ),
+        ] {
+            map.insert(
+                name.into(),
+                HookDef {
+                    name,
+                    required_perm: Some("post:read"), // Must be able to read posts
+                    description,
+                },
+            );
+        }
+
+        // === Comment Hooks ===
+        // These hooks carry comment content and commenter details
+        map.insert(
+            "filter_comment_created".into(),
+            HookDef {
+                name: "filter_comment_created",
+                required_perm: Some("comment:write"), // Can rewrite content and status
+                description: "Triggered before a new comment is stored (can edit it or mark it as spam)",
+            },
+        );
+
+        map.insert(
+            "action_comment_created".into(),
+            HookDef {
+                name: "action_comment_created",
+                required_perm: Some("comment:read"), // Must be able to read comments
+                description: "Triggered after a new comment is stored",
+            },
+        );
+
         // === User-Related Hooks ===
         // These hooks carry user data
         map.insert(
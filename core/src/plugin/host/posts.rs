@@ -1,19 +1,43 @@
+use crate::dto::CategoryTreeNode;
+use crate::dto::category::build_category_tree;
 use crate::plugin::rustpress::plugin::posts::*;
 use crate::repository::post_repository::PostRepository;
 
+/// Flatten the category tree in pre-order, recording each category's depth
+fn flatten_categories(nodes: Vec<CategoryTreeNode>, depth: u32, out: &mut Vec<Category>) {
+    for node in nodes {
+        out.push(Category {
+            id: node.id,
+            parent_id: node.parent_id,
+            name: node.name,
+            slug: node.slug,
+            description: node.description,
+            post_count: node.post_count,
+            depth,
+        });
+        flatten_categories(node.children, depth + 1, out);
+    }
+}
+
 #[async_trait::async_trait]
 impl Host for super::super::PluginHostState {
-    async fn list_categories(&mut self) -> anyhow::Result<Vec<String>> {
+    async fn list_categories(&mut self) -> anyhow::Result<Vec<Category>> {
         let repo = crate::repository::PostgresPostRepository::new(self.db.as_ref().clone());
-        let mut categories = repo
+        let categories = repo
             .get_category_stats()
             .await
             .map_err(|e| anyhow::anyhow!(e))?;
 
-        categories.sort_by(|a, b| b.1.cmp(&a.1));
-        Ok(categories
-            .iter()
-            .map(|(category, _count)| category.clone())
-            .collect())
+        let mut flattened = Vec::new();
+        flatten_categories(build_category_tree(categories), 0, &mut flattened);
+        Ok(flattened)
+    }
+
+    async fn list_tags(&mut self) -> anyhow::Result<Vec<String>> {
+        let repo = crate::repository::PostgresPostRepository::new(self.db.as_ref().clone());
+        let mut tags = repo.get_tag_stats().await.map_err(|e| anyhow::anyhow!(e))?;
+
+        tags.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
+        Ok(tags.into_iter().map(|(tag, _count)| tag.name).collect())
     }
 }
@@ -40,6 +40,11 @@ impl PluginRegistry {
         }
     }
 
+    /// Directory holding the installed `.rpk` packages
+    pub fn install_dir(&self) -> &std::path::Path {
+        self.rpk_processor.install_dir()
+    }
+
     /// Load a plugin from RPK data
     pub async fn load_plugin_from_rpk_data(
         &self,
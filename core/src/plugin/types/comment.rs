@@ -0,0 +1,52 @@
+use crate::{
+    dto::{Comment, CommentStatus, NewComment},
+    plugin::exports::rustpress::plugin::event_handler::OnCommentData,
+};
+
+impl From<NewComment> for OnCommentData {
+    fn from(comment: NewComment) -> Self {
+        OnCommentData {
+            id: None,
+            post_id: comment.post_id,
+            parent_id: comment.parent_id,
+            author_id: comment.author_id,
+            author_name: comment.author_name,
+            author_email: comment.author_email,
+            author_url: comment.author_url,
+            content: comment.content,
+            status: comment.status.as_str().to_string(),
+            ip_address: comment.ip_address,
+            user_agent: comment.user_agent,
+        }
+    }
+}
+
+impl From<Comment> for OnCommentData {
+    fn from(comment: Comment) -> Self {
+        OnCommentData {
+            id: Some(comment.id),
+            post_id: comment.post_id,
+            parent_id: comment.parent_id,
+            author_id: comment.author_id,
+            author_name: comment.author_name,
+            author_email: comment.author_email,
+            author_url: comment.author_url,
+            content: comment.content,
+            status: comment.status.as_str().to_string(),
+            ip_address: comment.ip_address,
+            user_agent: comment.user_agent,
+        }
+    }
+}
+
+impl NewComment {
+    /// Take over the fields a `filter_comment_created` plugin may change
+    ///
+    /// Unknown statuses are ignored and keep the current status.
+    pub fn apply_filter(&mut self, data: OnCommentData) {
+        self.content = data.content;
+        if let Some(status) = CommentStatus::parse(&data.status) {
+            self.status = status;
+        }
+    }
+}
@@ -6,10 +6,16 @@ impl From<OnPostPublishedData> for CreatePostRequest {
     fn from(data: OnPostPublishedData) -> Self {
         CreatePostRequest {
             title: data.title,
+            slug: None,
             content: data.content,
             category: data.category.clone(),
+            category_id: None,
             author_id: data.author_id,
             description: data.description,
+            tags: data.tags,
+            status: None,
+            publish_at: None,
+            unpublish_at: None,
         }
     }
 }
@@ -22,6 +28,7 @@ impl From<CreatePostRequest> for OnPostPublishedData {
             category: data.category.clone(),
             author_id: data.author_id,
             description: data.description,
+            tags: data.tags,
         }
     }
 }
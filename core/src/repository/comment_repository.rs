@@ -0,0 +1,36 @@
+use crate::dto::{Comment, CommentListQuery, CommentStatus, NewComment};
+use async_trait::async_trait;
+
+/// Comment repository interface
+#[async_trait]
+pub trait CommentRepository: Send + Sync {
+    /// Approved comments on a post, oldest first
+    async fn find_approved_by_post(
+        &self,
+        post_id: &i64,
+    ) -> Result<Vec<Comment>, Box<dyn std::error::Error + Send + Sync>>;
+    /// One page of comments matching the query filters, newest first
+    /// Returns the comments on the requested page (1-based) and the total number of matches
+    async fn find_page(
+        &self,
+        query: &CommentListQuery,
+        page: u64,
+        per_page: u64,
+    ) -> Result<(Vec<Comment>, u64), Box<dyn std::error::Error + Send + Sync>>;
+    async fn find_by_id(
+        &self,
+        id: &i64,
+    ) -> Result<Option<Comment>, Box<dyn std::error::Error + Send + Sync>>;
+    async fn create(
+        &self,
+        comment: NewComment,
+    ) -> Result<Comment, Box<dyn std::error::Error + Send + Sync>>;
+    /// Set the status of the given comments, returning how many exist
+    async fn set_status(
+        &self,
+        ids: &[i64],
+        status: CommentStatus,
+    ) -> Result<u64, Box<dyn std::error::Error + Send + Sync>>;
+    /// Delete the given comments and their replies, returning how many of the given ids existed
+    async fn delete(&self, ids: &[i64]) -> Result<u64, Box<dyn std::error::Error + Send + Sync>>;
+}
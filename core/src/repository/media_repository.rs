@@ -0,0 +1,63 @@
+use crate::dto::{
+    Media, MediaListQuery, MediaUsage, MediaVariant, NewMedia, NewMediaVariant, UpdateMediaRequest,
+};
+use async_trait::async_trait;
+
+/// Media repository interface
+///
+/// References from posts to files are kept up to date by the database whenever a
+/// post's content or a file is written, by looking for the file name in the content.
+/// A link to one of an image's variants counts as a reference to the image.
+#[async_trait]
+pub trait MediaRepository: Send + Sync {
+    /// Record an uploaded file
+    async fn create(
+        &self,
+        media: NewMedia,
+    ) -> Result<Media, Box<dyn std::error::Error + Send + Sync>>;
+    /// Record a resized copy of an image
+    async fn create_variant(
+        &self,
+        variant: NewMediaVariant,
+    ) -> Result<MediaVariant, Box<dyn std::error::Error + Send + Sync>>;
+    async fn find_by_id(
+        &self,
+        id: &i64,
+    ) -> Result<Option<Media>, Box<dyn std::error::Error + Send + Sync>>;
+    /// The file stored under `file_name`, or the image one of whose variants is
+    async fn find_by_file_name(
+        &self,
+        file_name: &str,
+    ) -> Result<Option<Media>, Box<dyn std::error::Error + Send + Sync>>;
+    /// The earliest recorded file with the given SHA-256 checksum
+    async fn find_by_checksum(
+        &self,
+        checksum: &str,
+    ) -> Result<Option<Media>, Box<dyn std::error::Error + Send + Sync>>;
+    /// Variants of the given files, narrowest first
+    async fn find_variants(
+        &self,
+        media_ids: &[i64],
+    ) -> Result<Vec<MediaVariant>, Box<dyn std::error::Error + Send + Sync>>;
+    /// One page of files matching the query filters, newest first, with the number
+    /// of posts linking to each
+    /// Returns the files on the requested page (1-based) and the total number of matches
+    async fn find_page(
+        &self,
+        query: &MediaListQuery,
+        page: u64,
+        per_page: u64,
+    ) -> Result<(Vec<(Media, u64)>, u64), Box<dyn std::error::Error + Send + Sync>>;
+    /// Posts whose content links to a file, including trashed ones
+    async fn find_usages(
+        &self,
+        id: &i64,
+    ) -> Result<Vec<MediaUsage>, Box<dyn std::error::Error + Send + Sync>>;
+    async fn update(
+        &self,
+        id: &i64,
+        request: UpdateMediaRequest,
+    ) -> Result<Option<Media>, Box<dyn std::error::Error + Send + Sync>>;
+    /// Forget a file and its variants, after they were deleted from storage
+    async fn delete(&self, id: &i64) -> Result<bool, Box<dyn std::error::Error + Send + Sync>>;
+}
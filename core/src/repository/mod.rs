@@ -1,12 +1,20 @@
+pub mod comment_repository;
+pub mod media_repository;
 pub mod openai_api_key_repository;
 pub mod post_repository;
+pub mod postgres_comment_repository;
+pub mod postgres_media_repository;
 pub mod postgres_openai_api_key_repository;
 pub mod postgres_repository;
 pub mod user_repository;
 pub mod postgres_user_repository;
 
+pub use comment_repository::*;
+pub use media_repository::*;
 pub use openai_api_key_repository::*;
 pub use post_repository::*;
+pub use postgres_comment_repository::*;
+pub use postgres_media_repository::*;
 pub use postgres_openai_api_key_repository::*;
 pub use postgres_repository::*;
 pub use user_repository::*;
@@ -1,18 +1,67 @@
-use crate::dto::{Post, CreatePostRequest, PostVersion, PostDraft, SaveDraftRequest};
+use crate::dto::{Category, CreateCategoryRequest, ImportKey, ImportPostRequest, ImportRecord, Post, CreatePostRequest, PostListQuery, PostSearchHit, PostSearchQuery, PostVersion, PostDraft, SaveDraftRequest, SitemapEntry, Tag};
 use async_trait::async_trait;
+use sea_orm::prelude::DateTimeWithTimeZone;
+
+/// Error returned by [`PostRepository::update`] when the post was changed since it was read
+#[derive(Debug, thiserror::Error)]
+#[error("Post {} was modified concurrently (now at revision {})", current.id, current.revision)]
+pub struct RevisionConflict {
+    /// The post as currently stored
+    pub current: Post,
+}
+
+/// Error returned by [`PostRepository::create`] and [`PostRepository::update`] when another
+/// post already uses the requested slug
+#[derive(Debug, thiserror::Error)]
+#[error("Slug {slug} is already used by another post")]
+pub struct SlugTaken {
+    pub slug: String,
+}
 
 /// Post repository interface
 #[allow(unused)]
 #[async_trait]
 pub trait PostRepository: Send + Sync {
     async fn find_all(&self) -> Result<Vec<Post>, Box<dyn std::error::Error + Send + Sync>>;
+    /// Find one page of non-deleted posts matching the query filters
+    /// Returns the posts on the requested page (1-based) and the total number of matches
+    async fn find_page(
+        &self,
+        query: &PostListQuery,
+        page: u64,
+        per_page: u64,
+    ) -> Result<(Vec<Post>, u64), Box<dyn std::error::Error + Send + Sync>>;
+    /// Full-text search over published posts, ranked by relevance
+    /// Returns the hits on the requested page (1-based) and the total number of matches
+    async fn search(
+        &self,
+        query: &PostSearchQuery,
+        page: u64,
+        per_page: u64,
+    ) -> Result<(Vec<PostSearchHit>, u64), Box<dyn std::error::Error + Send + Sync>>;
     async fn find_by_id(
         &self,
         id: &i64,
     ) -> Result<Option<Post>, Box<dyn std::error::Error + Send + Sync>>;
+    /// Find a non-deleted post by its current slug
+    async fn find_by_slug(&self, slug: &str) -> Result<Option<Post>, Box<dyn std::error::Error + Send + Sync>>;
+    /// Find a non-deleted post by a slug it used before being renamed
+    async fn find_by_previous_slug(&self, slug: &str) -> Result<Option<Post>, Box<dyn std::error::Error + Send + Sync>>;
+    /// Non-deleted posts whose scheduled change is due: `scheduled` posts whose `publish_at`
+    /// and `published` posts whose `unpublish_at` is not after `now`
+    async fn find_schedule_due(&self, now: DateTimeWithTimeZone, limit: u64) -> Result<Vec<Post>, Box<dyn std::error::Error + Send + Sync>>;
+    /// Whether a post other than `except` currently uses `slug`
+    async fn is_slug_taken(&self, slug: &str, except: Option<i64>) -> Result<bool, Box<dyn std::error::Error + Send + Sync>>;
     /// Create a new post
     /// ID, created_at and updated_at are automatically generated, no need to provide in request
+    /// A slug generated from the title is made unique by appending `-2`, `-3`, ... when needed;
+    /// a requested slug is used as is and fails with [`SlugTaken`] if another post has it
     async fn create(&self, request: CreatePostRequest) -> Result<Post, Box<dyn std::error::Error + Send + Sync>>;
+    /// Update a post; when the slug changes, the old slug keeps redirecting to the post
+    ///
+    /// The update and the optional version snapshot are applied in one transaction, and only
+    /// if `post.revision` is still the stored revision; otherwise fails with [`RevisionConflict`].
+    /// Fails with [`SlugTaken`] if another post already uses the new slug.
     async fn update(
         &self,
         id: &i64,
@@ -24,7 +73,19 @@ pub trait PostRepository: Send + Sync {
     async fn delete(&self, id: &i64) -> Result<bool, Box<dyn std::error::Error + Send + Sync>>;
     async fn archive(&self, id: &i64) -> Result<bool, Box<dyn std::error::Error + Send + Sync>>;
     async fn restore(&self, id: &i64) -> Result<bool, Box<dyn std::error::Error + Send + Sync>>;
+    /// Permanently delete a post; its versions, drafts, tags and comments go with it
     async fn hard_delete(&self, id: &i64) -> Result<bool, Box<dyn std::error::Error + Send + Sync>>;
+
+    // Trash
+    /// One page of deleted posts, most recently deleted first
+    /// Returns the posts on the requested page (1-based) and the total number of deleted posts
+    async fn find_trash_page(&self, page: u64, per_page: u64) -> Result<(Vec<Post>, u64), Box<dyn std::error::Error + Send + Sync>>;
+    /// Deleted posts that were moved to the trash before `before`, oldest first
+    async fn find_trashed_before(&self, before: DateTimeWithTimeZone, limit: u64) -> Result<Vec<Post>, Box<dyn std::error::Error + Send + Sync>>;
+    /// Content of a post, of its versions and of its drafts
+    async fn find_post_contents(&self, post_id: &i64) -> Result<Vec<String>, Box<dyn std::error::Error + Send + Sync>>;
+    /// Whether the content of any post, version or draft contains `text`
+    async fn is_referenced_in_content(&self, text: &str) -> Result<bool, Box<dyn std::error::Error + Send + Sync>>;
     
     // Version management
     async fn get_versions(&self, post_id: &i64) -> Result<Vec<PostVersion>, Box<dyn std::error::Error + Send + Sync>>;
@@ -37,6 +98,40 @@ pub trait PostRepository: Send + Sync {
     async fn delete_draft(&self, post_id: Option<i64>, author_id: i64) -> Result<bool, Box<dyn std::error::Error + Send + Sync>>;
     async fn get_all_drafts(&self, author_id: i64) -> Result<Vec<PostDraft>, Box<dyn std::error::Error + Send + Sync>>;
 
-    // Category statistics
-    async fn get_category_stats(&self) -> Result<Vec<(String, i64)>, Box<dyn std::error::Error + Send + Sync>>;
+    // Category management
+    /// All categories with the number of published posts directly in each, ordered by name
+    async fn get_category_stats(&self) -> Result<Vec<(Category, i64)>, Box<dyn std::error::Error + Send + Sync>>;
+    async fn find_category_by_id(&self, id: &i64) -> Result<Option<Category>, Box<dyn std::error::Error + Send + Sync>>;
+    /// Find a category by slug or exact name
+    async fn find_category_by_name_or_slug(&self, name: &str) -> Result<Option<Category>, Box<dyn std::error::Error + Send + Sync>>;
+    /// Create a category; the slug is generated from the name when not given
+    async fn create_category(&self, request: CreateCategoryRequest) -> Result<Category, Box<dyn std::error::Error + Send + Sync>>;
+    async fn update_category(&self, id: &i64, category: Category) -> Result<Option<Category>, Box<dyn std::error::Error + Send + Sync>>;
+    /// Delete a category, moving its posts to `reassign_to` (or uncategorized)
+    /// and its child categories to its parent
+    async fn delete_category(&self, id: &i64, reassign_to: Option<i64>) -> Result<bool, Box<dyn std::error::Error + Send + Sync>>;
+
+    // Tag management
+    /// All tags with the number of published posts using each, ordered by name
+    async fn get_tag_stats(&self) -> Result<Vec<(Tag, i64)>, Box<dyn std::error::Error + Send + Sync>>;
+    async fn find_tag_by_id(&self, id: &i64) -> Result<Option<Tag>, Box<dyn std::error::Error + Send + Sync>>;
+    async fn find_tag_by_slug(&self, slug: &str) -> Result<Option<Tag>, Box<dyn std::error::Error + Send + Sync>>;
+    /// Rename a tag, regenerating its slug from the new name
+    async fn rename_tag(&self, id: &i64, name: String) -> Result<Option<Tag>, Box<dyn std::error::Error + Send + Sync>>;
+    /// Move all posts from the source tags to the target tag and delete the source tags
+    async fn merge_tags(&self, source_ids: &[i64], target_id: &i64) -> Result<Option<Tag>, Box<dyn std::error::Error + Send + Sync>>;
+
+    // Sitemaps
+    /// Number of pages listed in sitemaps: published posts, and categories and authors with published posts
+    async fn count_sitemap_entries(&self) -> Result<u64, Box<dyn std::error::Error + Send + Sync>>;
+    /// One slice of the sitemap entries: categories, then authors, then posts, each ordered by id
+    async fn find_sitemap_entries(&self, offset: u64, limit: u64) -> Result<Vec<SitemapEntry>, Box<dyn std::error::Error + Send + Sync>>;
+
+    // Imports
+    /// What an earlier import created for an item, if it was imported before
+    async fn find_import_record(&self, key: &ImportKey) -> Result<Option<ImportRecord>, Box<dyn std::error::Error + Send + Sync>>;
+    /// Remember what an import created for an item (posts record themselves in `import_post`)
+    async fn save_import_record(&self, key: &ImportKey, record: ImportRecord) -> Result<(), Box<dyn std::error::Error + Send + Sync>>;
+    /// Create a post with its versions and draft, keeping the source's timestamps, and record its import key
+    async fn import_post(&self, request: ImportPostRequest) -> Result<Post, Box<dyn std::error::Error + Send + Sync>>;
 }
@@ -0,0 +1,133 @@
+use crate::dto::{Comment, CommentListQuery, CommentStatus, NewComment};
+use crate::entity::comments;
+use crate::repository::CommentRepository;
+use async_trait::async_trait;
+use sea_orm::sea_query::Expr;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, PaginatorTrait, QueryFilter,
+    QueryOrder, Set,
+};
+use std::sync::Arc;
+
+/// PostgreSQL implementation of comment repository (using SeaORM)
+pub struct PostgresCommentRepository {
+    db: Arc<DatabaseConnection>,
+}
+
+impl PostgresCommentRepository {
+    pub fn new(db: DatabaseConnection) -> Self {
+        Self { db: Arc::new(db) }
+    }
+}
+
+#[async_trait]
+impl CommentRepository for PostgresCommentRepository {
+    async fn find_approved_by_post(
+        &self,
+        post_id: &i64,
+    ) -> Result<Vec<Comment>, Box<dyn std::error::Error + Send + Sync>> {
+        let models = comments::Entity::find()
+            .filter(comments::Column::PostId.eq(*post_id))
+            .filter(comments::Column::Status.eq(CommentStatus::Approved.as_str()))
+            .order_by_asc(comments::Column::CreatedAt)
+            .order_by_asc(comments::Column::Id)
+            .all(self.db.as_ref())
+            .await?;
+
+        Ok(models.into_iter().map(Comment::from).collect())
+    }
+
+    async fn find_page(
+        &self,
+        query: &CommentListQuery,
+        page: u64,
+        per_page: u64,
+    ) -> Result<(Vec<Comment>, u64), Box<dyn std::error::Error + Send + Sync>> {
+        let mut select = comments::Entity::find();
+        if let Some(status) = query.status {
+            select = select.filter(comments::Column::Status.eq(status.as_str()));
+        }
+        if let Some(post_id) = query.post_id {
+            select = select.filter(comments::Column::PostId.eq(post_id));
+        }
+
+        let paginator = select
+            .order_by_desc(comments::Column::CreatedAt)
+            .order_by_desc(comments::Column::Id)
+            .paginate(self.db.as_ref(), per_page);
+        let total = paginator.num_items().await?;
+        let models = paginator.fetch_page(page.saturating_sub(1)).await?;
+
+        Ok((models.into_iter().map(Comment::from).collect(), total))
+    }
+
+    async fn find_by_id(
+        &self,
+        id: &i64,
+    ) -> Result<Option<Comment>, Box<dyn std::error::Error + Send + Sync>> {
+        let model = comments::Entity::find_by_id(*id)
+            .one(self.db.as_ref())
+            .await?;
+
+        Ok(model.map(Comment::from))
+    }
+
+    async fn create(
+        &self,
+        comment: NewComment,
+    ) -> Result<Comment, Box<dyn std::error::Error + Send + Sync>> {
+        let active_model = comments::ActiveModel {
+            post_id: Set(comment.post_id),
+            parent_id: Set(comment.parent_id),
+            author_id: Set(comment.author_id),
+            author_name: Set(comment.author_name),
+            author_email: Set(comment.author_email),
+            author_url: Set(comment.author_url),
+            content: Set(comment.content),
+            status: Set(comment.status.as_str().to_string()),
+            ip_address: Set(comment.ip_address),
+            user_agent: Set(comment.user_agent),
+            // created_at and updated_at default to the current time
+            ..Default::default()
+        };
+
+        let model = active_model.insert(self.db.as_ref()).await?;
+        Ok(Comment::from(model))
+    }
+
+    async fn set_status(
+        &self,
+        ids: &[i64],
+        status: CommentStatus,
+    ) -> Result<u64, Box<dyn std::error::Error + Send + Sync>> {
+        if ids.is_empty() {
+            return Ok(0);
+        }
+
+        let result = comments::Entity::update_many()
+            .col_expr(comments::Column::Status, Expr::value(status.as_str()))
+            .filter(comments::Column::Id.is_in(ids.iter().copied()))
+            .exec(self.db.as_ref())
+            .await?;
+
+        Ok(result.rows_affected)
+    }
+
+    async fn delete(&self, ids: &[i64]) -> Result<u64, Box<dyn std::error::Error + Send + Sync>> {
+        if ids.is_empty() {
+            return Ok(0);
+        }
+
+        // Replies go with their parent through the ON DELETE CASCADE foreign key
+        let existing = comments::Entity::find()
+            .filter(comments::Column::Id.is_in(ids.iter().copied()))
+            .count(self.db.as_ref())
+            .await?;
+        comments::Entity::delete_many()
+            .filter(comments::Column::Id.is_in(ids.iter().copied()))
+            .exec(self.db.as_ref())
+            .await?;
+
+        Ok(existing)
+    }
+}
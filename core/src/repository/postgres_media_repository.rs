@@ -0,0 +1,275 @@
+use crate::dto::{
+    Media, MediaListQuery, MediaUsage, MediaVariant, NewMedia, NewMediaVariant, PostStatus,
+    UpdateMediaRequest,
+};
+use crate::entity::{media, media_references, media_variants, posts};
+use crate::repository::MediaRepository;
+use async_trait::async_trait;
+use sea_orm::sea_query::{Expr, Query};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, Condition, DatabaseConnection, EntityTrait, PaginatorTrait,
+    QueryFilter, QueryOrder, QuerySelect, Set,
+};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Files no post links to
+const ORPHANED_SQL: &str =
+    "NOT EXISTS (SELECT 1 FROM media_references r WHERE r.media_id = media.id)";
+
+/// PostgreSQL implementation of media repository (using SeaORM)
+pub struct PostgresMediaRepository {
+    db: Arc<DatabaseConnection>,
+}
+
+impl PostgresMediaRepository {
+    pub fn new(db: DatabaseConnection) -> Self {
+        Self { db: Arc::new(db) }
+    }
+}
+
+/// `ILIKE` pattern matching values that contain `text`
+fn contains_pattern(text: &str) -> String {
+    let escaped = text
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_");
+    format!("%{}%", escaped)
+}
+
+#[async_trait]
+impl MediaRepository for PostgresMediaRepository {
+    async fn create(
+        &self,
+        media: NewMedia,
+    ) -> Result<Media, Box<dyn std::error::Error + Send + Sync>> {
+        let active_model = media::ActiveModel {
+            url: Set(media.url),
+            file_name: Set(media.file_name),
+            original_name: Set(media.original_name),
+            mime_type: Set(media.mime_type),
+            size: Set(media.size),
+            width: Set(media.width),
+            height: Set(media.height),
+            checksum: Set(media.checksum),
+            alt_text: Set(media.alt_text),
+            uploader_id: Set(media.uploader_id),
+            // created_at and updated_at default to the current time
+            ..Default::default()
+        };
+
+        let model = active_model.insert(self.db.as_ref()).await?;
+        Ok(Media::from(model))
+    }
+
+    async fn create_variant(
+        &self,
+        variant: NewMediaVariant,
+    ) -> Result<MediaVariant, Box<dyn std::error::Error + Send + Sync>> {
+        let active_model = media_variants::ActiveModel {
+            media_id: Set(variant.media_id),
+            kind: Set(variant.kind.as_str().to_string()),
+            url: Set(variant.url),
+            file_name: Set(variant.file_name),
+            mime_type: Set(variant.mime_type),
+            width: Set(variant.width),
+            height: Set(variant.height),
+            size: Set(variant.size),
+            ..Default::default()
+        };
+
+        let model = active_model.insert(self.db.as_ref()).await?;
+        Ok(MediaVariant::from(model))
+    }
+
+    async fn find_by_id(
+        &self,
+        id: &i64,
+    ) -> Result<Option<Media>, Box<dyn std::error::Error + Send + Sync>> {
+        let model = media::Entity::find_by_id(*id).one(self.db.as_ref()).await?;
+
+        Ok(model.map(Media::from))
+    }
+
+    async fn find_by_file_name(
+        &self,
+        file_name: &str,
+    ) -> Result<Option<Media>, Box<dyn std::error::Error + Send + Sync>> {
+        let model = media::Entity::find()
+            .filter(
+                Condition::any()
+                    .add(media::Column::FileName.eq(file_name))
+                    .add(
+                        media::Column::Id.in_subquery(
+                            Query::select()
+                                .column(media_variants::Column::MediaId)
+                                .from(media_variants::Entity)
+                                .and_where(media_variants::Column::FileName.eq(file_name))
+                                .to_owned(),
+                        ),
+                    ),
+            )
+            .one(self.db.as_ref())
+            .await?;
+
+        Ok(model.map(Media::from))
+    }
+
+    async fn find_by_checksum(
+        &self,
+        checksum: &str,
+    ) -> Result<Option<Media>, Box<dyn std::error::Error + Send + Sync>> {
+        let model = media::Entity::find()
+            .filter(media::Column::Checksum.eq(checksum))
+            .order_by_asc(media::Column::Id)
+            .one(self.db.as_ref())
+            .await?;
+
+        Ok(model.map(Media::from))
+    }
+
+    async fn find_variants(
+        &self,
+        media_ids: &[i64],
+    ) -> Result<Vec<MediaVariant>, Box<dyn std::error::Error + Send + Sync>> {
+        if media_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+        let models = media_variants::Entity::find()
+            .filter(media_variants::Column::MediaId.is_in(media_ids.iter().copied()))
+            .order_by_asc(media_variants::Column::MediaId)
+            .order_by_asc(media_variants::Column::Width)
+            .order_by_asc(media_variants::Column::Id)
+            .all(self.db.as_ref())
+            .await?;
+
+        Ok(models.into_iter().map(MediaVariant::from).collect())
+    }
+
+    async fn find_page(
+        &self,
+        query: &MediaListQuery,
+        page: u64,
+        per_page: u64,
+    ) -> Result<(Vec<(Media, u64)>, u64), Box<dyn std::error::Error + Send + Sync>> {
+        let mut select = media::Entity::find();
+        if let Some(q) = query.q.as_deref().map(str::trim).filter(|q| !q.is_empty()) {
+            let pattern = contains_pattern(q);
+            select = select.filter(Expr::cust_with_values(
+                "(media.original_name ILIKE $1 OR media.file_name ILIKE $2 OR media.alt_text ILIKE $3)",
+                [pattern.clone(), pattern.clone(), pattern],
+            ));
+        }
+        if let Some(mime_type) = query
+            .mime_type
+            .as_deref()
+            .map(str::trim)
+            .filter(|mime_type| !mime_type.is_empty())
+        {
+            select = if mime_type.ends_with('/') {
+                select.filter(media::Column::MimeType.starts_with(mime_type))
+            } else {
+                select.filter(media::Column::MimeType.eq(mime_type))
+            };
+        }
+        if let Some(uploader_id) = query.uploader_id {
+            select = select.filter(media::Column::UploaderId.eq(uploader_id));
+        }
+        match query.orphaned {
+            Some(true) => select = select.filter(Expr::cust(ORPHANED_SQL)),
+            Some(false) => select = select.filter(Expr::cust(ORPHANED_SQL).not()),
+            None => {}
+        }
+
+        let paginator = select
+            .order_by_desc(media::Column::CreatedAt)
+            .order_by_desc(media::Column::Id)
+            .paginate(self.db.as_ref(), per_page);
+        let total = paginator.num_items().await?;
+        let models = paginator.fetch_page(page.saturating_sub(1)).await?;
+
+        let ids: Vec<i64> = models.iter().map(|model| model.id).collect();
+        let counts: HashMap<i64, i64> = if ids.is_empty() {
+            HashMap::new()
+        } else {
+            media_references::Entity::find()
+                .select_only()
+                .column(media_references::Column::MediaId)
+                .column_as(media_references::Column::PostId.count(), "count")
+                .filter(media_references::Column::MediaId.is_in(ids))
+                .group_by(media_references::Column::MediaId)
+                .into_tuple::<(i64, i64)>()
+                .all(self.db.as_ref())
+                .await?
+                .into_iter()
+                .collect()
+        };
+
+        let items = models
+            .into_iter()
+            .map(|model| {
+                let count = counts.get(&model.id).copied().unwrap_or(0) as u64;
+                (Media::from(model), count)
+            })
+            .collect();
+        Ok((items, total))
+    }
+
+    async fn find_usages(
+        &self,
+        id: &i64,
+    ) -> Result<Vec<MediaUsage>, Box<dyn std::error::Error + Send + Sync>> {
+        let models = posts::Entity::find()
+            .filter(
+                posts::Column::Id.in_subquery(
+                    Query::select()
+                        .column(media_references::Column::PostId)
+                        .from(media_references::Entity)
+                        .and_where(media_references::Column::MediaId.eq(*id))
+                        .to_owned(),
+                ),
+            )
+            .order_by_desc(posts::Column::UpdatedAt)
+            .all(self.db.as_ref())
+            .await?;
+
+        Ok(models
+            .into_iter()
+            .map(|post| MediaUsage {
+                post_id: post.id,
+                title: post.title,
+                slug: post.slug,
+                status: PostStatus::parse(&post.status).unwrap_or_default(),
+                deleted: post.deleted_at.is_some(),
+            })
+            .collect())
+    }
+
+    async fn update(
+        &self,
+        id: &i64,
+        request: UpdateMediaRequest,
+    ) -> Result<Option<Media>, Box<dyn std::error::Error + Send + Sync>> {
+        let Some(model) = media::Entity::find_by_id(*id).one(self.db.as_ref()).await? else {
+            return Ok(None);
+        };
+
+        let mut active_model: media::ActiveModel = model.into();
+        if let Some(alt_text) = request.alt_text {
+            let alt_text = alt_text.trim();
+            active_model.alt_text = Set((!alt_text.is_empty()).then(|| alt_text.to_string()));
+        }
+        if let Some(original_name) = request.original_name {
+            active_model.original_name = Set(original_name.trim().to_string());
+        }
+        let model = active_model.update(self.db.as_ref()).await?;
+        Ok(Some(Media::from(model)))
+    }
+
+    async fn delete(&self, id: &i64) -> Result<bool, Box<dyn std::error::Error + Send + Sync>> {
+        let result = media::Entity::delete_by_id(*id)
+            .exec(self.db.as_ref())
+            .await?;
+        Ok(result.rows_affected > 0)
+    }
+}
@@ -1,13 +1,145 @@
-use crate::dto::{CreatePostRequest, Post, PostDraft, PostVersion, SaveDraftRequest};
-use crate::entity::{post_drafts, post_versions, posts};
-use crate::repository::PostRepository;
+use crate::dto::tag::normalize_tag_names;
+use crate::dto::{
+    Category, CreateCategoryRequest, CreatePostRequest, ImportKey, ImportPostRequest, ImportRecord,
+    Post, PostDraft, PostListQuery, PostSearchHit, PostSearchQuery, PostSortField, PostStatus,
+    PostVersion, SaveDraftRequest, SitemapEntry, SitemapEntryKind, SortOrder, Tag,
+};
+use crate::entity::{
+    categories, import_records, post_drafts, post_slug_history, post_tags, post_versions, posts,
+    tags,
+};
+use crate::repository::{PostRepository, RevisionConflict, SlugTaken};
+use crate::slug::{post_slug, slugify, unique_slug};
 use async_trait::async_trait;
 use chrono::Utc;
+use sea_orm::prelude::DateTimeWithTimeZone;
+use sea_orm::sea_query::{Expr, OnConflict, Query};
 use sea_orm::{
-    ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder, Set,
+    ActiveModelTrait, ColumnTrait, Condition, ConnectionTrait, DatabaseConnection, DbBackend,
+    DbErr, EntityTrait, FromQueryResult, Order, PaginatorTrait, QueryFilter, QueryOrder,
+    QuerySelect, Set, SqlErr, Statement, TransactionTrait,
 };
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
+/// Text search configuration used by the `posts.search_vector` generated column
+const SEARCH_CONFIG: &str = "english";
+/// Markers used by `ts_headline` around matched terms, replaced with `<mark>` after escaping
+const HIGHLIGHT_START: &str = "\u{1}";
+const HIGHLIGHT_STOP: &str = "\u{2}";
+
+/// Ids of the category with slug `$param` and all of its descendants
+fn category_subtree_sql(param: &str) -> String {
+    format!(
+        "WITH RECURSIVE subtree AS (
+             SELECT id FROM categories WHERE slug = {param}
+             UNION
+             SELECT c.id FROM categories c JOIN subtree ON c.parent_id = subtree.id
+         )
+         SELECT id FROM subtree",
+        param = param
+    )
+}
+
+/// Raw row returned by the full-text search query
+#[derive(Debug, FromQueryResult)]
+struct SearchRow {
+    id: i64,
+    title: String,
+    slug: String,
+    title_highlight: String,
+    snippet: String,
+    category: Option<String>,
+    category_id: Option<i64>,
+    description: Option<String>,
+    author_id: i64,
+    rank: f32,
+    created_at: DateTimeWithTimeZone,
+    updated_at: DateTimeWithTimeZone,
+}
+
+#[derive(Debug, FromQueryResult)]
+struct CountRow {
+    count: i64,
+}
+
+/// Category row with its post count, returned by the category statistics query
+#[derive(Debug, FromQueryResult)]
+struct CategoryCountRow {
+    id: i64,
+    name: String,
+    slug: String,
+    description: Option<String>,
+    parent_id: Option<i64>,
+    created_at: DateTimeWithTimeZone,
+    updated_at: DateTimeWithTimeZone,
+    post_count: i64,
+}
+
+/// Row of the sitemap entries query
+#[derive(Debug, FromQueryResult)]
+struct SitemapRow {
+    kind: String,
+    key: String,
+    updated_at: DateTimeWithTimeZone,
+}
+
+/// Pages listed in sitemaps, in sitemap order (`ord`, then `id`)
+///
+/// Categories and authors are only listed when they have published posts, with the
+/// latest update of those posts as their `updated_at`.
+const SITEMAP_ENTRIES_SQL: &str = r#"
+    SELECT 1 AS ord, c.id, 'category' AS kind, c.slug AS key, MAX(p.updated_at) AS updated_at
+    FROM categories c
+    JOIN posts p ON p.category_id = c.id AND p.deleted_at IS NULL AND p.status = 'published'
+    GROUP BY c.id
+    UNION ALL
+    SELECT 2, u.id, 'author', u.username, MAX(p.updated_at)
+    FROM users u
+    JOIN posts p ON p.author_id = u.id AND p.deleted_at IS NULL AND p.status = 'published'
+    GROUP BY u.id
+    UNION ALL
+    SELECT 3, p.id, 'post', p.slug, p.updated_at
+    FROM posts p
+    WHERE p.deleted_at IS NULL AND p.status = 'published'"#;
+
+/// Tag row with its post count, returned by the tag statistics query
+#[derive(Debug, FromQueryResult)]
+struct TagCountRow {
+    id: i64,
+    name: String,
+    slug: String,
+    created_at: DateTimeWithTimeZone,
+    post_count: i64,
+}
+
+/// HTML-escape a `ts_headline` result and turn the highlight markers into `<mark>` tags
+fn highlight_to_html(raw: &str) -> String {
+    let mut html = String::with_capacity(raw.len());
+    for c in raw.chars() {
+        match c {
+            '&' => html.push_str("&amp;"),
+            '<' => html.push_str("&lt;"),
+            '>' => html.push_str("&gt;"),
+            '"' => html.push_str("&quot;"),
+            '\'' => html.push_str("&#39;"),
+            _ => html.push(c),
+        }
+    }
+    html.replace(HIGHLIGHT_START, "<mark>")
+        .replace(HIGHLIGHT_STOP, "</mark>")
+}
+
+/// [`SlugTaken`] for a post write rejected by the unique index on `posts.slug`
+fn slug_taken(error: DbErr, slug: &str) -> Box<dyn std::error::Error + Send + Sync> {
+    match error.sql_err() {
+        Some(SqlErr::UniqueConstraintViolation(_)) => Box::new(SlugTaken {
+            slug: slug.to_string(),
+        }),
+        _ => Box::new(error),
+    }
+}
+
 /// PostgreSQL implementation of post repository (using SeaORM)
 pub struct PostgresPostRepository {
     db: Arc<DatabaseConnection>,
@@ -17,6 +149,260 @@ impl PostgresPostRepository {
     pub fn new(db: DatabaseConnection) -> Self {
         Self { db: Arc::new(db) }
     }
+
+    /// Load tag names for the given posts, keyed by post id (names sorted alphabetically)
+    async fn load_tags<C: ConnectionTrait>(
+        db: &C,
+        post_ids: &[i64],
+    ) -> Result<HashMap<i64, Vec<String>>, DbErr> {
+        let mut tags_by_post: HashMap<i64, Vec<String>> = HashMap::new();
+        if post_ids.is_empty() {
+            return Ok(tags_by_post);
+        }
+
+        let rows = post_tags::Entity::find()
+            .filter(post_tags::Column::PostId.is_in(post_ids.iter().copied()))
+            .find_also_related(tags::Entity)
+            .order_by_asc(tags::Column::Name)
+            .all(db)
+            .await?;
+
+        for (link, tag) in rows {
+            if let Some(tag) = tag {
+                tags_by_post.entry(link.post_id).or_default().push(tag.name);
+            }
+        }
+        Ok(tags_by_post)
+    }
+
+    /// Load category names for the given category ids
+    async fn load_category_names(
+        &self,
+        category_ids: impl IntoIterator<Item = Option<i64>>,
+    ) -> Result<HashMap<i64, String>, DbErr> {
+        let mut ids: Vec<i64> = category_ids.into_iter().flatten().collect();
+        ids.sort_unstable();
+        ids.dedup();
+        if ids.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let models = categories::Entity::find()
+            .filter(categories::Column::Id.is_in(ids))
+            .all(self.db.as_ref())
+            .await?;
+        Ok(models
+            .into_iter()
+            .map(|model| (model.id, model.name))
+            .collect())
+    }
+
+    /// Fill in `tags` and the category name for a batch of posts
+    async fn with_relations(&self, mut posts: Vec<Post>) -> Result<Vec<Post>, DbErr> {
+        let ids: Vec<i64> = posts.iter().map(|post| post.id).collect();
+        let mut tags_by_post = Self::load_tags(self.db.as_ref(), &ids).await?;
+        let category_names = self
+            .load_category_names(posts.iter().map(|post| post.category_id))
+            .await?;
+        for post in posts.iter_mut() {
+            post.tags = tags_by_post.remove(&post.id).unwrap_or_default();
+            post.category = post
+                .category_id
+                .and_then(|id| category_names.get(&id).cloned());
+        }
+        Ok(posts)
+    }
+
+    /// Fill in `tags` and the category name for a single post
+    async fn with_relations_one(&self, post: Post) -> Result<Post, DbErr> {
+        let mut posts = self.with_relations(vec![post]).await?;
+        Ok(posts.remove(0))
+    }
+
+    /// Fill in category names for a batch of versions
+    async fn versions_with_categories(
+        &self,
+        mut versions: Vec<PostVersion>,
+    ) -> Result<Vec<PostVersion>, DbErr> {
+        let names = self
+            .load_category_names(versions.iter().map(|version| version.category_id))
+            .await?;
+        for version in versions.iter_mut() {
+            version.category = version.category_id.and_then(|id| names.get(&id).cloned());
+        }
+        Ok(versions)
+    }
+
+    /// Fill in category names for a batch of drafts
+    async fn drafts_with_categories(
+        &self,
+        mut drafts: Vec<PostDraft>,
+    ) -> Result<Vec<PostDraft>, DbErr> {
+        let names = self
+            .load_category_names(drafts.iter().map(|draft| draft.category_id))
+            .await?;
+        for draft in drafts.iter_mut() {
+            draft.category = draft.category_id.and_then(|id| names.get(&id).cloned());
+        }
+        Ok(drafts)
+    }
+
+    /// First free slug for `base` (`base`, `base-2`, ...), ignoring post `except`
+    async fn available_slug<C: ConnectionTrait>(
+        db: &C,
+        base: &str,
+        except: Option<i64>,
+    ) -> Result<String, DbErr> {
+        let mut select = posts::Entity::find().filter(
+            Condition::any()
+                .add(posts::Column::Slug.eq(base))
+                .add(posts::Column::Slug.like(format!("{}-%", base))),
+        );
+        if let Some(id) = except {
+            select = select.filter(posts::Column::Id.ne(id));
+        }
+        let taken: HashSet<String> = select
+            .all(db)
+            .await?
+            .into_iter()
+            .map(|model| model.slug)
+            .collect();
+
+        Ok(unique_slug(base, &taken))
+    }
+
+    /// Snapshot the stored state of a post as its next version
+    async fn insert_version<C: ConnectionTrait>(
+        db: &C,
+        post: &posts::Model,
+        user_id: i64,
+        change_note: Option<String>,
+    ) -> Result<(), DbErr> {
+        let max_version = post_versions::Entity::find()
+            .filter(post_versions::Column::PostId.eq(post.id))
+            .order_by_desc(post_versions::Column::VersionNumber)
+            .one(db)
+            .await?;
+
+        let next_version = match max_version {
+            Some(v) => v.version_number + 1,
+            None => 1,
+        };
+
+        post_versions::ActiveModel {
+            post_id: Set(post.id),
+            title: Set(post.title.clone()),
+            content: Set(post.content.clone()),
+            category_id: Set(post.category_id),
+            description: Set(post.description.clone()),
+            version_number: Set(next_version),
+            created_by: Set(user_id),
+            change_note: Set(change_note),
+            ..Default::default()
+        }
+        .insert(db)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Record what an import created for an item
+    async fn insert_import_record<C: ConnectionTrait>(
+        db: &C,
+        key: &ImportKey,
+        record: ImportRecord,
+    ) -> Result<(), DbErr> {
+        import_records::ActiveModel {
+            source: Set(key.source.clone()),
+            kind: Set(key.kind.clone()),
+            source_id: Set(key.source_id.clone()),
+            target_id: Set(record.target_id),
+            target_url: Set(record.target_url),
+            ..Default::default()
+        }
+        .insert(db)
+        .await?;
+        Ok(())
+    }
+
+    /// Point `new_slug` at a post, recording `old_slug` as a redirect to it
+    async fn record_slug_change<C: ConnectionTrait>(
+        db: &C,
+        post_id: i64,
+        old_slug: &str,
+        new_slug: &str,
+    ) -> Result<(), DbErr> {
+        // A slug in use by a live post no longer redirects anywhere else
+        post_slug_history::Entity::delete_by_id(new_slug.to_string())
+            .exec(db)
+            .await?;
+
+        post_slug_history::Entity::insert(post_slug_history::ActiveModel {
+            slug: Set(old_slug.to_string()),
+            post_id: Set(post_id),
+            ..Default::default()
+        })
+        .on_conflict(
+            OnConflict::column(post_slug_history::Column::Slug)
+                .update_columns([
+                    post_slug_history::Column::PostId,
+                    post_slug_history::Column::CreatedAt,
+                ])
+                .to_owned(),
+        )
+        .exec_without_returning(db)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Replace a post's tags, creating tags that don't exist yet
+    ///
+    /// Names are matched by slug, so an existing tag keeps its current spelling.
+    /// Returns the resulting tag names sorted alphabetically.
+    async fn set_post_tags<C: ConnectionTrait>(
+        db: &C,
+        post_id: i64,
+        names: &[String],
+    ) -> Result<Vec<String>, DbErr> {
+        post_tags::Entity::delete_many()
+            .filter(post_tags::Column::PostId.eq(post_id))
+            .exec(db)
+            .await?;
+
+        let normalized = normalize_tag_names(names);
+        if normalized.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        tags::Entity::insert_many(normalized.iter().map(|(name, slug)| tags::ActiveModel {
+            name: Set(name.clone()),
+            slug: Set(slug.clone()),
+            ..Default::default()
+        }))
+        .on_conflict(
+            OnConflict::column(tags::Column::Slug)
+                .do_nothing()
+                .to_owned(),
+        )
+        .exec_without_returning(db)
+        .await?;
+
+        let tag_models = tags::Entity::find()
+            .filter(tags::Column::Slug.is_in(normalized.into_iter().map(|(_, slug)| slug)))
+            .order_by_asc(tags::Column::Name)
+            .all(db)
+            .await?;
+
+        post_tags::Entity::insert_many(tag_models.iter().map(|tag| post_tags::ActiveModel {
+            post_id: Set(post_id),
+            tag_id: Set(tag.id),
+        }))
+        .exec_without_returning(db)
+        .await?;
+
+        Ok(tag_models.into_iter().map(|tag| tag.name).collect())
+    }
 }
 
 #[async_trait]
@@ -28,7 +414,191 @@ impl PostRepository for PostgresPostRepository {
             .all(self.db.as_ref())
             .await?;
 
-        Ok(models.into_iter().map(Post::from).collect())
+        Ok(self
+            .with_relations(models.into_iter().map(Post::from).collect())
+            .await?)
+    }
+
+    async fn find_page(
+        &self,
+        query: &PostListQuery,
+        page: u64,
+        per_page: u64,
+    ) -> Result<(Vec<Post>, u64), Box<dyn std::error::Error + Send + Sync>> {
+        let mut select = posts::Entity::find().filter(posts::Column::DeletedAt.is_null());
+
+        if let Some(category) = &query.category {
+            select = select.filter(Expr::cust_with_values(
+                format!("posts.category_id IN ({})", category_subtree_sql("$1")),
+                [category.as_str()],
+            ));
+        }
+        if let Some(author_id) = query.author_id {
+            select = select.filter(posts::Column::AuthorId.eq(author_id));
+        }
+        if let Some(tag) = &query.tag {
+            select = select.filter(
+                posts::Column::Id.in_subquery(
+                    Query::select()
+                        .column((post_tags::Entity, post_tags::Column::PostId))
+                        .from(post_tags::Entity)
+                        .inner_join(
+                            tags::Entity,
+                            Expr::col((tags::Entity, tags::Column::Id))
+                                .equals((post_tags::Entity, post_tags::Column::TagId)),
+                        )
+                        .and_where(Expr::col((tags::Entity, tags::Column::Slug)).eq(tag.as_str()))
+                        .to_owned(),
+                ),
+            );
+        }
+        if let Some(from) = query.from {
+            select = select.filter(posts::Column::CreatedAt.gte(from));
+        }
+        if let Some(to) = query.to {
+            select = select.filter(posts::Column::CreatedAt.lte(to));
+        }
+        match query.archived {
+            Some(true) => select = select.filter(posts::Column::ArchivedAt.is_not_null()),
+            Some(false) => select = select.filter(posts::Column::ArchivedAt.is_null()),
+            None => {}
+        }
+        if let Some(status) = query.status {
+            select = select.filter(posts::Column::Status.eq(status.as_str()));
+        }
+
+        let order = match query.order {
+            SortOrder::Asc => Order::Asc,
+            SortOrder::Desc => Order::Desc,
+        };
+        let sort_column = match query.sort {
+            PostSortField::CreatedAt => posts::Column::CreatedAt,
+            PostSortField::UpdatedAt => posts::Column::UpdatedAt,
+            PostSortField::Title => posts::Column::Title,
+        };
+        // Tie-break on id so pages are stable when sort values are equal
+        let paginator = select
+            .order_by(sort_column, order.clone())
+            .order_by(posts::Column::Id, order)
+            .paginate(self.db.as_ref(), per_page);
+
+        let total = paginator.num_items().await?;
+        let models = paginator.fetch_page(page.saturating_sub(1)).await?;
+        let posts = self
+            .with_relations(models.into_iter().map(Post::from).collect())
+            .await?;
+
+        Ok((posts, total))
+    }
+
+    async fn search(
+        &self,
+        query: &PostSearchQuery,
+        page: u64,
+        per_page: u64,
+    ) -> Result<(Vec<PostSearchHit>, u64), Box<dyn std::error::Error + Send + Sync>> {
+        // $1 = query text; filters are appended as further parameters
+        let mut values: Vec<sea_orm::Value> = vec![query.q.clone().into()];
+        let mut conditions = vec![
+            "p.deleted_at IS NULL".to_string(),
+            "p.status = 'published'".to_string(),
+            "p.search_vector @@ q.query".to_string(),
+        ];
+        if let Some(category) = &query.category {
+            values.push(category.clone().into());
+            conditions.push(format!(
+                "p.category_id IN ({})",
+                category_subtree_sql(&format!("${}", values.len()))
+            ));
+        }
+        if let Some(author_id) = query.author_id {
+            values.push(author_id.into());
+            conditions.push(format!("p.author_id = ${}", values.len()));
+        }
+        let from_where = format!(
+            "FROM posts p LEFT JOIN categories c ON c.id = p.category_id, \
+             websearch_to_tsquery('{}', $1) AS q(query) WHERE {}",
+            SEARCH_CONFIG,
+            conditions.join(" AND ")
+        );
+
+        let count = CountRow::find_by_statement(Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            format!("SELECT COUNT(*) AS count {}", from_where),
+            values.clone(),
+        ))
+        .one(self.db.as_ref())
+        .await?
+        .map(|row| row.count as u64)
+        .unwrap_or(0);
+
+        if count == 0 {
+            return Ok((Vec::new(), 0));
+        }
+
+        let headline_options = format!(
+            "StartSel=\"{}\", StopSel=\"{}\", MaxWords=35, MinWords=15, MaxFragments=2",
+            HIGHLIGHT_START, HIGHLIGHT_STOP
+        );
+        let title_options = format!(
+            "StartSel=\"{}\", StopSel=\"{}\", HighlightAll=true",
+            HIGHLIGHT_START, HIGHLIGHT_STOP
+        );
+        let mut page_values = values.clone();
+        page_values.push(title_options.into());
+        let title_options_idx = page_values.len();
+        page_values.push(headline_options.into());
+        let headline_options_idx = page_values.len();
+        page_values.push((per_page as i64).into());
+        let limit_idx = page_values.len();
+        page_values.push((page.saturating_sub(1).saturating_mul(per_page) as i64).into());
+        let offset_idx = page_values.len();
+
+        let sql = format!(
+            r#"SELECT p.id, p.title, p.slug, c.name AS category, p.category_id, p.description, p.author_id,
+                      p.created_at, p.updated_at,
+                      ts_rank(p.search_vector, q.query) AS rank,
+                      ts_headline('{config}', p.title, q.query, ${title_opts}) AS title_highlight,
+                      ts_headline('{config}', coalesce(p.description, '') || ' ' || p.content,
+                                  q.query, ${headline_opts}) AS snippet
+               {from_where}
+               ORDER BY rank DESC, p.created_at DESC, p.id DESC
+               LIMIT ${limit} OFFSET ${offset}"#,
+            config = SEARCH_CONFIG,
+            title_opts = title_options_idx,
+            headline_opts = headline_options_idx,
+            from_where = from_where,
+            limit = limit_idx,
+            offset = offset_idx,
+        );
+
+        let rows = SearchRow::find_by_statement(Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            sql,
+            page_values,
+        ))
+        .all(self.db.as_ref())
+        .await?;
+
+        let hits = rows
+            .into_iter()
+            .map(|row| PostSearchHit {
+                id: row.id,
+                title: row.title,
+                slug: row.slug,
+                title_highlight: highlight_to_html(&row.title_highlight),
+                snippet: highlight_to_html(&row.snippet),
+                category: row.category,
+                category_id: row.category_id,
+                description: row.description,
+                author_id: row.author_id,
+                rank: row.rank,
+                created_at: row.created_at.into(),
+                updated_at: row.updated_at.into(),
+            })
+            .collect();
+
+        Ok((hits, count))
     }
 
     async fn find_by_id(
@@ -37,7 +607,10 @@ impl PostRepository for PostgresPostRepository {
     ) -> Result<Option<Post>, Box<dyn std::error::Error + Send + Sync>> {
         let model = posts::Entity::find_by_id(*id).one(self.db.as_ref()).await?;
 
-        Ok(model.map(Post::from))
+        match model {
+            Some(model) => Ok(Some(self.with_relations_one(Post::from(model)).await?)),
+            None => Ok(None),
+        }
     }
 
     async fn create(
@@ -46,21 +619,42 @@ impl PostRepository for PostgresPostRepository {
     ) -> Result<Post, Box<dyn std::error::Error + Send + Sync>> {
         // ID, created_at and updated_at are automatically generated by ActiveModelBehavior
         // Only need to set business fields
+        // Insert the post and its tags together
+        let txn = self.db.begin().await?;
+
+        // Only slugs generated from the title get a suffix; a requested slug is kept as is
+        let slug = match request.slug.as_deref() {
+            Some(slug) => post_slug(slug),
+            None => Self::available_slug(&txn, &post_slug(&request.title), None).await?,
+        };
+        post_slug_history::Entity::delete_by_id(slug.clone())
+            .exec(&txn)
+            .await?;
+
         let active_model = posts::ActiveModel {
             // id is not set, automatically generated by ActiveModelBehavior::before_save as UUID
             title: Set(request.title),
+            slug: Set(slug.clone()),
             content: Set(request.content),
-            category: Set(request.category),
+            category_id: Set(request.category_id),
             author_id: Set(request.author_id),
             description: Set(request.description),
+            status: Set(request.status.unwrap_or_default().as_str().to_string()),
+            publish_at: Set(request.publish_at.map(Into::into)),
+            unpublish_at: Set(request.unpublish_at.map(Into::into)),
             // created_at and updated_at are automatically set by ActiveModelBehavior
             // archived_at and deleted_at default to None
             ..Default::default()
         };
 
-        let model = active_model.insert(self.db.as_ref()).await?;
+        let model = active_model
+            .insert(&txn)
+            .await
+            .map_err(|e| slug_taken(e, &slug))?;
+        Self::set_post_tags(&txn, model.id, &request.tags).await?;
+        txn.commit().await?;
 
-        Ok(Post::from(model))
+        Ok(self.with_relations_one(Post::from(model)).await?)
     }
 
     async fn update(
@@ -71,52 +665,131 @@ impl PostRepository for PostgresPostRepository {
         change_note: Option<String>,
         user_id: i64,
     ) -> Result<Option<Post>, Box<dyn std::error::Error + Send + Sync>> {
-        let model = posts::Entity::find_by_id(*id).one(self.db.as_ref()).await?;
+        let txn = self.db.begin().await?;
 
-        if let Some(old_model) = model {
-            // Create version before update if requested
-            if create_version {
-                // Get the next version number
-                let max_version = post_versions::Entity::find()
-                    .filter(post_versions::Column::PostId.eq(*id))
-                    .order_by_desc(post_versions::Column::VersionNumber)
-                    .one(self.db.as_ref())
-                    .await?;
-
-                let next_version = match max_version {
-                    Some(v) => v.version_number + 1,
-                    None => 1,
-                };
+        // Lock the row so the revision check and the write can't interleave with another update
+        let Some(old_model) = posts::Entity::find_by_id(*id)
+            .lock_exclusive()
+            .one(&txn)
+            .await?
+        else {
+            return Ok(None);
+        };
 
-                // Create version record
-                let version_model = post_versions::ActiveModel {
-                    post_id: Set(*id),
-                    title: Set(old_model.title.clone()),
-                    content: Set(old_model.content.clone()),
-                    category: Set(old_model.category.clone().unwrap_or_else(|| "".to_string())),
-                    version_number: Set(next_version),
-                    created_by: Set(user_id),
-                    change_note: Set(change_note),
-                    ..Default::default()
-                };
-                version_model.insert(self.db.as_ref()).await?;
-            }
+        if old_model.revision != post.revision {
+            txn.rollback().await?;
+            let current = self.with_relations_one(Post::from(old_model)).await?;
+            return Err(Box::new(RevisionConflict { current }));
+        }
 
-            let mut active_model: posts::ActiveModel = old_model.into();
-            active_model.title = Set(post.title);
-            active_model.content = Set(post.content);
-            active_model.category = Set(post.category);
-            active_model.author_id = Set(post.author_id);
-            // updated_at is automatically updated by ActiveModelBehavior::before_save
-            active_model.archived_at = Set(post.archived_at);
-            active_model.deleted_at = Set(post.deleted_at);
+        // Create version before update if requested
+        if create_version {
+            Self::insert_version(&txn, &old_model, user_id, change_note).await?;
+        }
 
-            let updated = active_model.update(self.db.as_ref()).await?;
+        if post.slug != old_model.slug {
+            Self::record_slug_change(&txn, *id, &old_model.slug, &post.slug).await?;
+        }
 
-            Ok(Some(Post::from(updated)))
-        } else {
-            Ok(None)
+        let slug = post.slug.clone();
+        let mut active_model: posts::ActiveModel = old_model.into();
+        active_model.title = Set(post.title);
+        active_model.slug = Set(post.slug);
+        active_model.content = Set(post.content);
+        active_model.category_id = Set(post.category_id);
+        active_model.author_id = Set(post.author_id);
+        active_model.description = Set(post.description);
+        // updated_at and revision are bumped by database triggers
+        active_model.status = Set(post.status.as_str().to_string());
+        active_model.review_note = Set(post.review_note);
+        active_model.publish_at = Set(post.publish_at);
+        active_model.unpublish_at = Set(post.unpublish_at);
+        active_model.archived_at = Set(post.archived_at);
+        active_model.deleted_at = Set(post.deleted_at);
+
+        let updated = active_model
+            .update(&txn)
+            .await
+            .map_err(|e| slug_taken(e, &slug))?;
+        Self::set_post_tags(&txn, *id, &post.tags).await?;
+        txn.commit().await?;
+
+        Ok(Some(self.with_relations_one(Post::from(updated)).await?))
+    }
+
+    async fn find_by_slug(
+        &self,
+        slug: &str,
+    ) -> Result<Option<Post>, Box<dyn std::error::Error + Send + Sync>> {
+        let model = posts::Entity::find()
+            .filter(posts::Column::Slug.eq(slug))
+            .filter(posts::Column::DeletedAt.is_null())
+            .one(self.db.as_ref())
+            .await?;
+
+        match model {
+            Some(model) => Ok(Some(self.with_relations_one(Post::from(model)).await?)),
+            None => Ok(None),
+        }
+    }
+
+    async fn find_by_previous_slug(
+        &self,
+        slug: &str,
+    ) -> Result<Option<Post>, Box<dyn std::error::Error + Send + Sync>> {
+        let model = posts::Entity::find()
+            .inner_join(post_slug_history::Entity)
+            .filter(post_slug_history::Column::Slug.eq(slug))
+            .filter(posts::Column::DeletedAt.is_null())
+            .one(self.db.as_ref())
+            .await?;
+
+        match model {
+            Some(model) => Ok(Some(self.with_relations_one(Post::from(model)).await?)),
+            None => Ok(None),
+        }
+    }
+
+    async fn find_schedule_due(
+        &self,
+        now: DateTimeWithTimeZone,
+        limit: u64,
+    ) -> Result<Vec<Post>, Box<dyn std::error::Error + Send + Sync>> {
+        let models = posts::Entity::find()
+            .filter(posts::Column::DeletedAt.is_null())
+            .filter(
+                Condition::any()
+                    .add(
+                        Condition::all()
+                            .add(posts::Column::Status.eq(PostStatus::Scheduled.as_str()))
+                            .add(posts::Column::PublishAt.lte(now)),
+                    )
+                    .add(
+                        Condition::all()
+                            .add(posts::Column::Status.eq(PostStatus::Published.as_str()))
+                            .add(posts::Column::UnpublishAt.lte(now)),
+                    ),
+            )
+            .order_by_asc(posts::Column::Id)
+            .limit(limit)
+            .all(self.db.as_ref())
+            .await?;
+
+        Ok(self
+            .with_relations(models.into_iter().map(Post::from).collect())
+            .await?)
+    }
+
+    async fn is_slug_taken(
+        &self,
+        slug: &str,
+        except: Option<i64>,
+    ) -> Result<bool, Box<dyn std::error::Error + Send + Sync>> {
+        let mut select = posts::Entity::find().filter(posts::Column::Slug.eq(slug));
+        if let Some(id) = except {
+            select = select.filter(posts::Column::Id.ne(id));
         }
+        Ok(select.count(self.db.as_ref()).await? > 0)
     }
 
     async fn delete(&self, id: &i64) -> Result<bool, Box<dyn std::error::Error + Send + Sync>> {
@@ -166,6 +839,91 @@ impl PostRepository for PostgresPostRepository {
         Ok(result.rows_affected > 0)
     }
 
+    async fn find_trash_page(
+        &self,
+        page: u64,
+        per_page: u64,
+    ) -> Result<(Vec<Post>, u64), Box<dyn std::error::Error + Send + Sync>> {
+        let paginator = posts::Entity::find()
+            .filter(posts::Column::DeletedAt.is_not_null())
+            .order_by_desc(posts::Column::DeletedAt)
+            .order_by_desc(posts::Column::Id)
+            .paginate(self.db.as_ref(), per_page);
+
+        let total = paginator.num_items().await?;
+        let models = paginator.fetch_page(page.saturating_sub(1)).await?;
+        let posts = self
+            .with_relations(models.into_iter().map(Post::from).collect())
+            .await?;
+
+        Ok((posts, total))
+    }
+
+    async fn find_trashed_before(
+        &self,
+        before: DateTimeWithTimeZone,
+        limit: u64,
+    ) -> Result<Vec<Post>, Box<dyn std::error::Error + Send + Sync>> {
+        let models = posts::Entity::find()
+            .filter(posts::Column::DeletedAt.lt(before))
+            .order_by_asc(posts::Column::DeletedAt)
+            .limit(limit)
+            .all(self.db.as_ref())
+            .await?;
+
+        Ok(models.into_iter().map(Post::from).collect())
+    }
+
+    async fn find_post_contents(
+        &self,
+        post_id: &i64,
+    ) -> Result<Vec<String>, Box<dyn std::error::Error + Send + Sync>> {
+        let mut contents: Vec<String> = posts::Entity::find_by_id(*post_id)
+            .select_only()
+            .column(posts::Column::Content)
+            .into_tuple()
+            .all(self.db.as_ref())
+            .await?;
+        let versions: Vec<String> = post_versions::Entity::find()
+            .select_only()
+            .column(post_versions::Column::Content)
+            .filter(post_versions::Column::PostId.eq(*post_id))
+            .into_tuple()
+            .all(self.db.as_ref())
+            .await?;
+        let drafts: Vec<String> = post_drafts::Entity::find()
+            .select_only()
+            .column(post_drafts::Column::Content)
+            .filter(post_drafts::Column::PostId.eq(*post_id))
+            .into_tuple()
+            .all(self.db.as_ref())
+            .await?;
+        contents.extend(versions);
+        contents.extend(drafts);
+        Ok(contents)
+    }
+
+    async fn is_referenced_in_content(
+        &self,
+        text: &str,
+    ) -> Result<bool, Box<dyn std::error::Error + Send + Sync>> {
+        let row = self
+            .db
+            .query_one(Statement::from_sql_and_values(
+                DbBackend::Postgres,
+                r#"SELECT EXISTS (SELECT 1 FROM posts WHERE strpos(content, $1) > 0)
+                       OR EXISTS (SELECT 1 FROM post_versions WHERE strpos(content, $1) > 0)
+                       OR EXISTS (SELECT 1 FROM post_drafts WHERE strpos(content, $1) > 0)
+                       AS referenced"#,
+                [text.into()],
+            ))
+            .await?;
+        Ok(row
+            .map(|row| row.try_get::<bool>("", "referenced"))
+            .transpose()?
+            .unwrap_or(false))
+    }
+
     // Version management
     async fn get_versions(
         &self,
@@ -177,7 +935,9 @@ impl PostRepository for PostgresPostRepository {
             .all(self.db.as_ref())
             .await?;
 
-        Ok(models.into_iter().map(PostVersion::from).collect())
+        Ok(self
+            .versions_with_categories(models.into_iter().map(PostVersion::from).collect())
+            .await?)
     }
 
     async fn get_version(
@@ -187,7 +947,13 @@ impl PostRepository for PostgresPostRepository {
         let model = post_versions::Entity::find_by_id(*version_id)
             .one(self.db.as_ref())
             .await?;
-        Ok(model.map(PostVersion::from))
+        match model {
+            Some(model) => Ok(self
+                .versions_with_categories(vec![PostVersion::from(model)])
+                .await?
+                .pop()),
+            None => Ok(None),
+        }
     }
 
     async fn restore_from_version(
@@ -205,46 +971,31 @@ impl PostRepository for PostgresPostRepository {
                 return Err("Version does not belong to this post".into());
             }
 
-            // Get current post
+            // Get current post, locked until the backup and the restore are written
+            let txn = self.db.begin().await?;
             let post = posts::Entity::find_by_id(*post_id)
-                .one(self.db.as_ref())
+                .lock_exclusive()
+                .one(&txn)
                 .await?;
             if let Some(post_model) = post {
                 // Create a version of current state before restoring
-                let max_version = post_versions::Entity::find()
-                    .filter(post_versions::Column::PostId.eq(*post_id))
-                    .order_by_desc(post_versions::Column::VersionNumber)
-                    .one(self.db.as_ref())
-                    .await?;
-
-                let next_version = match max_version {
-                    Some(v) => v.version_number + 1,
-                    None => 1,
-                };
-
-                let backup_version = post_versions::ActiveModel {
-                    post_id: Set(*post_id),
-                    title: Set(post_model.title.clone()),
-                    content: Set(post_model.content.clone()),
-                    category: Set(post_model
-                        .category
-                        .clone()
-                        .unwrap_or_else(|| "".to_string())),
-                    version_number: Set(next_version),
-                    created_by: Set(user_id),
-                    change_note: Set(Some("Auto-created before restore".to_string())),
-                    ..Default::default()
-                };
-                backup_version.insert(self.db.as_ref()).await?;
+                Self::insert_version(
+                    &txn,
+                    &post_model,
+                    user_id,
+                    Some("Auto-created before restore".to_string()),
+                )
+                .await?;
 
                 // Restore from version
                 let mut active_model: posts::ActiveModel = post_model.into();
                 active_model.title = Set(version_model.title.clone());
                 active_model.content = Set(version_model.content.clone());
-                active_model.category = Set(Some(version_model.category.clone()));
+                active_model.category_id = Set(version_model.category_id);
 
-                let updated = active_model.update(self.db.as_ref()).await?;
-                Ok(Some(Post::from(updated)))
+                let updated = active_model.update(&txn).await?;
+                txn.commit().await?;
+                Ok(Some(self.with_relations_one(Post::from(updated)).await?))
             } else {
                 Ok(None)
             }
@@ -279,24 +1030,32 @@ impl PostRepository for PostgresPostRepository {
             let mut active_model: post_drafts::ActiveModel = existing.into();
             active_model.title = Set(request.title);
             active_model.content = Set(request.content);
-            active_model.category = Set(request.category);
+            active_model.category_id = Set(request.category_id);
             active_model.description = Set(request.description);
+            active_model.tags = Set(serde_json::json!(request.tags));
             // updated_at is automatically updated by trigger
             let updated = active_model.update(self.db.as_ref()).await?;
-            Ok(PostDraft::from(updated))
+            Ok(self
+                .drafts_with_categories(vec![PostDraft::from(updated)])
+                .await?
+                .remove(0))
         } else {
             // Create new draft
             let active_model = post_drafts::ActiveModel {
                 post_id: Set(request.post_id),
                 title: Set(request.title),
                 content: Set(request.content),
-                category: Set(request.category),
+                category_id: Set(request.category_id),
                 description: Set(request.description),
+                tags: Set(serde_json::json!(request.tags)),
                 author_id: Set(author_id),
                 ..Default::default()
             };
             let model = active_model.insert(self.db.as_ref()).await?;
-            Ok(PostDraft::from(model))
+            Ok(self
+                .drafts_with_categories(vec![PostDraft::from(model)])
+                .await?
+                .remove(0))
         }
     }
 
@@ -319,7 +1078,13 @@ impl PostRepository for PostgresPostRepository {
                 .await?
         };
 
-        Ok(model.map(PostDraft::from))
+        match model {
+            Some(model) => Ok(self
+                .drafts_with_categories(vec![PostDraft::from(model)])
+                .await?
+                .pop()),
+            None => Ok(None),
+        }
     }
 
     async fn delete_draft(
@@ -361,24 +1126,413 @@ impl PostRepository for PostgresPostRepository {
             .await?;
 
         let drafts = models.into_iter().map(PostDraft::from).collect();
-        Ok(drafts)
+        Ok(self.drafts_with_categories(drafts).await?)
     }
 
     async fn get_category_stats(
         &self,
-    ) -> Result<Vec<(String, i64)>, Box<dyn std::error::Error + Send + Sync>> {
-        use sea_orm::{QuerySelect, prelude::*};
+    ) -> Result<Vec<(Category, i64)>, Box<dyn std::error::Error + Send + Sync>> {
+        let rows = CategoryCountRow::find_by_statement(Statement::from_string(
+            DbBackend::Postgres,
+            r#"SELECT c.id, c.name, c.slug, c.description, c.parent_id, c.created_at, c.updated_at,
+                      COUNT(p.id) AS post_count
+               FROM categories c
+               LEFT JOIN posts p
+                   ON p.category_id = c.id AND p.deleted_at IS NULL AND p.status = 'published'
+               GROUP BY c.id
+               ORDER BY c.name"#,
+        ))
+        .all(self.db.as_ref())
+        .await?;
 
-        let stats = posts::Entity::find()
-            .filter(posts::Column::Category.is_not_null())
-            .select_only()
-            .column(posts::Column::Category)
-            .column_as(Expr::col(posts::Column::Category).count(), "count")
-            .group_by(posts::Column::Category)
-            .into_tuple::<(String, i64)>()
-            .all(self.db.as_ref())
+        Ok(rows
+            .into_iter()
+            .map(|row| {
+                let category = Category {
+                    id: row.id,
+                    name: row.name,
+                    slug: row.slug,
+                    description: row.description,
+                    parent_id: row.parent_id,
+                    created_at: row.created_at,
+                    updated_at: row.updated_at,
+                };
+                (category, row.post_count)
+            })
+            .collect())
+    }
+
+    async fn find_category_by_id(
+        &self,
+        id: &i64,
+    ) -> Result<Option<Category>, Box<dyn std::error::Error + Send + Sync>> {
+        let model = categories::Entity::find_by_id(*id)
+            .one(self.db.as_ref())
+            .await?;
+        Ok(model.map(Category::from))
+    }
+
+    async fn find_category_by_name_or_slug(
+        &self,
+        name: &str,
+    ) -> Result<Option<Category>, Box<dyn std::error::Error + Send + Sync>> {
+        let name = name.trim();
+        let model = categories::Entity::find()
+            .filter(
+                Condition::any()
+                    .add(categories::Column::Slug.eq(name))
+                    .add(categories::Column::Slug.eq(slugify(name)))
+                    .add(categories::Column::Name.eq(name)),
+            )
+            .one(self.db.as_ref())
+            .await?;
+        Ok(model.map(Category::from))
+    }
+
+    async fn create_category(
+        &self,
+        request: CreateCategoryRequest,
+    ) -> Result<Category, Box<dyn std::error::Error + Send + Sync>> {
+        let slug = request.slug.unwrap_or_else(|| slugify(&request.name));
+        let active_model = categories::ActiveModel {
+            name: Set(request.name),
+            slug: Set(slug),
+            description: Set(request.description),
+            parent_id: Set(request.parent_id),
+            ..Default::default()
+        };
+
+        let model = active_model.insert(self.db.as_ref()).await?;
+        Ok(Category::from(model))
+    }
+
+    async fn update_category(
+        &self,
+        id: &i64,
+        category: Category,
+    ) -> Result<Option<Category>, Box<dyn std::error::Error + Send + Sync>> {
+        let model = categories::Entity::find_by_id(*id)
+            .one(self.db.as_ref())
             .await?;
+        if let Some(model) = model {
+            let mut active_model: categories::ActiveModel = model.into();
+            active_model.name = Set(category.name);
+            active_model.slug = Set(category.slug);
+            active_model.description = Set(category.description);
+            active_model.parent_id = Set(category.parent_id);
+            // updated_at is automatically updated by trigger
+            let updated = active_model.update(self.db.as_ref()).await?;
+            Ok(Some(Category::from(updated)))
+        } else {
+            Ok(None)
+        }
+    }
+
+    async fn delete_category(
+        &self,
+        id: &i64,
+        reassign_to: Option<i64>,
+    ) -> Result<bool, Box<dyn std::error::Error + Send + Sync>> {
+        let txn = self.db.begin().await?;
+
+        let model = match categories::Entity::find_by_id(*id).one(&txn).await? {
+            Some(model) => model,
+            None => return Ok(false),
+        };
+
+        // Children move up to the deleted category's parent
+        categories::Entity::update_many()
+            .col_expr(categories::Column::ParentId, Expr::value(model.parent_id))
+            .filter(categories::Column::ParentId.eq(*id))
+            .exec(&txn)
+            .await?;
+
+        // Posts, versions and drafts move to the replacement category (or none)
+        posts::Entity::update_many()
+            .col_expr(posts::Column::CategoryId, Expr::value(reassign_to))
+            .filter(posts::Column::CategoryId.eq(*id))
+            .exec(&txn)
+            .await?;
+        post_versions::Entity::update_many()
+            .col_expr(post_versions::Column::CategoryId, Expr::value(reassign_to))
+            .filter(post_versions::Column::CategoryId.eq(*id))
+            .exec(&txn)
+            .await?;
+        post_drafts::Entity::update_many()
+            .col_expr(post_drafts::Column::CategoryId, Expr::value(reassign_to))
+            .filter(post_drafts::Column::CategoryId.eq(*id))
+            .exec(&txn)
+            .await?;
+
+        categories::Entity::delete_by_id(*id).exec(&txn).await?;
+
+        txn.commit().await?;
+        Ok(true)
+    }
+
+    async fn get_tag_stats(
+        &self,
+    ) -> Result<Vec<(Tag, i64)>, Box<dyn std::error::Error + Send + Sync>> {
+        let rows = TagCountRow::find_by_statement(Statement::from_string(
+            DbBackend::Postgres,
+            r#"SELECT t.id, t.name, t.slug, t.created_at, COUNT(p.id) AS post_count
+               FROM tags t
+               LEFT JOIN post_tags pt ON pt.tag_id = t.id
+               LEFT JOIN posts p
+                   ON p.id = pt.post_id AND p.deleted_at IS NULL AND p.status = 'published'
+               GROUP BY t.id
+               ORDER BY t.name"#,
+        ))
+        .all(self.db.as_ref())
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| {
+                let tag = Tag {
+                    id: row.id,
+                    name: row.name,
+                    slug: row.slug,
+                    created_at: row.created_at,
+                };
+                (tag, row.post_count)
+            })
+            .collect())
+    }
+
+    async fn find_tag_by_id(
+        &self,
+        id: &i64,
+    ) -> Result<Option<Tag>, Box<dyn std::error::Error + Send + Sync>> {
+        let model = tags::Entity::find_by_id(*id).one(self.db.as_ref()).await?;
+        Ok(model.map(Tag::from))
+    }
+
+    async fn find_tag_by_slug(
+        &self,
+        slug: &str,
+    ) -> Result<Option<Tag>, Box<dyn std::error::Error + Send + Sync>> {
+        let model = tags::Entity::find()
+            .filter(tags::Column::Slug.eq(slug))
+            .one(self.db.as_ref())
+            .await?;
+        Ok(model.map(Tag::from))
+    }
+
+    async fn rename_tag(
+        &self,
+        id: &i64,
+        name: String,
+    ) -> Result<Option<Tag>, Box<dyn std::error::Error + Send + Sync>> {
+        let model = tags::Entity::find_by_id(*id).one(self.db.as_ref()).await?;
+        if let Some(model) = model {
+            let slug = slugify(&name);
+            let mut active_model: tags::ActiveModel = model.into();
+            active_model.name = Set(name);
+            active_model.slug = Set(slug);
+            let updated = active_model.update(self.db.as_ref()).await?;
+            Ok(Some(Tag::from(updated)))
+        } else {
+            Ok(None)
+        }
+    }
+
+    async fn merge_tags(
+        &self,
+        source_ids: &[i64],
+        target_id: &i64,
+    ) -> Result<Option<Tag>, Box<dyn std::error::Error + Send + Sync>> {
+        let txn = self.db.begin().await?;
+
+        let target = match tags::Entity::find_by_id(*target_id).one(&txn).await? {
+            Some(target) => target,
+            None => return Ok(None),
+        };
+        let source_ids: Vec<i64> = source_ids
+            .iter()
+            .copied()
+            .filter(|id| id != target_id)
+            .collect();
+
+        if !source_ids.is_empty() {
+            let links = post_tags::Entity::find()
+                .filter(post_tags::Column::TagId.is_in(source_ids.iter().copied()))
+                .all(&txn)
+                .await?;
+
+            if !links.is_empty() {
+                // Posts that already have the target tag keep a single link
+                post_tags::Entity::insert_many(links.into_iter().map(|link| {
+                    post_tags::ActiveModel {
+                        post_id: Set(link.post_id),
+                        tag_id: Set(*target_id),
+                    }
+                }))
+                .on_conflict(
+                    OnConflict::columns([post_tags::Column::PostId, post_tags::Column::TagId])
+                        .do_nothing()
+                        .to_owned(),
+                )
+                .exec_without_returning(&txn)
+                .await?;
+            }
+
+            // Deleting the source tags cascades to their post_tags rows
+            tags::Entity::delete_many()
+                .filter(tags::Column::Id.is_in(source_ids))
+                .exec(&txn)
+                .await?;
+        }
+
+        txn.commit().await?;
+        Ok(Some(Tag::from(target)))
+    }
+
+    async fn count_sitemap_entries(&self) -> Result<u64, Box<dyn std::error::Error + Send + Sync>> {
+        let count = CountRow::find_by_statement(Statement::from_string(
+            DbBackend::Postgres,
+            format!(
+                "SELECT COUNT(*) AS count FROM ({}) entries",
+                SITEMAP_ENTRIES_SQL
+            ),
+        ))
+        .one(self.db.as_ref())
+        .await?
+        .map(|row| row.count as u64)
+        .unwrap_or(0);
+        Ok(count)
+    }
+
+    async fn find_sitemap_entries(
+        &self,
+        offset: u64,
+        limit: u64,
+    ) -> Result<Vec<SitemapEntry>, Box<dyn std::error::Error + Send + Sync>> {
+        let rows = SitemapRow::find_by_statement(Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            format!(
+                "SELECT kind, key, updated_at FROM ({}) entries ORDER BY ord, id OFFSET $1 LIMIT $2",
+                SITEMAP_ENTRIES_SQL
+            ),
+            [(offset as i64).into(), (limit as i64).into()],
+        ))
+        .all(self.db.as_ref())
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| SitemapEntry {
+                kind: match row.kind.as_str() {
+                    "category" => SitemapEntryKind::Category,
+                    "author" => SitemapEntryKind::Author,
+                    _ => SitemapEntryKind::Post,
+                },
+                key: row.key,
+                updated_at: row.updated_at,
+            })
+            .collect())
+    }
+
+    async fn find_import_record(
+        &self,
+        key: &ImportKey,
+    ) -> Result<Option<ImportRecord>, Box<dyn std::error::Error + Send + Sync>> {
+        let model = import_records::Entity::find()
+            .filter(import_records::Column::Source.eq(key.source.as_str()))
+            .filter(import_records::Column::Kind.eq(key.kind.as_str()))
+            .filter(import_records::Column::SourceId.eq(key.source_id.as_str()))
+            .one(self.db.as_ref())
+            .await?;
+        Ok(model.map(|model| ImportRecord {
+            target_id: model.target_id,
+            target_url: model.target_url,
+        }))
+    }
+
+    async fn save_import_record(
+        &self,
+        key: &ImportKey,
+        record: ImportRecord,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        Self::insert_import_record(self.db.as_ref(), key, record).await?;
+        Ok(())
+    }
+
+    async fn import_post(
+        &self,
+        request: ImportPostRequest,
+    ) -> Result<Post, Box<dyn std::error::Error + Send + Sync>> {
+        let txn = self.db.begin().await?;
+
+        let base = post_slug(request.slug.as_deref().unwrap_or(&request.title));
+        let slug = Self::available_slug(&txn, &base, None).await?;
+        post_slug_history::Entity::delete_by_id(slug.clone())
+            .exec(&txn)
+            .await?;
+
+        // Inserts keep explicit timestamps; only updates are stamped by triggers
+        let model = posts::ActiveModel {
+            title: Set(request.title),
+            slug: Set(slug),
+            content: Set(request.content),
+            category_id: Set(request.category_id),
+            author_id: Set(request.author_id),
+            description: Set(request.description.clone()),
+            status: Set(request.status.as_str().to_string()),
+            publish_at: Set(request.publish_at.map(Into::into)),
+            created_at: Set(request.created_at.into()),
+            updated_at: Set(request.updated_at.into()),
+            ..Default::default()
+        }
+        .insert(&txn)
+        .await?;
+        Self::set_post_tags(&txn, model.id, &request.tags).await?;
+
+        for (number, version) in request.versions.into_iter().enumerate() {
+            post_versions::ActiveModel {
+                post_id: Set(model.id),
+                title: Set(version.title),
+                content: Set(version.content),
+                category_id: Set(request.category_id),
+                description: Set(version.description),
+                version_number: Set(number as i32 + 1),
+                created_at: Set(version.created_at.into()),
+                created_by: Set(version.created_by),
+                change_note: Set(Some("Imported revision".to_string())),
+                ..Default::default()
+            }
+            .insert(&txn)
+            .await?;
+        }
+
+        if let Some(draft) = request.draft {
+            post_drafts::ActiveModel {
+                post_id: Set(Some(model.id)),
+                title: Set(draft.title),
+                content: Set(draft.content),
+                author_id: Set(draft.author_id),
+                description: Set(request.description),
+                tags: Set(serde_json::json!(request.tags)),
+                category_id: Set(request.category_id),
+                created_at: Set(draft.updated_at.into()),
+                updated_at: Set(draft.updated_at.into()),
+                ..Default::default()
+            }
+            .insert(&txn)
+            .await?;
+        }
+
+        Self::insert_import_record(
+            &txn,
+            &request.key,
+            ImportRecord {
+                target_id: Some(model.id),
+                target_url: None,
+            },
+        )
+        .await?;
+        txn.commit().await?;
 
-        Ok(stats)
+        Ok(self.with_relations_one(Post::from(model)).await?)
     }
 }
@@ -0,0 +1,134 @@
+//! Background scheduler for timed post status changes
+//!
+//! Publishes `scheduled` posts once their `publish_at` has passed and turns
+//! `published` posts back into drafts once their `unpublish_at` has passed.
+//! Every tick runs inside a transaction holding a Postgres advisory lock, so
+//! only one instance does the work when several are running.
+
+use crate::api::post_controller::{filter_existing_post, fire_status_hook};
+use crate::dto::{Post, PostStatus, PostTransition};
+use crate::plugin::registry::PluginRegistry;
+use crate::repository::PostRepository;
+use chrono::Utc;
+use sea_orm::{ConnectionTrait, DatabaseConnection, DbBackend, Statement, TransactionTrait};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Seconds between two runs when `SCHEDULER_INTERVAL_SECS` is not set
+const DEFAULT_INTERVAL_SECS: u64 = 30;
+
+/// Posts handled per run; the rest are picked up by the next run
+const BATCH_SIZE: u64 = 50;
+
+/// Advisory lock key shared by all instances ("rpsched")
+const LOCK_KEY: i64 = 0x72707363686564;
+
+/// Start the scheduler on the Tokio runtime
+pub fn spawn<PR: PostRepository + 'static>(
+    post_repository: Arc<PR>,
+    db: DatabaseConnection,
+    plugin_registry: Arc<PluginRegistry>,
+) -> tokio::task::JoinHandle<()> {
+    let interval_secs = std::env::var("SCHEDULER_INTERVAL_SECS")
+        .ok()
+        .and_then(|value| value.parse::<u64>().ok())
+        .filter(|secs| *secs > 0)
+        .unwrap_or(DEFAULT_INTERVAL_SECS);
+
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(interval_secs));
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        loop {
+            interval.tick().await;
+            if let Err(e) = run_once(post_repository.as_ref(), &db, &plugin_registry).await {
+                tracing::error!("Scheduler run failed: {:?}", e);
+            }
+        }
+    })
+}
+
+/// Apply all due status changes, unless another instance is already doing so
+///
+/// Returns the number of posts changed.
+async fn run_once<PR: PostRepository>(
+    post_repository: &PR,
+    db: &DatabaseConnection,
+    plugin_registry: &PluginRegistry,
+) -> Result<usize, Box<dyn std::error::Error + Send + Sync>> {
+    let txn = db.begin().await?;
+
+    // Released when the transaction ends
+    let locked = txn
+        .query_one(Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            "SELECT pg_try_advisory_xact_lock($1) AS locked",
+            [LOCK_KEY.into()],
+        ))
+        .await?
+        .map(|row| row.try_get::<bool>("", "locked"))
+        .transpose()?
+        .unwrap_or(false);
+    if !locked {
+        txn.rollback().await?;
+        return Ok(0);
+    }
+
+    let due = post_repository
+        .find_schedule_due(Utc::now().into(), BATCH_SIZE)
+        .await?;
+
+    let mut changed = 0;
+    for post in due {
+        let id = post.id;
+        match apply_due_change(post_repository, plugin_registry, post).await {
+            Ok(true) => changed += 1,
+            Ok(false) => {}
+            Err(e) => tracing::error!("Scheduler failed to update post {}: {:?}", id, e),
+        }
+    }
+
+    txn.commit().await?;
+
+    if changed > 0 {
+        tracing::info!("Scheduler updated {} post(s)", changed);
+    }
+    Ok(changed)
+}
+
+/// Publish or unpublish one due post
+async fn apply_due_change<PR: PostRepository>(
+    post_repository: &PR,
+    plugin_registry: &PluginRegistry,
+    mut post: Post,
+) -> Result<bool, Box<dyn std::error::Error + Send + Sync>> {
+    let from = post.status;
+    let transition = match from {
+        PostStatus::Scheduled => {
+            filter_existing_post(post_repository, plugin_registry, &mut post)
+                .await
+                .map_err(|status| format!("publish filter failed with {}", status))?;
+            PostTransition::Publish
+        }
+        PostStatus::Published => {
+            post.unpublish_at = None;
+            PostTransition::Unpublish
+        }
+        _ => return Ok(false),
+    };
+    post.status = match transition.target(from) {
+        Some(status) => status,
+        None => return Ok(false),
+    };
+
+    let id = post.id;
+    let author_id = post.author_id;
+    let Some(post) = post_repository
+        .update(&id, post, false, None, author_id)
+        .await?
+    else {
+        return Ok(false);
+    };
+
+    fire_status_hook(plugin_registry, &post, Some(from), transition, None).await;
+    Ok(true)
+}
@@ -0,0 +1,304 @@
+//! Meta tags for server-rendered page heads
+//!
+//! The frontend is a SPA, so crawlers and link previews only see its static
+//! `index.html`. For post pages the server fills in the head instead: title,
+//! description, canonical link, Open Graph / Twitter cards and JSON-LD data.
+
+use crate::dto::Post;
+use crate::feed::escape;
+use crate::slug::post_path;
+use chrono::{DateTime, SecondsFormat, Utc};
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::sync::{Arc, Mutex};
+
+/// Length of meta descriptions taken from post content
+const META_DESCRIPTION_CHARS: usize = 160;
+
+/// Number of pages kept in a [`PageMetaCache`]
+const PAGE_META_CACHE_CAPACITY: usize = 512;
+
+lazy_static! {
+    static ref TITLE_TAG: Regex = Regex::new(r"(?is)<title>.*?</title>\s*").unwrap();
+    static ref DESCRIPTION_TAG: Regex =
+        Regex::new(r#"(?is)<meta\s+name\s*=\s*"description"[^>]*>\s*"#).unwrap();
+    static ref IMAGE_SRC: Regex = Regex::new(r#"<img\s[^>]*src="([^"]+)""#).unwrap();
+}
+
+/// Meta data of a page, with absolute URLs
+#[derive(Debug, Clone)]
+pub struct PageMeta {
+    /// Page title; the document title also names the site
+    pub title: String,
+    pub description: String,
+    pub canonical_url: String,
+    pub site_name: String,
+    pub image: Option<String>,
+    /// Set for posts; adds `article:*` tags and JSON-LD `Article` data
+    pub article: Option<ArticleMeta>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ArticleMeta {
+    pub author: Option<String>,
+    pub published: DateTime<Utc>,
+    pub modified: DateTime<Utc>,
+    /// Category name
+    pub section: Option<String>,
+    pub tags: Vec<String>,
+}
+
+impl PageMeta {
+    /// Meta data of a post page, given its author's name and rendered content
+    ///
+    /// Without a description, the start of the content is used; the first image
+    /// of the content becomes the preview image.
+    pub fn for_post(
+        site_url: &str,
+        site_name: String,
+        post: &Post,
+        author: Option<String>,
+        content_html: &str,
+    ) -> Self {
+        let image = first_image(content_html).map(|src| {
+            if src.starts_with('/') {
+                format!("{}{}", site_url, src)
+            } else {
+                src
+            }
+        });
+        let description = post
+            .description
+            .clone()
+            .filter(|description| !description.trim().is_empty())
+            .unwrap_or_else(|| crate::markdown::excerpt(&post.content, META_DESCRIPTION_CHARS));
+
+        PageMeta {
+            title: post.title.clone(),
+            description,
+            canonical_url: format!("{}{}", site_url, post_path(&post.slug)),
+            site_name,
+            image,
+            article: Some(ArticleMeta {
+                author,
+                published: post.created_at.with_timezone(&Utc),
+                modified: post.updated_at.with_timezone(&Utc),
+                section: post.category.clone(),
+                tags: post.tags.clone(),
+            }),
+        }
+    }
+
+    /// Tags to put into the page's `<head>`, including `<title>`
+    pub fn head_tags(&self) -> String {
+        let mut html = String::new();
+        let title = if self.title == self.site_name {
+            self.title.clone()
+        } else {
+            format!("{} | {}", self.title, self.site_name)
+        };
+        let _ = write!(html, "<title>{}</title>", escape(&title));
+        meta(&mut html, "name", "description", &self.description);
+        let _ = write!(
+            html,
+            r#"<link rel="canonical" href="{}">"#,
+            escape(&self.canonical_url)
+        );
+
+        let kind = if self.article.is_some() {
+            "article"
+        } else {
+            "website"
+        };
+        meta(&mut html, "property", "og:type", kind);
+        meta(&mut html, "property", "og:title", &self.title);
+        meta(&mut html, "property", "og:description", &self.description);
+        meta(&mut html, "property", "og:url", &self.canonical_url);
+        meta(&mut html, "property", "og:site_name", &self.site_name);
+        let card = match &self.image {
+            Some(image) => {
+                meta(&mut html, "property", "og:image", image);
+                meta(&mut html, "name", "twitter:image", image);
+                "summary_large_image"
+            }
+            None => "summary",
+        };
+        meta(&mut html, "name", "twitter:card", card);
+        meta(&mut html, "name", "twitter:title", &self.title);
+        meta(&mut html, "name", "twitter:description", &self.description);
+
+        if let Some(article) = &self.article {
+            meta(
+                &mut html,
+                "property",
+                "article:published_time",
+                &rfc3339(article.published),
+            );
+            meta(
+                &mut html,
+                "property",
+                "article:modified_time",
+                &rfc3339(article.modified),
+            );
+            if let Some(author) = &article.author {
+                meta(&mut html, "property", "article:author", author);
+            }
+            if let Some(section) = &article.section {
+                meta(&mut html, "property", "article:section", section);
+            }
+            for tag in &article.tags {
+                meta(&mut html, "property", "article:tag", tag);
+            }
+            let _ = write!(
+                html,
+                r#"<script type="application/ld+json">{}</script>"#,
+                self.json_ld(article)
+            );
+        }
+        html
+    }
+
+    /// JSON-LD `Article`, safe to embed in a `<script>` element
+    fn json_ld(&self, article: &ArticleMeta) -> String {
+        let mut data = serde_json::json!({
+            "@context": "https://schema.org",
+            "@type": "Article",
+            "headline": self.title,
+            "description": self.description,
+            "url": self.canonical_url,
+            "mainEntityOfPage": self.canonical_url,
+            "datePublished": rfc3339(article.published),
+            "dateModified": rfc3339(article.modified),
+            "publisher": { "@type": "Organization", "name": self.site_name },
+        });
+        if let Some(author) = &article.author {
+            data["author"] = serde_json::json!({ "@type": "Person", "name": author });
+        }
+        if let Some(image) = &self.image {
+            data["image"] = serde_json::json!([image]);
+        }
+        if !article.tags.is_empty() {
+            data["keywords"] = serde_json::json!(article.tags);
+        }
+        // `</script>` or `<!--` inside strings must not end the element early
+        data.to_string()
+            .replace('<', "\\u003c")
+            .replace('>', "\\u003e")
+    }
+}
+
+/// Replace the template's `<title>` and description with `head_tags` and add them to its head
+pub fn inject_head(template: &str, head_tags: &str) -> String {
+    let html = TITLE_TAG.replace(template, "");
+    let html = DESCRIPTION_TAG.replace(&html, "");
+    match html.find("</head>") {
+        Some(end) => format!("{}{}\n  {}", &html[..end], head_tags, &html[end..]),
+        None => html.into_owned(),
+    }
+}
+
+/// Source of the first image in rendered HTML, for previews
+pub fn first_image(html: &str) -> Option<String> {
+    IMAGE_SRC
+        .captures(html)
+        .map(|captures| captures[1].replace("&amp;", "&"))
+}
+
+fn meta(html: &mut String, attribute: &str, name: &str, content: &str) {
+    let _ = write!(
+        html,
+        r#"<meta {}="{}" content="{}">"#,
+        attribute,
+        name,
+        escape(content)
+    );
+}
+
+fn rfc3339(time: DateTime<Utc>) -> String {
+    time.to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+/// Head tags of post pages, cached per post
+///
+/// Entries carry a fingerprint of everything they were built from (revision, site
+/// settings, ...) and are rebuilt when it changes.
+#[derive(Default)]
+pub struct PageMetaCache {
+    entries: Mutex<HashMap<i64, (String, Arc<String>)>>,
+}
+
+impl PageMetaCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn get(&self, post_id: i64, fingerprint: &str) -> Option<Arc<String>> {
+        self.lock()
+            .get(&post_id)
+            .filter(|(cached, _)| cached == fingerprint)
+            .map(|(_, tags)| tags.clone())
+    }
+
+    pub fn insert(&self, post_id: i64, fingerprint: String, tags: Arc<String>) {
+        let mut entries = self.lock();
+        if entries.len() >= PAGE_META_CACHE_CAPACITY && !entries.contains_key(&post_id) {
+            entries.clear();
+        }
+        entries.insert(post_id, (fingerprint, tags));
+    }
+
+    pub fn clear(&self) {
+        self.lock().clear();
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<i64, (String, Arc<String>)>> {
+        // A panic while holding the lock can't leave the map half-updated
+        self.entries
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_inject_head() {
+        let time = DateTime::parse_from_rfc3339("2026-01-02T03:04:05Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let meta = PageMeta {
+            title: "Hello \"world\"".to_string(),
+            description: "A </script> test".to_string(),
+            canonical_url: "https://example.com/p/hello".to_string(),
+            site_name: "Example".to_string(),
+            image: first_image(r#"<p><img src="/uploads/a.png?x=1&amp;y=2" alt=""></p>"#),
+            article: Some(ArticleMeta {
+                author: Some("admin".to_string()),
+                published: time,
+                modified: time,
+                section: None,
+                tags: vec!["rust".to_string()],
+            }),
+        };
+        let template = "<html><head>\n<title>App</title>\n<meta name=\"description\" content=\"Old\" />\n</head><body></body></html>";
+
+        let html = inject_head(template, &meta.head_tags());
+
+        assert_eq!(html.matches("<title>").count(), 1);
+        assert!(html.contains("<title>Hello &quot;world&quot; | Example</title>"));
+        assert!(html.contains(r#"<meta property="og:title" content="Hello &quot;world&quot;">"#));
+        assert!(!html.contains("Old"));
+        assert!(
+            html.contains(r#"<meta property="og:image" content="/uploads/a.png?x=1&amp;y=2">"#)
+        );
+        assert!(html.contains(r#"<meta name="twitter:card" content="summary_large_image">"#));
+        assert!(html.contains(r#""@type":"Article""#));
+        // The description must not close the JSON-LD script
+        assert_eq!(html.matches("</script>").count(), 1);
+        assert!(html.find("</script>").unwrap() < html.find("</head>").unwrap());
+    }
+}
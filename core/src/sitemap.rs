@@ -0,0 +1,133 @@
+//! XML sitemaps (sitemaps.org protocol)
+
+use crate::dto::{SitemapEntry, SitemapEntryKind};
+use crate::feed::escape;
+use crate::slug::{encode_path_segment, post_path};
+use chrono::{DateTime, SecondsFormat, Utc};
+use std::fmt::Write;
+
+/// Most URLs a single sitemap may list; larger sites get a sitemap index
+pub const MAX_SITEMAP_URLS: u64 = 50_000;
+
+const SITEMAP_NAMESPACE: &str = "http://www.sitemaps.org/schemas/sitemap/0.9";
+
+/// robots.txt used when the `robots_txt` setting is empty
+const DEFAULT_ROBOTS_TXT: &str = "User-agent: *\nDisallow: /admin\nDisallow: /api/\n";
+
+/// URL listed in a sitemap
+#[derive(Debug, Clone)]
+pub struct SitemapUrl {
+    /// Absolute URL of the page
+    pub loc: String,
+    pub lastmod: Option<DateTime<Utc>>,
+}
+
+impl SitemapUrl {
+    /// URL of the page of a sitemap entry
+    pub fn from_entry(site_url: &str, entry: SitemapEntry) -> Self {
+        let path = match entry.kind {
+            SitemapEntryKind::Category => {
+                format!("/category/{}/", encode_path_segment(&entry.key))
+            }
+            SitemapEntryKind::Author => format!("/author/{}/", encode_path_segment(&entry.key)),
+            SitemapEntryKind::Post => post_path(&entry.key),
+        };
+        SitemapUrl {
+            loc: format!("{}{}", site_url, path),
+            lastmod: Some(entry.updated_at.with_timezone(&Utc)),
+        }
+    }
+}
+
+/// `<urlset>` sitemap of the given pages
+pub fn urlset(urls: &[SitemapUrl]) -> String {
+    let mut xml = String::new();
+    xml.push_str(r#"<?xml version="1.0" encoding="UTF-8"?>"#);
+    let _ = write!(xml, r#"<urlset xmlns="{}">"#, SITEMAP_NAMESPACE);
+    for url in urls {
+        xml.push_str("<url>");
+        write_location(&mut xml, url);
+        xml.push_str("</url>");
+    }
+    xml.push_str("</urlset>");
+    xml
+}
+
+/// `<sitemapindex>` pointing at the given sitemaps
+pub fn sitemap_index(sitemaps: &[SitemapUrl]) -> String {
+    let mut xml = String::new();
+    xml.push_str(r#"<?xml version="1.0" encoding="UTF-8"?>"#);
+    let _ = write!(xml, r#"<sitemapindex xmlns="{}">"#, SITEMAP_NAMESPACE);
+    for sitemap in sitemaps {
+        xml.push_str("<sitemap>");
+        write_location(&mut xml, sitemap);
+        xml.push_str("</sitemap>");
+    }
+    xml.push_str("</sitemapindex>");
+    xml
+}
+
+/// robots.txt from the `robots_txt` setting (or the default), pointing crawlers at the sitemap
+///
+/// A `Sitemap:` line is added unless the setting already has one.
+pub fn robots_txt(setting: Option<String>, site_url: &str) -> String {
+    let mut robots = setting
+        .filter(|robots| !robots.trim().is_empty())
+        .unwrap_or_else(|| DEFAULT_ROBOTS_TXT.to_string());
+
+    let has_sitemap = robots.lines().any(|line| {
+        line.trim_start()
+            .to_ascii_lowercase()
+            .starts_with("sitemap:")
+    });
+    if !has_sitemap {
+        if !robots.ends_with('\n') {
+            robots.push('\n');
+        }
+        robots.push_str(&format!("\nSitemap: {}/sitemap.xml\n", site_url));
+    }
+    robots
+}
+
+fn write_location(xml: &mut String, url: &SitemapUrl) {
+    let _ = write!(xml, "<loc>{}</loc>", escape(&url.loc));
+    if let Some(lastmod) = url.lastmod {
+        let _ = write!(
+            xml,
+            "<lastmod>{}</lastmod>",
+            lastmod.to_rfc3339_opts(SecondsFormat::Secs, true)
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_urlset_and_index() {
+        let lastmod = DateTime::parse_from_rfc3339("2026-01-02T03:04:05+02:00")
+            .unwrap()
+            .with_timezone(&Utc);
+        let urls = [
+            SitemapUrl {
+                loc: "https://example.com/".to_string(),
+                lastmod: None,
+            },
+            SitemapUrl {
+                loc: "https://example.com/p/a&b".to_string(),
+                lastmod: Some(lastmod),
+            },
+        ];
+
+        let xml = urlset(&urls);
+        assert!(xml.contains("<url><loc>https://example.com/</loc></url>"));
+        assert!(xml.contains(
+            "<url><loc>https://example.com/p/a&amp;b</loc><lastmod>2026-01-02T01:04:05Z</lastmod></url>"
+        ));
+
+        let xml = sitemap_index(&urls[..1]);
+        assert!(xml.contains("<sitemapindex"));
+        assert!(xml.contains("<sitemap><loc>https://example.com/</loc></sitemap>"));
+    }
+}
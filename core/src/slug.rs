@@ -0,0 +1,130 @@
+//! URL slug generation
+//!
+//! Slugs are lowercase, hyphen-separated and stable for a given input, so they
+//! can be used as lookup keys (e.g. tag slugs) as well as in URLs.
+
+use std::collections::HashSet;
+
+/// Maximum length of a generated post slug, in bytes
+pub const MAX_POST_SLUG_LEN: usize = 80;
+
+/// Slug used for posts whose title has no letters or digits
+const FALLBACK_POST_SLUG: &str = "post";
+
+/// Turn arbitrary text into a URL slug
+///
+/// Alphanumeric characters (including non-ASCII letters) are kept and lowercased;
+/// every other run of characters becomes a single `-`. Leading and trailing
+/// separators are trimmed, so the result may be empty for input without letters or digits.
+pub fn slugify(input: &str) -> String {
+    let mut slug = String::with_capacity(input.len());
+    let mut pending_separator = false;
+
+    for c in input.chars() {
+        if c.is_alphanumeric() {
+            if pending_separator && !slug.is_empty() {
+                slug.push('-');
+            }
+            pending_separator = false;
+            slug.extend(c.to_lowercase());
+        } else {
+            pending_separator = true;
+        }
+    }
+
+    slug
+}
+
+/// Turn a post title (or a user-supplied slug) into an ASCII permalink slug
+///
+/// Non-ASCII text is transliterated first ("Straße" becomes "strasse", "北京" becomes
+/// "bei-jing"), the result is cut to [`MAX_POST_SLUG_LEN`] at a word boundary where
+/// possible, and falls back to `post` when nothing is left.
+pub fn post_slug(title: &str) -> String {
+    let mut slug = slugify(&deunicode::deunicode(title));
+    if slug.len() > MAX_POST_SLUG_LEN {
+        slug.truncate(MAX_POST_SLUG_LEN);
+        if let Some(cut) = slug.rfind('-').filter(|cut| *cut > MAX_POST_SLUG_LEN / 2) {
+            slug.truncate(cut);
+        }
+        slug = slug.trim_end_matches('-').to_string();
+    }
+
+    if slug.is_empty() {
+        FALLBACK_POST_SLUG.to_string()
+    } else {
+        slug
+    }
+}
+
+/// Make `base` unique by appending `-2`, `-3`, ... until it is not in `taken`
+pub fn unique_slug(base: &str, taken: &HashSet<String>) -> String {
+    if !taken.contains(base) {
+        return base.to_string();
+    }
+    (2..)
+        .map(|n| format!("{}-{}", base, n))
+        .find(|candidate| !taken.contains(candidate))
+        .expect("unbounded suffix range always yields a free slug")
+}
+
+/// Path of a post's permalink page (`/p/{slug}`)
+pub fn post_path(slug: &str) -> String {
+    format!("/p/{}", encode_path_segment(slug))
+}
+
+/// Percent-encode a value for use as one URL path segment
+pub fn encode_path_segment(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                encoded.push(byte as char)
+            }
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_slugify_basic() {
+        assert_eq!(slugify("Hello, World!"), "hello-world");
+        assert_eq!(slugify("  Rust   & WebAssembly  "), "rust-webassembly");
+    }
+
+    #[test]
+    fn test_slugify_keeps_unicode_letters() {
+        assert_eq!(slugify("Café Über"), "café-über");
+    }
+
+    #[test]
+    fn test_slugify_empty() {
+        assert_eq!(slugify("!!!"), "");
+    }
+
+    #[test]
+    fn test_post_slug_transliterates() {
+        assert_eq!(post_slug("Café Über Straße"), "cafe-uber-strasse");
+        assert_eq!(post_slug("北京"), "bei-jing");
+        assert_eq!(post_slug("???"), "post");
+    }
+
+    #[test]
+    fn test_post_slug_truncates_at_word_boundary() {
+        let slug = post_slug(&"word ".repeat(40));
+        assert!(slug.len() <= MAX_POST_SLUG_LEN);
+        assert!(slug.ends_with("word"));
+    }
+
+    #[test]
+    fn test_unique_slug() {
+        let taken: HashSet<String> = ["hello".to_string(), "hello-2".to_string()].into();
+        assert_eq!(unique_slug("hello", &taken), "hello-3");
+        assert_eq!(unique_slug("other", &taken), "other");
+    }
+}
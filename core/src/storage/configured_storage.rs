@@ -0,0 +1,136 @@
+//! Storage backend selected by configuration
+//!
+//! `STORAGE_BACKEND` picks the implementation: `local` (the default) for
+//! [`LocalStorage`], or `s3` for [`S3Storage`] configured from the `S3_*`
+//! environment variables.
+
+use crate::storage::{
+    FileMetadata, LocalStorage, S3Config, S3Storage, StorageBackend, StorageResult, StoredFile,
+};
+use async_trait::async_trait;
+use std::ops::Range;
+use std::path::Path;
+
+/// The storage backend the server was configured with
+pub enum ConfiguredStorage {
+    Local(LocalStorage),
+    S3(S3Storage),
+}
+
+impl ConfiguredStorage {
+    /// Create the backend chosen by `STORAGE_BACKEND` and check that it is usable
+    ///
+    /// # Arguments
+    /// * `storage_dir` - Directory used by local storage
+    /// * `base_url` - Base URL for accessing files
+    pub async fn from_env(storage_dir: impl AsRef<Path>, base_url: String) -> StorageResult<Self> {
+        let backend = std::env::var("STORAGE_BACKEND").unwrap_or_else(|_| "local".to_string());
+        match backend.as_str() {
+            "local" | "" => {
+                let storage = LocalStorage::new(storage_dir, base_url);
+                storage.ensure_directory().await?;
+                Ok(ConfiguredStorage::Local(storage))
+            }
+            "s3" => {
+                let storage = S3Storage::new(S3Config::from_env()?, base_url);
+                storage.check_bucket().await?;
+                Ok(ConfiguredStorage::S3(storage))
+            }
+            other => {
+                Err(format!("Unknown STORAGE_BACKEND {:?} (expected local or s3)", other).into())
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl StorageBackend for ConfiguredStorage {
+    async fn put_file(
+        &self,
+        file_data: Vec<u8>,
+        file_name: &str,
+        content_type: String,
+    ) -> StorageResult<String> {
+        match self {
+            ConfiguredStorage::Local(storage) => {
+                storage.put_file(file_data, file_name, content_type).await
+            }
+            ConfiguredStorage::S3(storage) => {
+                storage.put_file(file_data, file_name, content_type).await
+            }
+        }
+    }
+
+    async fn put_file_from_path(
+        &self,
+        path: &Path,
+        file_name: &str,
+        content_type: String,
+    ) -> StorageResult<String> {
+        match self {
+            ConfiguredStorage::Local(storage) => {
+                storage
+                    .put_file_from_path(path, file_name, content_type)
+                    .await
+            }
+            ConfiguredStorage::S3(storage) => {
+                storage
+                    .put_file_from_path(path, file_name, content_type)
+                    .await
+            }
+        }
+    }
+
+    async fn delete_file(&self, file_url: &str) -> StorageResult<()> {
+        match self {
+            ConfiguredStorage::Local(storage) => storage.delete_file(file_url).await,
+            ConfiguredStorage::S3(storage) => storage.delete_file(file_url).await,
+        }
+    }
+
+    async fn get(
+        &self,
+        file_url: &str,
+        range: Option<Range<u64>>,
+    ) -> StorageResult<Option<StoredFile>> {
+        match self {
+            ConfiguredStorage::Local(storage) => storage.get(file_url, range).await,
+            ConfiguredStorage::S3(storage) => storage.get(file_url, range).await,
+        }
+    }
+
+    async fn head(&self, file_url: &str) -> StorageResult<Option<FileMetadata>> {
+        match self {
+            ConfiguredStorage::Local(storage) => storage.head(file_url).await,
+            ConfiguredStorage::S3(storage) => storage.head(file_url).await,
+        }
+    }
+
+    async fn list(&self, prefix: &str) -> StorageResult<Vec<FileMetadata>> {
+        match self {
+            ConfiguredStorage::Local(storage) => storage.list(prefix).await,
+            ConfiguredStorage::S3(storage) => storage.list(prefix).await,
+        }
+    }
+
+    async fn copy(&self, file_url: &str, new_name: &str) -> StorageResult<String> {
+        match self {
+            ConfiguredStorage::Local(storage) => storage.copy(file_url, new_name).await,
+            ConfiguredStorage::S3(storage) => storage.copy(file_url, new_name).await,
+        }
+    }
+
+    async fn redirect_url(&self, file_url: &str) -> StorageResult<Option<String>> {
+        match self {
+            ConfiguredStorage::Local(storage) => storage.redirect_url(file_url).await,
+            ConfiguredStorage::S3(storage) => storage.redirect_url(file_url).await,
+        }
+    }
+
+    fn get_base_url(&self) -> &str {
+        match self {
+            ConfiguredStorage::Local(storage) => storage.get_base_url(),
+            ConfiguredStorage::S3(storage) => storage.get_base_url(),
+        }
+    }
+}
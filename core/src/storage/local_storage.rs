@@ -2,10 +2,13 @@
 //! 
 //! Stores files in a local directory and serves them via HTTP
 
-use crate::storage::{StorageBackend, StorageResult};
+use crate::storage::{FileMetadata, StorageBackend, StorageResult, StoredFile, stored_file_name};
 use async_trait::async_trait;
+use futures::StreamExt;
+use std::ops::Range;
 use std::path::{Path, PathBuf};
-use uuid::Uuid;
+use tokio::io::{AsyncReadExt, AsyncSeekExt};
+use tokio_util::io::ReaderStream;
 
 /// Local filesystem storage backend
 pub struct LocalStorage {
@@ -34,56 +37,154 @@ impl LocalStorage {
         Ok(())
     }
 
-    /// Generate a unique file name
-    fn generate_file_name(&self, original_name: &str) -> String {
-        let extension = Path::new(original_name)
-            .extension()
-            .and_then(|ext| ext.to_str())
-            .unwrap_or("bin");
-        
-        format!("{}.{}", Uuid::new_v4(), extension)
+    /// Directory where files are stored
+    pub fn storage_dir(&self) -> &Path {
+        &self.storage_dir
+    }
+
+    /// Metadata of a file on disk; `None` for directories and other non-files
+    fn file_metadata(file_name: &str, metadata: &std::fs::Metadata) -> Option<FileMetadata> {
+        if !metadata.is_file() {
+            return None;
+        }
+        Some(FileMetadata {
+            name: file_name.to_string(),
+            size: metadata.len(),
+            content_type: mime_guess::from_path(file_name)
+                .first()
+                .map(|mime| mime.to_string()),
+            last_modified: metadata.modified().ok().map(Into::into),
+        })
     }
 }
 
 #[async_trait]
 impl StorageBackend for LocalStorage {
-    async fn upload_file(
+    async fn put_file(
         &self,
         file_data: Vec<u8>,
-        file_name: String,
+        file_name: &str,
         _content_type: String,
     ) -> StorageResult<String> {
+        if stored_file_name(file_name) != Some(file_name) {
+            return Err(format!("Invalid file name: {}", file_name).into());
+        }
+
         // Ensure directory exists
         self.ensure_directory().await?;
 
-        // Generate unique file name
-        let unique_name = self.generate_file_name(&file_name);
-        let file_path = self.storage_dir.join(&unique_name);
-
         // Write file to disk
-        tokio::fs::write(&file_path, file_data).await?;
+        tokio::fs::write(self.storage_dir.join(file_name), file_data).await?;
 
         // Return relative path for local storage (e.g., "/uploads/xxx.jpg")
         // This makes URLs portable and independent of server domain
-        let url = format!("/uploads/{}", unique_name);
+        let url = format!("/uploads/{}", file_name);
         Ok(url)
     }
 
+    async fn put_file_from_path(
+        &self,
+        path: &Path,
+        file_name: &str,
+        _content_type: String,
+    ) -> StorageResult<String> {
+        if stored_file_name(file_name) != Some(file_name) {
+            return Err(format!("Invalid file name: {}", file_name).into());
+        }
+
+        self.ensure_directory().await?;
+        tokio::fs::copy(path, self.storage_dir.join(file_name)).await?;
+
+        Ok(format!("/uploads/{}", file_name))
+    }
+
     async fn delete_file(&self, file_url: &str) -> StorageResult<()> {
-        // Extract file name from URL
-        let file_name = file_url
-            .split('/')
-            .last()
-            .ok_or("Invalid file URL")?;
-        
-        let file_path = self.storage_dir.join(file_name);
-        
-        // Delete file if it exists
-        if file_path.exists() {
-            tokio::fs::remove_file(&file_path).await?;
+        let file_name = stored_file_name(file_url).ok_or("Invalid file URL")?;
+
+        // Deleting a missing file succeeds
+        match tokio::fs::remove_file(self.storage_dir.join(file_name)).await {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(e.into()),
         }
-        
-        Ok(())
+    }
+
+    async fn get(
+        &self,
+        file_url: &str,
+        range: Option<Range<u64>>,
+    ) -> StorageResult<Option<StoredFile>> {
+        let Some(file_name) = stored_file_name(file_url) else {
+            return Ok(None);
+        };
+
+        let mut file = match tokio::fs::File::open(self.storage_dir.join(file_name)).await {
+            Ok(file) => file,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e.into()),
+        };
+        let Some(metadata) = Self::file_metadata(file_name, &file.metadata().await?) else {
+            return Ok(None);
+        };
+
+        let body = match range {
+            Some(range) => {
+                file.seek(std::io::SeekFrom::Start(range.start)).await?;
+                ReaderStream::new(file.take(range.end.saturating_sub(range.start))).boxed()
+            }
+            None => ReaderStream::new(file).boxed(),
+        };
+        Ok(Some(StoredFile { metadata, body }))
+    }
+
+    async fn head(&self, file_url: &str) -> StorageResult<Option<FileMetadata>> {
+        let Some(file_name) = stored_file_name(file_url) else {
+            return Ok(None);
+        };
+
+        match tokio::fs::metadata(self.storage_dir.join(file_name)).await {
+            Ok(metadata) => Ok(Self::file_metadata(file_name, &metadata)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn list(&self, prefix: &str) -> StorageResult<Vec<FileMetadata>> {
+        let mut entries = match tokio::fs::read_dir(&self.storage_dir).await {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e.into()),
+        };
+
+        let mut files = Vec::new();
+        while let Some(entry) = entries.next_entry().await? {
+            let Ok(name) = entry.file_name().into_string() else {
+                continue;
+            };
+            if !name.starts_with(prefix) || stored_file_name(&name) != Some(name.as_str()) {
+                continue;
+            }
+            if let Some(file) = Self::file_metadata(&name, &entry.metadata().await?) {
+                files.push(file);
+            }
+        }
+        files.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(files)
+    }
+
+    async fn copy(&self, file_url: &str, new_name: &str) -> StorageResult<String> {
+        let file_name = stored_file_name(file_url).ok_or("Invalid file URL")?;
+        if stored_file_name(new_name) != Some(new_name) {
+            return Err(format!("Invalid file name: {}", new_name).into());
+        }
+
+        tokio::fs::copy(
+            self.storage_dir.join(file_name),
+            self.storage_dir.join(new_name),
+        )
+        .await?;
+
+        Ok(format!("/uploads/{}", new_name))
     }
 
     fn get_base_url(&self) -> &str {
@@ -91,3 +192,116 @@ impl StorageBackend for LocalStorage {
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn body(file: StoredFile) -> Vec<u8> {
+        let mut data = Vec::new();
+        let mut body = file.body;
+        while let Some(chunk) = body.next().await {
+            data.extend_from_slice(&chunk.unwrap());
+        }
+        data
+    }
+
+    #[tokio::test]
+    async fn test_put_head_and_get() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = LocalStorage::new(dir.path().join("uploads"), String::new());
+
+        let url = storage
+            .put_file(b"0123456789".to_vec(), "cat.png", "image/png".to_string())
+            .await
+            .unwrap();
+        assert_eq!(url, "/uploads/cat.png");
+
+        let metadata = storage.head(&url).await.unwrap().unwrap();
+        assert_eq!(metadata.name, "cat.png");
+        assert_eq!(metadata.size, 10);
+        assert_eq!(metadata.content_type.as_deref(), Some("image/png"));
+        assert!(metadata.last_modified.is_some());
+
+        let file = storage.get(&url, None).await.unwrap().unwrap();
+        assert_eq!(file.metadata, metadata);
+        assert_eq!(body(file).await, b"0123456789");
+        let file = storage.get(&url, Some(2..5)).await.unwrap().unwrap();
+        assert_eq!(body(file).await, b"234");
+
+        assert!(storage.exists(&url).await.unwrap());
+        for name in ["a/cat.png", ".env", ""] {
+            assert!(
+                storage
+                    .put_file(Vec::new(), name, String::new())
+                    .await
+                    .is_err(),
+                "{}",
+                name
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_missing_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = LocalStorage::new(dir.path().join("uploads"), String::new());
+
+        // Nothing stored yet, not even the directory
+        assert!(storage.list("").await.unwrap().is_empty());
+        assert!(storage.head("/uploads/dog.png").await.unwrap().is_none());
+        assert!(
+            storage
+                .get("/uploads/dog.png", None)
+                .await
+                .unwrap()
+                .is_none()
+        );
+        assert!(!storage.exists("/uploads/dog.png").await.unwrap());
+        storage.delete_file("/uploads/dog.png").await.unwrap();
+        assert!(storage.copy("/uploads/dog.png", "cat.png").await.is_err());
+
+        // Files outside the storage directory are never reached
+        std::fs::create_dir_all(dir.path().join("uploads")).unwrap();
+        std::fs::write(dir.path().join("uploads/.env"), "SECRET=1").unwrap();
+        assert!(storage.head("/uploads/.env").await.unwrap().is_none());
+        assert!(storage.delete_file("/uploads/.env").await.is_err());
+        assert!(dir.path().join("uploads/.env").exists());
+    }
+
+    #[tokio::test]
+    async fn test_list_copy_and_delete() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = LocalStorage::new(dir.path(), String::new());
+        for name in ["b.png", "a.jpg", "ab.txt"] {
+            storage
+                .put_file(name.as_bytes().to_vec(), name, String::new())
+                .await
+                .unwrap();
+        }
+        std::fs::write(dir.path().join(".hidden"), "").unwrap();
+        std::fs::create_dir(dir.path().join("a-dir")).unwrap();
+
+        let names =
+            |files: Vec<FileMetadata>| files.into_iter().map(|file| file.name).collect::<Vec<_>>();
+        assert_eq!(
+            names(storage.list("").await.unwrap()),
+            ["a.jpg", "ab.txt", "b.png"]
+        );
+        assert_eq!(names(storage.list("a").await.unwrap()), ["a.jpg", "ab.txt"]);
+        assert!(storage.list("c").await.unwrap().is_empty());
+
+        let copy = storage.copy("/uploads/a.jpg", "c.jpg").await.unwrap();
+        assert_eq!(copy, "/uploads/c.jpg");
+        assert_eq!(std::fs::read(dir.path().join("c.jpg")).unwrap(), b"a.jpg");
+        assert!(storage.copy("/uploads/a.jpg", "../c.jpg").await.is_err());
+
+        storage
+            .delete_file("http://localhost:3000/uploads/a.jpg")
+            .await
+            .unwrap();
+        assert_eq!(
+            names(storage.list("").await.unwrap()),
+            ["ab.txt", "b.png", "c.jpg"]
+        );
+    }
+}
@@ -3,9 +3,13 @@
 //! This module provides a trait-based storage interface that allows
 //! easy switching between different storage backends (local filesystem, S3, etc.)
 
+pub mod configured_storage;
 pub mod local_storage;
+pub mod s3_storage;
 pub mod storage_trait;
 
 pub use storage_trait::*;
 pub use local_storage::*;
+pub use s3_storage::*;
+pub use configured_storage::*;
 
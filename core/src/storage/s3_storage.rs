@@ -0,0 +1,504 @@
+//! S3-compatible object storage implementation
+//!
+//! Stores files as objects in a bucket on AWS S3 or any S3-compatible server
+//! (MinIO, Ceph, Cloudflare R2, ...). URLs stay `/uploads/<name>` as with local
+//! storage, so post content doesn't change when switching backends; requests for
+//! them are either streamed from the bucket or redirected to it.
+
+use crate::storage::{FileMetadata, StorageBackend, StorageResult, StoredFile, stored_file_name};
+use async_trait::async_trait;
+use aws_sdk_s3::Client;
+use aws_sdk_s3::config::{BehaviorVersion, Credentials, Region};
+use aws_sdk_s3::error::DisplayErrorContext;
+use aws_sdk_s3::presigning::PresigningConfig;
+use aws_sdk_s3::primitives::{ByteStream, DateTime as S3DateTime};
+use chrono::{DateTime, Utc};
+use futures::StreamExt;
+use std::ops::Range;
+use std::path::Path;
+use std::time::Duration;
+use tokio_util::io::ReaderStream;
+
+/// How long a presigned redirect stays valid
+const PRESIGNED_URL_TTL: Duration = Duration::from_secs(3600);
+
+/// Connection and behaviour settings of [`S3Storage`]
+#[derive(Debug, Clone)]
+pub struct S3Config {
+    /// Endpoint of an S3-compatible server (e.g. `http://localhost:9000`); AWS when `None`
+    pub endpoint: Option<String>,
+    pub bucket: String,
+    pub region: String,
+    pub access_key_id: String,
+    pub secret_access_key: String,
+    /// Address the bucket as `endpoint/bucket` instead of `bucket.endpoint`;
+    /// MinIO and most self-hosted servers need this
+    pub force_path_style: bool,
+    /// Prepended to the object keys (e.g. `uploads/`)
+    pub key_prefix: String,
+    /// Redirect requests for files to the bucket instead of streaming them
+    pub redirect: bool,
+    /// Public URL of the bucket, or of a CDN in front of it, to redirect to;
+    /// redirects use presigned URLs when `None`
+    pub public_url: Option<String>,
+}
+
+impl S3Config {
+    /// Read the configuration from the `S3_*` environment variables
+    pub fn from_env() -> StorageResult<Self> {
+        let required = |name: &str| {
+            std::env::var(name)
+                .ok()
+                .filter(|value| !value.is_empty())
+                .ok_or_else(|| format!("{} must be set for S3 storage", name))
+        };
+        let optional = |name: &str| std::env::var(name).ok().filter(|value| !value.is_empty());
+        let flag = |name: &str| {
+            optional(name).is_some_and(|value| matches!(value.as_str(), "true" | "1" | "yes"))
+        };
+
+        Ok(Self {
+            endpoint: optional("S3_ENDPOINT"),
+            bucket: required("S3_BUCKET")?,
+            region: optional("S3_REGION").unwrap_or_else(|| "us-east-1".to_string()),
+            access_key_id: required("S3_ACCESS_KEY_ID")?,
+            secret_access_key: required("S3_SECRET_ACCESS_KEY")?,
+            force_path_style: flag("S3_FORCE_PATH_STYLE"),
+            key_prefix: optional("S3_KEY_PREFIX").unwrap_or_default(),
+            redirect: flag("S3_REDIRECT"),
+            public_url: optional("S3_PUBLIC_URL"),
+        })
+    }
+}
+
+/// S3-compatible object storage backend
+pub struct S3Storage {
+    client: Client,
+    config: S3Config,
+    /// Base URL for accessing files (e.g., "http://localhost:3000/uploads")
+    base_url: String,
+}
+
+impl S3Storage {
+    /// Create a new S3 storage instance
+    ///
+    /// # Arguments
+    /// * `config` - Bucket, endpoint and credentials
+    /// * `base_url` - Base URL for accessing files
+    pub fn new(config: S3Config, base_url: String) -> Self {
+        let credentials = Credentials::new(
+            config.access_key_id.clone(),
+            config.secret_access_key.clone(),
+            None,
+            None,
+            "rustpress",
+        );
+        let mut builder = aws_sdk_s3::Config::builder()
+            .behavior_version(BehaviorVersion::latest())
+            .region(Region::new(config.region.clone()))
+            .credentials_provider(credentials)
+            .force_path_style(config.force_path_style);
+        if let Some(endpoint) = &config.endpoint {
+            builder = builder.endpoint_url(endpoint.clone());
+        }
+
+        Self {
+            client: Client::from_conf(builder.build()),
+            config,
+            base_url,
+        }
+    }
+
+    /// Bucket the files are stored in
+    pub fn bucket(&self) -> &str {
+        &self.config.bucket
+    }
+
+    /// Check that the bucket exists and the credentials can reach it
+    pub async fn check_bucket(&self) -> StorageResult<()> {
+        self.client
+            .head_bucket()
+            .bucket(&self.config.bucket)
+            .send()
+            .await
+            .map_err(|e| {
+                format!(
+                    "S3 bucket {} is not reachable: {}",
+                    self.config.bucket,
+                    DisplayErrorContext(e)
+                )
+            })?;
+        Ok(())
+    }
+
+    /// Object key of a file from its URL or path
+    fn key(&self, file_url: &str) -> Option<String> {
+        stored_file_name(file_url).map(|name| format!("{}{}", self.config.key_prefix, name))
+    }
+}
+
+fn to_utc(time: &S3DateTime) -> Option<DateTime<Utc>> {
+    DateTime::from_timestamp(time.secs(), time.subsec_nanos())
+}
+
+/// Size of the whole object from a `Content-Range` header (`bytes 0-99/1234`)
+fn content_range_size(content_range: &str) -> Option<u64> {
+    content_range.rsplit('/').next()?.parse().ok()
+}
+
+/// Percent-encode an object key for `x-amz-copy-source`, keeping the slashes
+fn encode_key(key: &str) -> String {
+    key.split('/')
+        .map(|segment| {
+            url::form_urlencoded::byte_serialize(segment.as_bytes())
+                .collect::<String>()
+                .replace('+', "%20")
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+#[async_trait]
+impl StorageBackend for S3Storage {
+    async fn put_file(
+        &self,
+        file_data: Vec<u8>,
+        file_name: &str,
+        content_type: String,
+    ) -> StorageResult<String> {
+        if stored_file_name(file_name) != Some(file_name) {
+            return Err(format!("Invalid file name: {}", file_name).into());
+        }
+
+        self.client
+            .put_object()
+            .bucket(&self.config.bucket)
+            .key(format!("{}{}", self.config.key_prefix, file_name))
+            .content_type(content_type)
+            .body(ByteStream::from(file_data))
+            .send()
+            .await?;
+
+        // Same relative URL as local storage, served by the /uploads route
+        Ok(format!("/uploads/{}", file_name))
+    }
+
+    async fn put_file_from_path(
+        &self,
+        path: &Path,
+        file_name: &str,
+        content_type: String,
+    ) -> StorageResult<String> {
+        if stored_file_name(file_name) != Some(file_name) {
+            return Err(format!("Invalid file name: {}", file_name).into());
+        }
+
+        // Streamed from disk rather than read into memory
+        self.client
+            .put_object()
+            .bucket(&self.config.bucket)
+            .key(format!("{}{}", self.config.key_prefix, file_name))
+            .content_type(content_type)
+            .body(ByteStream::from_path(path).await?)
+            .send()
+            .await?;
+
+        Ok(format!("/uploads/{}", file_name))
+    }
+
+    async fn delete_file(&self, file_url: &str) -> StorageResult<()> {
+        let key = self.key(file_url).ok_or("Invalid file URL")?;
+
+        // Deleting a missing object succeeds
+        self.client
+            .delete_object()
+            .bucket(&self.config.bucket)
+            .key(key)
+            .send()
+            .await?;
+
+        Ok(())
+    }
+
+    async fn get(
+        &self,
+        file_url: &str,
+        range: Option<Range<u64>>,
+    ) -> StorageResult<Option<StoredFile>> {
+        let Some(name) = stored_file_name(file_url) else {
+            return Ok(None);
+        };
+
+        let mut request = self
+            .client
+            .get_object()
+            .bucket(&self.config.bucket)
+            .key(format!("{}{}", self.config.key_prefix, name));
+        if let Some(range) = &range {
+            if range.start >= range.end {
+                // S3 can't return an empty range
+                let Some(metadata) = self.head(file_url).await? else {
+                    return Ok(None);
+                };
+                return Ok(Some(StoredFile {
+                    metadata,
+                    body: futures::stream::empty().boxed(),
+                }));
+            }
+            request = request.range(format!("bytes={}-{}", range.start, range.end - 1));
+        }
+        let object = match request.send().await {
+            Ok(object) => object,
+            Err(e) => {
+                let e = e.into_service_error();
+                if e.is_no_such_key() {
+                    return Ok(None);
+                }
+                return Err(e.into());
+            }
+        };
+
+        let size = match object.content_range.as_deref() {
+            Some(content_range) => content_range_size(content_range),
+            None => object
+                .content_length
+                .and_then(|length| u64::try_from(length).ok()),
+        };
+        Ok(Some(StoredFile {
+            metadata: FileMetadata {
+                name: name.to_string(),
+                size: size.unwrap_or_default(),
+                content_type: object.content_type,
+                last_modified: object.last_modified.as_ref().and_then(to_utc),
+            },
+            body: ReaderStream::new(object.body.into_async_read()).boxed(),
+        }))
+    }
+
+    async fn head(&self, file_url: &str) -> StorageResult<Option<FileMetadata>> {
+        let Some(name) = stored_file_name(file_url) else {
+            return Ok(None);
+        };
+
+        let object = match self
+            .client
+            .head_object()
+            .bucket(&self.config.bucket)
+            .key(format!("{}{}", self.config.key_prefix, name))
+            .send()
+            .await
+        {
+            Ok(object) => object,
+            Err(e) => {
+                let e = e.into_service_error();
+                if e.is_not_found() {
+                    return Ok(None);
+                }
+                return Err(e.into());
+            }
+        };
+
+        Ok(Some(FileMetadata {
+            name: name.to_string(),
+            size: object
+                .content_length
+                .and_then(|length| u64::try_from(length).ok())
+                .unwrap_or_default(),
+            content_type: object.content_type,
+            last_modified: object.last_modified.as_ref().and_then(to_utc),
+        }))
+    }
+
+    async fn list(&self, prefix: &str) -> StorageResult<Vec<FileMetadata>> {
+        let mut files = Vec::new();
+        let mut continuation_token = None;
+        loop {
+            let page = self
+                .client
+                .list_objects_v2()
+                .bucket(&self.config.bucket)
+                .prefix(format!("{}{}", self.config.key_prefix, prefix))
+                // Only the files directly under the key prefix
+                .delimiter("/")
+                .set_continuation_token(continuation_token)
+                .send()
+                .await?;
+
+            for object in page.contents() {
+                let Some(name) = object
+                    .key()
+                    .and_then(|key| key.strip_prefix(&self.config.key_prefix))
+                    .filter(|name| stored_file_name(name) == Some(*name))
+                else {
+                    continue;
+                };
+                files.push(FileMetadata {
+                    name: name.to_string(),
+                    size: object
+                        .size()
+                        .and_then(|size| u64::try_from(size).ok())
+                        .unwrap_or_default(),
+                    // Listings don't include the content type
+                    content_type: mime_guess::from_path(name)
+                        .first()
+                        .map(|mime| mime.to_string()),
+                    last_modified: object.last_modified().and_then(to_utc),
+                });
+            }
+
+            match page.next_continuation_token {
+                Some(token) if page.is_truncated == Some(true) => continuation_token = Some(token),
+                _ => break,
+            }
+        }
+        files.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(files)
+    }
+
+    async fn copy(&self, file_url: &str, new_name: &str) -> StorageResult<String> {
+        let key = self.key(file_url).ok_or("Invalid file URL")?;
+        if stored_file_name(new_name) != Some(new_name) {
+            return Err(format!("Invalid file name: {}", new_name).into());
+        }
+
+        self.client
+            .copy_object()
+            .bucket(&self.config.bucket)
+            .copy_source(format!("{}/{}", self.config.bucket, encode_key(&key)))
+            .key(format!("{}{}", self.config.key_prefix, new_name))
+            .send()
+            .await?;
+
+        Ok(format!("/uploads/{}", new_name))
+    }
+
+    async fn redirect_url(&self, file_url: &str) -> StorageResult<Option<String>> {
+        if !self.config.redirect {
+            return Ok(None);
+        }
+        let key = self.key(file_url).ok_or("Invalid file URL")?;
+
+        if let Some(public_url) = &self.config.public_url {
+            return Ok(Some(format!(
+                "{}/{}",
+                public_url.trim_end_matches('/'),
+                key
+            )));
+        }
+        let request = self
+            .client
+            .get_object()
+            .bucket(&self.config.bucket)
+            .key(key)
+            .presigned(PresigningConfig::expires_in(PRESIGNED_URL_TTL)?)
+            .await?;
+        Ok(Some(request.uri().to_string()))
+    }
+
+    fn get_base_url(&self) -> &str {
+        &self.base_url
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn storage(redirect: bool, public_url: Option<&str>) -> S3Storage {
+        S3Storage::new(
+            S3Config {
+                endpoint: Some("http://localhost:9000".to_string()),
+                bucket: "media".to_string(),
+                region: "us-east-1".to_string(),
+                access_key_id: "access".to_string(),
+                secret_access_key: "secret".to_string(),
+                force_path_style: true,
+                key_prefix: "site/".to_string(),
+                redirect,
+                public_url: public_url.map(str::to_string),
+            },
+            "http://localhost:3000/uploads".to_string(),
+        )
+    }
+
+    #[test]
+    fn test_key() {
+        let storage = storage(false, None);
+        assert_eq!(
+            storage.key("/uploads/cat.png").as_deref(),
+            Some("site/cat.png")
+        );
+        assert_eq!(storage.key("cat.png").as_deref(), Some("site/cat.png"));
+        assert_eq!(
+            storage
+                .key("https://blog.example/uploads/cat.png")
+                .as_deref(),
+            Some("site/cat.png")
+        );
+        assert_eq!(storage.key("/uploads/.env"), None);
+        assert_eq!(storage.key("/uploads/"), None);
+        assert_eq!(storage.key("/uploads/..\\secret"), None);
+    }
+
+    #[test]
+    fn test_header_helpers() {
+        assert_eq!(content_range_size("bytes 0-99/1234"), Some(1234));
+        assert_eq!(content_range_size("bytes 0-99/*"), None);
+        assert_eq!(encode_key("site/cat.png"), "site/cat.png");
+        assert_eq!(
+            encode_key("site/my cat+dog%.png"),
+            "site/my%20cat%2Bdog%25.png"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_put_file_rejects_paths() {
+        // Refused before any request is made
+        let storage = storage(false, None);
+        for name in ["a/cat.png", "../cat.png", ".env", ""] {
+            assert!(
+                storage
+                    .put_file(Vec::new(), name, "image/png".to_string())
+                    .await
+                    .is_err(),
+                "{}",
+                name
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_redirect_url() {
+        assert_eq!(
+            storage(false, Some("https://cdn.example"))
+                .redirect_url("/uploads/cat.png")
+                .await
+                .unwrap(),
+            None
+        );
+
+        let public = storage(true, Some("https://cdn.example/"));
+        assert_eq!(
+            public
+                .redirect_url("/uploads/cat.png")
+                .await
+                .unwrap()
+                .as_deref(),
+            Some("https://cdn.example/site/cat.png")
+        );
+        assert!(public.redirect_url("/uploads/.env").await.is_err());
+
+        let presigned = storage(true, None)
+            .redirect_url("/uploads/cat.png")
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(
+            presigned.starts_with("http://localhost:9000/media/site/cat.png?"),
+            "{}",
+            presigned
+        );
+        assert!(presigned.contains("X-Amz-Signature="), "{}", presigned);
+        assert!(presigned.contains("X-Amz-Expires=3600"), "{}", presigned);
+    }
+}
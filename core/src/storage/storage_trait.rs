@@ -3,18 +3,73 @@
 //! Defines the interface for storage backends (local filesystem, S3, etc.)
 
 use async_trait::async_trait;
+use bytes::Bytes;
+use chrono::{DateTime, Utc};
+use futures::stream::BoxStream;
 use std::error::Error;
+use std::ops::Range;
+use std::path::Path;
+use uuid::Uuid;
 
 /// Result type for storage operations
 pub type StorageResult<T> = Result<T, Box<dyn Error + Send + Sync>>;
 
+/// Metadata of a stored file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileMetadata {
+    /// Name of the file in storage, the last segment of its URL
+    pub name: String,
+    /// Size in bytes
+    pub size: u64,
+    /// MIME type recorded with the file, or guessed from its name
+    pub content_type: Option<String>,
+    pub last_modified: Option<DateTime<Utc>>,
+}
+
+/// A stored file, opened for reading
+pub struct StoredFile {
+    /// Metadata of the whole file
+    pub metadata: FileMetadata,
+    /// The requested bytes: the whole file, or the requested range of it
+    pub body: BoxStream<'static, std::io::Result<Bytes>>,
+}
+
+/// Extension of an uploaded file's original name, `bin` when it has none
+fn file_extension(original_name: &str) -> &str {
+    Path::new(original_name)
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .unwrap_or("bin")
+}
+
+/// Generate a unique name for a new file, keeping the original extension
+pub fn generate_file_name(original_name: &str) -> String {
+    format!("{}.{}", Uuid::new_v4(), file_extension(original_name))
+}
+
+/// Name of a file stored by its content: the hex SHA-256 digest and the
+/// original extension
+pub fn content_addressed_file_name(checksum: &str, original_name: &str) -> String {
+    format!("{}.{}", checksum, file_extension(original_name))
+}
+
+/// Name of a stored file from its URL or path, the last path segment;
+/// `None` for anything that could escape the storage location
+pub fn stored_file_name(file_url: &str) -> Option<&str> {
+    let name = file_url.rsplit('/').next()?;
+    if name.is_empty() || name.starts_with('.') || name.contains('\\') {
+        return None;
+    }
+    Some(name)
+}
+
 /// Storage backend trait
 /// 
 /// This trait defines the interface for storing and retrieving files.
 /// Implementations can be for local filesystem, S3, Azure Blob, etc.
 #[async_trait]
 pub trait StorageBackend: Send + Sync {
-    /// Upload a file and return its public URL
+    /// Upload a file under a new unique name and return its public URL
     /// 
     /// # Arguments
     /// * `file_data` - The file content as bytes
@@ -28,14 +83,100 @@ pub trait StorageBackend: Send + Sync {
         file_data: Vec<u8>,
         file_name: String,
         content_type: String,
+    ) -> StorageResult<String> {
+        self.put_file(file_data, &generate_file_name(&file_name), content_type)
+            .await
+    }
+
+    /// Write a file under exactly `file_name`, replacing any file of that name
+    ///
+    /// # Arguments
+    /// * `file_data` - The file content as bytes
+    /// * `file_name` - Name to store the file under
+    /// * `content_type` - The MIME type of the file
+    ///
+    /// # Returns
+    /// The public URL where the file can be accessed
+    async fn put_file(
+        &self,
+        file_data: Vec<u8>,
+        file_name: &str,
+        content_type: String,
     ) -> StorageResult<String>;
 
+    /// Write the contents of a local file under exactly `file_name`, replacing
+    /// any file of that name
+    ///
+    /// The default reads the whole file into memory; backends override it to
+    /// copy or stream the file instead.
+    ///
+    /// # Arguments
+    /// * `path` - The local file to store, e.g. a staged upload
+    /// * `file_name` - Name to store the file under
+    /// * `content_type` - The MIME type of the file
+    ///
+    /// # Returns
+    /// The public URL where the file can be accessed
+    async fn put_file_from_path(
+        &self,
+        path: &Path,
+        file_name: &str,
+        content_type: String,
+    ) -> StorageResult<String> {
+        let file_data = tokio::fs::read(path).await?;
+        self.put_file(file_data, file_name, content_type).await
+    }
+
     /// Delete a file by its URL or path
     /// 
     /// # Arguments
     /// * `file_url` - The URL or path of the file to delete
     async fn delete_file(&self, file_url: &str) -> StorageResult<()>;
 
+    /// Open a file by its URL or path for streaming
+    ///
+    /// # Arguments
+    /// * `file_url` - The URL or path of the file
+    /// * `range` - Byte range to read instead of the whole file; it is cut off
+    ///   at the end of the file
+    ///
+    /// # Returns
+    /// The file, or `None` if it does not exist
+    async fn get(
+        &self,
+        file_url: &str,
+        range: Option<Range<u64>>,
+    ) -> StorageResult<Option<StoredFile>>;
+
+    /// Get the metadata of a file by its URL or path, or `None` if it does not exist
+    async fn head(&self, file_url: &str) -> StorageResult<Option<FileMetadata>>;
+
+    /// Check whether a file exists
+    async fn exists(&self, file_url: &str) -> StorageResult<bool> {
+        Ok(self.head(file_url).await?.is_some())
+    }
+
+    /// List the stored files whose names start with `prefix`, sorted by name
+    async fn list(&self, prefix: &str) -> StorageResult<Vec<FileMetadata>>;
+
+    /// Copy a file to a new name, replacing any file of that name
+    ///
+    /// # Arguments
+    /// * `file_url` - The URL or path of the file to copy
+    /// * `new_name` - Name of the copy
+    ///
+    /// # Returns
+    /// The public URL of the copy
+    async fn copy(&self, file_url: &str, new_name: &str) -> StorageResult<String>;
+
+    /// URL that requests for a file should be redirected to
+    ///
+    /// Backends that return `None` (the default) have their files streamed
+    /// through the server with [`StorageBackend::get`].
+    async fn redirect_url(&self, _file_url: &str) -> StorageResult<Option<String>> {
+        Ok(None)
+    }
+
     /// Get the base URL for accessing files
     /// 
     /// This is used to construct full URLs for uploaded files
@@ -0,0 +1,291 @@
+//! Trash retention and permanent deletion of posts
+//!
+//! Deleting a post only moves it to the trash (`deleted_at`), where it can be
+//! restored until it is purged: from the admin API, or by the background job
+//! started with [`spawn`] once it has been in the trash for longer than the
+//! `trash_retention_days` setting. Purging a post deletes its versions and drafts
+//! with it, and the uploaded files it links to that no other post links to. Like
+//! the scheduler, every run of the job holds a Postgres advisory lock.
+
+use crate::api::admin_api::settings_helper::get_setting_value;
+use crate::dto::PurgeTrashResponse;
+use crate::repository::{MediaRepository, PostRepository};
+use crate::storage::StorageBackend;
+use chrono::{Duration as ChronoDuration, Utc};
+use sea_orm::prelude::DateTimeWithTimeZone;
+use sea_orm::{ConnectionTrait, DatabaseConnection, DbBackend, Statement, TransactionTrait};
+use std::collections::{BTreeSet, HashSet};
+use std::sync::Arc;
+use std::time::Duration;
+
+type TrashError = Box<dyn std::error::Error + Send + Sync>;
+
+/// Days posts stay in the trash when `trash_retention_days` is not set
+pub const DEFAULT_RETENTION_DAYS: i64 = 30;
+
+/// Seconds between two runs when `TRASH_PURGE_INTERVAL_SECS` is not set
+const DEFAULT_INTERVAL_SECS: u64 = 3600;
+
+/// Posts purged per query; a run keeps going until no expired post is left
+const BATCH_SIZE: u64 = 50;
+
+/// Advisory lock key shared by all instances ("rptrash")
+const LOCK_KEY: i64 = 0x72707472617368;
+
+/// Path that uploaded files are served under
+const UPLOADS_PATH: &str = "/uploads/";
+
+/// Days posts stay in the trash; `None` when they stay until purged by hand
+pub async fn retention_days(db: &Arc<DatabaseConnection>) -> Result<Option<i64>, TrashError> {
+    let days = get_setting_value(db, "trash_retention_days")
+        .await?
+        .and_then(|value| value.trim().parse::<i64>().ok())
+        .unwrap_or(DEFAULT_RETENTION_DAYS);
+    Ok((days > 0).then_some(days))
+}
+
+/// When a post deleted at `deleted_at` is purged by the background job
+pub fn purge_at(
+    deleted_at: DateTimeWithTimeZone,
+    retention_days: Option<i64>,
+) -> Option<DateTimeWithTimeZone> {
+    retention_days.map(|days| deleted_at + ChronoDuration::days(days))
+}
+
+/// Start the trash purge job on the Tokio runtime
+pub fn spawn<PR: PostRepository + 'static, SB: StorageBackend + 'static>(
+    post_repository: Arc<PR>,
+    media_repository: Arc<dyn MediaRepository>,
+    storage: Arc<SB>,
+    db: Arc<DatabaseConnection>,
+) -> tokio::task::JoinHandle<()> {
+    let interval_secs = std::env::var("TRASH_PURGE_INTERVAL_SECS")
+        .ok()
+        .and_then(|value| value.parse::<u64>().ok())
+        .filter(|secs| *secs > 0)
+        .unwrap_or(DEFAULT_INTERVAL_SECS);
+
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(interval_secs));
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        loop {
+            interval.tick().await;
+            if let Err(e) = run_once(
+                post_repository.as_ref(),
+                media_repository.as_ref(),
+                storage.as_ref(),
+                &db,
+            )
+            .await
+            {
+                tracing::error!("Trash purge failed: {:?}", e);
+            }
+        }
+    })
+}
+
+/// Purge the posts that have been in the trash for longer than the retention
+/// period, unless another instance is already doing so
+async fn run_once<PR: PostRepository, SB: StorageBackend>(
+    post_repository: &PR,
+    media_repository: &dyn MediaRepository,
+    storage: &SB,
+    db: &Arc<DatabaseConnection>,
+) -> Result<(), TrashError> {
+    let Some(days) = retention_days(db).await? else {
+        return Ok(());
+    };
+
+    let txn = db.begin().await?;
+
+    // Released when the transaction ends
+    let locked = txn
+        .query_one(Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            "SELECT pg_try_advisory_xact_lock($1) AS locked",
+            [LOCK_KEY.into()],
+        ))
+        .await?
+        .map(|row| row.try_get::<bool>("", "locked"))
+        .transpose()?
+        .unwrap_or(false);
+    if !locked {
+        txn.rollback().await?;
+        return Ok(());
+    }
+
+    let before = Utc::now() - ChronoDuration::days(days);
+    let purged =
+        purge_trashed_before(post_repository, media_repository, storage, before.into()).await;
+
+    txn.commit().await?;
+
+    let purged = purged?;
+    if purged.posts > 0 {
+        tracing::info!(
+            "Purged {} post(s) and {} media file(s) from the trash",
+            purged.posts,
+            purged.media_files
+        );
+    }
+    Ok(())
+}
+
+/// Purge all posts moved to the trash before `before`
+pub async fn purge_trashed_before<PR: PostRepository, SB: StorageBackend>(
+    post_repository: &PR,
+    media_repository: &dyn MediaRepository,
+    storage: &SB,
+    before: DateTimeWithTimeZone,
+) -> Result<PurgeTrashResponse, TrashError> {
+    let mut purged = PurgeTrashResponse::default();
+    loop {
+        let posts = post_repository
+            .find_trashed_before(before, BATCH_SIZE)
+            .await?;
+        let done = (posts.len() as u64) < BATCH_SIZE;
+        for post in posts {
+            if let Some(media_files) =
+                purge_post(post_repository, media_repository, storage, post.id).await?
+            {
+                purged.posts += 1;
+                purged.media_files += media_files;
+            }
+        }
+        if done {
+            return Ok(purged);
+        }
+    }
+}
+
+/// Permanently delete a post with its versions and drafts, and the uploaded
+/// files that only it links to (removing them from the media library). An image
+/// is kept, with its variants, as long as anything links to one of them.
+///
+/// Returns the number of files deleted, or `None` when the post does not exist.
+pub async fn purge_post<PR: PostRepository, SB: StorageBackend>(
+    post_repository: &PR,
+    media_repository: &dyn MediaRepository,
+    storage: &SB,
+    id: i64,
+) -> Result<Option<u64>, TrashError> {
+    let files: BTreeSet<String> = post_repository
+        .find_post_contents(&id)
+        .await?
+        .iter()
+        .flat_map(|content| media_files(content, storage.get_base_url()))
+        .collect();
+    if !post_repository.hard_delete(&id).await? {
+        return Ok(None);
+    }
+
+    let base_url = storage.get_base_url().trim_end_matches('/');
+    let mut deleted = 0;
+    let mut seen_media = HashSet::new();
+    for name in files {
+        let Some(media) = media_repository.find_by_file_name(&name).await? else {
+            // Uploaded before the media library existed
+            if post_repository.is_referenced_in_content(&name).await? {
+                continue;
+            }
+            // The post is gone already; a file left behind is not worth failing for
+            match storage.delete_file(&format!("{}/{}", base_url, name)).await {
+                Ok(()) => deleted += 1,
+                Err(e) => tracing::error!("Failed to delete media file {}: {}", name, e),
+            }
+            continue;
+        };
+        if !seen_media.insert(media.id) {
+            continue;
+        }
+
+        let variants = media_repository.find_variants(&[media.id]).await?;
+        let mut in_use = false;
+        for file_name in std::iter::once(&media.file_name)
+            .chain(variants.iter().map(|variant| &variant.file_name))
+        {
+            if post_repository.is_referenced_in_content(file_name).await? {
+                in_use = true;
+                break;
+            }
+        }
+        if in_use {
+            continue;
+        }
+        match crate::media::delete(storage, media_repository, &media).await {
+            Ok(()) => deleted += 1 + variants.len() as u64,
+            Err(e) => tracing::error!("Failed to delete media {}: {}", media.url, e),
+        }
+    }
+    Ok(Some(deleted))
+}
+
+/// Names of the uploaded files linked from `content`, by `/uploads/...` paths
+/// or by URLs under the storage's `base_url`
+pub fn media_files(content: &str, base_url: &str) -> BTreeSet<String> {
+    let base_url = format!("{}/", base_url.trim_end_matches('/'));
+    let mut names = BTreeSet::new();
+    for prefix in [UPLOADS_PATH, base_url.as_str()] {
+        for (start, _) in content.match_indices(prefix) {
+            // A path that continues some other URL is not one of ours
+            if prefix == UPLOADS_PATH
+                && content[..start]
+                    .chars()
+                    .next_back()
+                    .is_some_and(is_url_char)
+            {
+                continue;
+            }
+            let rest = &content[start + prefix.len()..];
+            let end = rest
+                .find(|c: char| !(c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-')))
+                .unwrap_or(rest.len());
+            // Links often end a sentence
+            let name = rest[..end].trim_end_matches('.');
+            if !name.is_empty() && !name.starts_with('.') {
+                names.insert(name.to_string());
+            }
+        }
+    }
+    names
+}
+
+fn is_url_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || matches!(c, '-' | '.' | '_' | '~' | ':' | '/' | '%' | '@')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BASE_URL: &str = "http://localhost:3000/uploads";
+
+    #[test]
+    fn test_media_files_finds_relative_and_absolute_links() {
+        let content = "![a](/uploads/a.png) <img src=\"/uploads/b.jpg\">\n\
+                       See http://localhost:3000/uploads/c.pdf.";
+        let names: Vec<_> = media_files(content, BASE_URL).into_iter().collect();
+        assert_eq!(names, ["a.png", "b.jpg", "c.pdf"]);
+    }
+
+    #[test]
+    fn test_media_files_ignores_other_sites() {
+        let content =
+            "![x](https://example.com/uploads/x.png) ![y](//cdn.example.com/uploads/y.png)";
+        assert!(media_files(content, BASE_URL).is_empty());
+    }
+
+    #[test]
+    fn test_media_files_ignores_directories_and_hidden_names() {
+        assert!(media_files("[up](/uploads/) [dot](/uploads/../x)", BASE_URL).is_empty());
+    }
+
+    #[test]
+    fn test_media_files_with_custom_base_url() {
+        let content = "![a](https://cdn.example.com/media/a.webp)";
+        let names: Vec<_> = media_files(content, "https://cdn.example.com/media/")
+            .into_iter()
+            .collect();
+        assert_eq!(names, ["a.webp"]);
+    }
+}
@@ -0,0 +1,749 @@
+//! Which uploads are accepted, and what they really are
+//!
+//! The type a client declares for a file is not trusted: the first bytes of the
+//! content are matched against known signatures, and a file whose content is
+//! something else than it claims to be is rejected. HTML is never accepted, and
+//! neither are SVG images with scripts, event handlers or embedded documents,
+//! since both would run in the site's origin when opened. Accepted types come
+//! from the `upload_allowed_types` setting and their size limits from
+//! `upload_size_limits`.
+//!
+//! The media library only stores files with a [`CheckedType`], which only
+//! [`UploadPolicy::check`] and [`UploadPolicy::check_file`] hand out, so every
+//! way into it (uploads, imports) goes through the same checks.
+
+use crate::api::admin_api::settings_helper::get_setting_value;
+use sea_orm::DatabaseConnection;
+use std::path::Path;
+use std::sync::Arc;
+use tokio::io::AsyncReadExt;
+
+/// Types accepted when `upload_allowed_types` is not set
+pub const DEFAULT_ALLOWED_TYPES: &str = "image/jpeg,image/png,image/gif,image/webp,image/avif,application/pdf,audio/mpeg,audio/ogg,audio/wav,audio/flac,audio/mp4,video/mp4,video/webm";
+
+/// Size limits in megabytes when `upload_size_limits` is not set
+pub const DEFAULT_SIZE_LIMITS: &str = "image/*=10,audio/*=50,video/*=200,*=20";
+
+/// Limit for types that no entry of `upload_size_limits` covers, in megabytes
+pub const FALLBACK_SIZE_LIMIT_MB: u64 = 20;
+
+/// Bound of a configured size limit, in megabytes
+pub const MAX_SIZE_LIMIT_MB: u64 = 10 * 1024;
+
+/// Bytes of the start of a file that [`sniff`] looks at
+pub const SNIFF_LEN: usize = 4096;
+
+const MEGABYTE: u64 = 1024 * 1024;
+
+/// Why an upload is refused
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum UploadRejection {
+    #[error("{0} uploads are not allowed")]
+    NotAllowed(String),
+    #[error("the content is {detected}, not {declared}")]
+    Mismatch { declared: String, detected: String },
+    #[error("the content is not recognizable as {0}")]
+    Unrecognized(String),
+    #[error("the file contains HTML or script that would run in the site's origin")]
+    ActiveContent,
+    #[error("{mime_type} uploads are limited to {limit} bytes")]
+    TooLarge { mime_type: String, limit: u64 },
+}
+
+/// Size limit for files matching a pattern (`video/mp4`, `video/*` or `*`)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SizeLimit {
+    pub pattern: String,
+    pub bytes: u64,
+}
+
+/// Type of a file that passed all checks of an [`UploadPolicy`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CheckedType(String);
+
+impl CheckedType {
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    pub fn into_string(self) -> String {
+        self.0
+    }
+}
+
+/// Accepted types and their size limits
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UploadPolicy {
+    /// MIME types, or `type/*` patterns, that may be uploaded
+    pub allowed_types: Vec<String>,
+    pub size_limits: Vec<SizeLimit>,
+}
+
+impl Default for UploadPolicy {
+    fn default() -> Self {
+        Self {
+            allowed_types: parse_allowed_types(DEFAULT_ALLOWED_TYPES).unwrap_or_default(),
+            size_limits: parse_size_limits(DEFAULT_SIZE_LIMITS).unwrap_or_default(),
+        }
+    }
+}
+
+impl UploadPolicy {
+    /// Read the settings; values that don't parse fall back to the defaults
+    pub async fn load(db: &Arc<DatabaseConnection>) -> Result<Self, sea_orm::DbErr> {
+        let defaults = Self::default();
+        let allowed_types = get_setting_value(db, "upload_allowed_types")
+            .await?
+            .and_then(|value| parse_allowed_types(&value))
+            .unwrap_or(defaults.allowed_types);
+        let size_limits = get_setting_value(db, "upload_size_limits")
+            .await?
+            .and_then(|value| parse_size_limits(&value))
+            .unwrap_or(defaults.size_limits);
+        Ok(Self {
+            allowed_types,
+            size_limits,
+        })
+    }
+
+    /// Check that files of this type may be uploaded
+    pub fn check_allowed(&self, mime_type: &str) -> Result<(), UploadRejection> {
+        if self
+            .allowed_types
+            .iter()
+            .any(|pattern| matches_pattern(pattern, mime_type))
+        {
+            Ok(())
+        } else {
+            Err(UploadRejection::NotAllowed(mime_type.to_string()))
+        }
+    }
+
+    /// Largest accepted file of this type in bytes, from the most specific
+    /// matching entry
+    pub fn size_limit(&self, mime_type: &str) -> u64 {
+        let specificity = |pattern: &str| {
+            if pattern == "*" {
+                0
+            } else if pattern.ends_with("/*") {
+                1
+            } else {
+                2
+            }
+        };
+        self.size_limits
+            .iter()
+            .filter(|limit| matches_pattern(&limit.pattern, mime_type))
+            .max_by_key(|limit| specificity(&limit.pattern))
+            .map(|limit| limit.bytes)
+            .unwrap_or(FALLBACK_SIZE_LIMIT_MB * MEGABYTE)
+    }
+
+    /// Check a file in memory: its type against the declared one, the allowed
+    /// types and their size limit, and SVG images for active content
+    pub fn check(&self, declared: &str, data: &[u8]) -> Result<CheckedType, UploadRejection> {
+        let head = &data[..data.len().min(SNIFF_LEN)];
+        let mime_type = self.check_type(declared, head, data.len() as u64)?;
+        if mime_type == "image/svg+xml" && svg_has_active_content(data) {
+            return Err(UploadRejection::ActiveContent);
+        }
+        Ok(CheckedType(mime_type))
+    }
+
+    /// Like [`check`](Self::check), for a file on disk; only SVG images are read
+    /// completely
+    pub async fn check_file(
+        &self,
+        declared: &str,
+        path: &Path,
+    ) -> std::io::Result<Result<CheckedType, UploadRejection>> {
+        let file = tokio::fs::File::open(path).await?;
+        let size = file.metadata().await?.len();
+        let mut head = Vec::with_capacity(SNIFF_LEN);
+        file.take(SNIFF_LEN as u64).read_to_end(&mut head).await?;
+
+        let mime_type = match self.check_type(declared, &head, size) {
+            Ok(mime_type) => mime_type,
+            Err(rejection) => return Ok(Err(rejection)),
+        };
+        if mime_type == "image/svg+xml" && svg_has_active_content(&tokio::fs::read(path).await?) {
+            return Ok(Err(UploadRejection::ActiveContent));
+        }
+        Ok(Ok(CheckedType(mime_type)))
+    }
+
+    fn check_type(
+        &self,
+        declared: &str,
+        head: &[u8],
+        size: u64,
+    ) -> Result<String, UploadRejection> {
+        let mime_type = content_type(declared, head)?;
+        self.check_allowed(&mime_type)?;
+        let limit = self.size_limit(&mime_type);
+        if size > limit {
+            return Err(UploadRejection::TooLarge { mime_type, limit });
+        }
+        Ok(mime_type)
+    }
+}
+
+fn matches_pattern(pattern: &str, mime_type: &str) -> bool {
+    match pattern.strip_suffix("/*") {
+        _ if pattern == "*" => true,
+        Some(top_level) => mime_type
+            .split_once('/')
+            .is_some_and(|(mime_top_level, _)| mime_top_level == top_level),
+        None => pattern == mime_type,
+    }
+}
+
+/// Whether a value is a MIME type, a `type/*` pattern or, with `allow_any`, `*`
+fn is_type_pattern(value: &str, allow_any: bool) -> bool {
+    if value == "*" {
+        return allow_any;
+    }
+    let Some((top_level, subtype)) = value.split_once('/') else {
+        return false;
+    };
+    let is_token = |part: &str| {
+        !part.is_empty()
+            && part
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || "!#$&-^_.+".contains(c))
+    };
+    is_token(top_level) && (subtype == "*" || is_token(subtype))
+}
+
+/// Parse a comma-separated list of MIME types and `type/*` patterns; an empty
+/// list is refused, since it would reject every upload
+pub fn parse_allowed_types(value: &str) -> Option<Vec<String>> {
+    let mut types = Vec::new();
+    for entry in value
+        .split(',')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+    {
+        let entry = normalize_mime_type(entry);
+        if !is_type_pattern(&entry, false) {
+            return None;
+        }
+        if !types.contains(&entry) {
+            types.push(entry);
+        }
+    }
+    (!types.is_empty()).then_some(types)
+}
+
+/// Parse comma-separated `pattern=megabytes` entries, e.g. `video/*=200,*=20`
+pub fn parse_size_limits(value: &str) -> Option<Vec<SizeLimit>> {
+    let mut limits: Vec<SizeLimit> = Vec::new();
+    for entry in value
+        .split(',')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+    {
+        let (pattern, megabytes) = entry.split_once('=')?;
+        let pattern = normalize_mime_type(pattern);
+        let megabytes = megabytes
+            .trim()
+            .parse::<u64>()
+            .ok()
+            .filter(|megabytes| (1..=MAX_SIZE_LIMIT_MB).contains(megabytes))?;
+        if !is_type_pattern(&pattern, true) {
+            return None;
+        }
+        // A later entry for the same pattern wins
+        limits.retain(|limit| limit.pattern != pattern);
+        limits.push(SizeLimit {
+            pattern,
+            bytes: megabytes * MEGABYTE,
+        });
+    }
+    Some(limits)
+}
+
+/// Size limits in the form [`parse_size_limits`] reads
+pub fn format_size_limits(limits: &[SizeLimit]) -> String {
+    limits
+        .iter()
+        .map(|limit| format!("{}={}", limit.pattern, limit.bytes / MEGABYTE))
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// Lowercase a MIME type, drop its parameters and map common aliases to the
+/// registered name
+pub fn normalize_mime_type(mime_type: &str) -> String {
+    let mime_type = mime_type
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase();
+    let canonical = match mime_type.as_str() {
+        "image/jpg" | "image/pjpeg" => "image/jpeg",
+        "audio/mp3" | "audio/mpeg3" | "audio/x-mpeg" => "audio/mpeg",
+        "audio/x-wav" | "audio/wave" | "audio/vnd.wave" => "audio/wav",
+        "audio/x-flac" => "audio/flac",
+        "audio/x-m4a" | "audio/m4a" => "audio/mp4",
+        "application/x-pdf" => "application/pdf",
+        "application/x-zip-compressed" => "application/zip",
+        _ => return mime_type,
+    };
+    canonical.to_string()
+}
+
+/// Types that share a container, where the signature can't tell them apart
+fn container_of(mime_type: &str) -> Option<&'static str> {
+    match mime_type {
+        "video/mp4" | "audio/mp4" => Some("mp4"),
+        "audio/ogg" | "video/ogg" | "application/ogg" => Some("ogg"),
+        "video/webm" | "audio/webm" | "video/x-matroska" => Some("matroska"),
+        "application/zip" | "application/epub+zip" => Some("zip"),
+        _ if mime_type.starts_with("application/vnd.openxmlformats-officedocument.")
+            || mime_type.starts_with("application/vnd.oasis.opendocument.") =>
+        {
+            Some("zip")
+        }
+        _ => None,
+    }
+}
+
+/// Types [`sniff`] recognizes; content declared as one of them must match
+const SNIFFED_TYPES: &[&str] = &[
+    "image/jpeg",
+    "image/png",
+    "image/gif",
+    "image/webp",
+    "image/avif",
+    "image/heic",
+    "image/svg+xml",
+    "application/pdf",
+    "audio/mpeg",
+    "audio/aac",
+    "audio/wav",
+    "audio/flac",
+    "video/quicktime",
+    "text/html",
+];
+
+/// The type of a file from the signature at its start, or `None` when it has
+/// no known signature
+pub fn sniff(head: &[u8]) -> Option<&'static str> {
+    let at = |offset: usize, signature: &[u8]| {
+        head.get(offset..)
+            .is_some_and(|rest| rest.starts_with(signature))
+    };
+
+    if at(0, b"\xFF\xD8\xFF") {
+        return Some("image/jpeg");
+    }
+    if at(0, b"\x89PNG\r\n\x1A\n") {
+        return Some("image/png");
+    }
+    if at(0, b"GIF87a") || at(0, b"GIF89a") {
+        return Some("image/gif");
+    }
+    if at(0, b"RIFF") && at(8, b"WEBP") {
+        return Some("image/webp");
+    }
+    if at(0, b"RIFF") && at(8, b"WAVE") {
+        return Some("audio/wav");
+    }
+    if at(4, b"ftyp") {
+        return Some(iso_media_type(head));
+    }
+    if at(0, b"%PDF-") {
+        return Some("application/pdf");
+    }
+    if at(0, b"ID3") {
+        return Some("audio/mpeg");
+    }
+    if head.len() >= 2 && head[0] == 0xFF && head[1] & 0xE0 == 0xE0 {
+        // ADTS frames (AAC) have layer bits 00; MPEG audio frames don't
+        return Some(if head[1] & 0x06 == 0 {
+            "audio/aac"
+        } else {
+            "audio/mpeg"
+        });
+    }
+    if at(0, b"OggS") {
+        return Some("audio/ogg");
+    }
+    if at(0, b"fLaC") {
+        return Some("audio/flac");
+    }
+    if at(0, b"\x1A\x45\xDF\xA3") {
+        return Some(if contains(head, b"webm") {
+            "video/webm"
+        } else {
+            "video/x-matroska"
+        });
+    }
+    if at(0, b"PK\x03\x04") {
+        return Some("application/zip");
+    }
+    markup_type(head)
+}
+
+/// Type of an ISO base media file (MP4, MOV, AVIF, HEIC) from its brands
+fn iso_media_type(head: &[u8]) -> &'static str {
+    let box_size = head
+        .get(..4)
+        .map(|size| u32::from_be_bytes([size[0], size[1], size[2], size[3]]) as usize)
+        .unwrap_or_default();
+    // The major brand, then the compatible brands after the minor version
+    let brands = head
+        .get(8..box_size.clamp(12, head.len().max(12)).min(head.len()))
+        .unwrap_or_default()
+        .chunks_exact(4)
+        .enumerate()
+        .filter(|(index, _)| *index != 1)
+        .map(|(_, brand)| brand)
+        .collect::<Vec<_>>();
+
+    let has = |names: &[&[u8]]| brands.iter().any(|brand| names.contains(brand));
+    if has(&[b"avif", b"avis"]) {
+        "image/avif"
+    } else if has(&[b"heic", b"heix", b"heim", b"heis"]) {
+        "image/heic"
+    } else if has(&[b"M4A ", b"M4B ", b"M4P "]) {
+        "audio/mp4"
+    } else if brands.first() == Some(&&b"qt  "[..]) {
+        "video/quicktime"
+    } else {
+        "video/mp4"
+    }
+}
+
+/// `text/html` or `image/svg+xml` for markup, from its first element
+fn markup_type(head: &[u8]) -> Option<&'static str> {
+    let text = String::from_utf8_lossy(head);
+    let mut rest = text.trim_start_matches('\u{FEFF}').trim_start();
+    loop {
+        if let Some(after) = rest.strip_prefix("<?") {
+            rest = after.split_once("?>")?.1.trim_start();
+        } else if let Some(after) = rest.strip_prefix("<!--") {
+            rest = after.split_once("-->")?.1.trim_start();
+        } else if rest.starts_with("<!") {
+            let (declaration, after) = rest.split_once('>')?;
+            if declaration
+                .to_ascii_lowercase()
+                .starts_with("<!doctype html")
+            {
+                return Some("text/html");
+            }
+            rest = after.trim_start();
+        } else {
+            break;
+        }
+    }
+
+    let element = rest
+        .strip_prefix('<')?
+        .split(|c: char| c.is_whitespace() || c == '>' || c == '/')
+        .next()?
+        .to_ascii_lowercase();
+    match element.as_str() {
+        "svg" => Some("image/svg+xml"),
+        "html" | "head" | "body" | "script" | "iframe" | "meta" | "title" | "style" | "div"
+        | "a" | "img" | "form" | "object" | "embed" => Some("text/html"),
+        _ => None,
+    }
+}
+
+fn contains(haystack: &[u8], needle: &[u8]) -> bool {
+    haystack
+        .windows(needle.len())
+        .any(|window| window == needle)
+}
+
+/// The type to store a file as, from the type the client declared and the
+/// start of its content
+///
+/// Content with a known signature must match the declared type (or share its
+/// container, like `audio/mp4` and `video/mp4`); content without one may not be
+/// declared as a type that has a signature. HTML is always rejected.
+pub fn content_type(declared: &str, head: &[u8]) -> Result<String, UploadRejection> {
+    let declared = normalize_mime_type(declared);
+    let unspecified = declared.is_empty() || declared == "application/octet-stream";
+
+    match sniff(head) {
+        Some("text/html") => Err(UploadRejection::ActiveContent),
+        Some(detected) if unspecified || declared == detected => Ok(detected.to_string()),
+        Some(detected)
+            if container_of(&declared).is_some()
+                && container_of(&declared) == container_of(detected) =>
+        {
+            Ok(declared)
+        }
+        Some(detected) => Err(UploadRejection::Mismatch {
+            declared,
+            detected: detected.to_string(),
+        }),
+        None if unspecified => Err(UploadRejection::Unrecognized(
+            "application/octet-stream".to_string(),
+        )),
+        None if SNIFFED_TYPES.contains(&declared.as_str())
+            || container_of(&declared).is_some()
+            || declared.starts_with("image/") =>
+        {
+            Err(UploadRejection::Unrecognized(declared))
+        }
+        None => Ok(declared),
+    }
+}
+
+/// Whether an SVG image can run code or load documents when opened on its own
+pub fn svg_has_active_content(svg: &[u8]) -> bool {
+    let svg = String::from_utf8_lossy(svg).to_ascii_lowercase();
+    const ACTIVE: &[&str] = &[
+        "<script",
+        "<foreignobject",
+        "<iframe",
+        "<embed",
+        "<object",
+        "<handler",
+        "<!entity",
+        "javascript:",
+        "data:text/html",
+    ];
+    if ACTIVE.iter().any(|marker| svg.contains(marker)) {
+        return true;
+    }
+
+    // Event handler attributes: onload=, onclick = ...
+    svg.match_indices("on").any(|(index, _)| {
+        let before = svg[..index].chars().next_back();
+        let name_end = svg[index + 2..]
+            .find(|c: char| !c.is_ascii_alphabetic())
+            .map(|end| index + 2 + end)
+            .unwrap_or(svg.len());
+        before.is_some_and(|c| c.is_whitespace() || c == '/' || c == '"' || c == '\'')
+            && name_end > index + 2
+            && svg[name_end..].trim_start().starts_with('=')
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sniff_signatures() {
+        assert_eq!(sniff(b"\xFF\xD8\xFF\xE0\0\x10JFIF"), Some("image/jpeg"));
+        assert_eq!(sniff(b"\x89PNG\r\n\x1A\n\0\0\0\rIHDR"), Some("image/png"));
+        assert_eq!(sniff(b"RIFF\0\0\0\0WEBPVP8 "), Some("image/webp"));
+        assert_eq!(sniff(b"RIFF\0\0\0\0WAVEfmt "), Some("audio/wav"));
+        assert_eq!(sniff(b"%PDF-1.7\n"), Some("application/pdf"));
+        assert_eq!(sniff(b"ID3\x04\0"), Some("audio/mpeg"));
+        assert_eq!(
+            sniff(b"\0\0\0\x1Cftypavif\0\0\0\0avifmif1miaf"),
+            Some("image/avif")
+        );
+        assert_eq!(
+            sniff(b"\0\0\0\x20ftypisom\0\0\x02\0isomiso2avc1mp41"),
+            Some("video/mp4")
+        );
+        assert_eq!(
+            sniff(b"\0\0\0\x18ftypM4A \0\0\0\0M4A mp42"),
+            Some("audio/mp4")
+        );
+        assert_eq!(
+            sniff(b"\x1A\x45\xDF\xA3\x9F\x42\x82\x84webm"),
+            Some("video/webm")
+        );
+        assert_eq!(sniff(b"hello world"), None);
+    }
+
+    #[test]
+    fn test_sniff_markup() {
+        assert_eq!(sniff(b"<!DOCTYPE html><p>hi"), Some("text/html"));
+        assert_eq!(
+            sniff(b"\xEF\xBB\xBF  <html lang=\"en\">"),
+            Some("text/html")
+        );
+        assert_eq!(sniff(b"<script>alert(1)</script>"), Some("text/html"));
+        assert_eq!(
+            sniff(b"<?xml version=\"1.0\"?>\n<!-- logo -->\n<!DOCTYPE svg>\n<svg xmlns=\"http://www.w3.org/2000/svg\"/>"),
+            Some("image/svg+xml")
+        );
+        assert_eq!(sniff(b"<note>plain xml</note>"), None);
+    }
+
+    #[test]
+    fn test_content_type_rejects_disguised_files() {
+        assert_eq!(
+            content_type("image/png", b"<html><script>alert(1)</script>"),
+            Err(UploadRejection::ActiveContent)
+        );
+        assert_eq!(
+            content_type("image/png", b"\xFF\xD8\xFF\xE0"),
+            Err(UploadRejection::Mismatch {
+                declared: "image/png".to_string(),
+                detected: "image/jpeg".to_string(),
+            })
+        );
+        assert_eq!(
+            content_type("image/png", b"just some text"),
+            Err(UploadRejection::Unrecognized("image/png".to_string()))
+        );
+        assert_eq!(
+            content_type("application/octet-stream", b"just some text"),
+            Err(UploadRejection::Unrecognized(
+                "application/octet-stream".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_content_type_accepts_matching_files() {
+        assert_eq!(
+            content_type("image/jpg", b"\xFF\xD8\xFF\xE0"),
+            Ok("image/jpeg".to_string())
+        );
+        assert_eq!(
+            content_type("application/octet-stream", b"%PDF-1.4"),
+            Ok("application/pdf".to_string())
+        );
+        // Same container, more specific declared type
+        assert_eq!(
+            content_type("audio/mp4", b"\0\0\0\x20ftypisom\0\0\x02\0isomiso2avc1mp41"),
+            Ok("audio/mp4".to_string())
+        );
+        // No signature to check
+        assert_eq!(
+            content_type("text/plain; charset=utf-8", b"just some text"),
+            Ok("text/plain".to_string())
+        );
+    }
+
+    #[test]
+    fn test_svg_active_content() {
+        assert!(!svg_has_active_content(
+            b"<svg xmlns=\"http://www.w3.org/2000/svg\"><circle r=\"5\" fill=\"red\"/><text>Contact us on Monday</text></svg>"
+        ));
+        assert!(svg_has_active_content(
+            b"<svg><script>alert(1)</script></svg>"
+        ));
+        assert!(svg_has_active_content(b"<svg onload=\"alert(1)\"></svg>"));
+        assert!(svg_has_active_content(
+            b"<svg><rect ONCLICK = 'x()'/></svg>"
+        ));
+        assert!(svg_has_active_content(
+            b"<svg><a href=\"javascript:alert(1)\"><text>x</text></a></svg>"
+        ));
+        assert!(svg_has_active_content(
+            b"<svg><foreignObject><iframe src=\"x\"/></foreignObject></svg>"
+        ));
+    }
+
+    #[test]
+    fn test_parse_settings() {
+        assert_eq!(
+            parse_allowed_types("image/*, application/PDF,image/*"),
+            Some(vec!["image/*".to_string(), "application/pdf".to_string()])
+        );
+        assert_eq!(parse_allowed_types("pdf"), None);
+        assert_eq!(parse_allowed_types(" , "), None);
+        assert_eq!(parse_allowed_types("*"), None);
+        assert_eq!(
+            parse_size_limits("video/*=200, *=5"),
+            Some(vec![
+                SizeLimit {
+                    pattern: "video/*".to_string(),
+                    bytes: 200 * MEGABYTE,
+                },
+                SizeLimit {
+                    pattern: "*".to_string(),
+                    bytes: 5 * MEGABYTE,
+                },
+            ])
+        );
+        assert_eq!(
+            format_size_limits(&parse_size_limits("Video/*=200,*=5,*=6").unwrap()),
+            "video/*=200,*=6"
+        );
+        assert_eq!(parse_size_limits(""), Some(Vec::new()));
+        assert_eq!(parse_size_limits("video/*=0"), None);
+        assert_eq!(parse_size_limits("video/*"), None);
+    }
+
+    #[test]
+    fn test_policy() {
+        let policy = UploadPolicy {
+            allowed_types: parse_allowed_types("image/*,application/pdf").unwrap(),
+            size_limits: parse_size_limits("image/*=10,image/gif=2,*=20").unwrap(),
+        };
+        assert!(policy.check_allowed("image/webp").is_ok());
+        assert!(policy.check_allowed("application/pdf").is_ok());
+        assert_eq!(
+            policy.check_allowed("video/mp4"),
+            Err(UploadRejection::NotAllowed("video/mp4".to_string()))
+        );
+        assert_eq!(policy.size_limit("image/gif"), 2 * MEGABYTE);
+        assert_eq!(policy.size_limit("image/png"), 10 * MEGABYTE);
+        assert_eq!(policy.size_limit("application/pdf"), 20 * MEGABYTE);
+
+        let defaults = UploadPolicy::default();
+        assert!(defaults.check_allowed("video/mp4").is_ok());
+        assert!(defaults.check_allowed("image/svg+xml").is_err());
+        assert_eq!(defaults.size_limit("video/webm"), 200 * MEGABYTE);
+    }
+
+    #[tokio::test]
+    async fn test_check() {
+        let policy = UploadPolicy {
+            allowed_types: parse_allowed_types("image/*").unwrap(),
+            size_limits: parse_size_limits("*=1").unwrap(),
+        };
+        let png = b"\x89PNG\r\n\x1A\n\0\0\0\rIHDR".to_vec();
+        let svg = br#"<svg xmlns="http://www.w3.org/2000/svg"><rect/></svg>"#.to_vec();
+        let evil_svg = br#"<svg xmlns="http://www.w3.org/2000/svg" onload="alert(1)"/>"#.to_vec();
+        let mut large = png.clone();
+        large.resize(MEGABYTE as usize + 1, 0);
+        let dir = tempfile::tempdir().unwrap();
+
+        let cases: [(&str, &[u8], Result<&str, UploadRejection>); 6] = [
+            ("image/png", &png, Ok("image/png")),
+            ("image/svg+xml", &svg, Ok("image/svg+xml")),
+            (
+                "image/svg+xml",
+                &evil_svg,
+                Err(UploadRejection::ActiveContent),
+            ),
+            (
+                "image/png",
+                b"<html><body>hi</body></html>",
+                Err(UploadRejection::ActiveContent),
+            ),
+            (
+                "application/pdf",
+                b"%PDF-1.7",
+                Err(UploadRejection::NotAllowed("application/pdf".to_string())),
+            ),
+            (
+                "image/png",
+                &large,
+                Err(UploadRejection::TooLarge {
+                    mime_type: "image/png".to_string(),
+                    limit: MEGABYTE,
+                }),
+            ),
+        ];
+        for (declared, data, expected) in cases {
+            let expected = expected.map(|mime_type| CheckedType(mime_type.to_string()));
+            assert_eq!(policy.check(declared, data), expected, "{}", declared);
+
+            let path = dir.path().join("upload");
+            std::fs::write(&path, data).unwrap();
+            assert_eq!(
+                policy.check_file(declared, &path).await.unwrap(),
+                expected,
+                "{}",
+                declared
+            );
+        }
+    }
+}
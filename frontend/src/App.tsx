@@ -5,7 +5,7 @@ import { SideBar } from './components/Layout/SideBar';
 import { LoginModal } from './components/Auth/LoginModal';
 import { PostVersions } from './components/Blog/PostVersions';
 import { HomePage } from './components/Pages/HomePage';
-import { PostDetailPage } from './components/Pages/PostDetailPage';
+import { PostDetailPage, PostBySlugPage } from './components/Pages/PostDetailPage';
 import { CreatePostPage } from './components/Pages/CreatePostPage';
 import { EditPostPage } from './components/Pages/EditPostPage';
 import { api, isAuthenticated, clearTokens } from './services/api';
@@ -35,7 +35,15 @@ export default function App() {
   useEffect(() => {
     const loadCurrentPost = async () => {
       const pathParts = location.pathname.split('/');
-      if (pathParts[1] === 'posts' && pathParts[2] && !pathParts[3]) {
+      if (pathParts[1] === 'p' && pathParts[2]) {
+        // Post permalink
+        try {
+          setCurrentPost(await api.getPostBySlug(decodeURIComponent(pathParts[2])));
+        } catch (error) {
+          console.error('Failed to load post:', error);
+          setCurrentPost(null);
+        }
+      } else if (pathParts[1] === 'posts' && pathParts[2] && !pathParts[3]) {
         // We're on a post detail page
         try {
           const postId = parseInt(pathParts[2]);
@@ -70,7 +78,7 @@ export default function App() {
   };
 
   const handlePostSelect = (post: PostResponse) => {
-    navigate(`/posts/${post.id}`);
+    navigate(`/p/${post.slug}`);
   };
 
   const handleCreatePost = () => {
@@ -110,6 +118,7 @@ export default function App() {
           <div className="flex-1 min-w-0">
             <Routes>
               <Route path="/" element={<HomePage onPostSelect={handlePostSelect} />} />
+              <Route path="/p/:slug" element={<PostBySlugPage onEdit={handleEditPost} />} />
               <Route path="/posts/:id" element={<PostDetailPage onEdit={handleEditPost} />} />
               <Route path="/posts/create" element={<CreatePostPage />} />
               <Route path="/posts/:id/edit" element={<EditPostPage editingPost={editingPost} />} />
@@ -3,7 +3,14 @@ import { Save, Eye, EyeOff, X, Loader2, Image as ImageIcon, Cloud } from 'lucide
 import { api, isAuthenticated } from '../../services/api';
 import { normalizeImageUrl } from '../../utils/url';
 import { MarkdownRenderer } from '../Markdown/MarkdownRenderer';
-import type { PostResponse } from '../../types';
+import type { CategoryTreeNode, PostResponse } from '../../types';
+
+// Flatten the category tree into indented options for the category picker
+const flattenCategories = (nodes: CategoryTreeNode[], depth = 0): { name: string; label: string }[] =>
+  nodes.flatMap((node) => [
+    { name: node.name, label: `${'\u00a0\u00a0'.repeat(depth)}${node.name}` },
+    ...flattenCategories(node.children, depth + 1),
+  ]);
 
 interface CreatePostProps {
   postId?: string;
@@ -16,6 +23,7 @@ export function CreatePost({ postId, initialPost, onSuccess, onCancel }: CreateP
   const isEditMode = !!postId;
   const [title, setTitle] = useState(initialPost?.title || '');
   const [category, setCategory] = useState(initialPost?.category || '');
+  const [categoryOptions, setCategoryOptions] = useState<{ name: string; label: string }[]>([]);
   const [description, setDescription] = useState(initialPost?.description || '');
   const [content, setContent] = useState(initialPost?.content || '');
   const [showPreview, setShowPreview] = useState(false);
@@ -31,6 +39,13 @@ export function CreatePost({ postId, initialPost, onSuccess, onCancel }: CreateP
   const lastContentRef = useRef(content);
   const charCountSinceLastSaveRef = useRef(0);
 
+  // Load categories for the category picker
+  useEffect(() => {
+    api.getCategories()
+      .then((tree) => setCategoryOptions(flattenCategories(tree)))
+      .catch(() => setCategoryOptions([]));
+  }, []);
+
   // Load draft on mount
   useEffect(() => {
     const loadDraft = async () => {
@@ -40,10 +55,10 @@ export function CreatePost({ postId, initialPost, onSuccess, onCancel }: CreateP
         const draft = await api.getDraft(postId || undefined);
         if (draft && (!initialPost || new Date(draft.updated_at) > new Date(initialPost.updated_at))) {
           setTitle(draft.title);
-          setCategory(draft.category);
+          setCategory(draft.category || '');
           setDescription(''); // Draft doesn't have description
           setContent(draft.content);
-          const draftContent = draft.title + draft.category + draft.content;
+          const draftContent = draft.title + (draft.category || '') + draft.content;
           setLastSavedContent(draftContent);
           lastContentRef.current = draftContent;
         } else if (initialPost) {
@@ -375,10 +390,18 @@ export function CreatePost({ postId, initialPost, onSuccess, onCancel }: CreateP
             type="text"
             value={category}
             onChange={(e) => setCategory(e.target.value)}
+            list="category-options"
             className="w-full px-4 py-2 border border-slate-300 rounded-lg focus:ring-2 focus:ring-orange-500 focus:border-orange-500 outline-none transition-colors"
             placeholder="e.g., Announcement, Tutorial, News (optional)"
             disabled={loading}
           />
+          <datalist id="category-options">
+            {categoryOptions.map((option) => (
+              <option key={option.name} value={option.name}>
+                {option.label}
+              </option>
+            ))}
+          </datalist>
         </div>
 
         {/* Description */}
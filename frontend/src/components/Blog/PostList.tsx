@@ -1,5 +1,5 @@
 import { useState, useEffect } from 'react';
-import { Calendar, Tag, ArrowRight } from 'lucide-react';
+import { Calendar, Tag, ArrowRight, ChevronLeft, ChevronRight } from 'lucide-react';
 import { api } from '../../services/api';
 import { markdownToPlainText } from '../../utils/markdown';
 import type { PostResponse } from '../../types';
@@ -39,18 +39,22 @@ interface PostListProps {
 
 export function PostList({ onPostSelect }: PostListProps) {
   const [posts, setPosts] = useState<PostResponse[]>([]);
+  const [page, setPage] = useState(1);
+  const [totalPages, setTotalPages] = useState(1);
   const [loading, setLoading] = useState(true);
   const [error, setError] = useState('');
 
   useEffect(() => {
-    loadPosts();
-  }, []);
+    loadPosts(page);
+  }, [page]);
 
-  const loadPosts = async () => {
+  const loadPosts = async (page: number) => {
     try {
       setLoading(true);
-      const data = await api.getPosts();
-      setPosts(data);
+      const data = await api.getPosts(page);
+      setPosts(data.posts);
+      setTotalPages(data.total_pages);
+      window.scrollTo({ top: 0 });
     } catch (err) {
       setError(err instanceof Error ? err.message : 'Failed to load posts');
     } finally {
@@ -123,6 +127,31 @@ export function PostList({ onPostSelect }: PostListProps) {
           </div>
         </article>
       ))}
+      {totalPages > 1 && (
+        <nav className="flex items-center justify-between pt-2" aria-label="Pagination">
+          <button
+            type="button"
+            onClick={() => setPage(page - 1)}
+            disabled={page <= 1}
+            className="inline-flex items-center gap-1 px-4 py-2 text-sm font-medium text-slate-700 bg-white border border-slate-200 rounded-lg hover:bg-slate-50 disabled:opacity-50 disabled:cursor-not-allowed"
+          >
+            <ChevronLeft size={16} />
+            Newer posts
+          </button>
+          <span className="text-sm text-slate-500">
+            Page {page} of {totalPages}
+          </span>
+          <button
+            type="button"
+            onClick={() => setPage(page + 1)}
+            disabled={page >= totalPages}
+            className="inline-flex items-center gap-1 px-4 py-2 text-sm font-medium text-slate-700 bg-white border border-slate-200 rounded-lg hover:bg-slate-50 disabled:opacity-50 disabled:cursor-not-allowed"
+          >
+            Older posts
+            <ChevronRight size={16} />
+          </button>
+        </nav>
+      )}
     </div>
   );
 }
@@ -1,5 +1,7 @@
+import { useState, useEffect } from 'react';
 import { useParams, useNavigate } from 'react-router-dom';
 import { PostDetail } from '../Blog/PostDetail';
+import { api } from '../../services/api';
 import type { PostResponse } from '../../types';
 
 interface PostDetailPageProps {
@@ -29,4 +31,42 @@ export function PostDetailPage({ onEdit }: PostDetailPageProps) {
       onEdit={handleEdit}
     />
   );
-}
\ No newline at end of file
+}
+
+export function PostBySlugPage({ onEdit }: PostDetailPageProps) {
+  const { slug } = useParams<{ slug: string }>();
+  const navigate = useNavigate();
+  const [postId, setPostId] = useState<string | null>(null);
+  const [notFound, setNotFound] = useState(false);
+
+  useEffect(() => {
+    if (!slug) return;
+    setPostId(null);
+    setNotFound(false);
+    api.getPostBySlug(slug)
+      .then((post) => {
+        setPostId(post.id);
+        // Renamed posts: show the current permalink
+        if (post.slug !== slug) {
+          navigate(`/p/${post.slug}`, { replace: true });
+        }
+      })
+      .catch(() => setNotFound(true));
+  }, [slug, navigate]);
+
+  if (notFound) {
+    return <div>Post not found</div>;
+  }
+
+  if (!postId) {
+    return null;
+  }
+
+  return (
+    <PostDetail
+      postId={postId}
+      onBack={() => navigate('/')}
+      onEdit={onEdit}
+    />
+  );
+}
@@ -1,5 +1,6 @@
 import type { 
   PostResponse, 
+  PostPage,
   LoginRequest, 
   LoginResponse, 
   User,
@@ -7,6 +8,7 @@ import type {
   PostDraftResponse,
   SaveDraftRequest,
   UpdatePostRequest,
+  CategoryTreeNode,
 } from '../types';
 
 const API_BASE_URL = '/api';
@@ -139,17 +141,30 @@ export const api = {
   },
 
   // Post related
-  async getPosts(): Promise<PostResponse[]> {
-    const response = await fetch(`${API_BASE_URL}/posts`);
+  async getPosts(page = 1): Promise<PostPage> {
+    const response = await fetch(`${API_BASE_URL}/posts?page=${page}`);
     if (!response.ok) {
       throw new Error('Failed to get post list');
     }
     const data = await response.json();
-    // Convert id from number to string
-    return data.map((post: any) => ({
-      ...post,
-      id: String(post.id),
-    }));
+    return {
+      // Convert id from number to string
+      posts: data.items.map((post: any) => ({
+        ...post,
+        id: String(post.id),
+      })),
+      page: data.page,
+      total: data.total,
+      total_pages: data.total_pages,
+    };
+  },
+
+  async getCategories(): Promise<CategoryTreeNode[]> {
+    const response = await fetch(`${API_BASE_URL}/categories`);
+    if (!response.ok) {
+      throw new Error('Failed to get categories');
+    }
+    return response.json();
   },
 
   async getPost(id: string | number): Promise<PostResponse> {
@@ -167,6 +182,20 @@ export const api = {
     };
   },
 
+  async getPostBySlug(slug: string): Promise<PostResponse> {
+    // Old slugs answer with a redirect to the current one, which fetch follows
+    const response = await fetch(`${API_BASE_URL}/posts/by-slug/${encodeURIComponent(slug)}`);
+    if (!response.ok) {
+      throw new Error('Failed to get post');
+    }
+    const data = await response.json();
+    // Convert id from number to string
+    return {
+      ...data,
+      id: String(data.id),
+    };
+  },
+
   async createPost(post: { title: string; category?: string | null; description?: string | null; content: string; author_id: number }): Promise<PostResponse> {
     // Remove null/empty fields from the request body
     const requestBody = { ...post };
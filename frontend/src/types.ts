@@ -3,20 +3,70 @@ export interface Post {
   title: string;
   content: string;
   category: string | null;
+  category_id: number | null;
   author_id: number;
   created_at: string;
   updated_at: string;
 }
 
+export type PostStatus = 'draft' | 'pending_review' | 'scheduled' | 'published' | 'private';
+
 export interface PostResponse {
   id: string;
   title: string;
+  slug: string;
   content: string;
   category: string | null;
+  category_id: number | null;
   description: string | null;
+  tags: string[];
   author_id: number;
+  revision: number;
+  status: PostStatus;
+  review_note: string | null;
+  publish_at: string | null;
+  unpublish_at: string | null;
   created_at: string;
   updated_at: string;
+  // Only present when requested with ?render=true
+  content_html?: string;
+  toc?: TocEntry[];
+}
+
+export interface PostPage {
+  posts: PostResponse[];
+  page: number;
+  total: number;
+  total_pages: number;
+}
+
+export interface TocEntry {
+  level: number;
+  id: string;
+  text: string;
+}
+
+export type CommentStatus = 'pending' | 'approved' | 'rejected' | 'spam';
+
+export interface CommentResponse {
+  id: number;
+  post_id: number;
+  parent_id: number | null;
+  author_id: number | null;
+  author_name: string;
+  author_url: string | null;
+  content: string;
+  status: CommentStatus;
+  replies: CommentResponse[];
+  created_at: string;
+}
+
+export interface CreateCommentRequest {
+  parent_id?: number;
+  author_name?: string;
+  author_email?: string;
+  author_url?: string;
+  content: string;
 }
 
 export interface PageData {
@@ -47,10 +97,16 @@ export interface User {
 
 export interface CreatePostRequest {
   title: string;
+  slug?: string;
   category?: string;
+  category_id?: number;
   description?: string;
+  tags?: string[];
   content: string;
   author_id: number;
+  status?: PostStatus;
+  publish_at?: string;
+  unpublish_at?: string;
 }
 
 export interface PostVersionResponse {
@@ -58,19 +114,64 @@ export interface PostVersionResponse {
   post_id: string;
   title: string;
   content: string;
-  category: string;
+  category: string | null;
+  category_id: number | null;
+  description: string | null;
   version_number: number;
   created_at: string;
   created_by: number;
   change_note: string | null;
 }
 
+export type DiffTag = 'equal' | 'delete' | 'insert' | 'replace';
+
+export interface DiffSegment {
+  text: string;
+  changed: boolean;
+}
+
+export interface DiffLine {
+  number: number;
+  segments: DiffSegment[];
+}
+
+export interface DiffRow {
+  tag: DiffTag;
+  old: DiffLine | null;
+  new: DiffLine | null;
+}
+
+export interface FieldDiff {
+  changed: boolean;
+  added_lines: number;
+  removed_lines: number;
+  rows: DiffRow[];
+}
+
+export interface DiffSide {
+  version_id: number | null;
+  version_number: number | null;
+  created_at: string;
+}
+
+export interface PostVersionDiff {
+  post_id: number;
+  from: DiffSide;
+  to: DiffSide;
+  title: FieldDiff;
+  content: FieldDiff;
+  category: FieldDiff;
+  description: FieldDiff;
+}
+
 export interface PostDraftResponse {
   id: string;
   post_id: string | null;
   title: string;
   content: string;
-  category: string;
+  category: string | null;
+  category_id: number | null;
+  tags: string[];
   author_id: number;
   created_at: string;
   updated_at: string;
@@ -80,15 +181,20 @@ export interface SaveDraftRequest {
   post_id?: number | null;
   title: string;
   content: string;
-  category: string;
+  category?: string | null;
+  category_id?: number | null;
   description?: string | null;
+  tags?: string[];
 }
 
 export interface UpdatePostRequest {
   title?: string;
+  slug?: string;
   content?: string;
   category?: string;
+  category_id?: number;
   description?: string;
+  tags?: string[];
   create_version?: boolean;
   change_note?: string;
 }
@@ -99,3 +205,22 @@ declare global {
   }
 }
 
+export interface TagResponse {
+  id: number;
+  name: string;
+  slug: string;
+  post_count: number;
+  created_at: string;
+}
+
+export interface CategoryTreeNode {
+  id: number;
+  name: string;
+  slug: string;
+  description: string | null;
+  parent_id: number | null;
+  post_count: number;
+  children: CategoryTreeNode[];
+  created_at: string;
+  updated_at: string;
+}
@@ -38,6 +38,10 @@ export default defineConfig({
         target: 'http://localhost:3000',
         changeOrigin: true,
       },
+      // Feeds, sitemaps and robots.txt are served by the backend too
+      '^/(((category|author)/[^/]+/)?(feed|atom)\\.xml|sitemap\\.xml|sitemaps/.*|robots\\.txt)$': {
+        target: 'http://localhost:3000',
+      },
     },
   },
   // @ts-ignore - Vitest types
@@ -63,14 +63,14 @@ fn on_post_published(
         }
     }
 
-    match generate_category(&post) {
-        Ok(category) => {
-            info!("Generated category: {}", category);
-            post.category = Some(category);
+    match suggest_tags(&post) {
+        Ok(tags) => {
+            info!("Suggested tags: {:?}", tags);
+            post.tags = tags;
         }
         Err(e) => {
-            error!("Failed to generate category: {}", e);
-            return Err(anyhow::anyhow!("Failed to generate category: {}", e));
+            error!("Failed to suggest tags: {}", e);
+            return Err(anyhow::anyhow!("Failed to suggest tags: {}", e));
         }
     }
     Ok(post)
@@ -119,24 +119,26 @@ fn generate_summary(post: &OnPostPublishedData) -> anyhow::Result<String> {
     }
 }
 
-fn generate_category(post: &OnPostPublishedData) -> anyhow::Result<String> {
-    if let Some(post) = post.category.clone() {
-        info!("Post already has category, skipping auto-label");
-        return Ok(post);
+fn suggest_tags(post: &OnPostPublishedData) -> anyhow::Result<Vec<String>> {
+    if !post.tags.is_empty() {
+        info!("Post already has tags, skipping tag suggestion");
+        return Ok(post.tags.clone());
     }
 
-    let categories = list_categories();
+    let tags = list_tags();
 
-    let system_prompt = format!("You are a helpful assistant that generates labels for blog posts.
-        Your task is to generate a label for the given blog post.
-        The label should be a single word or phrase that describes the post.
-        The label should be in the same language as the blog post. If the language is not detected, use English.
-        The label should be concise and to the point.
+    let system_prompt = format!("You are a helpful assistant that suggests tags for blog posts.
+        Your task is to suggest up to 5 tags for the given blog post.
+        Each tag should be a single word or short phrase that describes the post.
+        The tags should be in the same language as the blog post. If the language is not detected, use English.
+        Prefer existing tags when they fit.
 
-        The following is a list of categories, ranked by frequency (categories appearing more often are listed first).
-        Categories: {:?}.
-        If the post is not related to any of the categories, generate a new category.
-    ", categories.join(", "));
+        The following is a list of existing tags, ranked by frequency (tags appearing more often are listed first).
+        Tags: {:?}.
+        If none of the existing tags fit, suggest new ones.
+
+        Please only return the tags separated by commas, no other text.
+    ", tags.join(", "));
 
     let user_prompt = format!("Title: {}\n\nContent: {}", post.title, post.content);
 
@@ -158,9 +160,16 @@ fn generate_category(post: &OnPostPublishedData) -> anyhow::Result<String> {
     }) {
         Ok(response) => {
             if let Some(choice) = response.choices.first() {
-                let category = choice.message.content.trim();
-                info!("Generated category: {}", category);
-                Ok(category.to_string())
+                let suggested: Vec<String> = choice
+                    .message
+                    .content
+                    .split(',')
+                    .map(|tag| tag.trim().to_string())
+                    .filter(|tag| !tag.is_empty())
+                    .take(5)
+                    .collect();
+                info!("Suggested tags: {:?}", suggested);
+                Ok(suggested)
             } else {
                 Err(anyhow::anyhow!("No response choices returned from AI"))
             }
@@ -0,0 +1,93 @@
+use crate::commands::database::with_database;
+use anyhow::Result;
+use colored::*;
+use rustpress_core::backup::{self, BackupPaths};
+use rustpress_core::dto::BackupManifest;
+use rustpress_core::storage::ConfiguredStorage;
+use rustpress_migration::Migrator;
+use sea_orm_migration::MigratorTrait;
+use std::path::Path;
+
+pub fn create_backup(
+    database_url: &str,
+    out: &Path,
+    storage_dir: &Path,
+    plugins_dir: &Path,
+) -> Result<()> {
+    println!("{}", "Creating backup...".cyan().bold());
+
+    let paths = BackupPaths {
+        plugins_dir: plugins_dir.to_path_buf(),
+    };
+    let manifest = with_database(database_url, |db| async move {
+        let storage = ConfiguredStorage::from_env(storage_dir, String::new())
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to initialize storage: {}", e))?;
+        backup::create_backup(&db, &storage, &paths, out)
+            .await
+            .map_err(|e| anyhow::anyhow!("Backup failed: {}", e))
+    })?;
+
+    println!("{}", "✅ Backup created!".green().bold());
+    print_manifest(&manifest);
+    println!("  📦 Archive: {}", out.display());
+    Ok(())
+}
+
+pub fn restore_backup(
+    database_url: &str,
+    file: &Path,
+    yes: bool,
+    storage_dir: &Path,
+    plugins_dir: &Path,
+) -> Result<()> {
+    let manifest = backup::read_manifest(file)
+        .map_err(|e| anyhow::anyhow!("Failed to read {}: {}", file.display(), e))?;
+    println!(
+        "{} {}",
+        "Backup from".cyan().bold(),
+        manifest.created_at.to_rfc3339().cyan().bold()
+    );
+    print_manifest(&manifest);
+    if !yes {
+        anyhow::bail!(
+            "Restoring replaces all posts, users, settings and plugins in the database; pass --yes to continue"
+        );
+    }
+
+    let paths = BackupPaths {
+        plugins_dir: plugins_dir.to_path_buf(),
+    };
+    let report = with_database(database_url, |db| async move {
+        let storage = ConfiguredStorage::from_env(storage_dir, String::new())
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to initialize storage: {}", e))?;
+        // A fresh database gets the schema the server would create
+        Migrator::up(&db, None)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to run migrations: {}", e))?;
+        backup::restore_backup(&db, &storage, &paths, file)
+            .await
+            .map_err(|e| anyhow::anyhow!("Restore failed: {}", e))
+    })?;
+
+    println!("{}", "✅ Backup restored!".green().bold());
+    for warning in &report.warnings {
+        println!("  {} {}", "⚠️".yellow(), warning);
+    }
+    Ok(())
+}
+
+fn print_manifest(manifest: &BackupManifest) {
+    println!("  🏷️  RustPress version: {}", manifest.rustpress_version);
+    println!(
+        "  🗄️  Schema: {} migrations, latest {}",
+        manifest.migrations.len(),
+        manifest.migrations.last().map_or("-", String::as_str)
+    );
+    for (table, rows) in &manifest.tables {
+        println!("    - {}: {} rows", table, rows);
+    }
+    println!("  🔌 Plugin packages: {}", manifest.plugin_files);
+    println!("  🖼️  Media files: {}", manifest.media_files);
+}
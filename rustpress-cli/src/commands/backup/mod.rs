@@ -0,0 +1,75 @@
+mod archive;
+
+use anyhow::Result;
+use clap::Args;
+use std::path::PathBuf;
+
+/// Backup-related commands
+#[derive(Args)]
+pub struct BackupArgs {
+    #[command(subcommand)]
+    pub command: BackupCommand,
+}
+
+#[derive(clap::Subcommand)]
+pub enum BackupCommand {
+    /// Write a backup archive of the database, plugin packages and media
+    Create {
+        /// Archive to write
+        #[arg(long)]
+        out: PathBuf,
+
+        /// Database to back up
+        #[arg(long, env = "DATABASE_URL")]
+        database_url: String,
+
+        /// Upload directory of the local storage backend; `STORAGE_BACKEND=s3`
+        /// backs up the bucket instead
+        #[arg(long, env = "STORAGE_DIR", default_value = "uploads")]
+        storage_dir: PathBuf,
+
+        /// Directory of installed plugin packages
+        #[arg(long, default_value = "installed_plugins")]
+        plugins_dir: PathBuf,
+    },
+    /// Replace all content, users, settings, plugins and media with a backup
+    Restore {
+        /// Archive written by `backup create` or the admin backup endpoint
+        file: PathBuf,
+
+        /// Confirm that the current data may be replaced
+        #[arg(long)]
+        yes: bool,
+
+        /// Database to restore into; migrated to this build's schema first
+        #[arg(long, env = "DATABASE_URL")]
+        database_url: String,
+
+        /// Upload directory of the local storage backend; `STORAGE_BACKEND=s3`
+        /// restores into the bucket instead
+        #[arg(long, env = "STORAGE_DIR", default_value = "uploads")]
+        storage_dir: PathBuf,
+
+        /// Directory of installed plugin packages
+        #[arg(long, default_value = "installed_plugins")]
+        plugins_dir: PathBuf,
+    },
+}
+
+pub fn handle_backup_command(args: &BackupArgs) -> Result<()> {
+    match &args.command {
+        BackupCommand::Create {
+            out,
+            database_url,
+            storage_dir,
+            plugins_dir,
+        } => archive::create_backup(database_url, out, storage_dir, plugins_dir),
+        BackupCommand::Restore {
+            file,
+            yes,
+            database_url,
+            storage_dir,
+            plugins_dir,
+        } => archive::restore_backup(database_url, file, *yes, storage_dir, plugins_dir),
+    }
+}
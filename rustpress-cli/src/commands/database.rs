@@ -0,0 +1,18 @@
+use anyhow::{Context, Result};
+use sea_orm::DatabaseConnection;
+use std::future::Future;
+
+/// Connect to the database and run `task` with the connection
+pub fn with_database<T, F, Fut>(database_url: &str, task: F) -> Result<T>
+where
+    F: FnOnce(DatabaseConnection) -> Fut,
+    Fut: Future<Output = Result<T>>,
+{
+    let runtime = tokio::runtime::Runtime::new().context("Failed to start async runtime")?;
+    runtime.block_on(async {
+        let db = sea_orm::Database::connect(database_url)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to connect to the database: {}", e))?;
+        task(db).await
+    })
+}
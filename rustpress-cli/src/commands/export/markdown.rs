@@ -0,0 +1,27 @@
+use crate::commands::database::with_database;
+use anyhow::Result;
+use colored::*;
+use rustpress_core::export::markdown::export_posts;
+use rustpress_core::front_matter::{self, FrontMatterFormat};
+use rustpress_core::repository::{PostgresPostRepository, PostgresUserRepository};
+use std::path::Path;
+
+pub fn export_markdown(database_url: &str, out: &Path, format: FrontMatterFormat) -> Result<()> {
+    println!("{}", "Exporting posts as Markdown...".cyan().bold());
+
+    let files = with_database(database_url, |db| async move {
+        let post_repository = PostgresPostRepository::new(db.clone());
+        let user_repository = PostgresUserRepository::new(db);
+        export_posts(&post_repository, &user_repository, format)
+            .await
+            .map_err(|e| anyhow::anyhow!("Export failed: {}", e))
+    })?;
+
+    front_matter::write_dir(out, &files)
+        .map_err(|e| anyhow::anyhow!("Failed to write {}: {}", out.display(), e))?;
+
+    println!("{}", "✅ Export finished!".green().bold());
+    println!("  📝 Posts: {}", files.len());
+    println!("  📁 Output: {}", out.display());
+    Ok(())
+}
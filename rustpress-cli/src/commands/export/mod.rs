@@ -0,0 +1,58 @@
+mod markdown;
+
+use anyhow::Result;
+use clap::{Args, ValueEnum};
+use rustpress_core::front_matter::FrontMatterFormat;
+use std::path::PathBuf;
+
+/// Export-related commands
+#[derive(Args)]
+pub struct ExportArgs {
+    #[command(subcommand)]
+    pub command: ExportCommand,
+}
+
+#[derive(clap::Subcommand)]
+pub enum ExportCommand {
+    /// Export posts as Markdown files with front matter (for Hugo, Jekyll or `import markdown`)
+    Markdown {
+        /// Directory to write the files to
+        #[arg(long)]
+        out: PathBuf,
+
+        /// Front matter syntax
+        #[arg(long, value_enum, default_value_t = Format::Yaml)]
+        format: Format,
+
+        /// Database to export from
+        #[arg(long, env = "DATABASE_URL")]
+        database_url: String,
+    },
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum Format {
+    /// `---` delimited YAML
+    Yaml,
+    /// `+++` delimited TOML
+    Toml,
+}
+
+impl From<Format> for FrontMatterFormat {
+    fn from(format: Format) -> Self {
+        match format {
+            Format::Yaml => FrontMatterFormat::Yaml,
+            Format::Toml => FrontMatterFormat::Toml,
+        }
+    }
+}
+
+pub fn handle_export_command(args: &ExportArgs) -> Result<()> {
+    match &args.command {
+        ExportCommand::Markdown {
+            out,
+            format,
+            database_url,
+        } => markdown::export_markdown(database_url, out, (*format).into()),
+    }
+}
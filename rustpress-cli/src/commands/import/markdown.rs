@@ -0,0 +1,44 @@
+use super::{fallback_author, print_report};
+use crate::commands::database::with_database;
+use anyhow::Result;
+use colored::*;
+use rustpress_core::front_matter;
+use rustpress_core::import::markdown::{self, MarkdownImportOptions};
+use rustpress_core::repository::{PostgresPostRepository, PostgresUserRepository};
+use std::path::Path;
+
+pub fn import_markdown(
+    path: &Path,
+    author: &Option<String>,
+    dry_run: bool,
+    database_url: &str,
+) -> Result<()> {
+    println!("{}", "Importing Markdown files...".cyan().bold());
+
+    let files = if path.is_dir() {
+        front_matter::read_dir(path)
+    } else {
+        std::fs::read(path)
+            .map_err(|e| anyhow::anyhow!("Failed to read {}: {}", path.display(), e))
+            .map(|data| front_matter::read_zip(&data))?
+    }
+    .map_err(|e| anyhow::anyhow!("Failed to read Markdown files: {}", e))?;
+    println!("  📄 Files: {}", files.len());
+
+    let report = with_database(database_url, |db| async move {
+        let post_repository = PostgresPostRepository::new(db.clone());
+        let user_repository = PostgresUserRepository::new(db);
+        let fallback_author = fallback_author(&user_repository, author).await?;
+
+        let options = MarkdownImportOptions {
+            fallback_author_id: fallback_author.id,
+            dry_run,
+        };
+        markdown::import(&post_repository, &user_repository, &files, &options)
+            .await
+            .map_err(|e| anyhow::anyhow!("Import failed: {}", e))
+    })?;
+
+    print_report(&report);
+    Ok(())
+}
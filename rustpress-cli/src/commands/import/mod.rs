@@ -0,0 +1,154 @@
+mod markdown;
+mod wxr;
+
+use anyhow::{Context, Result};
+use clap::Args;
+use colored::*;
+use rustpress_core::dto::{ImportCounts, ImportReport, User, UserRole};
+use rustpress_core::repository::UserRepository;
+use std::path::PathBuf;
+
+/// Import-related commands
+#[derive(Args)]
+pub struct ImportArgs {
+    #[command(subcommand)]
+    pub command: ImportCommand,
+}
+
+#[derive(clap::Subcommand)]
+pub enum ImportCommand {
+    /// Import a WordPress export (WXR) file
+    Wxr {
+        /// WXR file written by WordPress' "Tools → Export"
+        file: PathBuf,
+
+        /// Copy of the site's wp-content/uploads directory, to import attachments from
+        #[arg(long)]
+        wp_uploads_dir: Option<PathBuf>,
+
+        /// User that owns posts whose author is not in the file (defaults to the root user)
+        #[arg(long)]
+        author: Option<String>,
+
+        /// Only report what would be imported
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Database to import into
+        #[arg(long, env = "DATABASE_URL")]
+        database_url: String,
+
+        /// Upload directory of the local storage backend
+        #[arg(long, env = "STORAGE_DIR", default_value = "uploads")]
+        storage_dir: PathBuf,
+    },
+    /// Import Markdown files with YAML or TOML front matter (Hugo or Jekyll content)
+    Markdown {
+        /// Directory of Markdown files, or a zip of them
+        path: PathBuf,
+
+        /// User that owns new posts whose author is missing or unknown (defaults to the root user)
+        #[arg(long)]
+        author: Option<String>,
+
+        /// Only report what would be imported
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Database to import into
+        #[arg(long, env = "DATABASE_URL")]
+        database_url: String,
+    },
+}
+
+pub fn handle_import_command(args: &ImportArgs) -> Result<()> {
+    match &args.command {
+        ImportCommand::Wxr {
+            file,
+            wp_uploads_dir,
+            author,
+            dry_run,
+            database_url,
+            storage_dir,
+        } => wxr::import_wxr(
+            file,
+            wp_uploads_dir,
+            author,
+            *dry_run,
+            database_url,
+            storage_dir,
+        ),
+        ImportCommand::Markdown {
+            path,
+            author,
+            dry_run,
+            database_url,
+        } => markdown::import_markdown(path, author, *dry_run, database_url),
+    }
+}
+
+/// User named by `--author`, or the root user
+async fn fallback_author<UR: UserRepository>(
+    user_repository: &UR,
+    author: &Option<String>,
+) -> Result<User> {
+    match author {
+        Some(username) => user_repository
+            .find_by_username(username)
+            .await
+            .map_err(|e| anyhow::anyhow!(e))?
+            .with_context(|| format!("User not found: {}", username)),
+        None => user_repository
+            .find_all()
+            .await
+            .map_err(|e| anyhow::anyhow!(e))?
+            .into_iter()
+            .find(|user| user.role == UserRole::Root)
+            .context("No root user found; pass --author"),
+    }
+}
+
+fn print_report(report: &ImportReport) {
+    if report.dry_run {
+        println!("{}", "🔍 Dry run, nothing was changed:".yellow().bold());
+    } else {
+        println!("{}", "✅ Import finished!".green().bold());
+    }
+
+    let counts = |label: &str, counts: ImportCounts| {
+        if counts.updated > 0 {
+            println!(
+                "  {}: {} new, {} updated, {} unchanged",
+                label, counts.created, counts.updated, counts.existing
+            );
+        } else {
+            println!(
+                "  {}: {} new, {} already present",
+                label, counts.created, counts.existing
+            );
+        }
+    };
+    counts("👤 Users", report.users);
+    counts("📂 Categories", report.categories);
+    counts("📝 Posts", report.posts);
+    counts("🖼️  Attachments", report.attachments);
+    println!("  🕘 Revisions: {}", report.versions);
+    println!("  ✏️  Drafts: {}", report.drafts);
+    println!("  ⏭️  Skipped items: {}", report.skipped);
+
+    if !report.missing_files.is_empty() {
+        println!("{}", "  Missing attachment files:".yellow());
+        for path in &report.missing_files {
+            println!("    - {}", path);
+        }
+    }
+    if !report.rejected_files.is_empty() {
+        println!("{}", "  Rejected attachment files:".yellow());
+        for rejection in &report.rejected_files {
+            println!("    - {}", rejection);
+        }
+    }
+    for warning in &report.warnings {
+        println!("  {} {}", "⚠️".yellow(), warning);
+    }
+}
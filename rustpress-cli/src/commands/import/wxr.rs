@@ -0,0 +1,74 @@
+use super::{fallback_author, print_report};
+use crate::commands::database::with_database;
+use anyhow::{Context, Result};
+use colored::*;
+use rustpress_core::import::wxr::{self, WxrImportOptions};
+use rustpress_core::media::FileNaming;
+use rustpress_core::repository::{
+    PostgresMediaRepository, PostgresPostRepository, PostgresUserRepository,
+};
+use rustpress_core::storage::ConfiguredStorage;
+use rustpress_core::upload_policy::UploadPolicy;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+pub fn import_wxr(
+    file: &Path,
+    wp_uploads_dir: &Option<PathBuf>,
+    author: &Option<String>,
+    dry_run: bool,
+    database_url: &str,
+    storage_dir: &Path,
+) -> Result<()> {
+    println!("{}", "Importing WordPress export...".cyan().bold());
+
+    let xml = std::fs::read_to_string(file)
+        .with_context(|| format!("Failed to read WXR file: {}", file.display()))?;
+    let document = wxr::parse(&xml).map_err(|e| anyhow::anyhow!("Invalid WXR file: {}", e))?;
+    println!("  🌐 Source: {}", document.site_url);
+
+    if let Some(dir) = wp_uploads_dir
+        && !dir.is_dir()
+    {
+        anyhow::bail!("Uploads directory not found: {}", dir.display());
+    }
+
+    let report = with_database(database_url, |db| async move {
+        let settings_db = Arc::new(db.clone());
+        let file_naming = FileNaming::load(&settings_db)
+            .await
+            .context("Failed to load upload settings")?;
+        let upload_policy = UploadPolicy::load(&settings_db)
+            .await
+            .context("Failed to load upload settings")?;
+        let post_repository = PostgresPostRepository::new(db.clone());
+        let media_repository = PostgresMediaRepository::new(db.clone());
+        let user_repository = PostgresUserRepository::new(db);
+        let storage = ConfiguredStorage::from_env(storage_dir, String::new())
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to initialize storage: {}", e))?;
+
+        let fallback_author = fallback_author(&user_repository, author).await?;
+
+        let options = WxrImportOptions {
+            uploads_dir: wp_uploads_dir.clone(),
+            fallback_author_id: fallback_author.id,
+            dry_run,
+            file_naming,
+            upload_policy,
+        };
+        wxr::import(
+            &post_repository,
+            &user_repository,
+            &media_repository,
+            &storage,
+            &document,
+            &options,
+        )
+        .await
+        .map_err(|e| anyhow::anyhow!("Import failed: {}", e))
+    })?;
+
+    print_report(&report);
+    Ok(())
+}
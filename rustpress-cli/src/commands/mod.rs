@@ -1 +1,7 @@
+mod database;
+
+pub mod backup;
+pub mod export;
+pub mod import;
 pub mod plugin;
+pub mod site;
@@ -0,0 +1,44 @@
+use crate::commands::database::with_database;
+use anyhow::Result;
+use colored::*;
+use rustpress_core::export::ExportOptions;
+use rustpress_core::storage::ConfiguredStorage;
+use std::path::Path;
+
+pub fn export_site(
+    database_url: &str,
+    out: &Path,
+    base_url: &Option<String>,
+    uploads_dir: &Path,
+    full: bool,
+) -> Result<()> {
+    println!("{}", "Exporting Rustpress site...".cyan().bold());
+
+    let options = ExportOptions {
+        out_dir: out.to_path_buf(),
+        site_url: base_url.clone(),
+        full,
+    };
+
+    let report = with_database(database_url, |db| async move {
+        let storage = ConfiguredStorage::from_env(uploads_dir, String::new())
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to initialize storage: {}", e))?;
+        rustpress_core::export::export_site(db, &storage, &options)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to export site: {}", e))
+    })?;
+
+    println!("{}", "✅ Site exported successfully!".green().bold());
+    println!("  📁 Output: {}", out.display());
+    println!(
+        "  📝 Posts: {} written, {} unchanged, {} removed",
+        report.posts_written, report.posts_unchanged, report.posts_removed
+    );
+    println!("  📄 Listing pages: {}", report.listings_written);
+    println!("  📡 Feeds: {}", report.feeds_written);
+    println!("  🗺️  Sitemaps: {}", report.sitemaps_written);
+    println!("  🖼️  Media files copied: {}", report.media_copied);
+
+    Ok(())
+}
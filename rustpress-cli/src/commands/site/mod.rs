@@ -0,0 +1,51 @@
+mod export;
+
+use anyhow::Result;
+use clap::Args;
+use std::path::PathBuf;
+
+/// Site-related commands
+#[derive(Args)]
+pub struct SiteArgs {
+    #[command(subcommand)]
+    pub command: SiteCommand,
+}
+
+#[derive(clap::Subcommand)]
+pub enum SiteCommand {
+    /// Export the published site as static files
+    Export {
+        /// Directory to write the site to
+        #[arg(long)]
+        out: PathBuf,
+
+        /// Public base URL of the exported site (defaults to the `site_url` setting)
+        #[arg(long)]
+        base_url: Option<String>,
+
+        /// Database to export from
+        #[arg(long, env = "DATABASE_URL")]
+        database_url: String,
+
+        /// Upload directory of the local storage backend; with
+        /// `STORAGE_BACKEND=s3` the media is read from the bucket instead
+        #[arg(long, env = "STORAGE_DIR", default_value = "uploads")]
+        uploads_dir: PathBuf,
+
+        /// Rewrite every page instead of only posts changed since the last export
+        #[arg(long)]
+        full: bool,
+    },
+}
+
+pub fn handle_site_command(args: &SiteArgs) -> Result<()> {
+    match &args.command {
+        SiteCommand::Export {
+            out,
+            base_url,
+            database_url,
+            uploads_dir,
+            full,
+        } => export::export_site(database_url, out, base_url, uploads_dir, *full),
+    }
+}
@@ -18,6 +18,14 @@ struct Cli {
 enum Commands {
     /// Plugin management commands
     Plugin(commands::plugin::PluginArgs),
+    /// Site management commands
+    Site(commands::site::SiteArgs),
+    /// Content import commands
+    Import(commands::import::ImportArgs),
+    /// Content export commands
+    Export(commands::export::ExportArgs),
+    /// Backup and restore commands
+    Backup(commands::backup::BackupArgs),
 }
 
 fn main() {
@@ -35,6 +43,9 @@ fn run() -> anyhow::Result<()> {
         args.remove(1);
     }
 
+    // DATABASE_URL and STORAGE_DIR may come from the server's .env file
+    dotenv::dotenv().ok();
+
     // 2. parse arguments
     let cli = Cli::parse_from(args);
 
@@ -43,6 +54,18 @@ fn run() -> anyhow::Result<()> {
         Commands::Plugin(plugin_args) => {
             commands::plugin::handle_plugin_command(&plugin_args)?;
         }
+        Commands::Site(site_args) => {
+            commands::site::handle_site_command(&site_args)?;
+        }
+        Commands::Import(import_args) => {
+            commands::import::handle_import_command(&import_args)?;
+        }
+        Commands::Export(export_args) => {
+            commands::export::handle_export_command(&export_args)?;
+        }
+        Commands::Backup(backup_args) => {
+            commands::backup::handle_backup_command(&backup_args)?;
+        }
     }
 
     Ok(())
@@ -0,0 +1,61 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Add generated tsvector column for full-text search
+        // Weights: title (A) > description (B) > content (C)
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                ALTER TABLE posts
+                    ADD COLUMN IF NOT EXISTS search_vector tsvector
+                    GENERATED ALWAYS AS (
+                        setweight(to_tsvector('english', coalesce(title, '')), 'A') ||
+                        setweight(to_tsvector('english', coalesce(description, '')), 'B') ||
+                        setweight(to_tsvector('english', coalesce(content, '')), 'C')
+                    ) STORED;
+                "#,
+            )
+            .await?;
+
+        // Create GIN index on the search vector
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "CREATE INDEX IF NOT EXISTS idx_posts_search_vector ON posts USING GIN (search_vector);",
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Drop index
+        manager
+            .drop_index(Index::drop().name("idx_posts_search_vector").to_owned())
+            .await?;
+
+        // Drop column
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Posts::Table)
+                    .drop_column(Posts::SearchVector)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum Posts {
+    Table,
+    SearchVector,
+}
@@ -0,0 +1,140 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Create tags table
+        manager
+            .create_table(
+                Table::create()
+                    .table(Tags::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Tags::Id)
+                            .big_integer()
+                            .not_null()
+                            .primary_key()
+                            .auto_increment(),
+                    )
+                    .col(ColumnDef::new(Tags::Name).string().not_null())
+                    .col(ColumnDef::new(Tags::Slug).string().not_null().unique_key())
+                    .col(
+                        ColumnDef::new(Tags::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Create post_tags join table
+        manager
+            .create_table(
+                Table::create()
+                    .table(PostTags::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(PostTags::PostId).big_integer().not_null())
+                    .col(ColumnDef::new(PostTags::TagId).big_integer().not_null())
+                    .primary_key(Index::create().col(PostTags::PostId).col(PostTags::TagId))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_post_tags_post_id")
+                            .from(PostTags::Table, PostTags::PostId)
+                            .to(Posts::Table, Posts::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_post_tags_tag_id")
+                            .from(PostTags::Table, PostTags::TagId)
+                            .to(Tags::Table, Tags::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Lookups by tag (posts by tag, tag counts)
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_post_tags_tag_id")
+                    .table(PostTags::Table)
+                    .col(PostTags::TagId)
+                    .to_owned(),
+            )
+            .await?;
+
+        // Drafts keep their tags as a plain list of names until published
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(PostDrafts::Table)
+                    .add_column(
+                        ColumnDef::new(PostDrafts::Tags)
+                            .json()
+                            .not_null()
+                            .default(Expr::val(serde_json::json!([]))),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(PostDrafts::Table)
+                    .drop_column(PostDrafts::Tags)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(PostTags::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(Tags::Table).to_owned())
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum Tags {
+    Table,
+    Id,
+    Name,
+    Slug,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum PostTags {
+    Table,
+    PostId,
+    TagId,
+}
+
+#[derive(DeriveIden)]
+enum PostDrafts {
+    Table,
+    Tags,
+}
+
+#[derive(DeriveIden)]
+enum Posts {
+    Table,
+    Id,
+}
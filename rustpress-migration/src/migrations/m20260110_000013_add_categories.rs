@@ -0,0 +1,232 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// The `default_category` setting value with JSON string encoding removed
+const DEFAULT_CATEGORY_VALUE_SQL: &str =
+    "(CASE WHEN value LIKE '\"%\"' THEN value::json #>> '{}' ELSE value END)";
+
+/// SQL expression producing the slug for a category name
+///
+/// Mirrors `slugify` in the core crate: runs of non-alphanumeric characters become `-`,
+/// lowercased and trimmed. Names without any alphanumerics get a hash-based slug.
+fn slug_sql(expr: &str) -> String {
+    format!(
+        "coalesce(nullif(trim(both '-' from lower(regexp_replace({expr}, '[^[:alnum:]]+', '-', 'g'))), ''), 'category-' || left(md5({expr}), 8))",
+        expr = expr
+    )
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Create categories table
+        manager
+            .create_table(
+                Table::create()
+                    .table(Categories::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Categories::Id)
+                            .big_integer()
+                            .not_null()
+                            .primary_key()
+                            .auto_increment(),
+                    )
+                    .col(ColumnDef::new(Categories::Name).string().not_null())
+                    .col(
+                        ColumnDef::new(Categories::Slug)
+                            .string()
+                            .not_null()
+                            .unique_key(),
+                    )
+                    .col(ColumnDef::new(Categories::Description).text().null())
+                    .col(ColumnDef::new(Categories::ParentId).big_integer().null())
+                    .col(
+                        ColumnDef::new(Categories::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        ColumnDef::new(Categories::UpdatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_categories_parent_id")
+                            .from(Categories::Table, Categories::ParentId)
+                            .to(Categories::Table, Categories::Id)
+                            .on_delete(ForeignKeyAction::SetNull)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_categories_parent_id")
+                    .table(Categories::Table)
+                    .col(Categories::ParentId)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                DROP TRIGGER IF EXISTS update_categories_updated_at ON categories;
+                CREATE TRIGGER update_categories_updated_at
+                    BEFORE UPDATE ON categories
+                    FOR EACH ROW
+                    EXECUTE FUNCTION update_updated_at_column();
+                "#,
+            )
+            .await?;
+
+        // Turn every distinct category string (including the default_category setting,
+        // which may be stored JSON-encoded) into a row. Spellings that map to the same
+        // slug ("Rust", "rust ") become a single category.
+        manager
+            .get_connection()
+            .execute_unprepared(&format!(
+                r#"
+                INSERT INTO categories (name, slug)
+                SELECT min(name), {slug}
+                FROM (
+                    SELECT DISTINCT trim(name) AS name
+                    FROM (
+                        SELECT category AS name FROM posts
+                        UNION ALL SELECT category FROM post_versions
+                        UNION ALL SELECT category FROM post_drafts
+                        UNION ALL SELECT {setting} FROM settings WHERE key = 'default_category'
+                    ) AS all_names
+                    WHERE name IS NOT NULL AND trim(name) <> ''
+                ) AS names
+                GROUP BY {slug}
+                ON CONFLICT (slug) DO NOTHING;
+                "#,
+                slug = slug_sql("name"),
+                setting = DEFAULT_CATEGORY_VALUE_SQL,
+            ))
+            .await?;
+
+        // Replace the category strings with category_id references
+        for table in ["posts", "post_versions", "post_drafts"] {
+            manager
+                .get_connection()
+                .execute_unprepared(&format!(
+                    r#"
+                    ALTER TABLE {table}
+                        ADD COLUMN category_id BIGINT NULL
+                        REFERENCES categories (id) ON DELETE SET NULL ON UPDATE CASCADE;
+
+                    UPDATE {table} t
+                    SET category_id = c.id
+                    FROM categories c
+                    WHERE trim(t.category) <> '' AND c.slug = {slug};
+
+                    ALTER TABLE {table} DROP COLUMN category;
+                    "#,
+                    table = table,
+                    slug = slug_sql("trim(t.category)"),
+                ))
+                .await?;
+        }
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_posts_category_id")
+                    .table(Posts::Table)
+                    .col(Posts::CategoryId)
+                    .to_owned(),
+            )
+            .await?;
+
+        // default_category now stores a category id
+        manager
+            .get_connection()
+            .execute_unprepared(&format!(
+                r#"
+                UPDATE settings
+                SET value = c.id::text
+                FROM categories c
+                WHERE settings.key = 'default_category'
+                  AND trim({setting}) <> ''
+                  AND c.slug = {slug};
+
+                DELETE FROM settings
+                WHERE key = 'default_category' AND value !~ '^[0-9]+$';
+                "#,
+                setting = DEFAULT_CATEGORY_VALUE_SQL,
+                slug = slug_sql(&format!("trim({})", DEFAULT_CATEGORY_VALUE_SQL)),
+            ))
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Restore category strings from the referenced rows
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                ALTER TABLE posts ADD COLUMN category VARCHAR NULL;
+                UPDATE posts p SET category = c.name FROM categories c WHERE c.id = p.category_id;
+                CREATE INDEX idx_posts_category ON posts (category);
+
+                ALTER TABLE post_versions ADD COLUMN category VARCHAR NOT NULL DEFAULT '';
+                UPDATE post_versions v SET category = c.name FROM categories c WHERE c.id = v.category_id;
+                ALTER TABLE post_versions ALTER COLUMN category DROP DEFAULT;
+
+                ALTER TABLE post_drafts ADD COLUMN category VARCHAR NOT NULL DEFAULT '';
+                UPDATE post_drafts d SET category = c.name FROM categories c WHERE c.id = d.category_id;
+                ALTER TABLE post_drafts ALTER COLUMN category DROP DEFAULT;
+
+                UPDATE settings s SET value = c.name
+                FROM categories c
+                WHERE s.key = 'default_category' AND s.value = c.id::text;
+
+                DROP INDEX IF EXISTS idx_posts_category_id;
+                ALTER TABLE posts DROP COLUMN category_id;
+                ALTER TABLE post_versions DROP COLUMN category_id;
+                ALTER TABLE post_drafts DROP COLUMN category_id;
+
+                DROP TRIGGER IF EXISTS update_categories_updated_at ON categories;
+                "#,
+            )
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(Categories::Table).to_owned())
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum Categories {
+    Table,
+    Id,
+    Name,
+    Slug,
+    Description,
+    ParentId,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Posts {
+    Table,
+    CategoryId,
+}
@@ -0,0 +1,191 @@
+use sea_orm::{ConnectionTrait, DbBackend, Statement};
+use sea_orm_migration::prelude::*;
+use std::collections::HashSet;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// Maximum slug length; mirrors `MAX_POST_SLUG_LEN` in the core crate
+const MAX_SLUG_LEN: usize = 80;
+
+/// Slug for an existing post title
+///
+/// Mirrors `post_slug` in the core crate: transliterate to ASCII, keep lowercase
+/// alphanumerics, join everything else with `-`.
+fn post_slug(title: &str) -> String {
+    let mut slug = String::new();
+    let mut pending_separator = false;
+    for c in deunicode::deunicode(title).chars() {
+        if c.is_ascii_alphanumeric() {
+            if pending_separator && !slug.is_empty() {
+                slug.push('-');
+            }
+            pending_separator = false;
+            slug.push(c.to_ascii_lowercase());
+        } else {
+            pending_separator = true;
+        }
+    }
+
+    if slug.len() > MAX_SLUG_LEN {
+        slug.truncate(MAX_SLUG_LEN);
+        if let Some(cut) = slug.rfind('-').filter(|cut| *cut > MAX_SLUG_LEN / 2) {
+            slug.truncate(cut);
+        }
+        slug = slug.trim_end_matches('-').to_string();
+    }
+
+    if slug.is_empty() {
+        "post".to_string()
+    } else {
+        slug
+    }
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Posts::Table)
+                    .add_column(ColumnDef::new(Posts::Slug).string().null())
+                    .to_owned(),
+            )
+            .await?;
+
+        // Old slugs of renamed posts, so old permalinks can redirect
+        manager
+            .create_table(
+                Table::create()
+                    .table(PostSlugHistory::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(PostSlugHistory::Slug)
+                            .string()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(PostSlugHistory::PostId)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(PostSlugHistory::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_post_slug_history_post_id")
+                            .from(PostSlugHistory::Table, PostSlugHistory::PostId)
+                            .to(Posts::Table, Posts::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_post_slug_history_post_id")
+                    .table(PostSlugHistory::Table)
+                    .col(PostSlugHistory::PostId)
+                    .to_owned(),
+            )
+            .await?;
+
+        // Backfill slugs for existing posts; older posts win on collisions
+        let db = manager.get_connection();
+        let rows = db
+            .query_all(Statement::from_string(
+                DbBackend::Postgres,
+                "SELECT id, title FROM posts ORDER BY id",
+            ))
+            .await?;
+
+        let mut taken = HashSet::new();
+        for row in rows {
+            let id: i64 = row.try_get("", "id")?;
+            let title: String = row.try_get("", "title")?;
+
+            let base = post_slug(&title);
+            let mut slug = base.clone();
+            let mut n = 2;
+            while taken.contains(&slug) {
+                slug = format!("{}-{}", base, n);
+                n += 1;
+            }
+
+            db.execute(Statement::from_sql_and_values(
+                DbBackend::Postgres,
+                "UPDATE posts SET slug = $1 WHERE id = $2",
+                [slug.clone().into(), id.into()],
+            ))
+            .await?;
+            taken.insert(slug);
+        }
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Posts::Table)
+                    .modify_column(ColumnDef::new(Posts::Slug).string().not_null())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_posts_slug")
+                    .table(Posts::Table)
+                    .col(Posts::Slug)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(PostSlugHistory::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_index(Index::drop().name("idx_posts_slug").to_owned())
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Posts::Table)
+                    .drop_column(Posts::Slug)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum Posts {
+    Table,
+    Id,
+    Slug,
+}
+
+#[derive(DeriveIden)]
+enum PostSlugHistory {
+    Table,
+    Slug,
+    PostId,
+    CreatedAt,
+}
@@ -0,0 +1,77 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Existing posts were all publicly visible, so they start out published
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Posts::Table)
+                    .add_column(
+                        ColumnDef::new(Posts::Status)
+                            .string()
+                            .not_null()
+                            .default("published"),
+                    )
+                    .add_column(ColumnDef::new(Posts::ReviewNote).text().null())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                ALTER TABLE posts ADD CONSTRAINT chk_posts_status
+                    CHECK (status IN ('draft', 'pending_review', 'scheduled', 'published', 'private'));
+                "#,
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_posts_status")
+                    .table(Posts::Table)
+                    .col(Posts::Status)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(Index::drop().name("idx_posts_status").to_owned())
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared("ALTER TABLE posts DROP CONSTRAINT IF EXISTS chk_posts_status;")
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Posts::Table)
+                    .drop_column(Posts::Status)
+                    .drop_column(Posts::ReviewNote)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum Posts {
+    Table,
+    Status,
+    ReviewNote,
+}
@@ -0,0 +1,72 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Posts::Table)
+                    .add_column(
+                        ColumnDef::new(Posts::PublishAt)
+                            .timestamp_with_time_zone()
+                            .null(),
+                    )
+                    .add_column(
+                        ColumnDef::new(Posts::UnpublishAt)
+                            .timestamp_with_time_zone()
+                            .null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // The scheduler only ever looks at scheduled posts that are due and
+        // published posts with an expiry, so keep the indexes partial
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                CREATE INDEX idx_posts_publish_at ON posts (publish_at)
+                    WHERE status = 'scheduled';
+                CREATE INDEX idx_posts_unpublish_at ON posts (unpublish_at)
+                    WHERE status = 'published' AND unpublish_at IS NOT NULL;
+                "#,
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(Index::drop().name("idx_posts_unpublish_at").to_owned())
+            .await?;
+
+        manager
+            .drop_index(Index::drop().name("idx_posts_publish_at").to_owned())
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Posts::Table)
+                    .drop_column(Posts::PublishAt)
+                    .drop_column(Posts::UnpublishAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum Posts {
+    Table,
+    PublishAt,
+    UnpublishAt,
+}
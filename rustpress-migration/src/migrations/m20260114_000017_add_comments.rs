@@ -0,0 +1,167 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Comments are threaded through parent_id; guests have no author_id
+        manager
+            .create_table(
+                Table::create()
+                    .table(Comments::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Comments::Id)
+                            .big_integer()
+                            .not_null()
+                            .primary_key()
+                            .auto_increment(),
+                    )
+                    .col(ColumnDef::new(Comments::PostId).big_integer().not_null())
+                    .col(ColumnDef::new(Comments::ParentId).big_integer().null())
+                    .col(ColumnDef::new(Comments::AuthorId).big_integer().null())
+                    .col(ColumnDef::new(Comments::AuthorName).string().not_null())
+                    .col(ColumnDef::new(Comments::AuthorEmail).string().null())
+                    .col(ColumnDef::new(Comments::AuthorUrl).string().null())
+                    .col(ColumnDef::new(Comments::Content).text().not_null())
+                    .col(
+                        ColumnDef::new(Comments::Status)
+                            .string()
+                            .not_null()
+                            .default("pending"),
+                    )
+                    .col(ColumnDef::new(Comments::IpAddress).string().null())
+                    .col(ColumnDef::new(Comments::UserAgent).string().null())
+                    .col(
+                        ColumnDef::new(Comments::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        ColumnDef::new(Comments::UpdatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_comments_post_id")
+                            .from(Comments::Table, Comments::PostId)
+                            .to(Posts::Table, Posts::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_comments_parent_id")
+                            .from(Comments::Table, Comments::ParentId)
+                            .to(Comments::Table, Comments::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_comments_author_id")
+                            .from(Comments::Table, Comments::AuthorId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::SetNull)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                ALTER TABLE comments ADD CONSTRAINT chk_comments_status
+                    CHECK (status IN ('pending', 'approved', 'rejected', 'spam'));
+
+                DROP TRIGGER IF EXISTS update_comments_updated_at ON comments;
+                CREATE TRIGGER update_comments_updated_at
+                    BEFORE UPDATE ON comments
+                    FOR EACH ROW
+                    EXECUTE FUNCTION update_updated_at_column();
+                "#,
+            )
+            .await?;
+
+        // Public thread of a post
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_comments_post_id_status")
+                    .table(Comments::Table)
+                    .col(Comments::PostId)
+                    .col(Comments::Status)
+                    .to_owned(),
+            )
+            .await?;
+
+        // Moderation queue
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_comments_status_created_at")
+                    .table(Comments::Table)
+                    .col(Comments::Status)
+                    .col(Comments::CreatedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_comments_parent_id")
+                    .table(Comments::Table)
+                    .col(Comments::ParentId)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(Comments::Table).to_owned())
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum Comments {
+    Table,
+    Id,
+    PostId,
+    ParentId,
+    AuthorId,
+    AuthorName,
+    AuthorEmail,
+    AuthorUrl,
+    Content,
+    Status,
+    IpAddress,
+    UserAgent,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Posts {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum Users {
+    Table,
+    Id,
+}
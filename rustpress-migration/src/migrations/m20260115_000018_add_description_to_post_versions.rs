@@ -0,0 +1,41 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Snapshot the description with each version so versions can be diffed.
+        // Versions recorded before this migration have no description.
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(PostVersions::Table)
+                    .add_column(ColumnDef::new(PostVersions::Description).string().null())
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(PostVersions::Table)
+                    .drop_column(PostVersions::Description)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum PostVersions {
+    Table,
+    Description,
+}
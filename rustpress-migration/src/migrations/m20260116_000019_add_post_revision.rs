@@ -0,0 +1,76 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Posts::Table)
+                    .add_column(
+                        ColumnDef::new(Posts::Revision)
+                            .integer()
+                            .not_null()
+                            .default(1),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Every write to a post bumps its revision, which clients send back in If-Match
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                CREATE OR REPLACE FUNCTION bump_post_revision()
+                RETURNS TRIGGER AS $$
+                BEGIN
+                    NEW.revision = OLD.revision + 1;
+                    RETURN NEW;
+                END;
+                $$ language 'plpgsql';
+
+                DROP TRIGGER IF EXISTS bump_posts_revision ON posts;
+                CREATE TRIGGER bump_posts_revision
+                    BEFORE UPDATE ON posts
+                    FOR EACH ROW
+                    EXECUTE FUNCTION bump_post_revision();
+                "#,
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                DROP TRIGGER IF EXISTS bump_posts_revision ON posts;
+                DROP FUNCTION IF EXISTS bump_post_revision();
+                "#,
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Posts::Table)
+                    .drop_column(Posts::Revision)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum Posts {
+    Table,
+    Revision,
+}
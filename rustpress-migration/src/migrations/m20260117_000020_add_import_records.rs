@@ -0,0 +1,73 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // What an import created for each item of its source, so that re-running
+        // the same import skips items that are already there
+        manager
+            .create_table(
+                Table::create()
+                    .table(ImportRecords::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(ImportRecords::Id)
+                            .big_integer()
+                            .not_null()
+                            .primary_key()
+                            .auto_increment(),
+                    )
+                    .col(ColumnDef::new(ImportRecords::Source).string().not_null())
+                    .col(ColumnDef::new(ImportRecords::Kind).string().not_null())
+                    .col(ColumnDef::new(ImportRecords::SourceId).string().not_null())
+                    .col(ColumnDef::new(ImportRecords::TargetId).big_integer().null())
+                    .col(ColumnDef::new(ImportRecords::TargetUrl).text().null())
+                    .col(
+                        ColumnDef::new(ImportRecords::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_import_records_source_kind_source_id")
+                    .table(ImportRecords::Table)
+                    .col(ImportRecords::Source)
+                    .col(ImportRecords::Kind)
+                    .col(ImportRecords::SourceId)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(ImportRecords::Table).to_owned())
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum ImportRecords {
+    Table,
+    Id,
+    Source,
+    Kind,
+    SourceId,
+    TargetId,
+    TargetUrl,
+    CreatedAt,
+}
@@ -0,0 +1,241 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Every uploaded file; file_name is the name the storage backend gave it
+        manager
+            .create_table(
+                Table::create()
+                    .table(Media::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Media::Id)
+                            .big_integer()
+                            .not_null()
+                            .primary_key()
+                            .auto_increment(),
+                    )
+                    .col(ColumnDef::new(Media::Url).text().not_null().unique_key())
+                    .col(ColumnDef::new(Media::FileName).string().not_null())
+                    .col(ColumnDef::new(Media::OriginalName).string().not_null())
+                    .col(ColumnDef::new(Media::MimeType).string().not_null())
+                    .col(ColumnDef::new(Media::Size).big_integer().not_null())
+                    .col(ColumnDef::new(Media::Width).integer().null())
+                    .col(ColumnDef::new(Media::Height).integer().null())
+                    .col(ColumnDef::new(Media::Checksum).string().not_null())
+                    .col(ColumnDef::new(Media::AltText).text().null())
+                    .col(ColumnDef::new(Media::UploaderId).big_integer().null())
+                    .col(
+                        ColumnDef::new(Media::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        ColumnDef::new(Media::UpdatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_media_uploader_id")
+                            .from(Media::Table, Media::UploaderId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::SetNull)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Posts whose content links to each file
+        manager
+            .create_table(
+                Table::create()
+                    .table(MediaReferences::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(MediaReferences::MediaId)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(MediaReferences::PostId)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .primary_key(
+                        Index::create()
+                            .col(MediaReferences::MediaId)
+                            .col(MediaReferences::PostId),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_media_references_media_id")
+                            .from(MediaReferences::Table, MediaReferences::MediaId)
+                            .to(Media::Table, Media::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_media_references_post_id")
+                            .from(MediaReferences::Table, MediaReferences::PostId)
+                            .to(Posts::Table, Posts::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_media_references_post_id")
+                    .table(MediaReferences::Table)
+                    .col(MediaReferences::PostId)
+                    .to_owned(),
+            )
+            .await?;
+
+        // Library listing, newest first
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_media_created_at")
+                    .table(Media::Table)
+                    .col(Media::CreatedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_media_checksum")
+                    .table(Media::Table)
+                    .col(Media::Checksum)
+                    .to_owned(),
+            )
+            .await?;
+
+        // References are found by scanning post content for the stored file names,
+        // whenever a post's content is written and whenever a file is added
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                DROP TRIGGER IF EXISTS update_media_updated_at ON media;
+                CREATE TRIGGER update_media_updated_at
+                    BEFORE UPDATE ON media
+                    FOR EACH ROW
+                    EXECUTE FUNCTION update_updated_at_column();
+
+                CREATE OR REPLACE FUNCTION refresh_post_media_references()
+                RETURNS TRIGGER AS $$
+                BEGIN
+                    DELETE FROM media_references WHERE post_id = NEW.id;
+                    INSERT INTO media_references (media_id, post_id)
+                        SELECT m.id, NEW.id FROM media m
+                        WHERE strpos(NEW.content, m.file_name) > 0;
+                    RETURN NULL;
+                END;
+                $$ language 'plpgsql';
+
+                DROP TRIGGER IF EXISTS refresh_posts_media_references ON posts;
+                CREATE TRIGGER refresh_posts_media_references
+                    AFTER INSERT OR UPDATE OF content ON posts
+                    FOR EACH ROW
+                    EXECUTE FUNCTION refresh_post_media_references();
+
+                CREATE OR REPLACE FUNCTION find_media_references()
+                RETURNS TRIGGER AS $$
+                BEGIN
+                    INSERT INTO media_references (media_id, post_id)
+                        SELECT NEW.id, p.id FROM posts p
+                        WHERE strpos(p.content, NEW.file_name) > 0;
+                    RETURN NULL;
+                END;
+                $$ language 'plpgsql';
+
+                DROP TRIGGER IF EXISTS find_media_references ON media;
+                CREATE TRIGGER find_media_references
+                    AFTER INSERT ON media
+                    FOR EACH ROW
+                    EXECUTE FUNCTION find_media_references();
+                "#,
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                DROP TRIGGER IF EXISTS refresh_posts_media_references ON posts;
+                DROP FUNCTION IF EXISTS refresh_post_media_references();
+                "#,
+            )
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(MediaReferences::Table).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(Media::Table).to_owned())
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared("DROP FUNCTION IF EXISTS find_media_references();")
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum Media {
+    Table,
+    Id,
+    Url,
+    FileName,
+    OriginalName,
+    MimeType,
+    Size,
+    Width,
+    Height,
+    Checksum,
+    AltText,
+    UploaderId,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum MediaReferences {
+    Table,
+    MediaId,
+    PostId,
+}
+
+#[derive(DeriveIden)]
+enum Posts {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum Users {
+    Table,
+    Id,
+}
@@ -0,0 +1,170 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Resized copies of an uploaded image: responsive widths and a thumbnail
+        manager
+            .create_table(
+                Table::create()
+                    .table(MediaVariants::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(MediaVariants::Id)
+                            .big_integer()
+                            .not_null()
+                            .primary_key()
+                            .auto_increment(),
+                    )
+                    .col(
+                        ColumnDef::new(MediaVariants::MediaId)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(MediaVariants::Kind).string().not_null())
+                    .col(
+                        ColumnDef::new(MediaVariants::Url)
+                            .text()
+                            .not_null()
+                            .unique_key(),
+                    )
+                    .col(ColumnDef::new(MediaVariants::FileName).string().not_null())
+                    .col(ColumnDef::new(MediaVariants::MimeType).string().not_null())
+                    .col(ColumnDef::new(MediaVariants::Width).integer().not_null())
+                    .col(ColumnDef::new(MediaVariants::Height).integer().not_null())
+                    .col(ColumnDef::new(MediaVariants::Size).big_integer().not_null())
+                    .col(
+                        ColumnDef::new(MediaVariants::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_media_variants_media_id")
+                            .from(MediaVariants::Table, MediaVariants::MediaId)
+                            .to(Media::Table, Media::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_media_variants_media_id")
+                    .table(MediaVariants::Table)
+                    .col(MediaVariants::MediaId)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_media_variants_file_name")
+                    .table(MediaVariants::Table)
+                    .col(MediaVariants::FileName)
+                    .to_owned(),
+            )
+            .await?;
+
+        // A post linking to a variant (e.g. from a srcset) uses the original file too
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                CREATE OR REPLACE FUNCTION refresh_post_media_references()
+                RETURNS TRIGGER AS $$
+                BEGIN
+                    DELETE FROM media_references WHERE post_id = NEW.id;
+                    INSERT INTO media_references (media_id, post_id)
+                        SELECT m.id, NEW.id FROM media m
+                        WHERE strpos(NEW.content, m.file_name) > 0
+                        UNION
+                        SELECT v.media_id, NEW.id FROM media_variants v
+                        WHERE strpos(NEW.content, v.file_name) > 0;
+                    RETURN NULL;
+                END;
+                $$ language 'plpgsql';
+
+                CREATE OR REPLACE FUNCTION find_media_variant_references()
+                RETURNS TRIGGER AS $$
+                BEGIN
+                    INSERT INTO media_references (media_id, post_id)
+                        SELECT NEW.media_id, p.id FROM posts p
+                        WHERE strpos(p.content, NEW.file_name) > 0
+                    ON CONFLICT DO NOTHING;
+                    RETURN NULL;
+                END;
+                $$ language 'plpgsql';
+
+                DROP TRIGGER IF EXISTS find_media_variant_references ON media_variants;
+                CREATE TRIGGER find_media_variant_references
+                    AFTER INSERT ON media_variants
+                    FOR EACH ROW
+                    EXECUTE FUNCTION find_media_variant_references();
+                "#,
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                CREATE OR REPLACE FUNCTION refresh_post_media_references()
+                RETURNS TRIGGER AS $$
+                BEGIN
+                    DELETE FROM media_references WHERE post_id = NEW.id;
+                    INSERT INTO media_references (media_id, post_id)
+                        SELECT m.id, NEW.id FROM media m
+                        WHERE strpos(NEW.content, m.file_name) > 0;
+                    RETURN NULL;
+                END;
+                $$ language 'plpgsql';
+                "#,
+            )
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(MediaVariants::Table).to_owned())
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared("DROP FUNCTION IF EXISTS find_media_variant_references();")
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum MediaVariants {
+    Table,
+    Id,
+    MediaId,
+    Kind,
+    Url,
+    FileName,
+    MimeType,
+    Width,
+    Height,
+    Size,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Media {
+    Table,
+    Id,
+}
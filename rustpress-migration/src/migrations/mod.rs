@@ -10,6 +10,18 @@ mod m20260105_000007_add_plugin_status;
 mod m20260105_000010_add_description_to_post_drafts;
 mod m20260106_000008_add_plugin_id;
 mod m20260107_000009_add_granted_permissions;
+mod m20260108_000011_add_post_search_vector;
+mod m20260109_000012_add_tags;
+mod m20260110_000013_add_categories;
+mod m20260111_000014_add_post_slugs;
+mod m20260112_000015_add_post_status;
+mod m20260113_000016_add_post_schedule;
+mod m20260114_000017_add_comments;
+mod m20260115_000018_add_description_to_post_versions;
+mod m20260116_000019_add_post_revision;
+mod m20260117_000020_add_import_records;
+mod m20260118_000021_add_media;
+mod m20260119_000022_add_media_variants;
 
 pub fn migration_list() -> Vec<Box<dyn MigrationTrait + 'static>> {
     vec![
@@ -23,5 +35,17 @@ pub fn migration_list() -> Vec<Box<dyn MigrationTrait + 'static>> {
         Box::new(m20260106_000008_add_plugin_id::Migration),
         Box::new(m20260107_000009_add_granted_permissions::Migration),
         Box::new(m20260105_000010_add_description_to_post_drafts::Migration),
+        Box::new(m20260108_000011_add_post_search_vector::Migration),
+        Box::new(m20260109_000012_add_tags::Migration),
+        Box::new(m20260110_000013_add_categories::Migration),
+        Box::new(m20260111_000014_add_post_slugs::Migration),
+        Box::new(m20260112_000015_add_post_status::Migration),
+        Box::new(m20260113_000016_add_post_schedule::Migration),
+        Box::new(m20260114_000017_add_comments::Migration),
+        Box::new(m20260115_000018_add_description_to_post_versions::Migration),
+        Box::new(m20260116_000019_add_post_revision::Migration),
+        Box::new(m20260117_000020_add_import_records::Migration),
+        Box::new(m20260118_000021_add_media::Migration),
+        Box::new(m20260119_000022_add_media_variants::Migration),
     ]
 }